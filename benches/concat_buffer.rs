@@ -0,0 +1,60 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use vrl::compiler::{compile, runtime::Runtime, TargetValue, TimeZone};
+use vrl::value;
+use vrl::value::{Secrets, Value};
+
+#[path = "support/allocations.rs"]
+mod allocations;
+
+use allocations::Allocations;
+
+/// Concatenates the event's fields, as programs deriving ids, keys and paths do. Run with and
+/// without `--features concat_buffer` to compare.
+const SOURCE: &str = r#"
+    host = string!(.host)
+    service = string!(.service)
+    env = string!(.env)
+    region = string!(.region)
+
+    .id = host + "-" + service + "-" + env
+    .key = service + ":" + region + ":" + to_string(int!(.status))
+    .path = "/" + env + "/" + region + "/" + host + "/" + service
+    .label = upcase(service) + "@" + host
+    .message = "[" + env + "] " + service + ": " + string!(.message)
+"#;
+
+fn targets() -> Vec<TargetValue> {
+    (0..100)
+        .map(|i| TargetValue {
+            value: value!({
+                host: (format!("web-{i}")),
+                service: "checkout",
+                env: "production",
+                region: "eu-west-1",
+                status: (200 + i),
+                message: "request handled",
+            }),
+            metadata: Value::from(value::ObjectMap::new()),
+            secrets: Secrets::new(),
+        })
+        .collect()
+}
+
+fn benchmark_concat_buffer(c: &mut Criterion<Allocations>) {
+    let program = compile(SOURCE, &vrl::stdlib::all()).unwrap().program;
+    let timezone = TimeZone::default();
+    let mut group = c.benchmark_group("vrl_compiler/concat_buffer/allocations");
+
+    group.bench_function("concat", |b| {
+        b.iter_batched(
+            targets,
+            |mut targets| Runtime::default().resolve_batch(&mut targets, &program, &timezone),
+            BatchSize::SmallInput,
+        );
+    });
+}
+
+criterion_group!(name = vrl_compiler_concat_buffer;
+                 config = Criterion::default().with_measurement(Allocations).without_plots();
+                 targets = benchmark_concat_buffer);
+criterion_main!(vrl_compiler_concat_buffer);
@@ -0,0 +1,60 @@
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use vrl::compiler::{compile, runtime::Runtime, TargetValue, TimeZone};
+use vrl::value;
+use vrl::value::{Secrets, Value};
+
+#[path = "support/allocations.rs"]
+mod allocations;
+
+use allocations::Allocations;
+
+/// The name and program of each case. Metrics go to the default no-op recorder, so only the
+/// allocations made by `emit_metric` itself are counted.
+const CASES: &[(&str, &str)] = &[
+    (
+        "literal_labels",
+        r#"emit_metric("requests.count", 1, labels: {"host": "localhost"})"#,
+    ),
+    (
+        "event_labels",
+        r#"emit_metric("requests.count", 1, labels: object!(.labels))"#,
+    ),
+    (
+        "dynamic_key",
+        r#"emit_metric(string!(.name), 1, labels: {"host": "localhost"})"#,
+    ),
+];
+
+fn benchmark_emit_metric(c: &mut Criterion<Allocations>) {
+    let timezone = TimeZone::default();
+    let mut functions = vrl::stdlib::all();
+    functions.push(Box::new(vrl::stdlib::EmitMetric));
+    let mut group = c.benchmark_group("vrl_stdlib/emit_metric/allocations");
+    group.throughput(Throughput::Elements(1));
+
+    for (name, source) in CASES {
+        let program = compile(source, &functions).unwrap().program;
+        let mut target = TargetValue {
+            value: value!({
+                name: "requests.count",
+                labels: { host: "localhost", status: "200", region: "us-east-1" },
+            }),
+            metadata: Value::from(value::ObjectMap::new()),
+            secrets: Secrets::new(),
+        };
+        let mut runtime = Runtime::default();
+
+        group.bench_function(*name, |b| {
+            b.iter(|| {
+                let result = runtime.resolve(&mut target, &program, &timezone);
+                runtime.clear();
+                result
+            });
+        });
+    }
+}
+
+criterion_group!(name = vrl_stdlib_emit_metric;
+                 config = Criterion::default().with_measurement(Allocations).without_plots();
+                 targets = benchmark_emit_metric);
+criterion_main!(vrl_stdlib_emit_metric);
@@ -0,0 +1,58 @@
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use vrl::compiler::{compile, runtime::Runtime, TargetValue, TimeZone};
+use vrl::value;
+use vrl::value::{Secrets, Value};
+
+#[path = "support/allocations.rs"]
+mod allocations;
+
+use allocations::Allocations;
+
+const ROWS: &str = "host, status, bytes, path, agent\n \
+    web-1 ,200, 512 ,\"/index.html\", \" curl/8.0 \"\n\
+    web-2,404,0,/missing,wget";
+
+/// The name, program and comment lines preceding `ROWS` of each case.
+const CASES: &[(&str, &str, &str)] = &[
+    ("plain", "parse_csv!(.message)", ""),
+    ("trim", "parse_csv!(.message, trim: true)", ""),
+    (
+        "comment",
+        r##"parse_csv!(.message, comment: "#")"##,
+        "# exported rows\n# by host\n",
+    ),
+    (
+        "headers",
+        r##"parse_csv!(.message, comment: "#", trim: true, headers: true)"##,
+        "# exported rows\n",
+    ),
+];
+
+fn benchmark_parse_csv(c: &mut Criterion<Allocations>) {
+    let timezone = TimeZone::default();
+    let mut group = c.benchmark_group("vrl_stdlib/parse_csv/allocations");
+    group.throughput(Throughput::Elements(1));
+
+    for (name, source, comments) in CASES {
+        let program = compile(source, &vrl::stdlib::all()).unwrap().program;
+        let mut target = TargetValue {
+            value: value!({ message: (format!("{comments}{ROWS}")) }),
+            metadata: Value::from(value::ObjectMap::new()),
+            secrets: Secrets::new(),
+        };
+        let mut runtime = Runtime::default();
+
+        group.bench_function(*name, |b| {
+            b.iter(|| {
+                let result = runtime.resolve(&mut target, &program, &timezone);
+                runtime.clear();
+                result
+            });
+        });
+    }
+}
+
+criterion_group!(name = vrl_stdlib_parse_csv;
+                 config = Criterion::default().with_measurement(Allocations).without_plots();
+                 targets = benchmark_parse_csv);
+criterion_main!(vrl_stdlib_parse_csv);
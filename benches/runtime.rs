@@ -0,0 +1,67 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use vrl::compiler::{compile, runtime::Runtime, TargetValue, TimeZone};
+use vrl::value;
+use vrl::value::{Secrets, Value};
+
+#[path = "support/allocations.rs"]
+mod allocations;
+
+use allocations::Allocations;
+
+const SOURCE: &str = r"
+    status = to_int!(.status)
+    message = upcase!(.message)
+    .success = status < 400
+    .message = message
+";
+
+const TARGETS: usize = 100;
+
+fn targets() -> Vec<TargetValue> {
+    (0..TARGETS)
+        .map(|i| TargetValue {
+            value: value!({ status: (200 + i), message: "hello world" }),
+            metadata: Value::from(value::ObjectMap::new()),
+            secrets: Secrets::new(),
+        })
+        .collect()
+}
+
+fn benchmark_resolve(c: &mut Criterion<Allocations>) {
+    let program = compile(SOURCE, &vrl::stdlib::all()).unwrap().program;
+    let timezone = TimeZone::default();
+    let mut group = c.benchmark_group("vrl_compiler/runtime/allocations");
+    group.throughput(Throughput::Elements(TARGETS as u64));
+
+    let mut runtime = Runtime::default();
+    group.bench_function("resolve_each", |b| {
+        b.iter_batched(
+            targets,
+            |mut targets| {
+                targets
+                    .iter_mut()
+                    .map(|target| {
+                        let result = runtime.resolve(target, &program, &timezone);
+                        runtime.clear();
+                        result
+                    })
+                    .collect::<Vec<_>>()
+            },
+            BatchSize::SmallInput,
+        );
+    });
+
+    let mut runtime = Runtime::default();
+    group.bench_function("resolve_batch", |b| {
+        b.iter_batched(
+            targets,
+            |mut targets| runtime.resolve_batch(&mut targets, &program, &timezone),
+            BatchSize::SmallInput,
+        );
+    });
+}
+
+criterion_group!(name = vrl_compiler_runtime;
+                 config = Criterion::default().with_measurement(Allocations).without_plots();
+                 targets = benchmark_resolve);
+criterion_main!(vrl_compiler_runtime);
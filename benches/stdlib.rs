@@ -317,6 +317,11 @@ bench_function! {
         args: func_args![value: "c29tZSs9c3RyaW5nL3ZhbHVl"],
         want: Ok("some+=string/value"),
     }
+
+    large {
+        args: func_args![value: "aGVsbG8g".repeat(1024)],
+        want: Ok("hello ".repeat(1024)),
+    }
 }
 
 bench_function! {
@@ -401,6 +406,11 @@ bench_function! {
         args: func_args![value: "some+=string/value"],
         want: Ok("c29tZSs9c3RyaW5nL3ZhbHVl"),
     }
+
+    large {
+        args: func_args![value: "hello ".repeat(1024)],
+        want: Ok("aGVsbG8g".repeat(1024)),
+    }
 }
 
 bench_function! {
@@ -659,19 +669,20 @@ bench_function! {
 bench_function! {
     get_env_var => vrl::stdlib::GetEnvVar;
 
-    // CARGO is set by `cargo`
-    get {
+    // Environment access is denied by default.
+    denied {
         args: func_args![name: "CARGO"],
-        want: Ok(env!("CARGO")),
+        want: Err("environment access is denied"),
     }
 }
 
 bench_function! {
     get_hostname => vrl::stdlib::GetHostname;
 
-    get {
+    // Hostname access is denied by default.
+    denied {
         args: func_args![],
-        want: Ok(hostname::get().unwrap().to_string_lossy()),
+        want: Err("hostname access is denied"),
     }
 }
 
@@ -1681,6 +1692,16 @@ bench_function! {
         args: func_args![value: "foo,bar"],
         want: Ok(value!(["foo","bar"]))
     }
+
+    large {
+        args: func_args![
+            value: (0..10_000)
+                .map(|row| (0..100).map(|i| format!("{row}.{i}")).collect::<Vec<_>>().join(","))
+                .collect::<Vec<_>>()
+                .join("\n")
+        ],
+        want: Ok((0..100).map(|i| format!("0.{i}")).collect::<Vec<_>>())
+    }
 }
 
 bench_function! {
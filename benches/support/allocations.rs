@@ -0,0 +1,89 @@
+//! Counts the allocations made by a benchmark, for use as a criterion [`Measurement`].
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::measurement::{Measurement, ValueFormatter};
+use criterion::Throughput;
+
+/// Counts the allocations made by the benchmarked code, so that the buffers reused across
+/// calls show up in the results.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+pub struct Allocations;
+
+impl Measurement for Allocations {
+    type Intermediate = usize;
+    type Value = usize;
+
+    fn start(&self) -> Self::Intermediate {
+        ALLOCATIONS.load(Ordering::Relaxed)
+    }
+
+    fn end(&self, start: Self::Intermediate) -> Self::Value {
+        ALLOCATIONS.load(Ordering::Relaxed) - start
+    }
+
+    fn add(&self, v1: &Self::Value, v2: &Self::Value) -> Self::Value {
+        v1 + v2
+    }
+
+    fn zero(&self) -> Self::Value {
+        0
+    }
+
+    fn to_f64(&self, value: &Self::Value) -> f64 {
+        *value as f64
+    }
+
+    fn formatter(&self) -> &dyn ValueFormatter {
+        &AllocationsFormatter
+    }
+}
+
+struct AllocationsFormatter;
+
+impl ValueFormatter for AllocationsFormatter {
+    fn scale_values(&self, _typical_value: f64, _values: &mut [f64]) -> &'static str {
+        "allocs"
+    }
+
+    fn scale_throughputs(
+        &self,
+        _typical_value: f64,
+        throughput: &Throughput,
+        values: &mut [f64],
+    ) -> &'static str {
+        if let Throughput::Elements(elements) = throughput {
+            for value in values {
+                *value /= *elements as f64;
+            }
+        }
+        "allocs/elem"
+    }
+
+    fn scale_for_machines(&self, _values: &mut [f64]) -> &'static str {
+        "allocs"
+    }
+}
@@ -3,6 +3,7 @@ use clap::Parser;
 use glob::glob;
 
 use vrl::compiler::{CompileConfig, TimeZone, VrlRuntime};
+use vrl::stdlib::{EnvironmentAccess, GeoipDatabaseAccess, HostnameAccess};
 use vrl::test::{get_tests_from_functions, run_tests, test_dir, Test, TestConfig};
 
 #[cfg(not(target_env = "msvc"))]
@@ -83,7 +84,15 @@ fn main() {
         tests,
         &cfg,
         &vrl::stdlib::all(),
-        || (CompileConfig::default(), ()),
+        || {
+            let mut config = CompileConfig::default();
+            // The `get_env`, `get_hostname` and `geoip_lookup` examples read from the host
+            // running the tests.
+            config.set_custom(EnvironmentAccess::Allowed);
+            config.set_custom(GeoipDatabaseAccess::Allowed);
+            config.set_custom(HostnameAccess::Allowed);
+            (config, ())
+        },
         |_| {},
     );
 }
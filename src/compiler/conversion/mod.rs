@@ -262,10 +262,8 @@ fn parse_unix_timestamp(timestamp_str: &str) -> LocalResult<DateTime<Utc>> {
 /// Returns an error if the string could not be matched by one of the
 /// predefined timestamp formats.
 fn parse_timestamp(tz: TimeZone, s: &str) -> Result<DateTime<Utc>, Error> {
-    for format in TIMESTAMP_LOCAL_FORMATS {
-        if let Ok(result) = tz.datetime_from_str(s, format) {
-            return Ok(result);
-        }
+    if let Some(result) = parse_local_formats(tz, s, TIMESTAMP_LOCAL_FORMATS) {
+        return Ok(result);
     }
 
     // This is equivalent to the "%s" format.
@@ -282,11 +280,30 @@ fn parse_timestamp(tz: TimeZone, s: &str) -> Result<DateTime<Utc>, Error> {
         return Ok(datetime_to_utc(&result));
     }
 
-    for format in TIMESTAMP_TZ_FORMATS {
-        if let Ok(result) = DateTime::parse_from_str(s, format) {
-            return Ok(datetime_to_utc(&result));
-        }
+    if let Some(result) = parse_tz_formats(s, TIMESTAMP_TZ_FORMATS) {
+        return Ok(result);
     }
 
     Err(Error::AutoTimestampParse { s: s.into() })
 }
+
+/// Parses a string with the first matching format of a set of formats without time zones,
+/// interpreting it in the given time zone.
+pub(crate) fn parse_local_formats(
+    tz: TimeZone,
+    s: &str,
+    formats: &[&str],
+) -> Option<DateTime<Utc>> {
+    formats
+        .iter()
+        .find_map(|format| tz.datetime_from_str(s, format).ok())
+}
+
+/// Parses a string with the first matching format of a set of formats with time zones, taking
+/// the offset from the string.
+pub(crate) fn parse_tz_formats(s: &str, formats: &[&str]) -> Option<DateTime<Utc>> {
+    formats
+        .iter()
+        .find_map(|format| DateTime::parse_from_str(s, format).ok())
+        .map(|result| datetime_to_utc(&result))
+}
@@ -397,7 +397,7 @@ impl Target {
                 // without any path appended and return early.
                 let path = match path.is_root() {
                     false => path,
-                    true => return ctx.state_mut().insert_variable(ident.clone(), value),
+                    true => return ctx.state_mut().insert_variable(ident, value),
                 };
 
                 // Update existing variable using the provided path, or create a
@@ -406,9 +406,7 @@ impl Target {
                     Some(stored) => {
                         stored.insert(path, value);
                     }
-                    None => ctx
-                        .state_mut()
-                        .insert_variable(ident.clone(), value.at_path(path)),
+                    None => ctx.state_mut().insert_variable(ident, value.at_path(path)),
                 }
             }
 
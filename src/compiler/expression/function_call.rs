@@ -8,14 +8,16 @@ use crate::compiler::{
         ArgumentList, Example, FunctionClosure, FunctionCompileContext, Parameter,
     },
     parser::{Ident, Node},
-    state::LocalEnv,
+    state::{LocalEnv, RuntimeState},
     type_def::Details,
     value::Kind,
-    CompileConfig, Context, Expression, Function, Resolved, Span, TypeDef,
+    CompileConfig, Context, Expression, Function, Resolved, Span, TargetValueRef, TimeZone,
+    TypeDef,
 };
 use crate::diagnostic::{DiagnosticMessage, Label, Note, Urls};
+use crate::value::{ObjectMap, Secrets, Value};
 
-use super::Block;
+use super::{Block, Expr};
 
 pub(crate) struct Builder<'a> {
     abort_on_error: bool,
@@ -428,8 +430,13 @@ impl<'a> Builder<'a> {
         // Asking for an infallible function to abort on error makes no sense.
         // We consider this an error at compile-time, because it makes the
         // resulting program incorrectly convey this function call might fail.
+        //
+        // An argument folded at compile time has the exact type of its value,
+        // which can make coercions such as `object!(parse_json!(s'{}'))`
+        // infallible. Those calls were valid before folding, so they still are.
         if self.abort_on_error
             && self.arguments_with_unknown_type_validity.is_empty()
+            && !self.has_folded_argument()
             && !expr
                 .type_info(state_before_function_args)
                 .result
@@ -473,6 +480,17 @@ impl<'a> Builder<'a> {
             }
         }
 
+        let folded = if self.function.fold() && self.arguments_with_unknown_type_validity.is_empty()
+        {
+            fold(expr.as_ref(), &self.list, state_before_function_args)
+        } else {
+            None
+        };
+        let (expr, folded) = match folded {
+            Some(folded) => (Box::new(folded) as Box<dyn Expression>, true),
+            None => (expr, false),
+        };
+
         Ok(CallCompilationResult {
             function_call: FunctionCall {
                 abort_on_error: self.abort_on_error,
@@ -484,11 +502,18 @@ impl<'a> Builder<'a> {
                 ident: self.function.identifier(),
                 function_id: self.function_id,
                 arguments: self.arguments.clone(),
+                folded,
             },
             error: invalid_argument_error,
         })
     }
 
+    fn has_folded_argument(&self) -> bool {
+        self.arguments
+            .iter()
+            .any(|argument| matches!(argument.expr(), Expr::FunctionCall(call) if call.folded))
+    }
+
     fn compile_closure(
         &mut self,
         closure_block: Option<Node<(Block, TypeDef)>>,
@@ -543,6 +568,57 @@ impl<'a> Builder<'a> {
     }
 }
 
+/// Resolves a call to a function that opted into [`Function::fold`], if all of
+/// its arguments are constant.
+///
+/// The folded call has the exact kind of its value, but keeps the fallibility
+/// of the original call, so that existing error handling such as `!` stays
+/// valid.
+fn fold(expr: &dyn Expression, arguments: &ArgumentList, state: &TypeState) -> Option<FoldedCall> {
+    if !arguments.is_constant(state) {
+        return None;
+    }
+
+    // Constant arguments can't read from the target, so an empty one is enough.
+    let mut value = Value::Object(ObjectMap::new());
+    let mut metadata = Value::Object(ObjectMap::new());
+    let mut secrets = Secrets::new();
+    let mut target = TargetValueRef {
+        value: &mut value,
+        metadata: &mut metadata,
+        secrets: &mut secrets,
+    };
+    let mut runtime_state = RuntimeState::default();
+    let timezone = TimeZone::default();
+    let mut ctx = Context::new(&mut target, &mut runtime_state, &timezone);
+
+    let value = expr.resolve(&mut ctx).ok()?;
+    let mut type_def = expr.type_def(state);
+    *type_def.kind_mut() = Kind::from(&value);
+
+    Some(FoldedCall { value, type_def })
+}
+
+#[derive(Debug, Clone)]
+struct FoldedCall {
+    value: Value,
+    type_def: TypeDef,
+}
+
+impl Expression for FoldedCall {
+    fn resolve(&self, _ctx: &mut Context) -> Resolved {
+        Ok(self.value.clone())
+    }
+
+    fn resolve_constant(&self, _state: &TypeState) -> Option<Value> {
+        Some(self.value.clone())
+    }
+
+    fn type_info(&self, state: &TypeState) -> TypeInfo {
+        TypeInfo::new(state, self.type_def.clone())
+    }
+}
+
 #[derive(Clone)]
 pub struct FunctionCall {
     abort_on_error: bool,
@@ -565,6 +641,9 @@ pub struct FunctionCall {
     #[allow(dead_code)]
     function_id: usize,
     arguments: Arc<Vec<Node<FunctionArgument>>>,
+
+    // Whether the call was resolved at compile time, see `fold`.
+    folded: bool,
 }
 
 impl FunctionCall {
@@ -677,6 +756,10 @@ impl Expression for FunctionCall {
         })
     }
 
+    fn resolve_constant(&self, state: &TypeState) -> Option<Value> {
+        self.expr.resolve_constant(state)
+    }
+
     fn type_info(&self, state: &TypeState) -> TypeInfo {
         let mut state = state.clone();
 
@@ -1382,4 +1465,78 @@ mod tests {
 
         assert_eq!(Ok(expected), params);
     }
+
+    #[test]
+    fn folded_calls_match_runtime() {
+        use crate::compiler::{compile, Program, TargetValue};
+        use crate::value;
+
+        let cases = [
+            (
+                r#"upcase("héllo")"#,
+                "upcase(string!(.input))",
+                value!("héllo"),
+            ),
+            (
+                r#"downcase("HÉLLO")"#,
+                "downcase(string!(.input))",
+                value!("HÉLLO"),
+            ),
+            (
+                r#"encode_base64("hello")"#,
+                "encode_base64(string!(.input))",
+                value!("hello"),
+            ),
+            (
+                r#"decode_base64!("aGVsbG8=")"#,
+                "decode_base64!(.input)",
+                value!("aGVsbG8="),
+            ),
+            (
+                r#"parse_csv!("a,b,c")"#,
+                "parse_csv!(.input)",
+                value!("a,b,c"),
+            ),
+            (
+                r#"parse_json!(s'["bar", true]')"#,
+                "parse_json!(.input)",
+                value!(r#"["bar", true]"#),
+            ),
+        ];
+
+        for (constant, dynamic, input) in cases {
+            let folded = compile(constant, &crate::stdlib::all()).unwrap().program;
+            let runtime = compile(dynamic, &crate::stdlib::all()).unwrap().program;
+
+            let resolve = |program: &Program| {
+                let mut target = TargetValue {
+                    value: Value::from(ObjectMap::from([("input".into(), input.clone())])),
+                    metadata: value!({}),
+                    secrets: Secrets::new(),
+                };
+                let mut state = RuntimeState::default();
+                let timezone = TimeZone::default();
+                program
+                    .resolve(&mut Context::new(&mut target, &mut state, &timezone))
+                    .unwrap()
+            };
+            let value = resolve(&folded);
+
+            assert_eq!(value, resolve(&runtime), "{constant}");
+
+            // The folded call is typed by its value, which the runtime type has to allow.
+            let folded_type = folded.final_type_info().result;
+            let runtime_type = runtime.final_type_info().result;
+            assert_eq!(folded_type.kind(), &Kind::from(&value), "{constant}");
+            assert!(
+                runtime_type.kind().is_superset(folded_type.kind()).is_ok(),
+                "{constant}"
+            );
+            assert_eq!(
+                folded_type.is_fallible(),
+                runtime_type.is_fallible(),
+                "{constant}"
+            );
+        }
+    }
 }
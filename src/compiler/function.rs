@@ -68,6 +68,21 @@ pub trait Function: Send + Sync + fmt::Debug {
     fn closure(&self) -> Option<closure::Definition> {
         None
     }
+
+    /// Whether calls to this function can be folded into their result at
+    /// compile time.
+    ///
+    /// When this returns `true` and all arguments of a call are constant, the
+    /// call is resolved once during compilation, and the resulting value is
+    /// returned at runtime instead. Calls that fail are left to fail at
+    /// runtime.
+    ///
+    /// Only functions whose result depends on nothing but their arguments
+    /// should opt in, which excludes functions reading the clock, the
+    /// environment, the target or any other external state.
+    fn fold(&self) -> bool {
+        false
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -378,6 +393,16 @@ impl ArgumentList {
             .ok_or(Error::ExpectedFunctionClosure)
     }
 
+    /// Whether all arguments resolve to a constant value, and no closure is
+    /// attached.
+    pub(crate) fn is_constant(&self, state: &TypeState) -> bool {
+        self.closure.is_none()
+            && self
+                .arguments
+                .values()
+                .all(|expr| expr.resolve_constant(state).is_some())
+    }
+
     pub(crate) fn keywords(&self) -> Vec<&'static str> {
         self.arguments.keys().copied().collect::<Vec<_>>()
     }
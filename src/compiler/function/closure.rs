@@ -274,7 +274,7 @@ fn insert(state: &mut RuntimeState, ident: Option<&Ident>, data: Value) -> Optio
 fn cleanup(state: &mut RuntimeState, ident: Option<&Ident>, data: Option<Value>) {
     match (ident, data) {
         (Some(ident), Some(value)) => {
-            state.insert_variable(ident.clone(), value);
+            state.insert_variable(ident, value);
         }
         (Some(ident), None) => state.remove_variable(ident),
         _ => {}
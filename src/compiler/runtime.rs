@@ -103,4 +103,88 @@ impl Runtime {
             Err(err @ ExpressionError::Error { .. }) => Err(Terminate::Error(err)),
         }
     }
+
+    /// Resolve the provided [`Program`] against each of the provided
+    /// [`Target`]s, in order.
+    ///
+    /// This is equivalent to calling [`Runtime::resolve`] for each target,
+    /// and clearing the runtime in between. Instead of clearing, the
+    /// variables of the program are reset to `null` between targets, so their
+    /// slots are only allocated for the first one. The runtime is cleared once
+    /// all targets are resolved.
+    pub fn resolve_batch<T: Target>(
+        &mut self,
+        targets: &mut [T],
+        program: &Program,
+        timezone: &TimeZone,
+    ) -> Vec<RuntimeResult> {
+        let results = targets
+            .iter_mut()
+            .map(|target| {
+                let result = self.resolve(target, program, timezone);
+                self.state.reset_variables();
+                result
+            })
+            .collect();
+        self.clear();
+
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{compile, TargetValue};
+    use crate::stdlib;
+    use crate::value;
+    use crate::value::Secrets;
+
+    fn target(value: Value) -> TargetValue {
+        TargetValue {
+            value,
+            metadata: value!({}),
+            secrets: Secrets::new(),
+        }
+    }
+
+    #[test]
+    fn resolve_batch_matches_resolve() {
+        let source = r"
+            count = to_int!(.count)
+            if count < 0 { abort }
+            info.count = count * 2
+            . = merge(., info)
+            upcase!(.name)
+        ";
+        let program = compile(source, &stdlib::all()).unwrap().program;
+        let timezone = TimeZone::default();
+        let events = [
+            value!({ count: "1", name: "a" }),
+            value!({ count: "-1", name: "b" }),
+            value!({ count: "x", name: "c" }),
+            value!({ count: 3, name: "d" }),
+        ];
+
+        let mut batch = events.iter().cloned().map(target).collect::<Vec<_>>();
+        let batch_results = Runtime::default().resolve_batch(&mut batch, &program, &timezone);
+
+        for ((event, batch_target), batch_result) in
+            events.into_iter().zip(batch).zip(batch_results)
+        {
+            let mut target = target(event);
+            let result = Runtime::default().resolve(&mut target, &program, &timezone);
+
+            assert_eq!(batch_result, result);
+            assert_eq!(batch_target.value, target.value);
+        }
+
+        let mut runtime = Runtime::default();
+        runtime.resolve_batch(
+            &mut [target(value!({ count: 1, name: "a" }))],
+            &program,
+            &timezone,
+        );
+        assert!(runtime.is_empty());
+    }
 }
@@ -182,6 +182,17 @@ impl RuntimeState {
         self.variables.clear();
     }
 
+    /// Resets all variables to `null`, keeping their slots allocated.
+    ///
+    /// Reading an unset variable resolves to `null` as well, so this behaves
+    /// like [`RuntimeState::clear`], but the next run of the same program
+    /// assigns its variables without allocating them again.
+    pub(crate) fn reset_variables(&mut self) {
+        for value in self.variables.values_mut() {
+            *value = Value::Null;
+        }
+    }
+
     #[must_use]
     pub fn variable(&self, ident: &Ident) -> Option<&Value> {
         self.variables.get(ident)
@@ -191,8 +202,13 @@ impl RuntimeState {
         self.variables.get_mut(ident)
     }
 
-    pub(crate) fn insert_variable(&mut self, ident: Ident, value: Value) {
-        self.variables.insert(ident, value);
+    pub(crate) fn insert_variable(&mut self, ident: &Ident, value: Value) {
+        match self.variables.get_mut(ident) {
+            Some(stored) => *stored = value,
+            None => {
+                self.variables.insert(ident.clone(), value);
+            }
+        }
     }
 
     pub(crate) fn remove_variable(&mut self, ident: &Ident) {
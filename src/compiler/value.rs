@@ -1,4 +1,5 @@
 mod arithmetic;
+pub(crate) mod concat_buffer;
 mod convert;
 mod error;
 pub mod kind;
@@ -7,9 +7,9 @@ use crate::compiler::{
     ExpressionError,
 };
 use crate::value::{ObjectMap, Value};
-use bytes::{BufMut, Bytes, BytesMut};
+use bytes::Bytes;
 
-use super::ValueError;
+use super::{concat_buffer, ValueError};
 
 pub trait VrlValueArithmetic: Sized {
     /// Similar to [`std::ops::Mul`], but fallible (e.g. `TryMul`).
@@ -137,13 +137,7 @@ impl VrlValueArithmetic for Value {
                 lhs.add(rhs).into()
             }
             (lhs @ Value::Bytes(_), Value::Null) => lhs,
-            (Value::Bytes(lhs), Value::Bytes(rhs)) => {
-                #[allow(clippy::arithmetic_side_effects)]
-                let mut value = BytesMut::with_capacity(lhs.len() + rhs.len());
-                value.put(lhs);
-                value.put(rhs);
-                value.freeze().into()
-            }
+            (Value::Bytes(lhs), Value::Bytes(rhs)) => concat_buffer::concat(&lhs, &rhs).into(),
             (Value::Null, rhs @ Value::Bytes(_)) => rhs,
             (lhs, rhs) => return Err(ValueError::Add(lhs.kind(), rhs.kind())),
         };
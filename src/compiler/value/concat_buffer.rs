@@ -0,0 +1,94 @@
+//! An experimental buffer for the strings built by string concatenation (`+`).
+//!
+//! With the `concat_buffer` feature, the result of adding two strings is carved out of a
+//! thread-local chunk rather than allocated on its own. A chunk is reused once every string
+//! carved out of it has been dropped, so programs concatenating many short-lived strings mostly
+//! stop calling the allocator. In exchange, a string that is kept around keeps its whole chunk
+//! alive. No other value built at runtime goes through this buffer.
+
+use bytes::{BufMut, Bytes, BytesMut};
+
+/// Concatenates two byte strings.
+#[cfg(not(feature = "concat_buffer"))]
+pub(crate) fn concat(lhs: &[u8], rhs: &[u8]) -> Bytes {
+    standalone(lhs, rhs)
+}
+
+/// Concatenates two byte strings, carving the result out of the thread's chunk.
+#[cfg(feature = "concat_buffer")]
+pub(crate) fn concat(lhs: &[u8], rhs: &[u8]) -> Bytes {
+    use std::cell::RefCell;
+
+    /// The size of the chunks strings are carved out of.
+    const CHUNK_SIZE: usize = 16 * 1024;
+
+    /// Strings larger than this are allocated on their own, so that they don't pin a chunk.
+    const MAX_LEN: usize = 1024;
+
+    thread_local! {
+        static CHUNK: RefCell<BytesMut> = RefCell::new(BytesMut::new());
+    }
+
+    #[allow(clippy::arithmetic_side_effects)]
+    let len = lhs.len() + rhs.len();
+    if len > MAX_LEN {
+        return standalone(lhs, rhs);
+    }
+
+    CHUNK
+        .try_with(|chunk| {
+            let mut chunk = chunk.borrow_mut();
+            if chunk.capacity() < len {
+                // Takes the chunk back in place when nothing carved out of it is alive anymore.
+                chunk.reserve(CHUNK_SIZE);
+            }
+            chunk.put_slice(lhs);
+            chunk.put_slice(rhs);
+            chunk.split().freeze()
+        })
+        // The thread is going away, so there's no chunk to carve the string out of.
+        .unwrap_or_else(|_| standalone(lhs, rhs))
+}
+
+fn standalone(lhs: &[u8], rhs: &[u8]) -> Bytes {
+    #[allow(clippy::arithmetic_side_effects)]
+    let mut buf = BytesMut::with_capacity(lhs.len() + rhs.len());
+    buf.put_slice(lhs);
+    buf.put_slice(rhs);
+    buf.freeze()
+}
+
+#[cfg(all(test, feature = "concat_buffer"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strings_do_not_overlap() {
+        let strings = (0..10_000)
+            .map(|i| {
+                let content = i.to_string().repeat(i % 7);
+                let (lhs, rhs) = content.split_at(content.len() / 2);
+                (content.clone(), concat(lhs.as_bytes(), rhs.as_bytes()))
+            })
+            .collect::<Vec<_>>();
+
+        for (content, built) in strings {
+            assert_eq!(built, content.as_bytes());
+        }
+    }
+
+    #[test]
+    fn chunk_reused_once_dropped() {
+        let first = concat(b"fo", b"o");
+        let start = first.as_ptr();
+        drop(first);
+
+        // Each string is dropped straight away, so once the chunk is full it is taken back
+        // rather than replaced.
+        let half = [b'x'; 256];
+        let reused = (0..100)
+            .map(|_| concat(&half, &half).as_ptr())
+            .any(|ptr| ptr == start);
+        assert!(reused);
+    }
+}
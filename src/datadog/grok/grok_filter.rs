@@ -1,8 +1,8 @@
 use std::{convert::TryFrom, fmt, string::ToString};
 
 use crate::compiler::prelude::Bytes;
-use crate::parsing::query_string::parse_query_string;
-use crate::parsing::ruby_hash::parse_ruby_hash;
+use crate::parsing::query_string::{parse_query_string, Repeated};
+use crate::parsing::ruby_hash::{parse_ruby_hash, Options};
 use crate::parsing::xml::{parse_xml, ParseOptions};
 use crate::value::Value;
 use ordered_float::NotNan;
@@ -201,11 +201,11 @@ pub fn apply_filter(value: &Value, filter: &GrokFilter) -> Result<Value, Interna
             serde_json::from_slice::<'_, serde_json::Value>(b)
         }),
         GrokFilter::Rubyhash => parse_value_error_prone(value, filter, |b| {
-            parse_ruby_hash(String::from_utf8_lossy(b).as_ref())
+            parse_ruby_hash(String::from_utf8_lossy(b).as_ref(), Options::DATADOG_GROK)
+        }),
+        GrokFilter::Querystring => parse_value_error_prone(value, filter, |s| {
+            parse_query_string(s, true, Repeated::Array, true)
         }),
-        GrokFilter::Querystring => {
-            parse_value_error_prone(value, filter, |s| parse_query_string(s, true))
-        }
         GrokFilter::Boolean => parse_value(value, filter, |b| {
             "true".eq_ignore_ascii_case(String::from_utf8_lossy(b).as_ref())
         }),
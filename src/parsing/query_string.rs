@@ -1,36 +1,83 @@
 use crate::compiler::prelude::*;
+use std::borrow::Cow;
 use std::collections::BTreeMap;
 use url::form_urlencoded;
 
-pub(crate) fn parse_query_string(bytes: &Bytes, ignore_keys_without_values: bool) -> Resolved {
+/// How the values of a key appearing more than once are combined.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Repeated {
+    /// Collect all the values into an array, in order.
+    Array,
+    /// Keep the first value.
+    First,
+    /// Keep the last value.
+    Last,
+}
+
+pub(crate) fn parse_query_string(
+    bytes: &Bytes,
+    ignore_keys_without_values: bool,
+    repeated: Repeated,
+    decode: bool,
+) -> Resolved {
     let mut query_string = bytes.as_ref();
     if !query_string.is_empty() && query_string[0] == b'?' {
         query_string = &query_string[1..];
     }
     let mut result = BTreeMap::new();
-    let parsed = form_urlencoded::parse(query_string);
+    let parsed: Box<dyn Iterator<Item = (Cow<str>, Cow<str>)>> = if decode {
+        Box::new(form_urlencoded::parse(query_string))
+    } else {
+        Box::new(split_pairs(query_string))
+    };
     for (k, value) in parsed {
         let value = value.as_ref();
         if ignore_keys_without_values && value.is_empty() {
             continue;
         }
-        result
-            .entry(k.into_owned().into())
-            .and_modify(|v| {
-                match v {
-                    Value::Array(v) => {
-                        v.push(value.into());
-                    }
-                    v => {
-                        *v = Value::Array(vec![v.clone(), value.into()]);
-                    }
-                };
-            })
-            .or_insert_with(|| value.into());
+        let entry = result.entry(k.into_owned().into());
+        match repeated {
+            Repeated::Array => {
+                entry
+                    .and_modify(|v| {
+                        match v {
+                            Value::Array(v) => {
+                                v.push(value.into());
+                            }
+                            v => {
+                                *v = Value::Array(vec![v.clone(), value.into()]);
+                            }
+                        };
+                    })
+                    .or_insert_with(|| value.into());
+            }
+            Repeated::First => {
+                entry.or_insert_with(|| value.into());
+            }
+            Repeated::Last => {
+                entry
+                    .and_modify(|v| *v = value.into())
+                    .or_insert_with(|| value.into());
+            }
+        }
     }
     Ok(result.into())
 }
 
+/// Splits a query string into its key/value pairs, leaving them percent-encoded.
+fn split_pairs(query_string: &[u8]) -> impl Iterator<Item = (Cow<str>, Cow<str>)> {
+    query_string
+        .split(|byte| *byte == b'&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = match pair.iter().position(|byte| *byte == b'=') {
+                Some(index) => (&pair[..index], &pair[index + 1..]),
+                None => (pair, &[][..]),
+            };
+            (String::from_utf8_lossy(key), String::from_utf8_lossy(value))
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -38,7 +85,13 @@ mod tests {
 
     #[test]
     fn test_parses_complete() {
-        let result = parse_query_string(&"foo=%2B1&bar=2&xyz=&abc".into(), false).unwrap();
+        let result = parse_query_string(
+            &"foo=%2B1&bar=2&xyz=&abc".into(),
+            false,
+            Repeated::Array,
+            true,
+        )
+        .unwrap();
         assert_eq!(
             result,
             Value::from(btreemap! {
@@ -52,7 +105,8 @@ mod tests {
 
     #[test]
     fn test_parses_multiple_values() {
-        let result = parse_query_string(&"foo=bar&foo=xyz".into(), false).unwrap();
+        let result =
+            parse_query_string(&"foo=bar&foo=xyz".into(), false, Repeated::Array, true).unwrap();
         assert_eq!(
             result,
             Value::from(btreemap! {
@@ -63,7 +117,13 @@ mod tests {
 
     #[test]
     fn test_parses_ruby_on_rails_multiple_values() {
-        let result = parse_query_string(&"?foo%5b%5d=bar&foo%5b%5d=xyz".into(), false).unwrap();
+        let result = parse_query_string(
+            &"?foo%5b%5d=bar&foo%5b%5d=xyz".into(),
+            false,
+            Repeated::Array,
+            true,
+        )
+        .unwrap();
         assert_eq!(
             result,
             Value::from(btreemap! {
@@ -74,7 +134,7 @@ mod tests {
 
     #[test]
     fn test_parses_empty_key() {
-        let result = parse_query_string(&"=&=".into(), false).unwrap();
+        let result = parse_query_string(&"=&=".into(), false, Repeated::Array, true).unwrap();
         assert_eq!(
             result,
             Value::from(btreemap! {
@@ -85,7 +145,7 @@ mod tests {
 
     #[test]
     fn test_parses_single_key() {
-        let result = parse_query_string(&"foo".into(), false).unwrap();
+        let result = parse_query_string(&"foo".into(), false, Repeated::Array, true).unwrap();
         assert_eq!(
             result,
             Value::from(btreemap! {
@@ -96,19 +156,64 @@ mod tests {
 
     #[test]
     fn ignores_key_without_values() {
-        let result = parse_query_string(&"foo".into(), true).unwrap();
+        let result = parse_query_string(&"foo".into(), true, Repeated::Array, true).unwrap();
         assert_eq!(result, Value::from(btreemap! {}));
     }
 
+    #[test]
+    fn test_parses_repeated_first() {
+        let result =
+            parse_query_string(&"a=1&b=2&a=3".into(), false, Repeated::First, true).unwrap();
+        assert_eq!(
+            result,
+            Value::from(btreemap! {
+                "a" => "1",
+                "b" => "2",
+            })
+        );
+    }
+
+    #[test]
+    fn test_parses_repeated_last() {
+        let result =
+            parse_query_string(&"a=1&b=2&a=3".into(), false, Repeated::Last, true).unwrap();
+        assert_eq!(
+            result,
+            Value::from(btreemap! {
+                "a" => "3",
+                "b" => "2",
+            })
+        );
+    }
+
+    #[test]
+    fn test_parses_without_decoding() {
+        let result = parse_query_string(
+            &"?foo%5b%5d=a%20b&bar=c+d&baz".into(),
+            false,
+            Repeated::Array,
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            Value::from(btreemap! {
+                "foo%5b%5d" => "a%20b",
+                "bar" => "c+d",
+                "baz" => "",
+            })
+        );
+    }
+
     #[test]
     fn test_parses_empty_string() {
-        let result = parse_query_string(&"".into(), false).unwrap();
+        let result = parse_query_string(&"".into(), false, Repeated::Array, true).unwrap();
         assert_eq!(result, Value::from(btreemap! {}));
     }
 
     #[test]
     fn test_parses_if_starts_with_question_mark() {
-        let result = parse_query_string(&"?foo=bar".into(), false).unwrap();
+        let result = parse_query_string(&"?foo=bar".into(), false, Repeated::Array, true).unwrap();
         assert_eq!(
             result,
             Value::from(btreemap! {
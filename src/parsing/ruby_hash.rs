@@ -2,18 +2,38 @@ use crate::compiler::prelude::*;
 use nom::{
     branch::alt,
     bytes::complete::{escaped, tag, take_while, take_while1},
-    character::complete::{char, digit1, satisfy},
-    combinator::{cut, map, opt, recognize, value},
+    character::complete::{char, digit1, one_of, satisfy},
+    combinator::{cut, map, map_res, not, opt, recognize, value, verify},
     error::{context, ContextError, FromExternalError, ParseError},
     multi::{many1, separated_list0},
     number::complete::double,
-    sequence::{preceded, separated_pair, terminated, tuple},
+    sequence::{pair, preceded, separated_pair, terminated, tuple},
     AsChar, IResult, InputTakeAtPosition,
 };
 use std::num::ParseIntError;
 
-pub(crate) fn parse_ruby_hash(input: &str) -> ExpressionResult<Value> {
-    let result = parse_hash(input)
+/// Controls how Ruby values map to VRL values.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Options {
+    /// Removes the leading colon of symbols, such as `:foo`, which otherwise become strings
+    /// with the colon kept.
+    pub(crate) strip_symbol_colon: bool,
+
+    /// Parses numbers without a fraction or an exponent as integers, rather than floats.
+    pub(crate) integers: bool,
+}
+
+impl Options {
+    /// The behavior of the Datadog grok `rubyhash` filter, which parses all numbers as floats.
+    pub(crate) const DATADOG_GROK: Self = Self {
+        strip_symbol_colon: true,
+        integers: false,
+    };
+}
+
+/// Parses a Ruby hash.
+pub(crate) fn parse_ruby_hash(input: &str, options: Options) -> ExpressionResult<Value> {
+    let result = parse_hash(input, options)
         .map_err(|err| match err {
             nom::Err::Error(err) | nom::Err::Failure(err) => {
                 // Create a descriptive error message if possible.
@@ -85,6 +105,15 @@ fn parse_boolean<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str,
     alt((parse_true, parse_false))(input)
 }
 
+fn parse_integer<'a, E: HashParseError<&'a str>>(input: &'a str) -> IResult<&'a str, i64, E> {
+    // Anything followed by a fraction or an exponent is left to the float parser, as are
+    // integers too large for an `i64`.
+    map_res(
+        terminated(recognize(pair(opt(char('-')), digit1)), not(one_of(".eE"))),
+        str::parse,
+    )(input)
+}
+
 fn parse_nil<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Value, E> {
     value(Value::Null, tag("nil"))(input)
 }
@@ -111,13 +140,20 @@ where
 
 fn parse_colon_key<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
     input: &'a str,
+    options: Options,
 ) -> IResult<&'a str, KeyString, E> {
     map(
         preceded(
             char(':'),
             alt((parse_str('"'), parse_str('\''), parse_symbol_key)),
         ),
-        KeyString::from,
+        |symbol: &str| {
+            if options.strip_symbol_colon {
+                KeyString::from(symbol)
+            } else {
+                KeyString::from(format!(":{symbol}"))
+            }
+        },
     )(input)
 }
 
@@ -129,24 +165,30 @@ fn parse_colon_key<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
 // That being said, handling all the corner cases from Ruby's syntax would imply
 // increasing a lot the code complexity which is probably not necessary considering
 // that Vector is not a Ruby parser.
-fn parse_key<'a, E: HashParseError<&'a str>>(input: &'a str) -> IResult<&'a str, KeyString, E> {
+fn parse_key<'a, E: HashParseError<&'a str>>(
+    input: &'a str,
+    options: Options,
+) -> IResult<&'a str, KeyString, E> {
     alt((
         map(
             alt((parse_str('"'), parse_str('\''), parse_symbol_key, digit1)),
             KeyString::from,
         ),
-        parse_colon_key,
+        |input| parse_colon_key(input, options),
     ))(input)
 }
 
-fn parse_array<'a, E: HashParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Value, E> {
+fn parse_array<'a, E: HashParseError<&'a str>>(
+    input: &'a str,
+    options: Options,
+) -> IResult<&'a str, Value, E> {
     context(
         "array",
         map(
             preceded(
                 char('['),
                 cut(terminated(
-                    separated_list0(preceded(sp, char(',')), parse_value),
+                    separated_list0(preceded(sp, char(',')), |input| parse_value(input, options)),
                     preceded(sp, char(']')),
                 )),
             ),
@@ -157,15 +199,19 @@ fn parse_array<'a, E: HashParseError<&'a str>>(input: &'a str) -> IResult<&'a st
 
 fn parse_key_value<'a, E: HashParseError<&'a str>>(
     input: &'a str,
+    options: Options,
 ) -> IResult<&'a str, (KeyString, Value), E> {
     separated_pair(
-        preceded(sp, parse_key),
+        preceded(sp, |input| parse_key(input, options)),
         cut(preceded(sp, alt((tag(":"), tag("=>"))))),
-        parse_value,
+        |input| parse_value(input, options),
     )(input)
 }
 
-fn parse_hash<'a, E: HashParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Value, E> {
+fn parse_hash<'a, E: HashParseError<&'a str>>(
+    input: &'a str,
+    options: Options,
+) -> IResult<&'a str, Value, E> {
     context(
         "map",
         map(
@@ -173,7 +219,9 @@ fn parse_hash<'a, E: HashParseError<&'a str>>(input: &'a str) -> IResult<&'a str
                 char('{'),
                 cut(terminated(
                     map(
-                        separated_list0(preceded(sp, char(',')), parse_key_value),
+                        separated_list0(preceded(sp, char(',')), |input| {
+                            parse_key_value(input, options)
+                        }),
                         |tuple_vec| tuple_vec.into_iter().collect(),
                     ),
                     preceded(sp, char('}')),
@@ -184,15 +232,19 @@ fn parse_hash<'a, E: HashParseError<&'a str>>(input: &'a str) -> IResult<&'a str
     )(input)
 }
 
-fn parse_value<'a, E: HashParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Value, E> {
+fn parse_value<'a, E: HashParseError<&'a str>>(
+    input: &'a str,
+    options: Options,
+) -> IResult<&'a str, Value, E> {
     preceded(
         sp,
         alt((
             parse_nil,
-            parse_hash,
-            parse_array,
-            map(parse_colon_key, Value::from),
+            |input| parse_hash(input, options),
+            |input| parse_array(input, options),
+            map(|input| parse_colon_key(input, options), Value::from),
             map(parse_bytes, Value::Bytes),
+            map(verify(parse_integer, |_| options.integers), Value::Integer),
             map(double, |value| Value::Float(NotNan::new(value).unwrap())),
             map(parse_boolean, Value::Boolean),
         )),
@@ -203,20 +255,29 @@ fn parse_value<'a, E: HashParseError<&'a str>>(input: &'a str) -> IResult<&'a st
 mod tests {
     use super::*;
 
+    const TYPED: Options = Options {
+        strip_symbol_colon: true,
+        integers: true,
+    };
+
     #[test]
     fn test_parse_empty_object() {
-        let result = parse_ruby_hash("{}").unwrap();
+        let result = parse_ruby_hash("{}", Options::DATADOG_GROK).unwrap();
         assert!(result.is_object());
     }
 
     #[test]
     fn test_parse_arrow_empty_array() {
-        parse_ruby_hash("{ :array => [] }").unwrap();
+        parse_ruby_hash("{ :array => [] }", Options::DATADOG_GROK).unwrap();
     }
 
     #[test]
     fn test_parse_symbol_key() {
-        let result = parse_ruby_hash(r#"{ :key => "foo", :number => 500 }"#).unwrap();
+        let result = parse_ruby_hash(
+            r#"{ :key => "foo", :number => 500 }"#,
+            Options::DATADOG_GROK,
+        )
+        .unwrap();
         assert!(result.is_object());
         let result = result.as_object().unwrap();
         let value = result.get("key").unwrap();
@@ -227,7 +288,7 @@ mod tests {
 
     #[test]
     fn test_parse_symbol_colon_separator() {
-        let result = parse_ruby_hash(r#"{ key: "foo" }"#).unwrap();
+        let result = parse_ruby_hash(r#"{ key: "foo" }"#, Options::DATADOG_GROK).unwrap();
         assert!(result.is_object());
         let result = result.as_object().unwrap();
         let value = result.get("key").unwrap();
@@ -239,6 +300,7 @@ mod tests {
     fn test_parse_arrow_object() {
         let result = parse_ruby_hash(
             r#"{ "hello" => "world", "number" => 42, "float" => 4.2, "array" => [1, 2.3], "object" => { "nope" => nil } }"#,
+            Options::DATADOG_GROK,
         )
             .unwrap();
         assert!(result.is_object());
@@ -254,7 +316,7 @@ mod tests {
 
     #[test]
     fn test_parse_arrow_object_key_number() {
-        let result = parse_ruby_hash(r#"{ 42 => "hello world" }"#).unwrap();
+        let result = parse_ruby_hash(r#"{ 42 => "hello world" }"#, Options::DATADOG_GROK).unwrap();
         assert!(result.is_object());
         let result = result.as_object().unwrap();
         assert!(result.get("42").unwrap().is_bytes());
@@ -264,6 +326,7 @@ mod tests {
     fn test_parse_arrow_object_key_colon() {
         let result = parse_ruby_hash(
             r#"{ :colon => "hello world", :"double" => "quote", :'simple' => "quote" }"#,
+            Options::DATADOG_GROK,
         )
         .unwrap();
         assert!(result.is_object());
@@ -275,7 +338,11 @@ mod tests {
 
     #[test]
     fn test_parse_arrow_object_key_underscore() {
-        let result = parse_ruby_hash(r#"{ :with_underscore => "hello world" }"#).unwrap();
+        let result = parse_ruby_hash(
+            r#"{ :with_underscore => "hello world" }"#,
+            Options::DATADOG_GROK,
+        )
+        .unwrap();
         assert!(result.is_object());
         let result = result.as_object().unwrap();
         assert!(result.get("with_underscore").unwrap().is_bytes());
@@ -283,7 +350,7 @@ mod tests {
 
     #[test]
     fn test_parse_colon_object_double_quote() {
-        let result = parse_ruby_hash(r#"{ "hello": "world" }"#).unwrap();
+        let result = parse_ruby_hash(r#"{ "hello": "world" }"#, Options::DATADOG_GROK).unwrap();
         assert!(result.is_object());
         let result = result.as_object().unwrap();
         let value = result.get("hello").unwrap();
@@ -292,7 +359,7 @@ mod tests {
 
     #[test]
     fn test_parse_colon_object_single_quote() {
-        let result = parse_ruby_hash("{ 'hello': 'world' }").unwrap();
+        let result = parse_ruby_hash("{ 'hello': 'world' }", Options::DATADOG_GROK).unwrap();
         assert!(result.is_object());
         let result = result.as_object().unwrap();
         let value = result.get("hello").unwrap();
@@ -301,7 +368,7 @@ mod tests {
 
     #[test]
     fn test_parse_colon_object_no_quote() {
-        let result = parse_ruby_hash(r#"{ hello: "world" }"#).unwrap();
+        let result = parse_ruby_hash(r#"{ hello: "world" }"#, Options::DATADOG_GROK).unwrap();
         assert!(result.is_object());
         let result = result.as_object().unwrap();
         let value = result.get("hello").unwrap();
@@ -310,7 +377,7 @@ mod tests {
 
     #[test]
     fn test_parse_dash() {
-        let result = parse_ruby_hash(r#"{ "with-dash" => "foo" }"#).unwrap();
+        let result = parse_ruby_hash(r#"{ "with-dash" => "foo" }"#, Options::DATADOG_GROK).unwrap();
         assert!(result.is_object());
         let result = result.as_object().unwrap();
         assert!(result.get("with-dash").unwrap().is_bytes());
@@ -318,7 +385,11 @@ mod tests {
 
     #[test]
     fn test_parse_quote() {
-        let result = parse_ruby_hash(r#"{ "with'quote" => "and\"double\"quote" }"#).unwrap();
+        let result = parse_ruby_hash(
+            r#"{ "with'quote" => "and\"double\"quote" }"#,
+            Options::DATADOG_GROK,
+        )
+        .unwrap();
         assert!(result.is_object());
         let result = result.as_object().unwrap();
         let value = result.get("with'quote").unwrap();
@@ -327,16 +398,56 @@ mod tests {
 
     #[test]
     fn test_parse_weird_format() {
-        let result =
-            parse_ruby_hash(r#"{:hello=>"world",'number'=>42,"weird"=>'format\'here'}"#).unwrap();
+        let result = parse_ruby_hash(
+            r#"{:hello=>"world",'number'=>42,"weird"=>'format\'here'}"#,
+            Options::DATADOG_GROK,
+        )
+        .unwrap();
         assert!(result.is_object());
         let result = result.as_object().unwrap();
         assert!(result.get("hello").unwrap().is_bytes());
         assert!(result.get("number").unwrap().is_float());
     }
 
+    #[test]
+    fn test_parse_typed_values() {
+        let result = parse_ruby_hash(
+            r#"{ :level => :warn, "count" => -3, "ratio" => 1e3, "big" => 99999999999999999999, "user" => nil, "ok" => false, "tags" => [:a, 1] }"#,
+            TYPED,
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            crate::value!({
+                "level": "warn",
+                "count": (-3),
+                "ratio": 1000.0,
+                "big": 1e20,
+                "user": null,
+                "ok": false,
+                "tags": ["a", 1],
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_symbol_colon_kept() {
+        let result = parse_ruby_hash(
+            r#"{ :level => :warn, status: 200, "name" => ":x" }"#,
+            Options {
+                strip_symbol_colon: false,
+                integers: true,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            crate::value!({":level": ":warn", "status": 200, "name": ":x"})
+        );
+    }
+
     #[test]
     fn test_non_hash() {
-        assert!(parse_ruby_hash(r#""hello world""#).is_err());
+        assert!(parse_ruby_hash(r#""hello world""#, Options::DATADOG_GROK).is_err());
     }
 }
@@ -0,0 +1,334 @@
+use crate::compiler::prelude::*;
+use chrono::{
+    DateTime, Datelike, Days, LocalResult, NaiveDate, NaiveDateTime, Offset, TimeZone as _, Utc,
+    Weekday,
+};
+
+use super::is_business_day::{is_business_date, parse_holidays};
+use super::util::timezone_arg;
+
+fn add_business_days(
+    value: Value,
+    days: Value,
+    holidays: Option<Value>,
+    timezone: Option<Value>,
+) -> Resolved {
+    let timestamp = value.try_timestamp()?;
+    let days = days.try_integer()?;
+    let holidays = parse_holidays(holidays)?;
+
+    let shifted = match timezone_arg(timezone)? {
+        None => shift_local(timestamp, &Utc, days, &holidays),
+        Some(TimeZone::Named(tz)) => shift_local(timestamp, &tz, days, &holidays),
+        Some(TimeZone::Local) => shift_local(timestamp, &chrono::Local, days, &holidays),
+    }?;
+
+    Ok(shifted.into())
+}
+
+/// Moves `timestamp` by `days` business days in `tz`, keeping its wall-clock time, so that the
+/// result is at the same local time even when a DST change lies in between.
+fn shift_local<Tz: chrono::TimeZone>(
+    timestamp: DateTime<Utc>,
+    tz: &Tz,
+    days: i64,
+    holidays: &[NaiveDate],
+) -> Result<DateTime<Utc>, ExpressionError> {
+    let local = timestamp.with_timezone(tz);
+    let date = add_business_dates(local.date_naive(), days, holidays)?;
+    let shifted = NaiveDateTime::new(date, local.time());
+
+    let shifted = match tz.from_local_datetime(&shifted) {
+        // Of a time repeated when the clocks go back, the first one is picked.
+        LocalResult::Single(shifted) | LocalResult::Ambiguous(shifted, _) => shifted,
+        // The time was skipped when the clocks went forward, so the offset from before the
+        // gap is used, which lands as far past the gap as the time was into it.
+        LocalResult::None => {
+            let offset = local.offset().fix();
+            offset
+                .from_local_datetime(&shifted)
+                .single()
+                .ok_or("date out of range")?
+                .with_timezone(tz)
+        }
+    };
+
+    Ok(shifted.with_timezone(&Utc))
+}
+
+/// Moves `date` by `days` business days.
+fn add_business_dates(
+    mut date: NaiveDate,
+    days: i64,
+    holidays: &[NaiveDate],
+) -> Result<NaiveDate, ExpressionError> {
+    let mut holidays = holidays
+        .iter()
+        .copied()
+        .filter(|holiday| !matches!(holiday.weekday(), Weekday::Sat | Weekday::Sun))
+        .collect::<Vec<_>>();
+    holidays.sort_unstable();
+    holidays.dedup();
+
+    let forwards = days >= 0;
+    let shift = |date: NaiveDate, days: u64| {
+        if forwards {
+            date.checked_add_days(Days::new(days))
+        } else {
+            date.checked_sub_days(Days::new(days))
+        }
+        .ok_or("date out of range")
+    };
+
+    // Any seven consecutive days hold five weekdays, so whole weeks are skipped at once, giving
+    // back the holidays that fell in them. At least one day is left over, so that the last
+    // steps land on a business day.
+    let mut remaining = days.unsigned_abs();
+    while remaining > 5 {
+        let weeks = (remaining - 1) / 5;
+        let next = shift(date, weeks * 7)?;
+        let (first, last) = if forwards { (date, next) } else { (next, date) };
+        let skipped_holidays = holidays
+            .iter()
+            .filter(|&&holiday| holiday >= first && holiday <= last && holiday != date)
+            .count() as u64;
+
+        remaining = remaining - weeks * 5 + skipped_holidays;
+        date = next;
+    }
+
+    for _ in 0..remaining {
+        loop {
+            date = shift(date, 1)?;
+            if is_business_date(date, &holidays) {
+                break;
+            }
+        }
+    }
+
+    Ok(date)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct AddBusinessDays;
+
+impl Function for AddBusinessDays {
+    fn identifier(&self) -> &'static str {
+        "add_business_days"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::TIMESTAMP,
+                required: true,
+            },
+            Parameter {
+                keyword: "days",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "holidays",
+                kind: kind::ARRAY,
+                required: false,
+            },
+            Parameter {
+                keyword: "timezone",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "skip the weekend",
+                source: "add_business_days!(t'2021-02-12T12:00:00Z', 1)",
+                result: Ok("t'2021-02-15T12:00:00Z'"),
+            },
+            Example {
+                title: "skip a holiday",
+                source: r#"add_business_days!(t'2021-02-12T12:00:00Z', 1, holidays: ["2021-02-15"])"#,
+                result: Ok("t'2021-02-16T12:00:00Z'"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let days = arguments.required("days");
+        let holidays = arguments.optional("holidays");
+        let timezone = arguments.optional("timezone");
+
+        Ok(AddBusinessDaysFn {
+            value,
+            days,
+            holidays,
+            timezone,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AddBusinessDaysFn {
+    value: Box<dyn Expression>,
+    days: Box<dyn Expression>,
+    holidays: Option<Box<dyn Expression>>,
+    timezone: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for AddBusinessDaysFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let days = self.days.resolve(ctx)?;
+        let holidays = self
+            .holidays
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+        let timezone = self
+            .timezone
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        add_business_days(value, days, holidays, timezone)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::timestamp().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    test_function![
+        add_business_days => AddBusinessDays;
+
+        within_week {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 2, 8, 12, 0, 0).unwrap(), days: 3],
+            want: Ok(Utc.with_ymd_and_hms(2021, 2, 11, 12, 0, 0).unwrap()),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        across_weekend {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 2, 11, 12, 0, 0).unwrap(), days: 2],
+            want: Ok(Utc.with_ymd_and_hms(2021, 2, 15, 12, 0, 0).unwrap()),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        across_weekend_and_holiday {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 2, 12, 12, 0, 0).unwrap(),
+                             days: 2,
+                             holidays: vec!["2021-02-15"]],
+            want: Ok(Utc.with_ymd_and_hms(2021, 2, 17, 12, 0, 0).unwrap()),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        backwards {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 2, 16, 12, 0, 0).unwrap(),
+                             days: -2,
+                             holidays: vec!["2021-02-15"]],
+            want: Ok(Utc.with_ymd_and_hms(2021, 2, 11, 12, 0, 0).unwrap()),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        from_weekend {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 2, 13, 12, 0, 0).unwrap(), days: 1],
+            want: Ok(Utc.with_ymd_and_hms(2021, 2, 15, 12, 0, 0).unwrap()),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        zero {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 2, 13, 12, 0, 0).unwrap(), days: 0],
+            want: Ok(Utc.with_ymd_and_hms(2021, 2, 13, 12, 0, 0).unwrap()),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        timezone {
+            // Friday 23:00 UTC is already Saturday in Tokyo, so the next business day is Monday.
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 2, 12, 23, 0, 0).unwrap(),
+                             days: 1,
+                             timezone: "Asia/Tokyo"],
+            want: Ok(Utc.with_ymd_and_hms(2021, 2, 14, 23, 0, 0).unwrap()),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        dst_change {
+            // Clocks go forward on Sunday, so noon on Monday is an hour sooner in UTC.
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 3, 12, 17, 0, 0).unwrap(),
+                             days: 1,
+                             timezone: "America/New_York"],
+            want: Ok(Utc.with_ymd_and_hms(2021, 3, 15, 16, 0, 0).unwrap()),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        many_weeks {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 2, 8, 12, 0, 0).unwrap(),
+                             days: 1000,
+                             holidays: vec!["2021-12-24", "2022-12-26", "2022-12-26", "2023-12-24"]],
+            want: Ok(Utc.with_ymd_and_hms(2024, 12, 11, 12, 0, 0).unwrap()),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        many_weeks_backwards {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 2, 8, 12, 0, 0).unwrap(),
+                             days: -300,
+                             holidays: vec!["2020-12-25"]],
+            want: Ok(Utc.with_ymd_and_hms(2019, 12, 13, 12, 0, 0).unwrap()),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        out_of_range {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 2, 8, 12, 0, 0).unwrap(), days: i64::MAX],
+            want: Err("date out of range"),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+    ];
+
+    #[test]
+    fn skipping_weeks_matches_stepping_days() {
+        let holidays = [
+            "2021-02-15",
+            "2021-02-16",
+            "2021-02-20",
+            "2021-03-01",
+            "2021-04-02",
+        ]
+        .map(|holiday| NaiveDate::parse_from_str(holiday, "%Y-%m-%d").unwrap());
+
+        for start in 0..14 {
+            let start = NaiveDate::from_ymd_opt(2021, 2, 1).unwrap() + Days::new(start);
+            for days in -60..=60 {
+                let mut want = start;
+                for _ in 0..i64::unsigned_abs(days) {
+                    loop {
+                        want = if days < 0 {
+                            want - Days::new(1)
+                        } else {
+                            want + Days::new(1)
+                        };
+                        if is_business_date(want, &holidays) {
+                            break;
+                        }
+                    }
+                }
+
+                let got = add_business_dates(start, days, &holidays).unwrap();
+                assert_eq!(got, want, "{start} + {days} business days");
+            }
+        }
+    }
+}
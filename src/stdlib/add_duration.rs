@@ -0,0 +1,124 @@
+use crate::compiler::prelude::*;
+use chrono::TimeDelta;
+
+use super::parse_duration::parse_duration_str;
+
+/// Moves `timestamp` forwards (or backwards when `subtract` is set) by a duration string
+/// using the same grammar as `parse_duration`.
+pub(super) fn shift_timestamp(timestamp: Value, duration: Value, subtract: bool) -> Resolved {
+    let timestamp = timestamp.try_timestamp()?;
+    let duration = duration.try_bytes_utf8_lossy()?;
+    let delta = TimeDelta::from_std(parse_duration_str(&duration)?)
+        .map_err(|_| format!("duration '{duration}' is out of range"))?;
+
+    let shifted = if subtract {
+        timestamp.checked_sub_signed(delta)
+    } else {
+        timestamp.checked_add_signed(delta)
+    };
+
+    Ok(shifted
+        .ok_or_else(|| format!("timestamp out of range after applying '{duration}'"))?
+        .into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct AddDuration;
+
+impl Function for AddDuration {
+    fn identifier(&self) -> &'static str {
+        "add_duration"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::TIMESTAMP,
+                required: true,
+            },
+            Parameter {
+                keyword: "duration",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "add hours and minutes",
+            source: r#"add_duration!(t'2021-02-10T23:32:00Z', "1h30m")"#,
+            result: Ok("t'2021-02-11T01:02:00Z'"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let duration = arguments.required("duration");
+
+        Ok(AddDurationFn { value, duration }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AddDurationFn {
+    value: Box<dyn Expression>,
+    duration: Box<dyn Expression>,
+}
+
+impl FunctionExpression for AddDurationFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let duration = self.duration.resolve(ctx)?;
+
+        shift_timestamp(value, duration, false)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::timestamp().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    test_function![
+        add_duration => AddDuration;
+
+        crosses_day_boundary {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 2, 10, 23, 32, 0).unwrap(),
+                             duration: "1h30m"],
+            want: Ok(Utc.with_ymd_and_hms(2021, 2, 11, 1, 2, 0).unwrap()),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        crosses_month_boundary {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 2, 27, 12, 0, 0).unwrap(),
+                             duration: "2d"],
+            want: Ok(Utc.with_ymd_and_hms(2021, 3, 1, 12, 0, 0).unwrap()),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        sub_second {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 2, 10, 0, 0, 0).unwrap(),
+                             duration: "1s 500ms"],
+            want: Ok(Utc.with_ymd_and_hms(2021, 2, 10, 0, 0, 1).unwrap() + TimeDelta::milliseconds(500)),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        invalid_duration {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 2, 10, 0, 0, 0).unwrap(),
+                             duration: "soon"],
+            want: Err("unable to parse duration: 'expected number at 0'"),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+    ];
+}
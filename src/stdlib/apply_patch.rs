@@ -0,0 +1,345 @@
+use super::validate_json_schema::json_eq;
+use crate::compiler::prelude::*;
+
+fn apply_patch(mut value: Value, patch: Value) -> Resolved {
+    for operation in patch.try_array()? {
+        let operation = operation.try_object()?;
+        apply_operation(&mut value, &operation)?;
+    }
+
+    Ok(value)
+}
+
+/// Applies a single RFC 6902 JSON Patch operation to `value` in place.
+fn apply_operation(value: &mut Value, operation: &ObjectMap) -> Result<(), ExpressionError> {
+    let op = field(operation, "op")?.try_bytes_utf8_lossy()?.into_owned();
+    let path = field(operation, "path")?
+        .try_bytes_utf8_lossy()?
+        .into_owned();
+    let tokens = parse_pointer(&path)?;
+
+    match op.as_str() {
+        "add" => add(value, &tokens, &path, field(operation, "value")?.clone()),
+        "remove" => remove(value, &tokens, &path).map(|_| ()),
+        "replace" => {
+            let new = field(operation, "value")?.clone();
+            *pointer_mut(value, &tokens, &path)? = new;
+            Ok(())
+        }
+        "move" => {
+            let from = field(operation, "from")?
+                .try_bytes_utf8_lossy()?
+                .into_owned();
+            let from_tokens = parse_pointer(&from)?;
+            if tokens.len() > from_tokens.len() && tokens.starts_with(&from_tokens) {
+                return Err(format!("cannot move {from} into its own child {path}").into());
+            }
+            let moved = remove(value, &from_tokens, &from)?;
+            add(value, &tokens, &path, moved)
+        }
+        "copy" => {
+            let from = field(operation, "from")?
+                .try_bytes_utf8_lossy()?
+                .into_owned();
+            let copied = pointer_mut(value, &parse_pointer(&from)?, &from)?.clone();
+            add(value, &tokens, &path, copied)
+        }
+        "test" => {
+            // Numbers compare by value, as in JSON, so `1` and `1.0` are equal.
+            let expected = field(operation, "value")?;
+            if json_eq(pointer_mut(value, &tokens, &path)?, expected) {
+                Ok(())
+            } else {
+                Err(format!("test failed at {path}").into())
+            }
+        }
+        op => Err(format!("unknown patch operation {op:?}").into()),
+    }
+}
+
+fn field<'a>(operation: &'a ObjectMap, name: &str) -> Result<&'a Value, ExpressionError> {
+    operation
+        .get(name)
+        .ok_or_else(|| format!("patch operation is missing {name:?}").into())
+}
+
+/// Splits a JSON Pointer such as `/a/0/b` into its unescaped reference tokens.
+fn parse_pointer(pointer: &str) -> Result<Vec<String>, ExpressionError> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let Some(pointer) = pointer.strip_prefix('/') else {
+        return Err(format!("invalid JSON pointer {pointer:?}").into());
+    };
+
+    Ok(pointer
+        .split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect())
+}
+
+fn array_index(token: &str) -> Option<usize> {
+    let leading_zero = token.len() > 1 && token.starts_with('0');
+    if token.is_empty() || leading_zero || !token.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    token.parse().ok()
+}
+
+fn not_found(pointer: &str) -> ExpressionError {
+    format!("path not found: {pointer}").into()
+}
+
+fn pointer_mut<'a>(
+    value: &'a mut Value,
+    tokens: &[String],
+    pointer: &str,
+) -> Result<&'a mut Value, ExpressionError> {
+    let mut current = value;
+    for token in tokens {
+        current = match current {
+            Value::Object(map) => map.get_mut(token.as_str()),
+            Value::Array(array) => array_index(token).and_then(|index| array.get_mut(index)),
+            _ => None,
+        }
+        .ok_or_else(|| not_found(pointer))?;
+    }
+
+    Ok(current)
+}
+
+fn add(
+    value: &mut Value,
+    tokens: &[String],
+    pointer: &str,
+    new: Value,
+) -> Result<(), ExpressionError> {
+    let Some((last, parent)) = tokens.split_last() else {
+        *value = new;
+        return Ok(());
+    };
+
+    match pointer_mut(value, parent, pointer)? {
+        Value::Object(map) => {
+            map.insert(last.as_str().into(), new);
+        }
+        Value::Array(array) if last == "-" => array.push(new),
+        Value::Array(array) => match array_index(last) {
+            Some(index) if index <= array.len() => array.insert(index, new),
+            _ => return Err(not_found(pointer)),
+        },
+        _ => return Err(not_found(pointer)),
+    }
+
+    Ok(())
+}
+
+fn remove(value: &mut Value, tokens: &[String], pointer: &str) -> Resolved {
+    let Some((last, parent)) = tokens.split_last() else {
+        return Err("cannot remove the root value".into());
+    };
+
+    match pointer_mut(value, parent, pointer)? {
+        Value::Object(map) => map.remove(last.as_str()),
+        Value::Array(array) => match array_index(last) {
+            Some(index) if index < array.len() => Some(array.remove(index)),
+            _ => None,
+        },
+        _ => None,
+    }
+    .ok_or_else(|| not_found(pointer))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ApplyPatch;
+
+impl Function for ApplyPatch {
+    fn identifier(&self) -> &'static str {
+        "apply_patch"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "patch",
+                kind: kind::ARRAY,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "apply patch",
+                source: r#"apply_patch!({"a": 1, "b": [1, 2]}, [{"op": "replace", "path": "/a", "value": 2}, {"op": "add", "path": "/b/-", "value": 3}])"#,
+                result: Ok(r#"{"a": 2, "b": [1, 2, 3]}"#),
+            },
+            Example {
+                title: "failed test",
+                source: r#"apply_patch!({"a": 1}, [{"op": "test", "path": "/a", "value": 2}])"#,
+                result: Err(
+                    r#"function call error for "apply_patch" at (0:66): test failed at /a"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let patch = arguments.required("patch");
+
+        Ok(ApplyPatchFn { value, patch }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ApplyPatchFn {
+    value: Box<dyn Expression>,
+    patch: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ApplyPatchFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let patch = self.patch.resolve(ctx)?;
+
+        apply_patch(value, patch)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::any().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        apply_patch => ApplyPatch;
+
+        add_field {
+            args: func_args![value: value!({"a": {"b": 1}}), patch: value!([{"op": "add", "path": "/a/c", "value": [1]}])],
+            want: Ok(value!({"a": {"b": 1, "c": [1]}})),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        add_array_element {
+            args: func_args![value: value!({"a": [1, 3]}), patch: value!([
+                {"op": "add", "path": "/a/1", "value": 2},
+                {"op": "add", "path": "/a/-", "value": 4},
+            ])],
+            want: Ok(value!({"a": [1, 2, 3, 4]})),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        remove {
+            args: func_args![value: value!({"a": [1, 2], "b": true}), patch: value!([
+                {"op": "remove", "path": "/a/0"},
+                {"op": "remove", "path": "/b"},
+            ])],
+            want: Ok(value!({"a": [2]})),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        remove_missing {
+            args: func_args![value: value!({"a": 1}), patch: value!([{"op": "remove", "path": "/b"}])],
+            want: Err("path not found: /b"),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        replace {
+            args: func_args![value: value!({"a": {"b": 1}}), patch: value!([{"op": "replace", "path": "/a/b", "value": "x"}])],
+            want: Ok(value!({"a": {"b": "x"}})),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        replace_missing {
+            args: func_args![value: value!({"a": 1}), patch: value!([{"op": "replace", "path": "/b", "value": 2}])],
+            want: Err("path not found: /b"),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        move_value {
+            args: func_args![value: value!({"a": {"b": 1}, "c": []}), patch: value!([{"op": "move", "from": "/a/b", "path": "/c/0"}])],
+            want: Ok(value!({"a": {}, "c": [1]})),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        move_into_child {
+            args: func_args![value: value!({"a": {"b": 1}}), patch: value!([{"op": "move", "from": "/a", "path": "/a/b/c"}])],
+            want: Err("cannot move /a into its own child /a/b/c"),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        copy {
+            args: func_args![value: value!({"a": {"b": 1}}), patch: value!([{"op": "copy", "from": "/a", "path": "/c"}])],
+            want: Ok(value!({"a": {"b": 1}, "c": {"b": 1}})),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        test_passes {
+            args: func_args![value: value!({"a": [1, {"b": null}]}), patch: value!([{"op": "test", "path": "/a/1/b", "value": null}])],
+            want: Ok(value!({"a": [1, {"b": null}]})),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        test_fails {
+            args: func_args![value: value!({"a": 1}), patch: value!([
+                {"op": "replace", "path": "/a", "value": 2},
+                {"op": "test", "path": "/a", "value": 1},
+            ])],
+            want: Err("test failed at /a"),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        test_compares_numbers_by_value {
+            args: func_args![value: value!({"a": 1, "b": [2.0]}), patch: value!([
+                {"op": "test", "path": "/a", "value": 1.0},
+                {"op": "test", "path": "/b", "value": [2]},
+            ])],
+            want: Ok(value!({"a": 1, "b": [2.0]})),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        escaped_pointer {
+            args: func_args![value: value!({"a/b": 1, "c~d": 2}), patch: value!([
+                {"op": "remove", "path": "/a~1b"},
+                {"op": "replace", "path": "/c~0d", "value": 3},
+            ])],
+            want: Ok(value!({"c~d": 3})),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        replace_root {
+            args: func_args![value: value!({"a": 1}), patch: value!([{"op": "replace", "path": "", "value": [1]}])],
+            want: Ok(value!([1])),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        unknown_operation {
+            args: func_args![value: value!({}), patch: value!([{"op": "merge", "path": "/a"}])],
+            want: Err(r#"unknown patch operation "merge""#),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        missing_value {
+            args: func_args![value: value!({}), patch: value!([{"op": "add", "path": "/a"}])],
+            want: Err(r#"patch operation is missing "value""#),
+            tdef: TypeDef::any().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,102 @@
+use crate::compiler::prelude::*;
+use indexmap::IndexSet;
+
+fn array_difference(value: Value, other: Value) -> Resolved {
+    let other: IndexSet<_> = other.try_array()?.into_iter().collect();
+    let set: IndexSet<_> = value
+        .try_array()?
+        .into_iter()
+        .filter(|item| !other.contains(item))
+        .collect();
+    Ok(set.into_iter().collect())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ArrayDifference;
+
+impl Function for ArrayDifference {
+    fn identifier(&self) -> &'static str {
+        "array_difference"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "difference",
+            source: r#"array_difference(["foo", "bar", "foo", "baz"], ["baz", "qux", "foo"])"#,
+            result: Ok(r#"["bar"]"#),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let other = arguments.required("other");
+
+        Ok(ArrayDifferenceFn { value, other }.as_expr())
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "other",
+                kind: kind::ARRAY,
+                required: true,
+            },
+        ]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ArrayDifferenceFn {
+    value: Box<dyn Expression>,
+    other: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ArrayDifferenceFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let other = self.other.resolve(ctx)?;
+        array_difference(value, other)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::array(Collection::any())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        array_difference => ArrayDifference;
+
+        overlapping {
+            args: func_args![value: value!(["a", 1, {"b": [2]}, "c", "c"]), other: value!([{"b": [2]}, "a"])],
+            want: Ok(value!([1, "c"])),
+            tdef: TypeDef::array(Collection::any()),
+        }
+
+        disjoint {
+            args: func_args![value: value!(["a", "b", "a"]), other: value!(["c"])],
+            want: Ok(value!(["a", "b"])),
+            tdef: TypeDef::array(Collection::any()),
+        }
+
+        nested_inequality {
+            args: func_args![value: value!([{"b": [2]}]), other: value!([{"b": [3]}])],
+            want: Ok(value!([{"b": [2]}])),
+            tdef: TypeDef::array(Collection::any()),
+        }
+    ];
+}
@@ -0,0 +1,102 @@
+use crate::compiler::prelude::*;
+use indexmap::IndexSet;
+
+fn array_intersection(value: Value, other: Value) -> Resolved {
+    let other: IndexSet<_> = other.try_array()?.into_iter().collect();
+    let set: IndexSet<_> = value
+        .try_array()?
+        .into_iter()
+        .filter(|item| other.contains(item))
+        .collect();
+    Ok(set.into_iter().collect())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ArrayIntersection;
+
+impl Function for ArrayIntersection {
+    fn identifier(&self) -> &'static str {
+        "array_intersection"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "intersection",
+            source: r#"array_intersection(["foo", "bar", "foo", "baz"], ["baz", "qux", "foo"])"#,
+            result: Ok(r#"["foo", "baz"]"#),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let other = arguments.required("other");
+
+        Ok(ArrayIntersectionFn { value, other }.as_expr())
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "other",
+                kind: kind::ARRAY,
+                required: true,
+            },
+        ]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ArrayIntersectionFn {
+    value: Box<dyn Expression>,
+    other: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ArrayIntersectionFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let other = self.other.resolve(ctx)?;
+        array_intersection(value, other)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::array(Collection::any())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        array_intersection => ArrayIntersection;
+
+        overlapping {
+            args: func_args![value: value!(["a", 1, {"b": [2]}, "a", 3]), other: value!([3, {"b": [2]}, "a", "z"])],
+            want: Ok(value!(["a", {"b": [2]}, 3])),
+            tdef: TypeDef::array(Collection::any()),
+        }
+
+        disjoint {
+            args: func_args![value: value!(["a", 1]), other: value!(["b", "1", 1.0])],
+            want: Ok(value!([])),
+            tdef: TypeDef::array(Collection::any()),
+        }
+
+        empty {
+            args: func_args![value: value!([]), other: value!(["a"])],
+            want: Ok(value!([])),
+            tdef: TypeDef::array(Collection::any()),
+        }
+    ];
+}
@@ -0,0 +1,98 @@
+use crate::compiler::prelude::*;
+use indexmap::IndexSet;
+
+fn array_union(value: Value, other: Value) -> Resolved {
+    let mut set: IndexSet<_> = value.try_array()?.into_iter().collect();
+    set.extend(other.try_array()?);
+    Ok(set.into_iter().collect())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ArrayUnion;
+
+impl Function for ArrayUnion {
+    fn identifier(&self) -> &'static str {
+        "array_union"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "union",
+            source: r#"array_union(["foo", "bar", "foo", "baz"], ["baz", "qux", "foo"])"#,
+            result: Ok(r#"["foo", "bar", "baz", "qux"]"#),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let other = arguments.required("other");
+
+        Ok(ArrayUnionFn { value, other }.as_expr())
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "other",
+                kind: kind::ARRAY,
+                required: true,
+            },
+        ]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ArrayUnionFn {
+    value: Box<dyn Expression>,
+    other: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ArrayUnionFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let other = self.other.resolve(ctx)?;
+        array_union(value, other)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::array(Collection::any())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        array_union => ArrayUnion;
+
+        overlapping {
+            args: func_args![value: value!(["a", 1, {"b": [2]}, "a"]), other: value!([{"b": [2]}, "c", 1])],
+            want: Ok(value!(["a", 1, {"b": [2]}, "c"])),
+            tdef: TypeDef::array(Collection::any()),
+        }
+
+        disjoint {
+            args: func_args![value: value!(["a", "b"]), other: value!(["c", "d"])],
+            want: Ok(value!(["a", "b", "c", "d"])),
+            tdef: TypeDef::array(Collection::any()),
+        }
+
+        deduplicates_other {
+            args: func_args![value: value!([]), other: value!(["a", "a"])],
+            want: Ok(value!(["a"])),
+            tdef: TypeDef::array(Collection::any()),
+        }
+    ];
+}
@@ -0,0 +1,267 @@
+use crate::compiler::prelude::*;
+
+fn assert_type(value: Value, expected: &Kind, type_name: &str, path: &str) -> Resolved {
+    let matches = match &value {
+        Value::Bytes(_) => expected.contains_bytes(),
+        Value::Regex(_) => expected.contains_regex(),
+        Value::Integer(_) => expected.contains_integer(),
+        Value::Float(_) => expected.contains_float(),
+        Value::Boolean(_) => expected.contains_boolean(),
+        Value::Timestamp(_) => expected.contains_timestamp(),
+        Value::Object(_) => expected.contains_object(),
+        Value::Array(_) => expected.contains_array(),
+        Value::Null => expected.contains_null(),
+    };
+
+    if matches {
+        Ok(value)
+    } else {
+        Err(format!("expected {path} to be {type_name}, got {}", value.kind()).into())
+    }
+}
+
+/// Parses a type name such as `"integer"` or `"integer|null"` into the [`Kind`] it describes.
+fn parse_type_name(type_name: &str) -> Option<Kind> {
+    type_name
+        .split('|')
+        .map(|name| match name.trim() {
+            "string" => Some(Kind::bytes()),
+            "integer" => Some(Kind::integer()),
+            "float" => Some(Kind::float()),
+            "boolean" => Some(Kind::boolean()),
+            "timestamp" => Some(Kind::timestamp()),
+            "regex" => Some(Kind::regex()),
+            "object" => Some(Kind::object(Collection::any())),
+            "array" => Some(Kind::array(Collection::any())),
+            "null" => Some(Kind::null()),
+            _ => None,
+        })
+        .try_fold(Kind::never(), |kind, other| Some(kind | other?))
+}
+
+/// Narrows the known kind of the value to the asserted kinds, keeping what is already known
+/// about them, such as the fields of an object. Returns `None` when they don't overlap.
+fn intersect(known: &Kind, expected: &Kind) -> Option<Kind> {
+    let mut kind = known.clone();
+    if !expected.contains_bytes() {
+        kind.remove_bytes();
+    }
+    if !expected.contains_integer() {
+        kind.remove_integer();
+    }
+    if !expected.contains_float() {
+        kind.remove_float();
+    }
+    if !expected.contains_boolean() {
+        kind.remove_boolean();
+    }
+    if !expected.contains_timestamp() {
+        kind.remove_timestamp();
+    }
+    if !expected.contains_regex() {
+        kind.remove_regex();
+    }
+    if !expected.contains_null() {
+        kind.remove_null();
+    }
+    if !expected.contains_undefined() {
+        kind.remove_undefined();
+    }
+    if !expected.contains_array() {
+        kind.remove_array();
+    }
+    if !expected.contains_object() {
+        kind.remove_object();
+    }
+
+    (!kind.is_never()).then_some(kind)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct AssertType;
+
+impl Function for AssertType {
+    fn identifier(&self) -> &'static str {
+        "assert_type"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "type",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "matching type",
+                source: r#"assert_type(42, "integer")"#,
+                result: Ok("42"),
+            },
+            Example {
+                title: "compound type",
+                source: r#"assert_type(null, "integer|null")"#,
+                result: Ok("null"),
+            },
+            Example {
+                title: "mismatched type",
+                source: r#"assert_type!(.foo, "integer")"#,
+                result: Err(
+                    r#"function call error for "assert_type" at (0:29): expected .foo to be integer, got null"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required_expr("value");
+        let type_name = arguments
+            .required_literal("type", state)?
+            .try_bytes_utf8_lossy()
+            .expect("type not bytes")
+            .into_owned();
+        let expected =
+            parse_type_name(&type_name).ok_or_else(|| function::Error::InvalidArgument {
+                keyword: "type",
+                value: type_name.clone().into(),
+                error: "unknown type name",
+            })?;
+
+        Ok(AssertTypeFn {
+            path: value.to_string(),
+            value: Box::new(value),
+            expected,
+            type_name,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AssertTypeFn {
+    value: Box<dyn Expression>,
+    expected: Kind,
+    type_name: String,
+    path: String,
+}
+
+impl FunctionExpression for AssertTypeFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        assert_type(value, &self.expected, &self.type_name, &self.path)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        // The returned value is narrowed to the asserted type, and the call can only
+        // fail when the value's type isn't already known to match.
+        let known = self.value.type_def(state).kind().clone();
+        let fallible = self.expected.is_superset(&known).is_err();
+        let kind = intersect(&known, &self.expected).unwrap_or_else(|| self.expected.clone());
+
+        TypeDef::from(kind).maybe_fallible(fallible)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::compile;
+    use crate::value;
+    use std::collections::BTreeMap;
+
+    test_function![
+        assert_type => AssertType;
+
+        integer_matches {
+            args: func_args![value: 42, type: "integer"],
+            want: Ok(42),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        compound_matches {
+            args: func_args![value: "foo", type: "integer|string"],
+            want: Ok("foo"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        compound_with_whitespace {
+            args: func_args![value: Value::Null, type: "integer | null"],
+            want: Ok(Value::Null),
+            tdef: TypeDef::null().infallible(),
+        }
+
+        object_matches {
+            args: func_args![value: value!({"foo": "bar"}), type: "object"],
+            want: Ok(value!({"foo": "bar"})),
+            tdef: TypeDef::object(BTreeMap::from([(Field::from("foo"), Kind::bytes())])).infallible(),
+        }
+
+        known_type_narrowed {
+            args: func_args![value: value!({"a": 1}), type: "object|string"],
+            want: Ok(value!({"a": 1})),
+            tdef: TypeDef::object(BTreeMap::from([(Field::from("a"), Kind::integer())])).infallible(),
+        }
+
+        mismatch {
+            args: func_args![value: "foo", type: "integer"],
+            want: Err(r#"expected "foo" to be integer, got string"#),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        compound_mismatch {
+            args: func_args![value: 1.5, type: "integer|null"],
+            want: Err("expected 1.5 to be integer|null, got float"),
+            tdef: TypeDef::integer().or_null().fallible(),
+        }
+
+        unknown_type_name {
+            args: func_args![value: 42, type: "number"],
+            want: Err("invalid argument"),
+            tdef: TypeDef::integer().infallible(),
+        }
+    ];
+    #[test]
+    fn union_narrowed_to_overlap() {
+        let program = compile(
+            indoc! {r#"
+                x = if .flag == true { 1 } else { "one" }
+                assert_type!(x, "string|null")
+            "#},
+            &crate::stdlib::all(),
+        )
+        .unwrap()
+        .program;
+
+        assert_eq!(
+            program.final_type_info().result,
+            TypeDef::bytes().infallible()
+        );
+    }
+
+    #[test]
+    fn no_overlap_falls_back_to_expected() {
+        let program = compile(r#"assert_type!(42, "string|null")"#, &crate::stdlib::all())
+            .unwrap()
+            .program;
+
+        assert_eq!(
+            program.final_type_info().result,
+            TypeDef::bytes().or_null().infallible()
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use super::lru_cache::SharedLruCache;
+use crate::compiler::prelude::*;
+
+const DEFAULT_CAPACITY: usize = 1000;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Cached;
+
+impl Function for Cached {
+    fn identifier(&self) -> &'static str {
+        "cached"
+    }
+
+    fn summary(&self) -> &'static str {
+        "memoize the result of a closure by key"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Returns the value previously computed for `key`, only running the closure when the
+            key is not in the cache.
+
+            Each call site keeps its own cache for the lifetime of the compiled program, holding
+            at most `capacity` entries and evicting the least recently used key once full. The
+            closure should be pure: whether it runs for a given event depends on which keys
+            earlier events used, so side effects inside it are not deterministic. Every cached
+            entry is held in memory until it is evicted, so keep `capacity` proportional to the
+            number of distinct keys you expect.
+        "}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "capacity",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "cache a computation",
+                source: r#"cached("greeting") -> || { upcase("hello") }"#,
+                result: Ok(r#""HELLO""#),
+            },
+            Example {
+                title: "closure runs once per key",
+                source: indoc! {r#"
+                    runs = 0
+                    for_each(["a", "b", "a", "a"]) -> |_index, key| {
+                        cached(key) -> || { runs = runs + 1; key }
+                    }
+                    runs
+                "#},
+                result: Ok("2"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let key = arguments.required("key");
+        let capacity = match arguments.optional_literal("capacity", state)? {
+            Some(capacity) => capacity
+                .as_integer()
+                .and_then(|capacity| usize::try_from(capacity).ok())
+                .filter(|capacity| *capacity > 0)
+                .ok_or_else(|| function::Error::InvalidArgument {
+                    keyword: "capacity",
+                    value: capacity,
+                    error: "capacity must be a positive integer",
+                })?,
+            None => DEFAULT_CAPACITY,
+        };
+        let closure = arguments.required_closure()?;
+
+        Ok(CachedFn {
+            key,
+            closure,
+            cache: SharedLruCache::new(capacity),
+        }
+        .as_expr())
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output};
+
+        Some(Definition {
+            inputs: vec![Input {
+                parameter_keyword: "key",
+                kind: Kind::bytes(),
+                variables: vec![],
+                output: Output::Kind(Kind::any()),
+                example: Example {
+                    title: "cache a computation",
+                    source: r#"cached("greeting") -> || { upcase("hello") }"#,
+                    result: Ok(r#""HELLO""#),
+                },
+            }],
+            is_iterator: false,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedFn {
+    key: Box<dyn Expression>,
+    closure: FunctionClosure,
+    cache: SharedLruCache<String, Value>,
+}
+
+impl FunctionExpression for CachedFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let key = self.key.resolve(ctx)?.try_bytes_utf8_lossy()?.into_owned();
+
+        if let Some(value) = self.cache.get(&key) {
+            return Ok(value);
+        }
+
+        // The cache isn't locked while the closure runs, so a nested call to the same
+        // function can't deadlock. When another event holds the lock the closure just runs
+        // uncached.
+        let value = self.closure.block.resolve(ctx)?;
+        self.cache.insert(key, value.clone());
+
+        Ok(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::from(self.closure.block_type_def.kind().clone())
+    }
+}
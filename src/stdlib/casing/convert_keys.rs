@@ -0,0 +1,233 @@
+use crate::compiler::prelude::*;
+
+use convert_case::{Case, Casing};
+
+fn convert_keys(value: Value, case: Case, recursive: bool, allow_collisions: bool) -> Resolved {
+    let object = value.try_object()?;
+    Ok(convert_object(object, case, recursive, allow_collisions)?.into())
+}
+
+fn convert_object(
+    object: ObjectMap,
+    case: Case,
+    recursive: bool,
+    allow_collisions: bool,
+) -> Result<ObjectMap, ExpressionError> {
+    let mut converted = ObjectMap::new();
+    for (key, value) in object {
+        let value = if recursive {
+            convert_value(value, case, recursive, allow_collisions)?
+        } else {
+            value
+        };
+        let converted_key = KeyString::from(key.as_str().to_case(case));
+        // Keys are visited in sorted order, so on a collision the key sorting last wins.
+        if !allow_collisions && converted.contains_key(&converted_key) {
+            return Err(format!(r#"multiple keys convert to "{converted_key}""#).into());
+        }
+        converted.insert(converted_key, value);
+    }
+    Ok(converted)
+}
+
+fn convert_value(
+    value: Value,
+    case: Case,
+    recursive: bool,
+    allow_collisions: bool,
+) -> Result<Value, ExpressionError> {
+    match value {
+        Value::Object(object) => {
+            Ok(convert_object(object, case, recursive, allow_collisions)?.into())
+        }
+        Value::Array(array) => array
+            .into_iter()
+            .map(|value| convert_value(value, case, recursive, allow_collisions))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Into::into),
+        value => Ok(value),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ConvertKeys;
+
+impl Function for ConvertKeys {
+    fn identifier(&self) -> &'static str {
+        "convert_keys"
+    }
+
+    fn summary(&self) -> &'static str {
+        "convert the keys of an object to a case convention"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Rewrites the keys of an object to the given `case`, one of `camelCase`, `PascalCase`,
+            `SCREAMING_SNAKE`, `snake_case` or `kebab-case`.
+
+            Keys of nested objects, including objects inside arrays, are converted too unless
+            `recursive` is `false`. When two keys of the same object convert to the same key, an
+            error is returned, unless `allow_collisions` is `true`, in which case the value of the
+            key that sorts last is kept.
+        "}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::OBJECT,
+                required: true,
+            },
+            Parameter {
+                keyword: "case",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "recursive",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "allow_collisions",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "snake_case keys",
+                source: r#"convert_keys!({ "userId": 1, "userName": { "firstName": "Ada" } }, "snake_case")"#,
+                result: Ok(r#"{ "user_id": 1, "user_name": { "first_name": "Ada" } }"#),
+            },
+            Example {
+                title: "top-level keys only",
+                source: r#"convert_keys!({ "user-id": 1, "user-name": { "first-name": "Ada" } }, "camelCase", recursive: false)"#,
+                result: Ok(r#"{ "userId": 1, "userName": { "first-name": "Ada" } }"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let case = arguments
+            .required_enum("case", &super::variants(), state)?
+            .try_bytes_utf8_lossy()
+            .expect("case not bytes")
+            .into_owned();
+        let case = super::into_case(case)?;
+        let recursive = arguments.optional("recursive").unwrap_or(expr!(true));
+        let allow_collisions = arguments
+            .optional("allow_collisions")
+            .unwrap_or(expr!(false));
+
+        Ok(ConvertKeysFn {
+            value,
+            case,
+            recursive,
+            allow_collisions,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ConvertKeysFn {
+    value: Box<dyn Expression>,
+    case: Case,
+    recursive: Box<dyn Expression>,
+    allow_collisions: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ConvertKeysFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let recursive = self.recursive.resolve(ctx)?.try_boolean()?;
+        let allow_collisions = self.allow_collisions.resolve(ctx)?.try_boolean()?;
+
+        convert_keys(value, self.case, recursive, allow_collisions)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(Collection::any()).fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        convert_keys => ConvertKeys;
+
+        nested_snake_case {
+            args: func_args![
+                value: value!({
+                    "requestId": "abc",
+                    "httpRequest": {
+                        "statusCode": 200,
+                        "responseHeaders": [{ "contentType": "text/html" }],
+                    },
+                }),
+                case: "snake_case",
+            ],
+            want: Ok(value!({
+                "request_id": "abc",
+                "http_request": {
+                    "status_code": 200,
+                    "response_headers": [{ "content_type": "text/html" }],
+                },
+            })),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        not_recursive {
+            args: func_args![
+                value: value!({ "requestId": "abc", "httpRequest": { "statusCode": 200 } }),
+                case: "kebab-case",
+                recursive: false,
+            ],
+            want: Ok(value!({ "request-id": "abc", "http-request": { "statusCode": 200 } })),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        pascal_case {
+            args: func_args![value: value!({ "hello_world": 1, "hello-there": 2 }), case: "PascalCase"],
+            want: Ok(value!({ "HelloWorld": 1, "HelloThere": 2 })),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        collision {
+            args: func_args![value: value!({ "user_id": 1, "userId": 2 }), case: "snake_case"],
+            want: Err(r#"multiple keys convert to "user_id""#),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        collision_allowed {
+            args: func_args![
+                value: value!({ "user_id": 1, "userId": 2 }),
+                case: "snake_case",
+                allow_collisions: true,
+            ],
+            want: Ok(value!({ "user_id": 1 })),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        invalid_case {
+            args: func_args![value: value!({}), case: "Title Case"],
+            want: Err(r#"invalid enum variant""#),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+    ];
+}
@@ -5,6 +5,7 @@ use crate::prelude::*;
 cfg_if::cfg_if! {
     if #[cfg(feature = "stdlib")] {
         pub(crate) mod camelcase;
+        pub(crate) mod convert_keys;
         pub(crate) mod pascalcase;
         pub(crate) mod snakecase;
         pub(crate) mod screamingsnakecase;
@@ -34,7 +35,7 @@ pub(crate) fn into_case(s: String) -> Result<Case, Box<dyn DiagnosticMessage>> {
     match s.as_ref() {
         "camelCase" => Ok(Case::Camel),
         "PascalCase" => Ok(Case::Pascal),
-        "SREAMING_SNAKE" => Ok(Case::ScreamingSnake),
+        "SCREAMING_SNAKE" => Ok(Case::ScreamingSnake),
         "snake_case" => Ok(Case::Snake),
         "kebab-case" => Ok(Case::Kebab),
         _ => Err(Box::new(ExpressionError::from(format!(
@@ -67,4 +68,12 @@ mod tests {
             variants_msg()
         );
     }
+
+    #[test]
+    fn test_into_case() {
+        for variant in variants() {
+            let variant = variant.as_str().unwrap().into_owned();
+            assert!(into_case(variant.clone()).is_ok(), "{variant}");
+        }
+    }
 }
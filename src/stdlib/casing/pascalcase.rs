@@ -99,5 +99,17 @@ mod tests {
             want: Ok(value!("InputString")),
             tdef: TypeDef::bytes(),
         }
+
+        from_camel_case {
+            args: func_args![value: value!("helloWorld")],
+            want: Ok(value!("HelloWorld")),
+            tdef: TypeDef::bytes(),
+        }
+
+        from_kebab_case {
+            args: func_args![value: value!("hello-world")],
+            want: Ok(value!("HelloWorld")),
+            tdef: TypeDef::bytes(),
+        }
     ];
 }
@@ -99,5 +99,23 @@ mod tests {
             want: Ok(value!("INPUT_STRING")),
             tdef: TypeDef::bytes(),
         }
+
+        from_camel_case {
+            args: func_args![value: value!("helloWorld")],
+            want: Ok(value!("HELLO_WORLD")),
+            tdef: TypeDef::bytes(),
+        }
+
+        from_kebab_case {
+            args: func_args![value: value!("hello-world")],
+            want: Ok(value!("HELLO_WORLD")),
+            tdef: TypeDef::bytes(),
+        }
+
+        from_screaming_snake {
+            args: func_args![value: value!("HELLO_WORLD"), original_case: "SCREAMING_SNAKE"],
+            want: Ok(value!("HELLO_WORLD")),
+            tdef: TypeDef::bytes(),
+        }
     ];
 }
@@ -99,5 +99,17 @@ mod tests {
             want: Ok(value!("camel_case")),
             tdef: TypeDef::bytes(),
         }
+
+        from_camel_case {
+            args: func_args![value: value!("helloWorld")],
+            want: Ok(value!("hello_world")),
+            tdef: TypeDef::bytes(),
+        }
+
+        from_kebab_case {
+            args: func_args![value: value!("hello-world")],
+            want: Ok(value!("hello_world")),
+            tdef: TypeDef::bytes(),
+        }
     ];
 }
@@ -0,0 +1,177 @@
+use crate::compiler::expression::Expr;
+use crate::compiler::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Coalesce;
+
+impl Function for Coalesce {
+    fn identifier(&self) -> &'static str {
+        "coalesce"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "values",
+            kind: kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "first non-null",
+                source: r#"coalesce([null, .missing, "fallback", "unused"])"#,
+                result: Ok(r#""fallback""#),
+            },
+            Example {
+                title: "all null",
+                source: "coalesce([null, .missing])",
+                result: Ok("null"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        // The array elements are kept as separate expressions so they can be evaluated
+        // lazily, stopping at the first one that produces a value.
+        let values = arguments.required_array("values")?;
+
+        Ok(CoalesceFn { values }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CoalesceFn {
+    values: Vec<Expr>,
+}
+
+impl FunctionExpression for CoalesceFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        for expr in &self.values {
+            match expr.resolve(ctx) {
+                Ok(Value::Null) | Err(_) => {}
+                Ok(value) => return Ok(value),
+            }
+        }
+
+        Ok(Value::Null)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        let mut kind = Kind::never();
+        for expr in &self.values {
+            let type_def = expr.type_def(state);
+            let mut value_kind = type_def.kind().clone();
+            let nullable = value_kind.remove_null();
+            kind = kind | value_kind;
+            if !nullable && type_def.is_infallible() && cannot_fail(expr, state) {
+                // Later values are never reached once one always produces a value.
+                return TypeDef::from(kind).infallible();
+            }
+        }
+
+        TypeDef::from(kind.or_null()).infallible()
+    }
+}
+
+/// Whether `expr` never errors at runtime.
+///
+/// Calls ending in `!` are typed as infallible, even nested inside other expressions, yet
+/// their errors still reach `coalesce`, which skips the value. Only expressions that can't
+/// contain such a call are relied upon.
+fn cannot_fail(expr: &Expr, state: &state::TypeState) -> bool {
+    matches!(expr, Expr::Literal(_) | Expr::Query(_) | Expr::Variable(_))
+        || expr.resolve_constant(state).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        coalesce => Coalesce;
+
+        third_wins {
+            args: func_args![values: vec![Value::Null, Value::Null, Value::from("third"), Value::from(4)]],
+            want: Ok("third"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        first_wins {
+            args: func_args![values: vec![Value::from(1), Value::Null]],
+            want: Ok(1),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        all_null {
+            args: func_args![values: vec![Value::Null, Value::Null]],
+            want: Ok(Value::Null),
+            tdef: TypeDef::null().infallible(),
+        }
+
+        empty {
+            args: func_args![values: Vec::<Value>::new()],
+            want: Ok(Value::Null),
+            tdef: TypeDef::null().infallible(),
+        }
+    ];
+
+    fn compile(source: &str) -> crate::compiler::Program {
+        crate::compiler::compile(source, &crate::stdlib::all())
+            .unwrap()
+            .program
+    }
+
+    #[test]
+    fn erroring_values_are_skipped() {
+        use crate::compiler::{runtime::Runtime, TargetValue, TimeZone};
+        use crate::value;
+        use crate::value::Secrets;
+
+        let program = compile(r#"coalesce([parse_int!(.a), "fallback"])"#);
+        assert_eq!(
+            program.final_type_info().result,
+            TypeDef::integer().or_bytes().infallible()
+        );
+
+        for (a, want) in [("42", value!(42)), ("nope", value!("fallback"))] {
+            let mut target = TargetValue {
+                value: value!({ a: a }),
+                metadata: value!({}),
+                secrets: Secrets::new(),
+            };
+            let got = Runtime::default()
+                .resolve(&mut target, &program, &TimeZone::default())
+                .unwrap();
+            assert_eq!(got, want);
+        }
+    }
+
+    #[test]
+    fn type_def_with_erroring_values() {
+        assert_eq!(
+            compile("coalesce([parse_int!(.a)])")
+                .final_type_info()
+                .result,
+            TypeDef::integer().or_null().infallible()
+        );
+        assert_eq!(
+            compile(r"coalesce([upcase(to_string!(.a)), 1])")
+                .final_type_info()
+                .result,
+            TypeDef::bytes().or_integer().infallible()
+        );
+        assert_eq!(
+            compile(r#"coalesce(["first", parse_int!(.a)])"#)
+                .final_type_info()
+                .result,
+            TypeDef::bytes().infallible()
+        );
+    }
+}
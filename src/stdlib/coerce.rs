@@ -0,0 +1,163 @@
+use super::to_bool::to_bool;
+use super::to_float::to_float;
+use super::to_int::to_int;
+use super::to_string::to_string;
+use crate::compiler::prelude::*;
+
+fn coerce(value: Value, target: &Value) -> Resolved {
+    let target = target.try_bytes_utf8_lossy()?;
+    match target.as_ref() {
+        "integer" => to_int(value),
+        "float" => to_float(value),
+        "boolean" => to_bool(value),
+        "string" => to_string(value),
+        target => Err(format!("unknown coercion type {target:?}").into()),
+    }
+}
+
+fn target_kind(target: &str) -> Option<Kind> {
+    match target {
+        "integer" => Some(Kind::integer()),
+        "float" => Some(Kind::float()),
+        "boolean" => Some(Kind::boolean()),
+        "string" => Some(Kind::bytes()),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Coerce;
+
+impl Function for Coerce {
+    fn identifier(&self) -> &'static str {
+        "coerce"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "type",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "string to integer",
+                source: r#"coerce!("42", "integer")"#,
+                result: Ok("42"),
+            },
+            Example {
+                title: "float to string",
+                source: r#"coerce!(1.5, "string")"#,
+                result: Ok(r#""1.5""#),
+            },
+            Example {
+                title: "unconvertible",
+                source: r#"coerce!([1], "boolean")"#,
+                result: Err(
+                    r#"function call error for "coerce" at (0:23): unable to coerce [integer] into boolean"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let target = arguments.required("type");
+
+        Ok(CoerceFn { value, target }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CoerceFn {
+    value: Box<dyn Expression>,
+    target: Box<dyn Expression>,
+}
+
+impl FunctionExpression for CoerceFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let target = self.target.resolve(ctx)?;
+
+        coerce(value, &target)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        // The target type usually comes from configuration, so only narrow the result
+        // when it is known at compile time.
+        let kind = self
+            .target
+            .resolve_constant(state)
+            .and_then(|target| target_kind(&target.try_bytes_utf8_lossy().ok()?))
+            .unwrap_or_else(|| Kind::integer() | Kind::float() | Kind::boolean() | Kind::bytes());
+
+        TypeDef::from(kind).fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        coerce => Coerce;
+
+        string_to_integer {
+            args: func_args![value: "42", type: "integer"],
+            want: Ok(42),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        integer_to_float {
+            args: func_args![value: 3, type: "float"],
+            want: Ok(3.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        boolean_to_string {
+            args: func_args![value: value!(true), type: "string"],
+            want: Ok("true"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        string_to_boolean {
+            args: func_args![value: "yes", type: "boolean"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        failed_boolean {
+            args: func_args![value: "maybe", type: "boolean"],
+            want: Err("Invalid boolean value \"maybe\""),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        unconvertible {
+            args: func_args![value: value!([1]), type: "integer"],
+            want: Err("unable to coerce [integer] into integer"),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        unknown_type {
+            args: func_args![value: 1, type: "decimal"],
+            want: Err(r#"unknown coercion type "decimal""#),
+            tdef: TypeDef::integer().or_float().or_boolean().or_bytes().fallible(),
+        }
+    ];
+}
@@ -1,5 +1,5 @@
 use crate::compiler::prelude::*;
-use crate::stdlib::string_utils::convert_to_string;
+use crate::stdlib::string_utils::{convert_to_string, lowercase_strings};
 
 fn contains_all(value: Value, substrings: Value, case_sensitive: Option<Value>) -> Resolved {
     let case_sensitive = match case_sensitive {
@@ -7,6 +7,19 @@ fn contains_all(value: Value, substrings: Value, case_sensitive: Option<Value>)
         None => true,
     };
 
+    // Arrays are treated as sets, using VRL value equality for membership.
+    if let Value::Array(elements) = value {
+        let subset = substrings.try_array()?;
+        if case_sensitive {
+            return Ok(subset.iter().all(|item| elements.contains(item)).into());
+        }
+        let elements = lowercase_strings(elements);
+        return Ok(lowercase_strings(subset)
+            .iter()
+            .all(|item| elements.contains(item))
+            .into());
+    }
+
     let value_string = convert_to_string(value, !case_sensitive)?;
     let substring_values = substrings.try_array()?;
 
@@ -31,7 +44,7 @@ impl Function for ContainsAll {
         &[
             Parameter {
                 keyword: "value",
-                kind: kind::BYTES,
+                kind: kind::BYTES | kind::ARRAY,
                 required: true,
             },
             Parameter {
@@ -77,6 +90,11 @@ impl Function for ContainsAll {
                 source: r#"contains_all("the NEEDLE in the haystack", ["needle", "haystack"])"#,
                 result: Ok("false"),
             },
+            Example {
+                title: "contains_all array",
+                source: r#"contains_all(["admin", "billing", "support"], ["billing", "admin"])"#,
+                result: Ok("true"),
+            },
         ]
     }
 }
@@ -101,6 +119,10 @@ impl FunctionExpression for ContainsAllFn {
     }
 
     fn type_def(&self, state: &TypeState) -> TypeDef {
+        if self.value.type_def(state).is_array() {
+            return TypeDef::boolean().infallible();
+        }
+
         let substring_type_def = self.substrings.type_def(state);
         let collection = substring_type_def.as_array().expect("must be an array");
         let bytes_collection = Collection::from_unknown(Kind::bytes());
@@ -167,5 +189,41 @@ mod tests {
             want: Ok(value!(true)),
             tdef: TypeDef::boolean().infallible(),
         }
+
+        array_full_overlap {
+            args: func_args![value: value!(["a", 1, {"b": [2]}]),
+                             substrings: value!([{"b": [2]}, "a"])],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        array_partial_overlap {
+            args: func_args![value: value!(["a", 1, {"b": [2]}]),
+                             substrings: value!(["a", {"b": [3]}])],
+            want: Ok(value!(false)),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        array_empty_subset {
+            args: func_args![value: value!(["a"]),
+                             substrings: value!([])],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        array_case_insensitive {
+            args: func_args![value: value!(["Admin", "Billing", 1]),
+                             substrings: value!(["admin", "BILLING", 1]),
+                             case_sensitive: false],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        array_case_sensitive {
+            args: func_args![value: value!(["Admin", "Billing"]),
+                             substrings: value!(["admin", "Billing"])],
+            want: Ok(value!(false)),
+            tdef: TypeDef::boolean().infallible(),
+        }
     ];
 }
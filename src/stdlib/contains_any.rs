@@ -0,0 +1,207 @@
+use crate::compiler::prelude::*;
+use crate::stdlib::string_utils::{convert_to_string, lowercase_strings};
+
+fn contains_any(value: Value, substrings: Value, case_sensitive: Option<Value>) -> Resolved {
+    let case_sensitive = match case_sensitive {
+        Some(v) => v.try_boolean()?,
+        None => true,
+    };
+
+    // Arrays are treated as sets, using VRL value equality for membership.
+    if let Value::Array(elements) = value {
+        let subset = substrings.try_array()?;
+        if case_sensitive {
+            return Ok(subset.iter().any(|item| elements.contains(item)).into());
+        }
+        let elements = lowercase_strings(elements);
+        return Ok(lowercase_strings(subset)
+            .iter()
+            .any(|item| elements.contains(item))
+            .into());
+    }
+
+    let value_string = convert_to_string(value, !case_sensitive)?;
+    let substring_values = substrings.try_array()?;
+
+    for substring_value in substring_values {
+        let substring = convert_to_string(substring_value, !case_sensitive)?;
+        if value_string.contains(&substring) {
+            return Ok(true.into());
+        }
+    }
+    Ok(false.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ContainsAny;
+
+impl Function for ContainsAny {
+    fn identifier(&self) -> &'static str {
+        "contains_any"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES | kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "substrings",
+                kind: kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "case_sensitive",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let substrings = arguments.required("substrings");
+        let case_sensitive = arguments.optional("case_sensitive");
+
+        Ok(ContainsAnyFn {
+            value,
+            substrings,
+            case_sensitive,
+        }
+        .as_expr())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "contains_any true",
+                source: r#"contains_any("The Needle In The Haystack", ["Thread", "Needle"])"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "contains_any false",
+                source: r#"contains_any("the NEEDLE in the haystack", ["Needle", "Haystack"])"#,
+                result: Ok("false"),
+            },
+            Example {
+                title: "contains_any array",
+                source: r#"contains_any(["admin", "billing"], ["root", "admin"])"#,
+                result: Ok("true"),
+            },
+        ]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ContainsAnyFn {
+    value: Box<dyn Expression>,
+    substrings: Box<dyn Expression>,
+    case_sensitive: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for ContainsAnyFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let substrings = self.substrings.resolve(ctx)?;
+        let case_sensitive = self
+            .case_sensitive
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+        contains_any(value, substrings, case_sensitive)
+    }
+
+    fn type_def(&self, state: &TypeState) -> TypeDef {
+        if self.value.type_def(state).is_array() {
+            return TypeDef::boolean().infallible();
+        }
+
+        let substring_type_def = self.substrings.type_def(state);
+        let collection = substring_type_def.as_array().expect("must be an array");
+        let bytes_collection = Collection::from_unknown(Kind::bytes());
+        TypeDef::boolean().maybe_fallible(bytes_collection.is_superset(collection).is_err())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::value;
+
+    use super::*;
+
+    test_function![
+        contains_any => ContainsAny;
+
+        no {
+            args: func_args![value: value!("The Needle In The Haystack"),
+                             substrings: value!(["the", "duck"])],
+            want: Ok(value!(false)),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        substring_type {
+            args: func_args![value: value!("The Needle In The Haystack"),
+                             substrings: value!([1, 2])],
+            want: Err("expected string, got integer"),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        yes {
+            args: func_args![value: value!("The Needle In The Haystack"),
+                             substrings: value!(["duck", "Needle In"])],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        case_insensitive_yes {
+            args: func_args![value: value!("The Needle In The Haystack"),
+                             substrings: value!(["thread", "haystack"]),
+                             case_sensitive: false],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        array_partial_overlap {
+            args: func_args![value: value!(["a", 1, {"b": [2]}]),
+                             substrings: value!(["z", {"b": [2]}])],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        array_disjoint {
+            args: func_args![value: value!(["a", 1, {"b": [2]}]),
+                             substrings: value!(["1", {"b": [3]}])],
+            want: Ok(value!(false)),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        array_empty_subset {
+            args: func_args![value: value!(["a"]),
+                             substrings: value!([])],
+            want: Ok(value!(false)),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        array_case_insensitive {
+            args: func_args![value: value!(["Admin", "Billing"]),
+                             substrings: value!(["support", "ADMIN"]),
+                             case_sensitive: false],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        array_case_sensitive {
+            args: func_args![value: value!(["Admin", "Billing"]),
+                             substrings: value!(["support", "ADMIN"])],
+            want: Ok(value!(false)),
+            tdef: TypeDef::boolean().infallible(),
+        }
+    ];
+}
@@ -0,0 +1,207 @@
+use crate::compiler::prelude::*;
+use crate::value;
+
+fn date_diff(a: Value, b: Value, unit: &Bytes, fractional: Value) -> Resolved {
+    let delta = a.try_timestamp()?.signed_duration_since(b.try_timestamp()?);
+
+    if fractional.try_boolean()? {
+        let seconds = delta.num_seconds() as f64 + f64::from(delta.subsec_nanos()) / 1e9;
+        let divisor = match unit.as_ref() {
+            b"seconds" => 1.0,
+            b"minutes" => 60.0,
+            b"hours" => 3600.0,
+            b"days" => 86400.0,
+            _ => unreachable!("enum invariant"),
+        };
+
+        return Ok(Value::from_f64_or_zero(seconds / divisor));
+    }
+
+    let diff = match unit.as_ref() {
+        b"seconds" => delta.num_seconds(),
+        b"minutes" => delta.num_minutes(),
+        b"hours" => delta.num_hours(),
+        b"days" => delta.num_days(),
+        _ => unreachable!("enum invariant"),
+    };
+
+    Ok(diff.into())
+}
+
+fn units() -> Vec<Value> {
+    vec![
+        value!("seconds"),
+        value!("minutes"),
+        value!("hours"),
+        value!("days"),
+    ]
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DateDiff;
+
+impl Function for DateDiff {
+    fn identifier(&self) -> &'static str {
+        "date_diff"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "a",
+                kind: kind::TIMESTAMP,
+                required: true,
+            },
+            Parameter {
+                keyword: "b",
+                kind: kind::TIMESTAMP,
+                required: true,
+            },
+            Parameter {
+                keyword: "unit",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "fractional",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "difference in hours",
+                source: r#"date_diff(t'2021-02-10T12:00:00Z', t'2021-02-10T09:00:00Z', "hours")"#,
+                result: Ok("3"),
+            },
+            Example {
+                title: "negative difference",
+                source: r#"date_diff(t'2021-02-09T12:00:00Z', t'2021-02-10T12:00:00Z', "days")"#,
+                result: Ok("-1"),
+            },
+            Example {
+                title: "fractional difference",
+                source: r#"date_diff(t'2021-02-10T10:30:00Z', t'2021-02-10T09:00:00Z', "hours", fractional: true)"#,
+                result: Ok("1.5"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let a = arguments.required("a");
+        let b = arguments.required("b");
+        let unit = arguments
+            .required_enum("unit", &units(), state)?
+            .try_bytes()
+            .expect("unit not bytes");
+        let fractional = arguments
+            .optional("fractional")
+            .unwrap_or_else(|| expr!(false));
+
+        Ok(DateDiffFn {
+            a,
+            b,
+            unit,
+            fractional,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DateDiffFn {
+    a: Box<dyn Expression>,
+    b: Box<dyn Expression>,
+    unit: Bytes,
+    fractional: Box<dyn Expression>,
+}
+
+impl FunctionExpression for DateDiffFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let a = self.a.resolve(ctx)?;
+        let b = self.b.resolve(ctx)?;
+        let fractional = self.fractional.resolve(ctx)?;
+
+        date_diff(a, b, &self.unit, fractional)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        match self
+            .fractional
+            .resolve_constant(state)
+            .and_then(|v| v.as_boolean())
+        {
+            Some(true) => TypeDef::float(),
+            Some(false) => TypeDef::integer(),
+            None => TypeDef::integer().or_float(),
+        }
+        .infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    test_function![
+        date_diff => DateDiff;
+
+        hours {
+            args: func_args![a: Utc.with_ymd_and_hms(2021, 2, 10, 12, 0, 0).unwrap(),
+                             b: Utc.with_ymd_and_hms(2021, 2, 10, 9, 0, 0).unwrap(),
+                             unit: "hours"],
+            want: Ok(3),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        seconds {
+            args: func_args![a: Utc.with_ymd_and_hms(2021, 2, 10, 12, 0, 0).unwrap(),
+                             b: Utc.with_ymd_and_hms(2021, 2, 10, 9, 0, 0).unwrap(),
+                             unit: "seconds"],
+            want: Ok(10800),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        negative {
+            args: func_args![a: Utc.with_ymd_and_hms(2021, 2, 10, 9, 0, 0).unwrap(),
+                             b: Utc.with_ymd_and_hms(2021, 2, 10, 12, 0, 0).unwrap(),
+                             unit: "minutes"],
+            want: Ok(-180),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        truncates_toward_zero {
+            args: func_args![a: Utc.with_ymd_and_hms(2021, 2, 8, 0, 0, 0).unwrap(),
+                             b: Utc.with_ymd_and_hms(2021, 2, 10, 12, 0, 0).unwrap(),
+                             unit: "days"],
+            want: Ok(-2),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        fractional {
+            args: func_args![a: Utc.with_ymd_and_hms(2021, 2, 8, 0, 0, 0).unwrap(),
+                             b: Utc.with_ymd_and_hms(2021, 2, 10, 12, 0, 0).unwrap(),
+                             unit: "days",
+                             fractional: true],
+            want: Ok(-2.5),
+            tdef: TypeDef::float().infallible(),
+        }
+
+        invalid_unit {
+            args: func_args![a: Utc.with_ymd_and_hms(2021, 2, 10, 12, 0, 0).unwrap(),
+                             b: Utc.with_ymd_and_hms(2021, 2, 10, 9, 0, 0).unwrap(),
+                             unit: "weeks"],
+            want: Err(r#"invalid enum variant""#),
+            tdef: TypeDef::integer().infallible(),
+        }
+    ];
+}
@@ -0,0 +1,113 @@
+use crate::compiler::prelude::*;
+use chrono::Datelike;
+
+use super::util::date_in_timezone;
+
+fn day_of_year(value: Value, timezone: Option<Value>) -> Resolved {
+    let date = date_in_timezone(value.try_timestamp()?, timezone)?;
+
+    Ok(i64::from(date.ordinal()).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DayOfYear;
+
+impl Function for DayOfYear {
+    fn identifier(&self) -> &'static str {
+        "day_of_year"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::TIMESTAMP,
+                required: true,
+            },
+            Parameter {
+                keyword: "timezone",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "day of year",
+                source: "day_of_year(t'2021-02-10T23:32:00Z')",
+                result: Ok("41"),
+            },
+            Example {
+                title: "day of year in timezone",
+                source: r#"day_of_year!(t'2021-02-10T23:32:00Z', timezone: "Europe/Berlin")"#,
+                result: Ok("42"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let timezone = arguments.optional("timezone");
+
+        Ok(DayOfYearFn { value, timezone }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DayOfYearFn {
+    value: Box<dyn Expression>,
+    timezone: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for DayOfYearFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let timezone = self
+            .timezone
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        day_of_year(value, timezone)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::integer().maybe_fallible(self.timezone.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    test_function![
+        day_of_year => DayOfYear;
+
+        first_day {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap()],
+            want: Ok(1),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        leap_year_last_day {
+            args: func_args![value: Utc.with_ymd_and_hms(2020, 12, 31, 12, 0, 0).unwrap()],
+            want: Ok(366),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        timezone_crosses_year {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 1, 1, 2, 0, 0).unwrap(),
+                             timezone: "America/New_York"],
+            want: Ok(366),
+            tdef: TypeDef::integer().fallible(),
+        }
+    ];
+}
@@ -3,7 +3,7 @@ use nom::AsBytes;
 use std::str;
 
 fn decode_base16(value: Value) -> Resolved {
-    match base16::decode(&value.try_bytes_utf8_lossy()?.to_string()) {
+    match base16::decode(value.try_bytes_utf8_lossy()?.as_bytes()) {
         Ok(s) => Ok((s.as_bytes()).into()),
         Err(_) => Err("unable to decode value to base16".into()),
     }
@@ -1,8 +1,7 @@
 use crate::compiler::prelude::*;
-use base64::Engine as _;
 use std::str::FromStr;
 
-use super::util::Base64Charset;
+use super::util::{base64_decode, Base64Charset};
 
 fn decode_base64(charset: Option<Value>, value: Value) -> Resolved {
     let charset = charset
@@ -11,18 +10,11 @@ fn decode_base64(charset: Option<Value>, value: Value) -> Resolved {
         .map(|c| Base64Charset::from_str(&String::from_utf8_lossy(&c)))
         .transpose()?
         .unwrap_or_default();
-    let alphabet = match charset {
-        Base64Charset::Standard => base64::alphabet::STANDARD,
-        Base64Charset::UrlSafe => base64::alphabet::URL_SAFE,
-    };
     let value = value.try_bytes()?;
-    let config = base64::engine::general_purpose::GeneralPurposeConfig::new()
-        .with_decode_padding_mode(base64::engine::DecodePaddingMode::Indifferent);
-    let engine = base64::engine::GeneralPurpose::new(&alphabet, config);
 
-    match engine.decode(value) {
-        Ok(s) => Ok(Value::from(Bytes::from(s))),
-        Err(_) => Err("unable to decode value to base64".into()),
+    match base64_decode(&value, charset) {
+        Some(s) => Ok(Value::from(Bytes::from(s))),
+        None => Err("unable to decode value to base64".into()),
     }
 }
 
@@ -49,6 +41,10 @@ impl Function for DecodeBase64 {
         ]
     }
 
+    fn fold(&self) -> bool {
+        true
+    }
+
     fn compile(
         &self,
         _state: &state::TypeState,
@@ -136,4 +132,48 @@ mod test {
             tdef: TypeDef::bytes().fallible(),
         }
     ];
+
+    #[cfg(feature = "simd_base64")]
+    #[test]
+    fn simd_backend_matches_scalar() {
+        use crate::stdlib::util::{scalar_base64, simd_base64};
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        for charset in [Base64Charset::Standard, Base64Charset::UrlSafe] {
+            for len in 0..100 {
+                let bytes: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+                for padding in [true, false] {
+                    let encoded = scalar_base64::encode(&bytes, charset, padding);
+                    assert_eq!(simd_base64::encode(&bytes, charset, padding), encoded);
+                    assert_eq!(
+                        simd_base64::decode(encoded.as_bytes(), charset),
+                        Some(bytes.clone())
+                    );
+                }
+
+                let padded = scalar_base64::encode(&bytes, charset, true);
+                let partial = padded.strip_suffix('=').unwrap_or(&padded);
+                assert_eq!(
+                    simd_base64::decode(partial.as_bytes(), charset),
+                    scalar_base64::decode(partial.as_bytes(), charset),
+                    "{partial:?}"
+                );
+            }
+
+            let alphabet = b"ABCab01+/-_=";
+            for _ in 0..10_000 {
+                let len = rng.gen_range(0..12);
+                let input: Vec<u8> = (0..len)
+                    .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+                    .collect();
+                assert_eq!(
+                    simd_base64::decode(&input, charset),
+                    scalar_base64::decode(&input, charset),
+                    "{:?}",
+                    String::from_utf8_lossy(&input)
+                );
+            }
+        }
+    }
 }
@@ -1,16 +1,17 @@
+use super::util::{max_size_limit, read_to_end_limited};
 use crate::compiler::prelude::*;
 use flate2::read::MultiGzDecoder;
-use std::io::Read;
 
-fn decode_gzip(value: Value) -> Resolved {
+fn decode_gzip(value: Value, max_size: Option<Value>) -> Resolved {
+    let max_size = max_size_limit(max_size)?;
     let value = value.try_bytes()?;
-    let mut buf = Vec::new();
-    let result = MultiGzDecoder::new(std::io::Cursor::new(value)).read_to_end(&mut buf);
+    let buf = read_to_end_limited(
+        MultiGzDecoder::new(std::io::Cursor::new(value)),
+        max_size,
+        "Gzip",
+    )?;
 
-    match result {
-        Ok(_) => Ok(Value::Bytes(buf.into())),
-        Err(_) => Err("unable to decode value with Gzip decoder".into()),
-    }
+    Ok(Value::Bytes(buf.into()))
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -36,29 +37,44 @@ impl Function for DecodeGzip {
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
+        let max_size = arguments.optional("max_size");
 
-        Ok(DecodeGzipFn { value }.as_expr())
+        Ok(DecodeGzipFn { value, max_size }.as_expr())
     }
 
     fn parameters(&self) -> &'static [Parameter] {
-        &[Parameter {
-            keyword: "value",
-            kind: kind::BYTES,
-            required: true,
-        }]
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "max_size",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
     }
 }
 
 #[derive(Clone, Debug)]
 struct DecodeGzipFn {
     value: Box<dyn Expression>,
+    max_size: Option<Box<dyn Expression>>,
 }
 
 impl FunctionExpression for DecodeGzipFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
 
-        decode_gzip(value)
+        let max_size = self
+            .max_size
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        decode_gzip(value, max_size)
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
@@ -73,6 +89,7 @@ mod tests {
     use crate::value;
     use flate2::read::GzEncoder;
     use nom::AsBytes;
+    use std::io::Read;
 
     fn get_encoded_bytes(text: &str) -> Vec<u8> {
         let mut buf = Vec::new();
@@ -96,5 +113,23 @@ mod tests {
             want: Err("unable to decode value with Gzip decoder"),
             tdef: TypeDef::bytes().fallible(),
         }
+
+        within_max_size {
+            args: func_args![value: value!(get_encoded_bytes("sample").as_bytes()), max_size: 6],
+            want: Ok(value!(b"sample")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        exceeds_max_size {
+            args: func_args![value: value!(get_encoded_bytes("sample").as_bytes()), max_size: 5],
+            want: Err("decompressed size exceeds max_size of 5 bytes"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        negative_max_size {
+            args: func_args![value: value!(get_encoded_bytes("sample").as_bytes()), max_size: -1],
+            want: Err("max_size must be a non-negative integer"),
+            tdef: TypeDef::bytes().fallible(),
+        }
     ];
 }
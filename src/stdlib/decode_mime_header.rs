@@ -0,0 +1,244 @@
+use charset::Charset;
+use data_encoding::BASE64_MIME;
+
+use crate::compiler::prelude::*;
+
+fn decode_mime_header(value: Value) -> Resolved {
+    let input = value.try_bytes_utf8_lossy()?;
+    Ok(decode_header(&unfold(&input)).into())
+}
+
+/// Joins the lines of a folded header, dropping each line break that precedes whitespace.
+fn unfold(input: &str) -> String {
+    input
+        .replace("\r\n ", " ")
+        .replace("\r\n\t", "\t")
+        .replace("\n ", " ")
+        .replace("\n\t", "\t")
+}
+
+fn decode_header(input: &str) -> String {
+    let mut decoded = String::with_capacity(input.len());
+    // The charset and bytes of the encoded-words decoded since the last text. Adjacent words in
+    // the same charset are converted together, as encoders sometimes split a character
+    // across them.
+    let mut pending: Option<(Charset, Vec<u8>)> = None;
+    let mut rest = input;
+
+    while let Some(start) = rest.find("=?") {
+        let (text, word) = rest.split_at(start);
+        let Some((charset, bytes, len)) = parse_word(word) else {
+            // Not an encoded-word, so it is kept as text.
+            flush(&mut pending, &mut decoded);
+            decoded.push_str(&rest[..start + 2]);
+            rest = &rest[start + 2..];
+            continue;
+        };
+
+        // Whitespace between two encoded-words is not part of the header.
+        let adjacent = pending.is_some() && text.chars().all(char::is_whitespace);
+        if !adjacent {
+            flush(&mut pending, &mut decoded);
+            decoded.push_str(text);
+        }
+        match &mut pending {
+            Some((pending_charset, pending_bytes)) if *pending_charset == charset => {
+                pending_bytes.extend_from_slice(&bytes);
+            }
+            _ => {
+                flush(&mut pending, &mut decoded);
+                pending = Some((charset, bytes));
+            }
+        }
+        rest = &word[len..];
+    }
+
+    flush(&mut pending, &mut decoded);
+    decoded.push_str(rest);
+    decoded
+}
+
+fn flush(pending: &mut Option<(Charset, Vec<u8>)>, decoded: &mut String) {
+    if let Some((charset, bytes)) = pending.take() {
+        decoded.push_str(&charset.decode_without_bom_handling(&bytes).0);
+    }
+}
+
+/// Parses and decodes the `=?charset?encoding?text?=` encoded-word at the start of `input`,
+/// returning its charset, its decoded bytes and its length.
+///
+/// Returns `None` when the word is malformed or its charset is unknown.
+fn parse_word(input: &str) -> Option<(Charset, Vec<u8>, usize)> {
+    let mut parts = input.strip_prefix("=?")?.splitn(4, '?');
+    let (label, encoding, text) = (parts.next()?, parts.next()?, parts.next()?);
+    let end = parts.next()?.strip_prefix('=')?;
+    if [label, text]
+        .iter()
+        .any(|part| part.contains(char::is_whitespace))
+    {
+        return None;
+    }
+
+    // RFC 2231 allows a language after the charset, as in `utf-8*en`.
+    let label = label.split_once('*').map_or(label, |(label, _)| label);
+    let charset = Charset::for_label_no_replacement(label.as_bytes())?;
+    let bytes = match encoding {
+        "B" | "b" => BASE64_MIME.decode(text.as_bytes()).ok()?,
+        "Q" | "q" => decode_q(text)?,
+        _ => return None,
+    };
+
+    Some((charset, bytes, input.len() - end.len()))
+}
+
+/// Decodes the "Q" encoding, where `_` stands for a space and `=XX` for the byte `XX`.
+fn decode_q(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut input = text.bytes();
+    while let Some(byte) = input.next() {
+        match byte {
+            b'_' => bytes.push(b' '),
+            b'=' => {
+                let hex = [input.next()?, input.next()?];
+                let hex = std::str::from_utf8(&hex).ok()?;
+                bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            }
+            _ => bytes.push(byte),
+        }
+    }
+    Some(bytes)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeMimeHeader;
+
+impl Function for DecodeMimeHeader {
+    fn identifier(&self) -> &'static str {
+        "decode_mime_header"
+    }
+
+    fn summary(&self) -> &'static str {
+        "decode the encoded-words of a MIME header"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Decodes the RFC 2047 encoded-words, such as `=?UTF-8?B?SGVsbG8=?=`, of an email
+            header `value` to UTF-8. Both the \"B\" (Base64) and \"Q\" (Quoted-Printable)
+            encodings are supported, in any charset known to the WHATWG Encoding Standard.
+
+            Folded lines are joined, whitespace between adjacent encoded-words is dropped, and
+            text outside encoded-words is kept as is. Malformed encoded-words, and those in an
+            unknown charset, are kept as text rather than failing the whole header.
+        "}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(DecodeMimeHeaderFn { value }.as_expr())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "Base64",
+                source: r#"decode_mime_header("=?UTF-8?B?SGVsbG8sIFdvcmxkIQ==?=")"#,
+                result: Ok("Hello, World!"),
+            },
+            Example {
+                title: "Multiple encoded-words",
+                source: r#"decode_mime_header("Subject: =?ISO-8859-1?Q?=A1Hola,_?= =?UTF-8?B?c2XDsW9yIQ==?=")"#,
+                result: Ok("Subject: ¡Hola, señor!"),
+            },
+        ]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeMimeHeaderFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for DecodeMimeHeaderFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        decode_mime_header(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        decode_mime_header => DecodeMimeHeader;
+
+        base64 {
+            args: func_args![value: value!("Subject: =?UTF-8?B?w4ljaGFudGlsbG9uIGTigJlhdmlz?=")],
+            want: Ok(value!("Subject: Échantillon d’avis")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        quoted_printable {
+            args: func_args![value: value!("Subject: =?iso-8859-1?Q?=A1Hola,_se=F1or!?=")],
+            want: Ok(value!("Subject: ¡Hola, señor!")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        adjacent_words {
+            args: func_args![value: value!("=?UTF-8?Q?Hello,?=  \t=?UTF-8?Q?_World!?= =?UTF-8?B?IPCfkYs=?= <hello@example.com>")],
+            want: Ok(value!("Hello, World! 👋 <hello@example.com>")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        character_split_across_words {
+            args: func_args![value: value!("=?UTF-8?B?8J+R?= =?UTF-8?B?iw==?=")],
+            want: Ok(value!("👋")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        folded {
+            args: func_args![value: value!("Subject: =?UTF-8?Q?Quarterly?=\r\n =?UTF-8?Q?_report?=\r\n\tattached")],
+            want: Ok(value!("Subject: Quarterly report\tattached")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        language {
+            args: func_args![value: value!("=?US-ASCII*EN?Q?Keith_Moore?=")],
+            want: Ok(value!("Keith Moore")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        not_encoded {
+            args: func_args![value: value!("Is =? equal to ?= or not?")],
+            want: Ok(value!("Is =? equal to ?= or not?")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        malformed_words {
+            args: func_args![value: value!("=?iso-9001?Q?hello?= =?UTF-8?X?hello?= =?UTF-8?Q?hello=ZZ?= =?UTF-8?B?aGVsbG8=?=")],
+            want: Ok(value!("=?iso-9001?Q?hello?= =?UTF-8?X?hello?= =?UTF-8?Q?hello=ZZ?= hello")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
@@ -123,6 +123,12 @@ mod test {
             tdef: TypeDef::bytes().fallible(),
         }
 
+        german_domain {
+            args: func_args![value: value!("xn--mnchen-3ya.de")],
+            want: Ok(value!("münchen.de")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
         bidi_error {
             args: func_args![value: value!("xn--8hbb.xn--fiba.xn--8hbf.xn--eib.")],
             want: Err("unable to decode punycode"),
@@ -0,0 +1,147 @@
+use crate::compiler::prelude::*;
+
+fn decode_quoted_printable(value: Value) -> Resolved {
+    let value = value.try_bytes()?;
+    // Robust parsing never fails, malformed escapes are kept as they are.
+    let decoded = quoted_printable::decode(value, quoted_printable::ParseMode::Robust)
+        .expect("robust parsing is infallible");
+    Ok(Value::Bytes(decoded.into()))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeQuotedPrintable;
+
+impl Function for DecodeQuotedPrintable {
+    fn identifier(&self) -> &'static str {
+        "decode_quoted_printable"
+    }
+
+    fn summary(&self) -> &'static str {
+        "decode a Quoted-Printable string"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Decodes the `value` from the Quoted-Printable transfer encoding of RFC 2045, as used
+            in email bodies.
+
+            `=XX` hex escapes are decoded, in either case, and soft line breaks, a `=` at the end
+            of a line, are removed. Line breaks are returned as CRLF and trailing whitespace on
+            each line is dropped, as the RFC requires. Malformed escapes are kept as they are,
+            and characters outside printable ASCII, which encoded data can't contain, are
+            dropped.
+        "}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(DecodeQuotedPrintableFn { value }.as_expr())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "decode string",
+            source: r#"decode_quoted_printable("Gr=C3=BC=C3=9Fe =3D Gree=\r\ntings")"#,
+            result: Ok("Grüße = Greetings"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeQuotedPrintableFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for DecodeQuotedPrintableFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        decode_quoted_printable(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        decode_quoted_printable => DecodeQuotedPrintable;
+
+        escapes {
+            args: func_args![value: value!("caf=C3=A9 =3d 5=E2=82=AC\r\nnext=0Aline")],
+            want: Ok(value!("caf\u{e9} = 5\u{20ac}\r\nnext\nline")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        soft_line_breaks {
+            args: func_args![value: value!("The quick brown fox=\r\n jumps over=\n the lazy dog.")],
+            want: Ok(value!("The quick brown fox jumps over the lazy dog.")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        line_breaks {
+            args: func_args![value: value!("trailing  \nwhitespace=20\n")],
+            want: Ok(value!("trailing\r\nwhitespace \r\n")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        malformed_escapes {
+            args: func_args![value: value!("100=ZZ done=4")],
+            want: Ok(value!("100=ZZ done=4")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+
+    #[test]
+    fn round_trip() {
+        use crate::compiler::{compile, runtime::Runtime, TargetValue, TimeZone};
+        use crate::value::Secrets;
+
+        let program = compile(
+            "decode_quoted_printable(encode_quoted_printable(string!(.message)))",
+            &crate::stdlib::all(),
+        )
+        .unwrap()
+        .program;
+
+        for message in [
+            "",
+            "plain text",
+            "caf\u{e9} = 5\u{20ac}",
+            "first line\r\nsecond line\nthird line\rend",
+            "trailing whitespace \t\r\nnext line",
+            "binary \x00\x01\x7f bytes",
+            // Long enough to be wrapped with soft line breaks, some of them next to escapes.
+            &"The quick brown fox jumps over the lazy dog. \u{1f98a} ".repeat(10),
+        ] {
+            let mut target = TargetValue {
+                value: value!({ message: message }),
+                metadata: value!({}),
+                secrets: Secrets::new(),
+            };
+            let decoded = Runtime::default()
+                .resolve(&mut target, &program, &TimeZone::default())
+                .unwrap();
+            assert_eq!(decoded, value!(message));
+        }
+    }
+}
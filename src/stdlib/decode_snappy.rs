@@ -1,14 +1,24 @@
 use crate::compiler::prelude::*;
 use snap::raw::Decoder;
+use snap::read::FrameDecoder;
+use std::io::Read;
 
-fn decode_snappy(value: Value) -> Resolved {
+fn decode_snappy(value: Value, framed: bool) -> Resolved {
     let value = value.try_bytes()?;
-    let mut decoder = Decoder::new();
-    let result = decoder.decompress_vec(&value);
+    let result = if framed {
+        let mut buf = Vec::new();
+        FrameDecoder::new(&value[..])
+            .read_to_end(&mut buf)
+            .map(|_| buf)
+            .map_err(|_| ())
+    } else {
+        Decoder::new().decompress_vec(&value).map_err(|_| ())
+    };
 
     match result {
         Ok(buf) => Ok(Value::Bytes(buf.into())),
-        Err(_) => Err("unable to decode value with Snappy decoder".into()),
+        Err(()) if framed => Err("unable to decode value with Snappy frame decoder".into()),
+        Err(()) => Err("unable to decode value with Snappy decoder".into()),
     }
 }
 
@@ -35,29 +45,39 @@ impl Function for DecodeSnappy {
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
+        let framed = arguments.optional("framed").unwrap_or_else(|| expr!(false));
 
-        Ok(DecodeSnappyFn { value }.as_expr())
+        Ok(DecodeSnappyFn { value, framed }.as_expr())
     }
 
     fn parameters(&self) -> &'static [Parameter] {
-        &[Parameter {
-            keyword: "value",
-            kind: kind::BYTES,
-            required: true,
-        }]
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "framed",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
     }
 }
 
 #[derive(Clone, Debug)]
 struct DecodeSnappyFn {
     value: Box<dyn Expression>,
+    framed: Box<dyn Expression>,
 }
 
 impl FunctionExpression for DecodeSnappyFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
+        let framed = self.framed.resolve(ctx)?.try_boolean()?;
 
-        decode_snappy(value)
+        decode_snappy(value, framed)
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
@@ -82,6 +102,14 @@ mod tests {
         engine.decode(text).expect("Cannot decode from Base64")
     }
 
+    fn get_framed_bytes(text: &str) -> Vec<u8> {
+        let mut buf = Vec::new();
+        snap::read::FrameEncoder::new(text.as_bytes())
+            .read_to_end(&mut buf)
+            .expect("Cannot encode bytes with Snappy frame encoder");
+        buf
+    }
+
     test_function![
         decode_snappy => DecodeSnappy;
 
@@ -96,5 +124,17 @@ mod tests {
             want: Err("unable to decode value with Snappy decoder"),
             tdef: TypeDef::bytes().fallible(),
         }
+
+        right_framed_snappy {
+            args: func_args![value: value!(get_framed_bytes("The quick brown fox jumps over 13 lazy dogs.").as_bytes()), framed: true],
+            want: Ok(value!(b"The quick brown fox jumps over 13 lazy dogs.")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        raw_input_as_framed {
+            args: func_args![value: value!(decode_base64("LKxUaGUgcXVpY2sgYnJvd24gZm94IGp1bXBzIG92ZXIgMTMgbGF6eSBkb2dzLg==").as_bytes()), framed: true],
+            want: Err("unable to decode value with Snappy frame decoder"),
+            tdef: TypeDef::bytes().fallible(),
+        }
     ];
 }
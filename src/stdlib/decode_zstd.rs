@@ -1,14 +1,15 @@
+use super::util::{max_size_limit, read_to_end_limited};
 use crate::compiler::prelude::*;
 use nom::AsBytes;
 
-fn decode_zstd(value: Value) -> Resolved {
+fn decode_zstd(value: Value, max_size: Option<Value>) -> Resolved {
+    let max_size = max_size_limit(max_size)?;
     let value = value.try_bytes()?;
-    let result = zstd::decode_all(value.as_bytes());
+    let decoder = zstd::stream::read::Decoder::new(value.as_bytes())
+        .map_err(|_| "unable to decode value with Zstd decoder")?;
+    let decoded_bytes = read_to_end_limited(decoder, max_size, "Zstd")?;
 
-    match result {
-        Ok(decoded_bytes) => Ok(Value::Bytes(decoded_bytes.into())),
-        Err(_) => Err("unable to decode value with Zstd decoder".into()),
-    }
+    Ok(Value::Bytes(decoded_bytes.into()))
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -34,29 +35,44 @@ impl Function for DecodeZstd {
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
+        let max_size = arguments.optional("max_size");
 
-        Ok(DecodeZstdFn { value }.as_expr())
+        Ok(DecodeZstdFn { value, max_size }.as_expr())
     }
 
     fn parameters(&self) -> &'static [Parameter] {
-        &[Parameter {
-            keyword: "value",
-            kind: kind::BYTES,
-            required: true,
-        }]
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "max_size",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
     }
 }
 
 #[derive(Clone, Debug)]
 struct DecodeZstdFn {
     value: Box<dyn Expression>,
+    max_size: Option<Box<dyn Expression>>,
 }
 
 impl FunctionExpression for DecodeZstdFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
 
-        decode_zstd(value)
+        let max_size = self
+            .max_size
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        decode_zstd(value, max_size)
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
@@ -92,5 +108,17 @@ mod tests {
             want: Err("unable to decode value with Zstd decoder"),
             tdef: TypeDef::bytes().fallible(),
         }
+
+        within_max_size {
+            args: func_args![value: value!(get_encoded_bytes("sample").as_bytes()), max_size: 6],
+            want: Ok(value!(b"sample")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        exceeds_max_size {
+            args: func_args![value: value!(get_encoded_bytes("sample").as_bytes()), max_size: 5],
+            want: Err("decompressed size exceeds max_size of 5 bytes"),
+            tdef: TypeDef::bytes().fallible(),
+        }
     ];
 }
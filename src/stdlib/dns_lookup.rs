@@ -19,7 +19,7 @@ mod non_wasm {
     use std::time::Duration;
 
     use domain::base::iana::Class;
-    use domain::base::{Name, RecordSection, Rtype};
+    use domain::base::{Message, Name, RecordSection, Rtype};
     use domain::rdata::AllRecordData;
     use domain::resolv::stub::conf::{ResolvConf, ResolvOptions, ServerConf, Transport};
     use domain::resolv::stub::Answer;
@@ -133,7 +133,7 @@ mod non_wasm {
         }
         .map_err(|err| format!("query failed: {err}"))?;
 
-        Ok(parse_answer(answer)?.into())
+        Ok(parse_message(&answer)?.into())
     }
 
     #[derive(Debug, Clone)]
@@ -242,15 +242,16 @@ mod non_wasm {
         Ok(conf)
     }
 
-    fn parse_answer(answer: Answer) -> Result<ObjectMap, ExpressionError> {
+    /// Converts a DNS message into the object returned by `dns_lookup` and `parse_dns_message`.
+    pub(crate) fn parse_message(message: &Message<Bytes>) -> Result<ObjectMap, ExpressionError> {
         let mut result = ObjectMap::new();
-        let header_section = answer.header();
+        let header_section = message.header();
         let rcode = header_section.rcode();
         result.insert("fullRcode".into(), rcode.to_int().into());
         result.insert("rcodeName".into(), rcode.to_string().into());
         let header = {
             let mut header_obj = ObjectMap::new();
-            let counts = answer.header_counts();
+            let counts = message.header_counts();
             header_obj.insert("aa".into(), header_section.aa().into());
             header_obj.insert("ad".into(), header_section.ad().into());
             header_obj.insert("cd".into(), header_section.cd().into());
@@ -268,7 +269,7 @@ mod non_wasm {
         };
         result.insert("header".into(), header.into());
 
-        let (question, answer_section, authority, additional) = answer
+        let (question, answer_section, authority, additional) = message
             .sections()
             .map_err(|err| format!("parsing response sections failed: {err}"))?;
 
@@ -375,7 +376,7 @@ mod non_wasm {
         ])
     }
 
-    pub(super) fn inner_kind() -> BTreeMap<Field, Kind> {
+    pub(crate) fn inner_kind() -> BTreeMap<Field, Kind> {
         BTreeMap::from([
             (Field::from("fullRcode"), Kind::integer()),
             (Field::from("rcodeName"), Kind::bytes() | Kind::null()),
@@ -406,6 +407,9 @@ mod non_wasm {
 #[cfg(not(target_arch = "wasm32"))]
 use non_wasm::*;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) use non_wasm::{inner_kind, parse_message};
+
 #[derive(Clone, Copy, Debug)]
 pub struct DnsLookup;
 
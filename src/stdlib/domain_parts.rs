@@ -0,0 +1,200 @@
+use crate::compiler::prelude::*;
+use std::collections::BTreeMap;
+
+fn domain_parts(value: Value) -> Resolved {
+    let host = value.try_bytes_utf8_lossy()?;
+
+    let suffix =
+        psl::suffix(host.as_bytes()).ok_or(format!("unable to determine eTLD for {host}"))?;
+    let tld = core::str::from_utf8(suffix.as_bytes())
+        .map_err(|err| format!("could not convert eTLD to UTF8 {err}"))?;
+
+    let labels = host.split('.').collect::<Vec<_>>();
+    let suffix_labels = tld.split('.').count();
+    // Labels left of the public suffix: the last one is the registrable label, the others make
+    // up the subdomain.
+    let prefix = &labels[..labels.len().saturating_sub(suffix_labels)];
+
+    let registrable_domain = prefix
+        .last()
+        .map(|label| format!("{label}.{tld}"))
+        .map_or(Value::Null, Into::into);
+    let subdomain = match prefix.len() {
+        0 | 1 => Value::Null,
+        n => prefix[..n - 1].join(".").into(),
+    };
+
+    let mut map = BTreeMap::<&str, Value>::new();
+
+    map.insert("tld", tld.to_owned().into());
+    map.insert("registrable_domain", registrable_domain);
+    map.insert("subdomain", subdomain);
+    map.insert(
+        "labels",
+        labels
+            .into_iter()
+            .map(|label| Value::from(label.to_owned()))
+            .collect::<Vec<_>>()
+            .into(),
+    );
+    map.insert("known_suffix", suffix.is_known().into());
+
+    Ok(map
+        .into_iter()
+        .map(|(k, v)| (k.to_owned(), v))
+        .collect::<Value>())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DomainParts;
+
+impl Function for DomainParts {
+    fn identifier(&self) -> &'static str {
+        "domain_parts"
+    }
+
+    fn summary(&self) -> &'static str {
+        "split a host name into its domain parts"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Splits a host name into its public suffix (`tld`), its registrable domain (the eTLD+1),
+            the subdomain to the left of it and the full list of `labels`, using the Public
+            Suffix List.
+
+            `registrable_domain` and `subdomain` are `null` when the host has no labels left of
+            the suffix or of the registrable domain respectively. Any other level, such as the
+            eTLD+2, can be built from `labels` and `tld`.
+        "}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "domain parts",
+            source: r#"domain_parts!("a.b.example.co.uk")"#,
+            result: Ok(indoc! {r#"
+                {
+                    "known_suffix": true,
+                    "labels": ["a", "b", "example", "co", "uk"],
+                    "registrable_domain": "example.co.uk",
+                    "subdomain": "a.b",
+                    "tld": "co.uk"
+                }
+            "#}),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(DomainPartsFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DomainPartsFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for DomainPartsFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        domain_parts(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        ("tld".into(), Kind::bytes()),
+        ("registrable_domain".into(), Kind::bytes().or_null()),
+        ("subdomain".into(), Kind::bytes().or_null()),
+        (
+            "labels".into(),
+            Kind::array(Collection::from_unknown(Kind::bytes())),
+        ),
+        ("known_suffix".into(), Kind::boolean()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        domain_parts => DomainParts;
+
+        multi_level_suffix {
+            args: func_args![value: value!("a.b.example.co.uk")],
+            want: Ok(value!({
+                tld: "co.uk",
+                registrable_domain: "example.co.uk",
+                subdomain: "a.b",
+                labels: ["a", "b", "example", "co", "uk"],
+                known_suffix: true,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        no_subdomain {
+            args: func_args![value: value!("vector.dev")],
+            want: Ok(value!({
+                tld: "dev",
+                registrable_domain: "vector.dev",
+                subdomain: null,
+                labels: ["vector", "dev"],
+                known_suffix: true,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        suffix_only {
+            args: func_args![value: value!("co.uk")],
+            want: Ok(value!({
+                tld: "co.uk",
+                registrable_domain: null,
+                subdomain: null,
+                labels: ["co", "uk"],
+                known_suffix: true,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        unknown_tld {
+            args: func_args![value: value!("www.vector.unknowndev")],
+            want: Ok(value!({
+                tld: "unknowndev",
+                registrable_domain: "vector.unknowndev",
+                subdomain: "www",
+                labels: ["www", "vector", "unknowndev"],
+                known_suffix: false,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        empty_host {
+            args: func_args![value: value!("")],
+            want: Err("unable to determine eTLD for "),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
@@ -0,0 +1,486 @@
+use crate::compiler::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct EmitLog;
+
+impl Function for EmitLog {
+    fn identifier(&self) -> &'static str {
+        "emit_log"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "message",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "level",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "fields",
+                kind: kind::OBJECT,
+                required: false,
+            },
+            Parameter {
+                keyword: "rate_limit_secs",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "default log level (info)",
+                source: r#"emit_log("record processed")"#,
+                result: Ok("null"),
+            },
+            Example {
+                title: "with level and fields",
+                source: r#"emit_log("dropped malformed record", "warn", {"reason": "invalid json", "attempt": 2})"#,
+                result: Ok("null"),
+            },
+            Example {
+                title: "custom rate limit",
+                source: r#"emit_log("record processed", fields: {"id": 42}, rate_limit_secs: 60)"#,
+                result: Ok("null"),
+            },
+        ]
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let levels = vec![
+            "trace".into(),
+            "debug".into(),
+            "info".into(),
+            "warn".into(),
+            "error".into(),
+        ];
+
+        let message = arguments.required("message");
+        let level = arguments
+            .optional_enum("level", &levels, state)?
+            .unwrap_or_else(|| "info".into())
+            .try_bytes()
+            .expect("log level not bytes");
+        // Tracing needs the field names up front, so the fields must be an object literal,
+        // though their values can be computed at runtime.
+        let fields = arguments.optional_object("fields")?.unwrap_or_default();
+        if fields.len() > implementation::FIELD_LIMIT {
+            return Err(function::Error::InvalidArgument {
+                keyword: "fields",
+                value: fields.keys().map(|key| Value::from(key.as_str())).collect(),
+                error: "at most 32 fields can be emitted",
+            }
+            .into());
+        }
+        let rate_limit_secs = arguments.optional("rate_limit_secs");
+
+        let (names, fields): (Vec<_>, Vec<_>) = fields.into_iter().unzip();
+        Ok(implementation::EmitLogFn {
+            span: ctx.span(),
+            message,
+            callsite: implementation::callsite(&level, &names),
+            rate_limit_secs,
+            names,
+            fields,
+        }
+        .as_expr())
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        ctx: &mut FunctionCompileContext,
+        _arguments: ArgumentList,
+    ) -> Compiled {
+        Ok(super::WasmUnsupportedFunction::new(ctx.span(), TypeDef::null().infallible()).as_expr())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod implementation {
+    use std::collections::HashMap;
+    use std::sync::{Mutex, OnceLock, PoisonError};
+
+    use tracing::callsite::{self, Callsite, Identifier};
+    use tracing::field::{display, DisplayValue, Field, FieldSet, Value as FieldValue};
+    use tracing::level_filters::LevelFilter;
+    use tracing::metadata::Kind as MetadataKind;
+    use tracing::subscriber::Interest;
+    use tracing::{Event, Level, Metadata};
+
+    use crate::compiler::expression::Expr;
+    use crate::compiler::prelude::*;
+    use crate::value;
+
+    /// The most user supplied fields a single event can have.
+    pub(super) const FIELD_LIMIT: usize = 32;
+
+    /// The fields of every event, ahead of the user supplied ones. The rate limit is read by
+    /// the same subscribers that limit `log`.
+    const BUILTIN_FIELDS: [&str; 3] = ["message", "internal_log_rate_secs", "vrl_position"];
+
+    /// The field holding all user supplied fields of events emitted through a fallback
+    /// callsite.
+    const FALLBACK_FIELD: &str = "fields";
+
+    /// The most distinct sets of field names that get a callsite of their own.
+    const CALLSITE_LIMIT: usize = 1024;
+
+    /// The callsite of the events emitted with one level and set of field names.
+    ///
+    /// The tracing macros declare a static callsite listing the field names, which can't be
+    /// done for names only known once a program is compiled. Instead, a callsite is leaked
+    /// for each level and set of names, and shared by every program using them. Once
+    /// `CALLSITE_LIMIT` callsites exist, new sets of names share a fallback callsite per
+    /// level, which records all user supplied fields as a single `fields` field, so the
+    /// memory held by callsites stays bounded however many programs are compiled.
+    #[derive(Debug)]
+    pub(super) struct LogCallsite {
+        metadata: OnceLock<Metadata<'static>>,
+        fallback: bool,
+    }
+
+    impl Callsite for LogCallsite {
+        // Nothing is cached, as whether the event is enabled is checked as it is emitted.
+        fn set_interest(&self, _: Interest) {}
+
+        fn metadata(&self) -> &Metadata<'_> {
+            self.metadata.get().expect("metadata is set on creation")
+        }
+    }
+
+    pub(super) fn callsite(level: &Bytes, names: &[KeyString]) -> &'static LogCallsite {
+        static CALLSITES: OnceLock<Mutex<Callsites>> = OnceLock::new();
+
+        CALLSITES
+            .get_or_init(|| Mutex::new(Callsites::new(CALLSITE_LIMIT)))
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(level, names)
+    }
+
+    /// The callsites created so far, keyed by level and set of names. Fallback callsites are
+    /// keyed without names.
+    #[derive(Debug)]
+    pub(super) struct Callsites {
+        limit: usize,
+        callsites: HashMap<(Level, Option<Vec<String>>), &'static LogCallsite>,
+    }
+
+    impl Callsites {
+        /// A registry giving at most `limit` sets of names a callsite of their own.
+        pub(super) fn new(limit: usize) -> Self {
+            Self {
+                limit,
+                callsites: HashMap::new(),
+            }
+        }
+
+        pub(super) fn get(&mut self, level: &Bytes, names: &[KeyString]) -> &'static LogCallsite {
+            let level = match level.as_ref() {
+                b"trace" => Level::TRACE,
+                b"debug" => Level::DEBUG,
+                b"warn" => Level::WARN,
+                b"error" => Level::ERROR,
+                _ => Level::INFO,
+            };
+            let mut key = (
+                level,
+                Some(names.iter().map(|name| name.as_str().to_owned()).collect()),
+            );
+
+            if self.callsites.len() >= self.limit && !self.callsites.contains_key(&key) {
+                key.1 = None;
+            }
+            self.callsites
+                .entry(key)
+                .or_insert_with_key(|(level, names)| match names {
+                    Some(names) => leak_callsite(
+                        *level,
+                        names
+                            .iter()
+                            .map(|name| &*Box::leak(name.clone().into_boxed_str()))
+                            .collect(),
+                        false,
+                    ),
+                    None => leak_callsite(*level, vec![FALLBACK_FIELD], true),
+                })
+        }
+    }
+
+    fn leak_callsite(
+        level: Level,
+        names: Vec<&'static str>,
+        fallback: bool,
+    ) -> &'static LogCallsite {
+        let names: Vec<&'static str> = BUILTIN_FIELDS.into_iter().chain(names).collect();
+        let callsite: &'static LogCallsite = Box::leak(Box::new(LogCallsite {
+            metadata: OnceLock::new(),
+            fallback,
+        }));
+        let metadata = Metadata::new(
+            concat!("event ", file!(), ":", line!()),
+            module_path!(),
+            level,
+            Some(file!()),
+            Some(line!()),
+            Some(module_path!()),
+            FieldSet::new(Box::leak(names.into_boxed_slice()), Identifier(callsite)),
+            MetadataKind::EVENT,
+        );
+        callsite
+            .metadata
+            .set(metadata)
+            .expect("callsite is only created once");
+        callsite::register(callsite);
+        callsite
+    }
+
+    /// A field value in a form tracing can record.
+    enum Recorded {
+        Integer(i64),
+        Float(f64),
+        Boolean(bool),
+        Text(DisplayValue<String>),
+    }
+
+    impl Recorded {
+        fn new(value: Value) -> Option<Self> {
+            Some(match value {
+                Value::Null => return None,
+                Value::Integer(integer) => Self::Integer(integer),
+                Value::Float(float) => Self::Float(float.into_inner()),
+                Value::Boolean(boolean) => Self::Boolean(boolean),
+                value => Self::Text(display(value.to_string_lossy().into_owned())),
+            })
+        }
+
+        fn as_value(&self) -> &dyn FieldValue {
+            match self {
+                Self::Integer(integer) => integer,
+                Self::Float(float) => float,
+                Self::Boolean(boolean) => boolean,
+                Self::Text(text) => text,
+            }
+        }
+    }
+
+    /// Renders the non-null `fields` as `name=value` pairs, for a fallback callsite.
+    fn render_fields(names: &[KeyString], fields: Vec<Value>) -> Vec<Value> {
+        let rendered = names
+            .iter()
+            .zip(fields)
+            .filter(|(_, value)| !value.is_null())
+            .map(|(name, value)| format!("{name}={}", value.to_string_lossy()))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        vec![rendered.into()]
+    }
+
+    /// Emits `message` through `tracing`, with each of the user supplied `fields` recorded as
+    /// a field of its own, or all together through a fallback callsite. Null fields are left
+    /// out.
+    pub(super) fn emit_log(
+        message: Value,
+        callsite: &'static LogCallsite,
+        rate_limit_secs: Value,
+        names: &[KeyString],
+        fields: Vec<Value>,
+        span: Span,
+    ) -> Resolved {
+        let message = message.try_bytes_utf8_lossy()?.into_owned();
+        let rate_limit_secs = rate_limit_secs.try_integer()?;
+
+        let metadata = callsite.metadata();
+        if *metadata.level() > LevelFilter::current()
+            || !tracing::dispatcher::get_default(|dispatch| dispatch.enabled(metadata))
+        {
+            return Ok(Value::Null);
+        }
+
+        let fields = if callsite.fallback {
+            render_fields(names, fields)
+        } else {
+            fields
+        };
+        let message = display(message);
+        let position = span.start();
+        let builtins: [&dyn FieldValue; BUILTIN_FIELDS.len()] =
+            [&message, &rate_limit_secs, &position];
+        let fields: Vec<_> = fields.into_iter().map(Recorded::new).collect();
+        let names: Vec<Field> = metadata.fields().iter().collect();
+        // Value sets have a fixed length, so unused entries repeat the last field without a
+        // value, which isn't recorded.
+        let values: [(&Field, Option<&dyn FieldValue>); BUILTIN_FIELDS.len() + FIELD_LIMIT] =
+            std::array::from_fn(|index| {
+                let value = match index.checked_sub(BUILTIN_FIELDS.len()) {
+                    None => Some(builtins[index]),
+                    Some(index) => fields
+                        .get(index)
+                        .and_then(Option::as_ref)
+                        .map(Recorded::as_value),
+                };
+                (&names[index.min(names.len() - 1)], value)
+            });
+        Event::dispatch(metadata, &metadata.fields().value_set(&values));
+
+        Ok(Value::Null)
+    }
+
+    #[derive(Debug, Clone)]
+    pub(super) struct EmitLogFn {
+        pub(super) span: Span,
+        pub(super) message: Box<dyn Expression>,
+        pub(super) callsite: &'static LogCallsite,
+        pub(super) rate_limit_secs: Option<Box<dyn Expression>>,
+        pub(super) names: Vec<KeyString>,
+        pub(super) fields: Vec<Expr>,
+    }
+
+    impl FunctionExpression for EmitLogFn {
+        fn resolve(&self, ctx: &mut Context) -> Resolved {
+            let message = self.message.resolve(ctx)?;
+            let rate_limit_secs = match &self.rate_limit_secs {
+                Some(expr) => expr.resolve(ctx)?,
+                None => value!(1),
+            };
+            let fields = self
+                .fields
+                .iter()
+                .map(|expr| expr.resolve(ctx))
+                .collect::<Result<_, _>>()?;
+
+            emit_log(
+                message,
+                self.callsite,
+                rate_limit_secs,
+                &self.names,
+                fields,
+                self.span,
+            )
+        }
+
+        fn type_def(&self, _: &state::TypeState) -> TypeDef {
+            TypeDef::null().infallible().impure()
+        }
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use tracing_test::traced_test;
+
+    use super::*;
+    use crate::value;
+
+    test_function![
+        emit_log => EmitLog;
+
+        message_only {
+            args: func_args![message: "record processed"],
+            want: Ok(Value::Null),
+            tdef: TypeDef::null().infallible().impure(),
+        }
+
+        invalid_level {
+            args: func_args![message: "record processed", level: "fatal"],
+            want: Err(r#"invalid enum variant""#),
+            tdef: TypeDef::null().infallible().impure(),
+        }
+    ];
+
+    #[traced_test]
+    #[test]
+    fn emits_structured_fields() {
+        let names = ["attempt".into(), "reason".into(), "user".into()];
+        implementation::emit_log(
+            value!("dropped malformed record"),
+            implementation::callsite(&Bytes::from("warn"), &names),
+            value!(10),
+            &names,
+            vec![value!(2), value!("invalid json"), Value::Null],
+            Span::default(),
+        )
+        .unwrap();
+
+        assert!(logs_contain("WARN"));
+        assert!(logs_contain("dropped malformed record"));
+        assert!(logs_contain("internal_log_rate_secs=10"));
+        assert!(logs_contain("attempt=2 reason=invalid json"));
+        assert!(!logs_contain("user="));
+    }
+
+    #[test]
+    fn callsites_are_shared() {
+        let names = ["reason".into()];
+        let callsite = implementation::callsite(&Bytes::from("info"), &names);
+
+        assert!(std::ptr::eq(
+            callsite,
+            implementation::callsite(&Bytes::from("info"), &names)
+        ));
+        assert!(!std::ptr::eq(
+            callsite,
+            implementation::callsite(&Bytes::from("error"), &names)
+        ));
+    }
+
+    #[traced_test]
+    #[test]
+    fn callsites_are_bounded() {
+        // A registry of its own, so the process wide one isn't filled up for other tests.
+        let mut callsites = implementation::Callsites::new(2);
+        let info = Bytes::from("info");
+        let first = callsites.get(&info, &["first".into()]);
+        callsites.get(&info, &["second".into()]);
+        let names = ["attempt".into(), "reason".into()];
+        let callsite = callsites.get(&info, &names);
+
+        assert!(std::ptr::eq(first, callsites.get(&info, &["first".into()])));
+        assert!(std::ptr::eq(
+            callsite,
+            callsites.get(&info, &["other".into()])
+        ));
+
+        implementation::emit_log(
+            value!("dropped malformed record"),
+            callsite,
+            value!(1),
+            &names,
+            vec![value!(2), value!("invalid json")],
+            Span::default(),
+        )
+        .unwrap();
+
+        assert!(logs_contain("fields=attempt=2 reason=invalid json"));
+    }
+
+    #[test]
+    fn fields_must_be_object_literal() {
+        let functions = crate::stdlib::all();
+
+        assert!(crate::compiler::compile(
+            r#"emit_log("processed", fields: {"id": .id, "status": upcase!(.status)})"#,
+            &functions,
+        )
+        .is_ok());
+        assert!(
+            crate::compiler::compile(r#"emit_log("processed", fields: .)"#, &functions).is_err()
+        );
+    }
+}
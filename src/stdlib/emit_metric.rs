@@ -1,42 +1,213 @@
-use metrics::{counter, gauge, histogram, Label};
-use std::collections::BTreeMap;
 use crate::compiler::prelude::*;
+use metrics::{counter, describe_histogram, gauge, histogram, Key, Label, SharedString};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+/// The cap on the number of labels when `auto_labels` turns a whole event into labels and no
+/// `max_labels` is given.
+const DEFAULT_AUTO_MAX_LABELS: usize = 32;
+
+/// Controls whether `emit_metric` records to the installed metrics recorder.
+///
+/// Metrics are recorded by default. Registering `MetricEmission::DryRun` with
+/// `CompileConfig::set_custom` makes every `emit_metric` call validate its arguments and
+/// return `null` without recording anything, so programs can be tested without a recorder.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MetricEmission {
+    #[default]
+    Record,
+    DryRun,
+}
+
+/// Names the argument a type mismatch was found in, since several arguments share a kind and
+/// `expected object, got string` alone doesn't say which one to fix.
+fn argument_error(argument: &'static str) -> impl Fn(ValueError) -> ExpressionError {
+    move |err| format!("invalid argument `{argument}`: {err}").into()
+}
+
+/// Controls how the `labels` object is turned into metric labels.
+#[derive(Debug, Clone, Default)]
+struct LabelOptions {
+    /// Stringify every scalar field instead of only keeping string fields, so a whole event
+    /// can be passed as `labels`.
+    auto: bool,
+    exclude: Vec<String>,
+    /// Labels given explicitly are only capped when asked to, while `auto` falls back to
+    /// `DEFAULT_AUTO_MAX_LABELS`.
+    max_labels: Option<usize>,
+}
+
+impl LabelOptions {
+    fn max_labels(&self) -> Option<usize> {
+        self.max_labels
+            .or(self.auto.then_some(DEFAULT_AUTO_MAX_LABELS))
+    }
+}
+
+fn build_labels(
+    metric_labels: BTreeMap<KeyString, Value>,
+    options: &LabelOptions,
+) -> Result<Vec<Label>, ExpressionError> {
+    let mut labels = Vec::new();
+    for (key, value) in metric_labels {
+        if options
+            .exclude
+            .iter()
+            .any(|excluded| excluded == key.as_str())
+        {
+            continue;
+        }
+
+        let value = match value {
+            Value::Bytes(_) => value
+                .try_bytes_utf8_lossy()
+                .map_err(argument_error("labels"))?
+                .into_owned(),
+            Value::Integer(_) | Value::Float(_) | Value::Boolean(_) | Value::Timestamp(_)
+                if options.auto =>
+            {
+                value.to_string_lossy().into_owned()
+            }
+            _ => continue,
+        };
+        labels.push(Label::new(String::from(key), value));
+    }
+
+    // Every label multiplies the number of series a metric can produce, so refuse to emit
+    // rather than silently creating a cardinality explosion downstream.
+    if let Some(max_labels) = options.max_labels() {
+        if labels.len() > max_labels {
+            return Err(format!(
+                "metric has {} labels, exceeding max_labels of {max_labels}",
+                labels.len(),
+            )
+            .into());
+        }
+    }
+
+    Ok(labels)
+}
+
+/// The `metrics` facade has no notion of an explicit sample time, so a `timestamp`
+/// argument is carried as an RFC 3339 `timestamp` label. Most recorders treat it as an
+/// ordinary label rather than backdating the sample; only a recorder that knows to look
+/// for it can use it for backfill.
+fn timestamp_label(timestamp: Value) -> Result<Label, ExpressionError> {
+    let timestamp = timestamp
+        .try_timestamp()
+        .map_err(argument_error("timestamp"))?;
+    Ok(Label::new(
+        "timestamp",
+        timestamp.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+    ))
+}
+
+/// Parses the `buckets` argument into strictly increasing upper bounds.
+fn parse_buckets(buckets: Value) -> Result<Vec<f64>, ExpressionError> {
+    let mut bounds: Vec<f64> = Vec::new();
+    for bucket in buckets.try_array().map_err(argument_error("buckets"))? {
+        let bound = bucket.try_into_f64().map_err(argument_error("buckets"))?;
+        if bounds.last().is_some_and(|last| *last >= bound) {
+            return Err("buckets must be strictly increasing".into());
+        }
+        bounds.push(bound);
+    }
+
+    if bounds.is_empty() {
+        return Err("buckets must not be empty".into());
+    }
+
+    Ok(bounds)
+}
+
+/// The `metrics` facade leaves bucketing to the exporter and can't carry bucket boundaries
+/// with a sample, so they are passed to the recorder as the histogram's description, for
+/// example `buckets: 0.1,0.5,1`, for recorders that configure their buckets from it.
+fn describe_buckets(key: &str, buckets: &[f64]) {
+    let bounds = buckets
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    describe_histogram!(key.to_owned(), format!("buckets: {bounds}"));
+}
+
+/// The last total reported for each absolute counter of an `emit_metric` call.
+///
+/// Recorders can't be relied on to lower a counter, atomic-backed ones keep the maximum of
+/// the totals they are given, so absolute counters are recorded as the increase since the
+/// previous total instead.
+#[derive(Debug, Default)]
+struct AbsoluteTotals(Mutex<HashMap<Key, u64>>);
+
+impl AbsoluteTotals {
+    /// Returns how much the counter grew since its previous total. A lower total means the
+    /// source's counter was reset, so the new total becomes the baseline and counts in full.
+    /// A negative total is taken as a reset to zero.
+    fn increase(&self, key: Key, total: i64) -> u64 {
+        let total = u64::try_from(total).unwrap_or(0);
+        let last = self
+            .0
+            .lock()
+            .expect("absolute totals lock poisoned")
+            .insert(key, total)
+            .unwrap_or(0);
+
+        total.checked_sub(last).unwrap_or(total)
+    }
+}
 
 fn emit_metric(
-    metric_name: Value,
+    key: SharedString,
     metric_value: Value,
     metric_type: Bytes,
-    metric_labels: BTreeMap<KeyString, Value>,
+    labels: Vec<Label>,
+    absolute: Option<&AbsoluteTotals>,
 ) -> Resolved {
-    let key = metric_name.try_bytes_utf8_lossy().unwrap().to_string();
-    let labels: Vec<Label> = metric_labels
-        .into_iter()
-        .filter_map(|(key, value)| {
-            if value.is_bytes() {
-                Some(Label::new(
-                    String::from(key.as_str()),
-                    value.try_bytes_utf8_lossy().unwrap().to_string(),
-                ))
-            } else {
-                None
-            }
-        })
-        .collect();
-
     match metric_type.as_ref() {
+        // An absolute counter reports the source's running total, so re-reporting the same
+        // total doesn't double count.
+        b"counter" if absolute.is_some() => {
+            let total = metric_value
+                .try_integer()
+                .map_err(argument_error("value"))?;
+            let counter = counter!(key.clone(), labels.clone());
+            let increase = absolute
+                .expect("checked above")
+                .increase(Key::from_parts(key, labels), total);
+            counter.increment(increase);
+        }
         b"counter" => {
             let c = counter!(key, labels);
-            c.increment(metric_value.try_integer()? as u64);
-        },
+            c.increment(
+                metric_value
+                    .try_integer()
+                    .map_err(argument_error("value"))? as u64,
+            );
+        }
         b"gauge" => {
             let g = gauge!(key, labels);
-            g.set(metric_value.try_into_f64()?);
-        },
+            g.set(
+                metric_value
+                    .try_into_f64()
+                    .map_err(argument_error("value"))?,
+            );
+        }
         b"histogram" => {
             let h = histogram!(key, labels);
-            h.record(metric_value.try_into_f64()?);
-        },
-        _ => todo!(),
+            h.record(
+                metric_value
+                    .try_into_f64()
+                    .map_err(argument_error("value"))?,
+            );
+        }
+        _ => {
+            return Err(format!(
+                "unsupported metric type: {}",
+                String::from_utf8_lossy(&metric_type)
+            )
+            .into());
+        }
     }
 
     Ok(Value::Null)
@@ -50,20 +221,41 @@ impl Function for EmitMetric {
     }
 
     fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "emit a metric from VRL",
-            source: r#"emit_metric!(s'success.count', 1, s'counter')"#,
-            result: Ok(r#"No Result"#),
-        }]
+        &[
+            Example {
+                title: "emit a metric from VRL",
+                source: "emit_metric(s'success.count', 1, s'counter')",
+                result: Ok("null"),
+            },
+            Example {
+                title: "gauge with labels",
+                source: r#"emit_metric("queue.depth", 12.5, "gauge", labels: {"queue": "ingest"})"#,
+                result: Ok("null"),
+            },
+            Example {
+                title: "labels from the whole event",
+                source: indoc! {r#"
+                    . = {"host": "web-1", "status": 200, "message": "GET /"}
+                    emit_metric!("requests", 1, labels: ., auto_labels: true, exclude: ["message"])
+                "#},
+                result: Ok("null"),
+            },
+        ]
     }
 
     fn compile(
         &self,
         state: &state::TypeState,
-        _ctx: &mut FunctionCompileContext,
+        ctx: &mut FunctionCompileContext,
         arguments: ArgumentList,
     ) -> Compiled {
         let metric_name = arguments.required("key");
+        // Metric names are usually literals, which are shared rather than copied into a new
+        // `String` for every metric recorded.
+        let static_key = metric_name
+            .resolve_constant(state)
+            .and_then(|key| key.as_str().map(|key| Arc::<str>::from(key.as_ref())))
+            .map(SharedString::from_shared);
         let metric_value = arguments.required("value");
         let metric_types = vec!["counter".into(), "gauge".into(), "histogram".into()];
 
@@ -74,12 +266,31 @@ impl Function for EmitMetric {
             .expect("type not bytes");
 
         let metric_labels = arguments.optional("labels");
+        let auto_labels = arguments.optional("auto_labels");
+        let exclude = arguments.optional("exclude");
+        let max_labels = arguments.optional("max_labels");
+        let timestamp = arguments.optional("timestamp");
+        let buckets = arguments.optional("buckets");
+        let absolute = arguments.optional("absolute");
+        let emission = ctx
+            .get_external_context::<MetricEmission>()
+            .copied()
+            .unwrap_or_default();
 
         Ok(EmitMetricFn {
             metric_name,
+            static_key,
             metric_value,
             metric_type,
             metric_labels,
+            auto_labels,
+            exclude,
+            max_labels,
+            timestamp,
+            buckets,
+            absolute,
+            absolute_totals: Arc::default(),
+            emission,
         }
         .as_expr())
     }
@@ -106,6 +317,36 @@ impl Function for EmitMetric {
                 kind: kind::OBJECT,
                 required: false,
             },
+            Parameter {
+                keyword: "auto_labels",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "exclude",
+                kind: kind::ARRAY,
+                required: false,
+            },
+            Parameter {
+                keyword: "max_labels",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "timestamp",
+                kind: kind::TIMESTAMP,
+                required: false,
+            },
+            Parameter {
+                keyword: "buckets",
+                kind: kind::ARRAY,
+                required: false,
+            },
+            Parameter {
+                keyword: "absolute",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
         ]
     }
 }
@@ -113,40 +354,164 @@ impl Function for EmitMetric {
 #[derive(Debug, Clone)]
 struct EmitMetricFn {
     metric_name: Box<dyn Expression>,
+    static_key: Option<SharedString>,
     metric_value: Box<dyn Expression>,
     metric_type: Bytes,
     metric_labels: Option<Box<dyn Expression>>,
+    auto_labels: Option<Box<dyn Expression>>,
+    exclude: Option<Box<dyn Expression>>,
+    max_labels: Option<Box<dyn Expression>>,
+    timestamp: Option<Box<dyn Expression>>,
+    buckets: Option<Box<dyn Expression>>,
+    absolute: Option<Box<dyn Expression>>,
+    absolute_totals: Arc<AbsoluteTotals>,
+    emission: MetricEmission,
+}
+
+impl EmitMetricFn {
+    fn label_options(&self, ctx: &mut Context) -> Result<LabelOptions, ExpressionError> {
+        let mut options = LabelOptions::default();
+
+        if let Some(auto) = &self.auto_labels {
+            options.auto = auto
+                .resolve(ctx)?
+                .try_boolean()
+                .map_err(argument_error("auto_labels"))?;
+        }
+
+        if let Some(exclude) = &self.exclude {
+            let exclude = exclude
+                .resolve(ctx)?
+                .try_array()
+                .map_err(argument_error("exclude"))?;
+            for key in exclude {
+                let key = key
+                    .try_bytes_utf8_lossy()
+                    .map_err(argument_error("exclude"))?;
+                options.exclude.push(key.into_owned());
+            }
+        }
+
+        if let Some(max_labels) = &self.max_labels {
+            let max_labels = max_labels
+                .resolve(ctx)?
+                .try_integer()
+                .map_err(argument_error("max_labels"))?;
+            options.max_labels = Some(
+                usize::try_from(max_labels)
+                    .map_err(|_| "max_labels must be a non-negative integer")?,
+            );
+        }
+
+        Ok(options)
+    }
+
+    fn key(&self, ctx: &mut Context) -> Result<SharedString, ExpressionError> {
+        if let Some(key) = &self.static_key {
+            return Ok(key.clone());
+        }
+
+        let metric_name = self.metric_name.resolve(ctx)?;
+        let key = metric_name
+            .try_bytes_utf8_lossy()
+            .map_err(argument_error("key"))?;
+        Ok(key.into_owned().into())
+    }
 }
 
 impl FunctionExpression for EmitMetricFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
-        let metric_name = self.metric_name.resolve(ctx)?;
-        if !metric_name.is_bytes() {
-            return Err(ExpressionError::from(ValueError::Expected {
-                got: metric_name.kind(),
-                expected: Kind::bytes(),
-            }));
-        }
+        let key = self.key(ctx)?;
 
         let metric_value = self.metric_value.resolve(ctx)?;
         if !(metric_value.is_integer() || metric_value.is_float()) {
-            return Err(ExpressionError::from(ValueError::Expected {
-                got: metric_name.kind(),
+            return Err(argument_error("value")(ValueError::Expected {
+                got: metric_value.kind(),
                 expected: Kind::integer() | Kind::float(),
             }));
         }
 
         let metric_type = self.metric_type.clone();
 
+        let buckets = match &self.buckets {
+            Some(buckets) if metric_type.as_ref() == b"histogram" => {
+                Some(parse_buckets(buckets.resolve(ctx)?)?)
+            }
+            Some(_) => return Err("buckets are only supported for histograms".into()),
+            None => None,
+        };
+
         let metric_labels = match self.metric_labels.as_ref() {
-            Some(v) => v.resolve(ctx)?.try_object()?,
+            Some(v) => v
+                .resolve(ctx)?
+                .try_object()
+                .map_err(argument_error("labels"))?,
             None => BTreeMap::new(),
         };
-        emit_metric(metric_name, metric_value, metric_type, metric_labels)
+        let options = self.label_options(ctx)?;
+        let mut labels = build_labels(metric_labels, &options)?;
+        if let Some(timestamp) = &self.timestamp {
+            labels.push(timestamp_label(timestamp.resolve(ctx)?)?);
+        }
+
+        let absolute = match &self.absolute {
+            Some(absolute) => absolute
+                .resolve(ctx)?
+                .try_boolean()
+                .map_err(argument_error("absolute"))?,
+            None => false,
+        };
+
+        let record = |totals: &AbsoluteTotals| {
+            if let Some(buckets) = buckets {
+                describe_buckets(&key, &buckets);
+            }
+
+            emit_metric(
+                key,
+                metric_value,
+                metric_type,
+                labels,
+                absolute.then_some(totals),
+            )
+        };
+
+        match self.emission {
+            MetricEmission::Record => record(&self.absolute_totals),
+            // Recording against a no-op recorder still runs every validation, but never reaches
+            // the global recorder, nor changes the totals of absolute counters.
+            MetricEmission::DryRun => metrics::with_local_recorder(&metrics::NoopRecorder, || {
+                record(&AbsoluteTotals::default())
+            }),
+        }
     }
 
-    fn type_def(&self, _: &state::TypeState) -> TypeDef {
-        TypeDef::null().infallible()
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        // A call using only `key`, `value`, `mtype` and `labels` with arguments of the expected
+        // types can't fail, so it stays infallible as it always was. The options added since
+        // are checked at runtime and make the call fallible.
+        let value = self.metric_value.type_def(state);
+        let value_fallible = if self.metric_type.as_ref() == b"counter" {
+            !value.is_integer()
+        } else {
+            (Kind::integer() | Kind::float())
+                .is_superset(value.kind())
+                .is_err()
+        };
+        let fallible = value_fallible
+            || !self.metric_name.type_def(state).is_bytes()
+            || self
+                .metric_labels
+                .as_ref()
+                .is_some_and(|labels| !labels.type_def(state).is_object())
+            || self.auto_labels.is_some()
+            || self.exclude.is_some()
+            || self.max_labels.is_some()
+            || self.timestamp.is_some()
+            || self.buckets.is_some()
+            || self.absolute.is_some();
+
+        TypeDef::null().maybe_fallible(fallible)
     }
 }
 
@@ -154,11 +519,57 @@ impl FunctionExpression for EmitMetricFn {
 mod tests {
     use super::*;
     use crate::btreemap;
+    use crate::compiler::{CompileConfig, TimeZone};
+    use crate::diagnostic::Span;
     use crate::value;
-    use metrics::Key;
+    use chrono::{TimeZone as _, Utc};
+    use metrics::{Key, SharedString, Unit};
     use metrics_util::debugging::{DebugValue, DebuggingRecorder};
     use metrics_util::{CompositeKey, MetricKind};
     use ordered_float::OrderedFloat;
+    use std::collections::HashMap;
+
+    type Snapshot = (CompositeKey, Option<Unit>, Option<SharedString>, DebugValue);
+
+    /// Compiles and resolves an `emit_metric` call against a local debugging recorder,
+    /// returning everything it recorded.
+    fn record(args: HashMap<&'static str, Value>) -> Vec<(CompositeKey, DebugValue)> {
+        record_with_config(args, CompileConfig::default()).unwrap()
+    }
+
+    fn record_with_config(
+        args: HashMap<&'static str, Value>,
+        config: CompileConfig,
+    ) -> Result<Vec<(CompositeKey, DebugValue)>, ExpressionError> {
+        Ok(snapshot_with_config(args, config)?
+            .into_iter()
+            .map(|(key, _, _, value)| (key, value))
+            .collect())
+    }
+
+    /// Like `record_with_config`, but keeps the unit and description of each metric.
+    fn snapshot_with_config(
+        args: HashMap<&'static str, Value>,
+        config: CompileConfig,
+    ) -> Result<Vec<Snapshot>, ExpressionError> {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+
+        let state = state::TypeState::default();
+        let mut compile_ctx = FunctionCompileContext::new(Span::default(), config);
+        let expr = EmitMetric
+            .compile(&state, &mut compile_ctx, args.into())
+            .unwrap();
+
+        let mut target = value!({});
+        let mut runtime_state = state::RuntimeState::default();
+        let tz = TimeZone::default();
+        let mut ctx = Context::new(&mut target, &mut runtime_state, &tz);
+        let result = metrics::with_local_recorder(&recorder, || expr.resolve(&mut ctx))?;
+        assert_eq!(result, Value::Null);
+
+        Ok(snapshotter.snapshot().into_vec())
+    }
 
     test_function![
         emit_metric  => EmitMetric;
@@ -170,8 +581,8 @@ mod tests {
                 },
                 value: value!(1)
             ],
-            want: Err(format!(r"expected string, got {{ lvl: string }}")),
-            tdef: TypeDef::null().infallible(),
+            want: Err(format!(r"invalid argument `key`: expected string, got {{ lvl: string }}")),
+            tdef: TypeDef::null().fallible(),
         }
 
         BadValue {
@@ -181,8 +592,8 @@ mod tests {
                     "lvl" => "info",
                 },
             ],
-            want: Err(format!(r"expected integer or float, got string")),
-            tdef: TypeDef::null().infallible(),
+            want: Err(format!(r"invalid argument `value`: expected integer or float, got {{ lvl: string }}")),
+            tdef: TypeDef::null().fallible(),
         }
 
         BadLabels {
@@ -191,19 +602,329 @@ mod tests {
                 value: 1,
                 labels: b"foo",
             ],
-            want: Err(format!(r"expected object, got string")),
+            want: Err(format!(r"invalid argument `labels`: expected object, got string")),
+            tdef: TypeDef::null().fallible(),
+        }
+
+        TooManyLabels {
+            args: func_args![
+                key: b"some.key",
+                value: 1,
+                labels: value!({"host": "a", "region": "b", "zone": "c"}),
+                max_labels: 2,
+            ],
+            want: Err("metric has 3 labels, exceeding max_labels of 2"),
+            tdef: TypeDef::null().fallible(),
+        }
+
+        BadValueKind {
+            args: func_args![key: b"some.key", value: "1"],
+            want: Err("invalid argument `value`: expected integer or float, got string"),
+            tdef: TypeDef::null().fallible(),
+        }
+
+        BadMaxLabels {
+            args: func_args![key: b"some.key", value: 1, max_labels: 1.5],
+            want: Err("invalid argument `max_labels`: expected integer, got float"),
+            tdef: TypeDef::null().fallible(),
+        }
+
+        PlainCall {
+            args: func_args![key: b"some.key", value: 1, labels: value!({"host": "web-1"})],
+            want: Ok(Value::Null),
             tdef: TypeDef::null().infallible(),
         }
+
+        FloatCounter {
+            args: func_args![key: b"some.key", value: 1.5],
+            want: Err("invalid argument `value`: expected integer, got float"),
+            tdef: TypeDef::null().fallible(),
+        }
+
+        BucketsForCounter {
+            args: func_args![key: b"some.key", value: 1, buckets: value!([1, 2])],
+            want: Err("buckets are only supported for histograms"),
+            tdef: TypeDef::null().fallible(),
+        }
     ];
 
+    #[test]
+    fn timestamp_carried_as_label() {
+        let timestamp = Utc.with_ymd_and_hms(2021, 2, 3, 4, 5, 6).unwrap();
+        let recorded = record(func_args![
+            key: "backfilled",
+            value: 3,
+            labels: value!({"host": "web-1"}),
+            timestamp: timestamp,
+        ]);
+
+        assert_eq!(
+            recorded,
+            vec![(
+                CompositeKey::new(
+                    MetricKind::Counter,
+                    Key::from_parts(
+                        "backfilled",
+                        vec![
+                            Label::new("host", "web-1"),
+                            Label::new("timestamp", "2021-02-03T04:05:06Z"),
+                        ]
+                    )
+                ),
+                DebugValue::Counter(3),
+            )]
+        );
+    }
+
+    #[test]
+    fn histogram_buckets() {
+        let recorded = snapshot_with_config(
+            func_args![
+                key: "latency",
+                value: 0.3,
+                mtype: "histogram",
+                buckets: value!([0.1, 0.5, 1]),
+            ],
+            CompileConfig::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            recorded,
+            vec![(
+                CompositeKey::new(MetricKind::Histogram, Key::from_name("latency")),
+                None,
+                Some("buckets: 0.1,0.5,1".into()),
+                DebugValue::Histogram(vec![OrderedFloat::from(0.3)]),
+            )]
+        );
+    }
+
+    /// Reports each total as an absolute counter, returning the recorded value after each.
+    fn absolute_counter_values(totals: &[i64]) -> Vec<DebugValue> {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let absolute_totals = AbsoluteTotals::default();
+
+        totals
+            .iter()
+            .map(|total| {
+                metrics::with_local_recorder(&recorder, || {
+                    emit_metric(
+                        "requests_total".into(),
+                        (*total).into(),
+                        "counter".into(),
+                        vec![Label::new("host", "web-1")],
+                        Some(&absolute_totals),
+                    )
+                    .unwrap();
+                });
+                let mut recorded = snapshotter.snapshot().into_vec();
+                assert_eq!(recorded.len(), 1);
+                recorded.remove(0).3
+            })
+            .collect()
+    }
+
+    #[test]
+    fn absolute_counter() {
+        assert_eq!(
+            absolute_counter_values(&[10, 10, 25]),
+            vec![
+                DebugValue::Counter(10),
+                DebugValue::Counter(10),
+                DebugValue::Counter(25),
+            ]
+        );
+    }
+
+    #[test]
+    fn absolute_counter_reset() {
+        // After the source resets from 25 down to 4, the 4 requests counted since are added,
+        // and later totals grow from the new baseline of 4.
+        assert_eq!(
+            absolute_counter_values(&[10, 25, 4, 9]),
+            vec![
+                DebugValue::Counter(10),
+                DebugValue::Counter(25),
+                DebugValue::Counter(29),
+                DebugValue::Counter(34),
+            ]
+        );
+    }
+
+    #[test]
+    fn absolute_counter_negative() {
+        assert_eq!(
+            absolute_counter_values(&[10, -1, 3]),
+            vec![
+                DebugValue::Counter(10),
+                DebugValue::Counter(10),
+                DebugValue::Counter(13),
+            ]
+        );
+    }
+
+    #[test]
+    fn absolute_counter_argument() {
+        let recorded = record(func_args![key: "requests_total", value: 7, absolute: true]);
+
+        assert_eq!(
+            recorded,
+            vec![(
+                CompositeKey::new(MetricKind::Counter, Key::from_name("requests_total")),
+                DebugValue::Counter(7),
+            )]
+        );
+    }
+
+    #[test]
+    fn dry_run() {
+        let dry_run = || {
+            let mut config = CompileConfig::default();
+            config.set_custom(MetricEmission::DryRun);
+            config
+        };
+        let recorded = record_with_config(
+            func_args![
+                key: "latency",
+                value: 0.3,
+                mtype: "histogram",
+                buckets: value!([0.1, 0.5]),
+            ],
+            dry_run(),
+        )
+        .unwrap();
+        assert_eq!(recorded, vec![]);
+
+        // Inputs are still validated.
+        let err = record_with_config(
+            func_args![key: "requests_total", value: 1.5, absolute: true],
+            dry_run(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid argument `value`: expected integer, got float"
+        );
+    }
+
+    #[test]
+    fn unsupported_metric_type() {
+        assert_eq!(
+            emit_metric(
+                "requests_total".into(),
+                1.into(),
+                "summary".into(),
+                vec![],
+                None,
+            )
+            .unwrap_err()
+            .to_string(),
+            "unsupported metric type: summary"
+        );
+    }
+
+    #[test]
+    fn invalid_buckets() {
+        assert_eq!(
+            parse_buckets(value!([1, 0.5])).unwrap_err().to_string(),
+            "buckets must be strictly increasing"
+        );
+        assert_eq!(
+            parse_buckets(value!([])).unwrap_err().to_string(),
+            "buckets must not be empty"
+        );
+    }
+
+    #[test]
+    fn auto_labels_from_event() {
+        let event = value!({
+            "host": "web-1",
+            "status": 200,
+            "ratio": 0.5,
+            "cached": true,
+            "message": "GET /",
+            "request": {"path": "/"},
+            "tags": ["a"],
+            "missing": null,
+        });
+        let options = LabelOptions {
+            auto: true,
+            exclude: vec!["message".to_owned()],
+            ..LabelOptions::default()
+        };
+
+        assert_eq!(
+            build_labels(event.try_object().unwrap(), &options).unwrap(),
+            vec![
+                Label::new("cached", "true"),
+                Label::new("host", "web-1"),
+                Label::new("ratio", "0.5"),
+                Label::new("status", "200"),
+            ]
+        );
+    }
+
+    #[test]
+    fn only_string_labels_without_auto() {
+        let labels = value!({"host": "web-1", "status": 200});
+
+        assert_eq!(
+            build_labels(labels.try_object().unwrap(), &LabelOptions::default()).unwrap(),
+            vec![Label::new("host", "web-1")]
+        );
+    }
+
+    #[test]
+    fn auto_labels_cardinality_cap() {
+        let event = value!({"a": 1, "b": 2, "c": 3});
+        let options = LabelOptions {
+            auto: true,
+            max_labels: Some(2),
+            ..LabelOptions::default()
+        };
+
+        assert_eq!(
+            build_labels(event.try_object().unwrap(), &options)
+                .unwrap_err()
+                .to_string(),
+            "metric has 3 labels, exceeding max_labels of 2"
+        );
+    }
+
+    #[test]
+    fn default_cap_only_for_auto_labels() {
+        let labels = (0..=DEFAULT_AUTO_MAX_LABELS)
+            .map(|i| (KeyString::from(format!("label{i}")), Value::from("value")))
+            .collect::<BTreeMap<_, _>>();
+
+        assert_eq!(
+            build_labels(labels.clone(), &LabelOptions::default())
+                .unwrap()
+                .len(),
+            DEFAULT_AUTO_MAX_LABELS + 1
+        );
+
+        let options = LabelOptions {
+            auto: true,
+            ..LabelOptions::default()
+        };
+        assert_eq!(
+            build_labels(labels, &options).unwrap_err().to_string(),
+            "metric has 33 labels, exceeding max_labels of 32"
+        );
+    }
+
     #[test]
     fn test_emit_metrics() {
+        static COUNTER_METRIC_NAME: &str = "test_counter";
+        static GAUGE_METRIC_NAME: &str = "test_gauge";
+        static HISTOGRAM_METRIC_NAME: &str = "test_histo";
+
         let recorder = DebuggingRecorder::new();
         let snapshotter = recorder.snapshotter();
         recorder.install().expect("Should not fail");
-        static COUNTER_METRIC_NAME: &'static str = "test_counter";
-        static GAUGE_METRIC_NAME: &'static str = "test_gauge";
-        static HISTOGRAM_METRIC_NAME: &'static str = "test_histo";
 
         let labels: BTreeMap<KeyString, Value> = btreemap! {
             KeyString::from("l1") => "v1",
@@ -211,36 +932,41 @@ mod tests {
             KeyString::from("non_string2") => vec![1,2,3],
             KeyString::from("l2") => "v2"
         };
+        let labels = build_labels(labels, &LabelOptions::default()).unwrap();
 
         let mut emit_result = emit_metric(
-            Value::from("test_counter"),
+            "test_counter".into(),
             Value::from(21),
             "counter".into(),
             labels.clone(),
+            None,
         );
         assert!(emit_result.is_ok());
 
         emit_result = emit_metric(
-            Value::from("test_counter"),
+            "test_counter".into(),
             Value::from(21),
             "counter".into(),
             labels.clone(),
+            None,
         );
         assert!(emit_result.is_ok());
 
         emit_result = emit_metric(
-            Value::from("test_gauge"),
+            "test_gauge".into(),
             Value::from(42),
             "gauge".into(),
             labels.clone(),
+            None,
         );
         assert!(emit_result.is_ok());
 
         emit_result = emit_metric(
-            Value::from("test_histo"),
+            "test_histo".into(),
             Value::from(42),
             "histogram".into(),
             labels.clone(),
+            None,
         );
         assert!(emit_result.is_ok());
 
@@ -254,7 +980,7 @@ mod tests {
                     CompositeKey::new(
                         MetricKind::Counter,
                         Key::from_parts(
-                            &COUNTER_METRIC_NAME[..],
+                            COUNTER_METRIC_NAME,
                             vec![Label::new("l1", "v1"), Label::new("l2", "v2")]
                         )
                     ),
@@ -266,7 +992,7 @@ mod tests {
                     CompositeKey::new(
                         MetricKind::Gauge,
                         Key::from_parts(
-                            &GAUGE_METRIC_NAME[..],
+                            GAUGE_METRIC_NAME,
                             vec![Label::new("l1", "v1"), Label::new("l2", "v2")]
                         )
                     ),
@@ -278,7 +1004,7 @@ mod tests {
                     CompositeKey::new(
                         MetricKind::Histogram,
                         Key::from_parts(
-                            &HISTOGRAM_METRIC_NAME[..],
+                            HISTOGRAM_METRIC_NAME,
                             vec![Label::new("l1", "v1"), Label::new("l2", "v2")]
                         )
                     ),
@@ -1,8 +1,7 @@
 use crate::compiler::prelude::*;
-use base64::Engine as _;
 use std::str::FromStr;
 
-use super::util::Base64Charset;
+use super::util::{base64_encode, Base64Charset};
 
 fn encode_base64(value: Value, padding: Option<Value>, charset: Option<Value>) -> Resolved {
     let value = value.try_bytes()?;
@@ -17,13 +16,7 @@ fn encode_base64(value: Value, padding: Option<Value>, charset: Option<Value>) -
         .transpose()?
         .unwrap_or_default();
 
-    let engine = base64::engine::GeneralPurpose::new(
-        &base64::alphabet::Alphabet::from(charset),
-        base64::engine::general_purpose::GeneralPurposeConfig::default()
-            .with_encode_padding(padding),
-    );
-
-    Ok(engine.encode(value).into())
+    Ok(base64_encode(&value, charset, padding).into())
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -54,6 +47,10 @@ impl Function for EncodeBase64 {
         ]
     }
 
+    fn fold(&self) -> bool {
+        true
+    }
+
     fn compile(
         &self,
         _state: &state::TypeState,
@@ -148,4 +148,27 @@ mod test {
             tdef: TypeDef::bytes().fallible(),
         }
     ];
+
+    #[test]
+    fn compression_level_selection() {
+        use flate2::read::MultiGzDecoder;
+
+        let input = "a".repeat(1024);
+        let encode = |level: i64| match encode_gzip(value!(input.clone()), Some(value!(level))) {
+            Ok(Value::Bytes(bytes)) => bytes,
+            other => panic!("unexpected result: {other:?}"),
+        };
+
+        let stored = encode(0);
+        let best = encode(9);
+        assert!(stored.len() > best.len());
+
+        for encoded in [stored, best] {
+            let mut decoded = String::new();
+            MultiGzDecoder::new(&encoded[..])
+                .read_to_string(&mut decoded)
+                .expect("valid gzip stream");
+            assert_eq!(decoded, input);
+        }
+    }
 }
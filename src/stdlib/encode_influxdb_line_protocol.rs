@@ -0,0 +1,237 @@
+use std::fmt::Write as _;
+
+use crate::compiler::prelude::*;
+
+/// Escapes measurement names. Unlike keys, these may contain unescaped equals signs.
+fn escape_measurement(value: &str) -> String {
+    value.replace(',', "\\,").replace(' ', "\\ ")
+}
+
+/// Escapes tag keys, tag values and field keys.
+fn escape_key(value: &str) -> String {
+    value
+        .replace(',', "\\,")
+        .replace('=', "\\=")
+        .replace(' ', "\\ ")
+}
+
+fn tag_value(key: &str, value: &Value) -> Result<Option<String>, ExpressionError> {
+    let value = match value {
+        Value::Bytes(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+        Value::Integer(v) => v.to_string(),
+        Value::Float(v) => v.to_string(),
+        Value::Boolean(v) => v.to_string(),
+        Value::Null => return Ok(None),
+        value => return Err(format!("tag {key:?} has unsupported type {}", value.kind()).into()),
+    };
+
+    // InfluxDB does not allow empty tag values, so those tags are left out entirely.
+    Ok((!value.is_empty()).then(|| escape_key(&value)))
+}
+
+fn field_value(key: &str, value: &Value) -> Result<Option<String>, ExpressionError> {
+    Ok(Some(match value {
+        Value::Integer(v) => format!("{v}i"),
+        Value::Float(v) if v.is_finite() => v.to_string(),
+        Value::Boolean(v) => v.to_string(),
+        Value::Bytes(bytes) => format!(
+            "\"{}\"",
+            String::from_utf8_lossy(bytes)
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+        ),
+        Value::Null => return Ok(None),
+        Value::Float(v) => return Err(format!("field {key:?} is not finite: {v}").into()),
+        value => return Err(format!("field {key:?} has unsupported type {}", value.kind()).into()),
+    }))
+}
+
+fn encode_influxdb_line_protocol(value: Value) -> Resolved {
+    let mut object = value.try_object()?;
+
+    let measurement = object
+        .remove("measurement")
+        .ok_or("missing measurement")?
+        .try_bytes_utf8_lossy()?
+        .into_owned();
+    let mut line = escape_measurement(&measurement);
+
+    // Tags are written in key order, as recommended by InfluxDB for write performance.
+    if let Some(tags) = object.remove("tags").filter(|tags| !tags.is_null()) {
+        for (key, value) in tags.try_object()? {
+            if let Some(value) = tag_value(&key, &value)? {
+                write!(line, ",{}={value}", escape_key(&key)).expect("write to String");
+            }
+        }
+    }
+
+    let fields = object
+        .remove("fields")
+        .ok_or("missing fields")?
+        .try_object()?;
+    let mut separator = ' ';
+    for (key, value) in fields {
+        if let Some(value) = field_value(&key, &value)? {
+            write!(line, "{separator}{}={value}", escape_key(&key)).expect("write to String");
+            separator = ',';
+        }
+    }
+    if separator == ' ' {
+        return Err("at least one non-null field is required".into());
+    }
+
+    match object.remove("timestamp") {
+        Some(Value::Timestamp(ts)) => {
+            let nanos = ts
+                .timestamp_nanos_opt()
+                .ok_or_else(|| format!("timestamp is out of range: {ts}"))?;
+            write!(line, " {nanos}").expect("write to String");
+        }
+        Some(Value::Integer(nanos)) => write!(line, " {nanos}").expect("write to String"),
+        Some(Value::Null) | None => {}
+        Some(value) => {
+            return Err(format!("timestamp has unsupported type {}", value.kind()).into())
+        }
+    }
+
+    Ok(line.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeInfluxdbLineProtocol;
+
+impl Function for EncodeInfluxdbLineProtocol {
+    fn identifier(&self) -> &'static str {
+        "encode_influxdb_line_protocol"
+    }
+
+    fn summary(&self) -> &'static str {
+        "encode an object as an InfluxDB line protocol entry"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Encodes an object with `measurement`, `tags`, `fields` and an optional `timestamp`
+            as a single InfluxDB line protocol entry, the inverse of
+            `parse_influxdb_line_protocol`.
+
+            Tags are sorted by key. Integer fields are written with the `i` suffix, strings are
+            quoted and null tags or fields are skipped. Timestamps are written in nanoseconds.
+        "}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "encode line protocol entry",
+            source: indoc! {r#"
+                encode_influxdb_line_protocol!({
+                    "measurement": "weather",
+                    "tags": {"region": "us west", "host": "a"},
+                    "fields": {"temperature": 82, "humidity": 71.5, "note": "light wind"},
+                    "timestamp": t'2016-06-13T17:43:50.1004002Z'
+                })
+            "#},
+            result: Ok(
+                r#"s'weather,host=a,region=us\ west humidity=71.5,note="light wind",temperature=82i 1465839830100400200'"#,
+            ),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(EncodeInfluxdbLineProtocolFn { value }.as_expr())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodeInfluxdbLineProtocolFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for EncodeInfluxdbLineProtocolFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        encode_influxdb_line_protocol(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+    use crate::value;
+
+    test_function![
+        encode_influxdb_line_protocol => EncodeInfluxdbLineProtocol;
+
+        sorted_tags_and_typed_fields {
+            args: func_args![value: value!({
+                "measurement": "cpu",
+                "tags": {"region": "us-west", "host": "A", "az": "1a"},
+                "fields": {"usage": 64, "load": 0.5, "up": true, "state": "ok"},
+                "timestamp": (Utc.timestamp_nanos(1_590_488_773_254_420_000)),
+            })],
+            want: Ok(r#"cpu,az=1a,host=A,region=us-west load=0.5,state="ok",up=true,usage=64i 1590488773254420000"#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        escaping {
+            args: func_args![value: value!({
+                "measurement": "my measurement,x=1",
+                "tags": {"tag key": "a,b", "host=name": "x y"},
+                "fields": {"field,key": r#"say "hi" \o/"#},
+            })],
+            want: Ok(r#"my\ measurement\,x=1,host\=name=x\ y,tag\ key=a\,b field\,key="say \"hi\" \\o/""#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        null_and_empty_values_skipped {
+            args: func_args![value: value!({
+                "measurement": "cpu",
+                "tags": {"host": "", "region": null, "rack": 7},
+                "fields": {"value": 1, "missing": null},
+                "timestamp": 1_590_488_773,
+            })],
+            want: Ok("cpu,rack=7 value=1i 1590488773"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        missing_fields {
+            args: func_args![value: value!({"measurement": "cpu", "tags": {"host": "A"}})],
+            want: Err("missing fields"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        only_null_fields {
+            args: func_args![value: value!({"measurement": "cpu", "fields": {"value": null}})],
+            want: Err("at least one non-null field is required"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        unsupported_field_type {
+            args: func_args![value: value!({"measurement": "cpu", "fields": {"value": [1]}})],
+            want: Err(r#"field "value" has unsupported type [integer]"#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
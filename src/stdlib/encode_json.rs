@@ -1,7 +1,8 @@
 use crate::compiler::prelude::*;
 
 fn encode_json(value: Value, pretty: bool) -> Value {
-    // With `vrl::Value` it should not be possible to get `Err`.
+    // With `vrl::Value` it should not be possible to get `Err`: invalid UTF-8 is replaced with
+    // U+FFFD, and timestamps and regexes are serialized as strings.
 
     let result = if pretty {
         serde_json::to_string_pretty(&value)
@@ -215,5 +216,20 @@ mod tests {
             want: Ok("null"),
             tdef: TypeDef::bytes().infallible(),
         }
+
+        invalid_utf8 {
+            args: func_args![value: Bytes::from_static(b"foo\xFFbar")],
+            want: Ok("\"foo\u{FFFD}bar\""),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        invalid_utf8_field {
+            args: func_args![value: Value::from_iter([
+                (String::from("message"), Value::from(Bytes::from_static(b"\xC3\x28 bad"))),
+                (String::from("regex"), Value::from(Regex::new("^a\\d+$").unwrap())),
+            ])],
+            want: Ok("{\"message\":\"\u{FFFD}( bad\",\"regex\":\"^a\\\\d+$\"}"),
+            tdef: TypeDef::bytes().infallible(),
+        }
     ];
 }
@@ -0,0 +1,294 @@
+use crate::compiler::prelude::*;
+use crate::value::value::timestamp_to_string;
+
+fn encode_json_canonical(value: &Value) -> Resolved {
+    let mut json = String::new();
+    write_value(value, &mut json)?;
+    Ok(json.into())
+}
+
+fn write_value(value: &Value, json: &mut String) -> Result<(), ExpressionError> {
+    match value {
+        Value::Null => json.push_str("null"),
+        Value::Boolean(boolean) => json.push_str(if *boolean { "true" } else { "false" }),
+        // JCS numbers are IEEE 754 doubles, so integers beyond 2^53 lose precision.
+        #[allow(clippy::cast_precision_loss)]
+        Value::Integer(integer) => write_number(*integer as f64, json)?,
+        Value::Float(float) => write_number(float.into_inner(), json)?,
+        Value::Bytes(bytes) => write_string(&String::from_utf8_lossy(bytes), json),
+        Value::Timestamp(timestamp) => write_string(&timestamp_to_string(timestamp), json),
+        Value::Regex(regex) => write_string(regex.as_str(), json),
+        Value::Array(array) => {
+            json.push('[');
+            for (i, value) in array.iter().enumerate() {
+                if i > 0 {
+                    json.push(',');
+                }
+                write_value(value, json)?;
+            }
+            json.push(']');
+        }
+        Value::Object(object) => {
+            // Keys are sorted by their UTF-16 code units, which differs from the UTF-8 order of
+            // the map for characters beyond the basic multilingual plane.
+            let mut fields = object.iter().collect::<Vec<_>>();
+            fields
+                .sort_by(|(a, _), (b, _)| a.as_str().encode_utf16().cmp(b.as_str().encode_utf16()));
+
+            json.push('{');
+            for (i, (key, value)) in fields.into_iter().enumerate() {
+                if i > 0 {
+                    json.push(',');
+                }
+                write_string(key.as_str(), json);
+                json.push(':');
+                write_value(value, json)?;
+            }
+            json.push('}');
+        }
+    }
+
+    Ok(())
+}
+
+/// Escapes the string as JCS requires, which is what `serde_json` does: only `"`, `\` and
+/// control characters are escaped, using the short forms where there is one.
+fn write_string(string: &str, json: &mut String) {
+    json.push_str(&serde_json::to_string(string).expect("strings always serialize"));
+}
+
+/// Writes the number the way ECMAScript's `Number.prototype.toString` does.
+fn write_number(number: f64, json: &mut String) -> Result<(), ExpressionError> {
+    if !number.is_finite() {
+        return Err(format!("unable to encode {number} as canonical JSON").into());
+    }
+    if number == 0.0 {
+        // Covers negative zero as well.
+        json.push('0');
+        return Ok(());
+    }
+    if number.is_sign_negative() {
+        json.push('-');
+    }
+
+    // The shortest digits that round-trip, and where the decimal point goes among them.
+    let scientific = format!("{:e}", number.abs());
+    let (mantissa, exponent) = scientific.split_once('e').expect("exponent");
+    let digits = round_half_even(number.abs(), mantissa.replace('.', ""), exponent);
+    let len = i32::try_from(digits.len()).expect("at most 17 digits");
+    let point = exponent.parse::<i32>().expect("exponent") + 1;
+
+    if len <= point && point <= 21 {
+        json.push_str(&digits);
+        json.push_str(&"0".repeat((point - len) as usize));
+    } else if 0 < point && point <= 21 {
+        let (integer, fraction) = digits.split_at(point as usize);
+        json.push_str(integer);
+        json.push('.');
+        json.push_str(fraction);
+    } else if -6 < point && point <= 0 {
+        json.push_str("0.");
+        json.push_str(&"0".repeat(-point as usize));
+        json.push_str(&digits);
+    } else {
+        let (first, rest) = digits.split_at(1);
+        json.push_str(first);
+        if !rest.is_empty() {
+            json.push('.');
+            json.push_str(rest);
+        }
+        let exponent = point - 1;
+        json.push_str(if exponent < 0 { "e-" } else { "e+" });
+        json.push_str(&exponent.abs().to_string());
+    }
+
+    Ok(())
+}
+
+/// Rust breaks ties between two shortest digit strings upwards, where ECMAScript picks the
+/// even one, so the lower one is taken when the number lies exactly halfway between them.
+fn round_half_even(number: f64, mut digits: String, exponent: &str) -> String {
+    let last = digits.as_bytes()[digits.len() - 1] - b'0';
+    if digits.len() == 1 || last % 2 == 0 {
+        return digits;
+    }
+
+    let mut lower = digits.clone();
+    lower.pop();
+    lower.push(char::from(b'0' + last - 1));
+    let (first, rest) = lower.split_at(1);
+    if format!("{first}.{rest}e{exponent}").parse() != Ok(number) {
+        return digits;
+    }
+
+    // Both round-trip, so compare the exact value of the number with the midpoint between them.
+    let exact = format!("{number:.1100e}");
+    let (mantissa, exact_exponent) = exact.split_once('e').expect("exponent");
+    let exact_digits = mantissa.replace('.', "");
+    if exact_exponent == exponent && exact_digits.trim_end_matches('0') == format!("{lower}5") {
+        digits = lower;
+    }
+    digits
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeJsonCanonical;
+
+impl Function for EncodeJsonCanonical {
+    fn identifier(&self) -> &'static str {
+        "encode_json_canonical"
+    }
+
+    fn summary(&self) -> &'static str {
+        "encode a value to canonical JSON"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Encodes the `value` to JSON in the canonical form of RFC 8785, the JSON
+            Canonicalization Scheme, so that equal values always encode to the same bytes and
+            can be hashed or signed.
+
+            Object keys are sorted by their UTF-16 code units, there is no whitespace between
+            tokens, and numbers are written the way ECMAScript writes them. As in JCS, numbers
+            are IEEE 754 doubles, so integers beyond 2^53 lose precision. Infinite floats can't
+            be encoded and return an error. Timestamps and regexes are encoded as strings, and
+            invalid UTF-8 is replaced with U+FFFD.
+        "}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::ANY,
+            required: true,
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(EncodeJsonCanonicalFn { value }.as_expr())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "encode object",
+            source: r#"encode_json_canonical!({"b": [1.50, 0.0020], "a": 10.0})"#,
+            result: Ok(r#"s'{"a":10,"b":[1.5,0.002]}'"#),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodeJsonCanonicalFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for EncodeJsonCanonicalFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        encode_json_canonical(&value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        // Infinite floats can't be encoded.
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        encode_json_canonical => EncodeJsonCanonical;
+
+        // RFC 8785, section 3.2.2.
+        rfc_example {
+            args: func_args![value: value!({
+                numbers: [333_333_333.333_333_3, 1e30, 4.50, 2e-3, 0.000_000_000_000_000_000_000_000_001],
+                string: "\u{20ac}$\u{000F}\nA'B\"\\\\\"/",
+                literals: [null, true, false],
+            })],
+            want: Ok(r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        // RFC 8785, section 3.2.3.
+        rfc_sorting {
+            args: func_args![value: Value::from_iter([
+                ("\u{20ac}", "Euro Sign"),
+                ("\r", "Carriage Return"),
+                ("\u{fb33}", "Hebrew Letter Dalet With Dagesh"),
+                ("1", "One"),
+                ("\u{1f600}", "Emoji: Grinning Face"),
+                ("\u{0080}", "Control"),
+                ("\u{00f6}", "Latin Small Letter O With Diaeresis"),
+            ].map(|(key, value)| (key.to_owned(), Value::from(value))))],
+            want: Ok("{\"\\r\":\"Carriage Return\",\"1\":\"One\",\"\u{0080}\":\"Control\",\"\u{00f6}\":\"Latin Small Letter O With Diaeresis\",\"\u{20ac}\":\"Euro Sign\",\"\u{1f600}\":\"Emoji: Grinning Face\",\"\u{fb33}\":\"Hebrew Letter Dalet With Dagesh\"}"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        integer {
+            args: func_args![value: 9_007_199_254_740_992_i64],
+            want: Ok("9007199254740992"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_utf8 {
+            args: func_args![value: value!({ message: (Bytes::from_static(b"foo\xFFbar")) })],
+            want: Ok("{\"message\":\"foo\u{FFFD}bar\"}"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        infinite {
+            args: func_args![value: f64::INFINITY],
+            want: Err("unable to encode inf as canonical JSON"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+
+    // RFC 8785, appendix B.
+    #[test]
+    fn rfc_numbers() {
+        let vectors = [
+            (0x0000_0000_0000_0000, "0"),
+            (0x8000_0000_0000_0000, "0"),
+            (0x0000_0000_0000_0001, "5e-324"),
+            (0x8000_0000_0000_0001, "-5e-324"),
+            (0x7fef_ffff_ffff_ffff, "1.7976931348623157e+308"),
+            (0xffef_ffff_ffff_ffff, "-1.7976931348623157e+308"),
+            (0x4340_0000_0000_0000, "9007199254740992"),
+            (0xc340_0000_0000_0000, "-9007199254740992"),
+            (0x4430_0000_0000_0000, "295147905179352830000"),
+            (0x44b5_2d02_c7e1_4af5, "9.999999999999997e+22"),
+            (0x44b5_2d02_c7e1_4af6, "1e+23"),
+            (0x44b5_2d02_c7e1_4af7, "1.0000000000000001e+23"),
+            (0x444b_1ae4_d6e2_ef4e, "999999999999999700000"),
+            (0x444b_1ae4_d6e2_ef4f, "999999999999999900000"),
+            (0x444b_1ae4_d6e2_ef50, "1e+21"),
+            (0x3eb0_c6f7_a0b5_ed8c, "9.999999999999997e-7"),
+            (0x3eb0_c6f7_a0b5_ed8d, "0.000001"),
+            (0x41b3_de43_5555_5553, "333333333.3333332"),
+            (0x41b3_de43_5555_5554, "333333333.33333325"),
+            (0x41b3_de43_5555_5555, "333333333.3333333"),
+            (0x41b3_de43_5555_5556, "333333333.3333334"),
+            (0x41b3_de43_5555_5557, "333333333.33333343"),
+            (0xbecb_f647_612f_3696, "-0.0000033333333333333333"),
+            (0x4314_3ff3_c1cb_0959, "1424953923781206.2"),
+        ];
+
+        for (bits, want) in vectors {
+            let mut json = String::new();
+            write_number(f64::from_bits(bits), &mut json).unwrap();
+            assert_eq!(json, want, "{bits:#018x}");
+        }
+    }
+}
@@ -127,6 +127,11 @@ impl Function for EncodePercent {
                 source: r#"encode_percent("foo@bar?")"#,
                 result: Ok("s'foo%40bar%3F'"),
             },
+            Example {
+                title: "percent encode a path segment",
+                source: r#"encode_percent("foo bar/baz", ascii_set: "PATH")"#,
+                result: Ok("s'foo%20bar/baz'"),
+            },
         ]
     }
 }
@@ -203,6 +208,36 @@ mod tests {
             tdef: TypeDef::bytes().infallible(),
         }
 
+        space_and_slash_query {
+            args: func_args![value: "a b/c", ascii_set: "QUERY"],
+            want: Ok("a%20b/c"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        space_and_slash_path {
+            args: func_args![value: "a b/c", ascii_set: "PATH"],
+            want: Ok("a%20b/c"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        space_and_slash_userinfo {
+            args: func_args![value: "a b/c", ascii_set: "USERINFO"],
+            want: Ok("a%20b%2Fc"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        space_and_slash_component {
+            args: func_args![value: "a b/c", ascii_set: "COMPONENT"],
+            want: Ok("a%20b%2Fc"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        space_and_slash_controls {
+            args: func_args![value: "a b/c", ascii_set: "CONTROLS"],
+            want: Ok("a b/c"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
         www_form_urlencoded {
             args: func_args![value: r#"foo #"<>?`{}/:;=@[\]^|$%&+,!'()~bar"#, ascii_set: "WWW_FORM_URLENCODED"],
             want: Ok("foo%20%23%22%3C%3E%3F%60%7B%7D%2F%3A%3B%3D%40%5B%5C%5D%5E%7C%24%25%26%2B%2C%21%27%28%29%7Ebar"),
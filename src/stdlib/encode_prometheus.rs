@@ -0,0 +1,356 @@
+use std::fmt::Write as _;
+
+use indexmap::IndexMap;
+
+use crate::compiler::prelude::*;
+
+fn format_float(value: f64) -> String {
+    if value.is_infinite() {
+        if value.is_sign_positive() {
+            "+Inf"
+        } else {
+            "-Inf"
+        }
+        .to_owned()
+    } else {
+        value.to_string()
+    }
+}
+
+fn format_value(field: &str, value: Option<&Value>) -> Result<String, ExpressionError> {
+    match value {
+        Some(Value::Integer(v)) => Ok(v.to_string()),
+        Some(Value::Float(v)) => Ok(format_float(v.into_inner())),
+        // Prometheus represents missing observations, such as empty quantiles, as `NaN`.
+        Some(Value::Null) | None => Ok("NaN".to_owned()),
+        Some(value) => Err(format!("{field} must be a number, got {}", value.kind()).into()),
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn format_labels(labels: &[(String, String)]) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+
+    let labels = labels
+        .iter()
+        .map(|(key, value)| format!("{key}=\"{}\"", escape_label_value(value)))
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("{{{labels}}}")
+}
+
+fn metric_labels(metric: &ObjectMap) -> Result<Vec<(String, String)>, ExpressionError> {
+    let Some(labels) = metric.get("labels").filter(|labels| !labels.is_null()) else {
+        return Ok(Vec::new());
+    };
+
+    labels
+        .clone()
+        .try_object()?
+        .into_iter()
+        .map(|(key, value)| {
+            let value = match value {
+                Value::Bytes(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+                value => value.to_string(),
+            };
+            Ok((key.into(), value))
+        })
+        .collect()
+}
+
+/// Writes a single sample line, appending `extra` (such as `le` or `quantile`) after the
+/// metric's own labels.
+fn write_sample(
+    out: &mut String,
+    name: &str,
+    labels: &[(String, String)],
+    extra: Option<(&str, String)>,
+    value: &str,
+    timestamp: &str,
+) {
+    let mut labels = labels.to_vec();
+    if let Some((key, bound)) = extra {
+        labels.push((key.to_owned(), bound));
+    }
+    writeln!(out, "{name}{} {value}{timestamp}", format_labels(&labels)).expect("write to String");
+}
+
+fn write_metric(out: &mut String, name: &str, metric: &ObjectMap) -> Result<(), ExpressionError> {
+    let labels = metric_labels(metric)?;
+    let timestamp = match metric.get("timestamp") {
+        Some(Value::Timestamp(ts)) => format!(" {}", ts.timestamp_millis()),
+        Some(Value::Integer(ms)) => format!(" {ms}"),
+        Some(Value::Null) | None => String::new(),
+        Some(value) => {
+            return Err(format!("timestamp has unsupported type {}", value.kind()).into());
+        }
+    };
+
+    let metric_type = metric_type(metric)?;
+    let (samples, bound_key, bound_label, value_key) = match metric_type.as_str() {
+        "histogram" => ("buckets", "upper_limit", "le", "count"),
+        "summary" => ("quantiles", "quantile", "quantile", "value"),
+        _ => {
+            let value = format_value("value", metric.get("value"))?;
+            write_sample(out, name, &labels, None, &value, &timestamp);
+            return Ok(());
+        }
+    };
+
+    let mut sample_name = name.to_owned();
+    if metric_type == "histogram" {
+        sample_name.push_str("_bucket");
+    }
+    let entries = match metric.get(samples) {
+        Some(Value::Array(entries)) => entries.as_slice(),
+        Some(Value::Null) | None => &[],
+        Some(value) => {
+            return Err(format!("{samples} must be an array, got {}", value.kind()).into());
+        }
+    };
+    for entry in entries {
+        let entry = entry.clone().try_object()?;
+        let bound = format_value(bound_key, entry.get(bound_key))?;
+        let value = format_value(value_key, entry.get(value_key))?;
+        let extra = Some((bound_label, bound));
+        write_sample(out, &sample_name, &labels, extra, &value, &timestamp);
+    }
+
+    let count = format_value("count", metric.get("count"))?;
+    if metric_type == "histogram" {
+        let extra = Some(("le", "+Inf".to_owned()));
+        write_sample(out, &sample_name, &labels, extra, &count, &timestamp);
+    }
+    let sum = format_value("sum", metric.get("sum"))?;
+    write_sample(out, &format!("{name}_sum"), &labels, None, &sum, &timestamp);
+    write_sample(
+        out,
+        &format!("{name}_count"),
+        &labels,
+        None,
+        &count,
+        &timestamp,
+    );
+
+    Ok(())
+}
+
+fn metric_type(metric: &ObjectMap) -> Result<String, ExpressionError> {
+    match metric.get("type") {
+        Some(Value::Null) | None => Ok("untyped".to_owned()),
+        Some(value) => {
+            let metric_type = value.clone().try_bytes_utf8_lossy()?.into_owned();
+            match metric_type.as_str() {
+                "counter" | "gauge" | "histogram" | "summary" | "untyped" => Ok(metric_type),
+                _ => Err(format!("unknown metric type {metric_type:?}").into()),
+            }
+        }
+    }
+}
+
+fn encode_prometheus(value: Value) -> Resolved {
+    // Samples of the same metric have to be written together under a single HELP and TYPE
+    // line, so metrics are grouped by name in order of first appearance.
+    let mut families: IndexMap<String, Vec<ObjectMap>> = IndexMap::new();
+    for metric in value.try_array()? {
+        let metric = metric.try_object()?;
+        let name = metric
+            .get("name")
+            .ok_or("metric is missing a name")?
+            .clone()
+            .try_bytes_utf8_lossy()?
+            .into_owned();
+        families.entry(name).or_default().push(metric);
+    }
+
+    let mut out = String::new();
+    for (name, metrics) in families {
+        if let Some(help) = metrics
+            .iter()
+            .find_map(|metric| metric.get("help").and_then(Value::as_str))
+        {
+            let help = help.replace('\\', "\\\\").replace('\n', "\\n");
+            writeln!(out, "# HELP {name} {help}").expect("write to String");
+        }
+        let metric_type = metric_type(&metrics[0])?;
+        writeln!(out, "# TYPE {name} {metric_type}").expect("write to String");
+
+        for metric in &metrics {
+            write_metric(&mut out, &name, metric)?;
+        }
+    }
+
+    Ok(out.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodePrometheus;
+
+impl Function for EncodePrometheus {
+    fn identifier(&self) -> &'static str {
+        "encode_prometheus"
+    }
+
+    fn summary(&self) -> &'static str {
+        "encode metrics as Prometheus exposition format text"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Encodes an array of metric objects, as returned by `parse_prometheus`, into
+            Prometheus text exposition format.
+
+            Metrics sharing a name are written together under one `HELP` and `TYPE` line.
+            Histograms are written as `_bucket` samples, including the `+Inf` bucket taken from
+            `count`, followed by `_sum` and `_count`. Summaries are written as `quantile`
+            samples followed by `_sum` and `_count`. `null` values are written as `NaN`.
+        "}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "counter",
+            source: indoc! {r#"
+                encode_prometheus!([{
+                    "name": "http_requests_total",
+                    "type": "counter",
+                    "help": "Total requests.",
+                    "labels": {"method": "post", "code": "200"},
+                    "value": 1027
+                }])
+            "#},
+            result: Ok(indoc! {r#"
+                s'# HELP http_requests_total Total requests.
+                # TYPE http_requests_total counter
+                http_requests_total{code="200",method="post"} 1027
+                '"#}),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(EncodePrometheusFn { value }.as_expr())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodePrometheusFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for EncodePrometheusFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        encode_prometheus(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        encode_prometheus => EncodePrometheus;
+
+        counters_grouped_by_name {
+            args: func_args![value: value!([
+                {"name": "requests_total", "type": "counter", "help": "Requests.", "labels": {"code": "200"}, "value": 10},
+                {"name": "up", "type": "gauge", "value": 1.0, "timestamp": 1_395_066_363_000_i64},
+                {"name": "requests_total", "type": "counter", "labels": {"code": "500", "path": "/a \"b\""}, "value": 2},
+            ])],
+            want: Ok(indoc! {r#"
+                # HELP requests_total Requests.
+                # TYPE requests_total counter
+                requests_total{code="200"} 10
+                requests_total{code="500",path="/a \"b\""} 2
+                # TYPE up gauge
+                up 1 1395066363000
+            "#}),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        histogram {
+            args: func_args![value: value!([{
+                "name": "request_duration_seconds",
+                "type": "histogram",
+                "labels": {"path": "/"},
+                "buckets": [
+                    {"upper_limit": 0.05, "count": 24054},
+                    {"upper_limit": 0.5, "count": 129_389},
+                ],
+                "sum": 53423.5,
+                "count": 144_320,
+            }])],
+            want: Ok(indoc! {r#"
+                # TYPE request_duration_seconds histogram
+                request_duration_seconds_bucket{path="/",le="0.05"} 24054
+                request_duration_seconds_bucket{path="/",le="0.5"} 129389
+                request_duration_seconds_bucket{path="/",le="+Inf"} 144320
+                request_duration_seconds_sum{path="/"} 53423.5
+                request_duration_seconds_count{path="/"} 144320
+            "#}),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        summary {
+            args: func_args![value: value!([{
+                "name": "rpc_duration_seconds",
+                "type": "summary",
+                "quantiles": [
+                    {"quantile": 0.5, "value": 4773},
+                    {"quantile": 0.99, "value": null},
+                ],
+                "sum": 17_560_473,
+                "count": 2693,
+            }])],
+            want: Ok(indoc! {r#"
+                # TYPE rpc_duration_seconds summary
+                rpc_duration_seconds{quantile="0.5"} 4773
+                rpc_duration_seconds{quantile="0.99"} NaN
+                rpc_duration_seconds_sum 17560473
+                rpc_duration_seconds_count 2693
+            "#}),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        missing_name {
+            args: func_args![value: value!([{"type": "counter", "value": 1}])],
+            want: Err("metric is missing a name"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_value {
+            args: func_args![value: value!([{"name": "up", "value": "yes"}])],
+            want: Err("value must be a number, got string"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
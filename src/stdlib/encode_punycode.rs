@@ -141,6 +141,12 @@ mod test {
             tdef: TypeDef::bytes().fallible(),
         }
 
+        german_domain {
+            args: func_args![value: value!("münchen.de")],
+            want: Ok(value!("xn--mnchen-3ya.de")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
         ascii_string {
             args: func_args![value: value!("www.cafe.com")],
             want: Ok(value!("www.cafe.com")),
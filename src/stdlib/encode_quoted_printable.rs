@@ -0,0 +1,109 @@
+use crate::compiler::prelude::*;
+
+fn encode_quoted_printable(value: Value) -> Resolved {
+    let value = value.try_bytes()?;
+    Ok(Value::Bytes(quoted_printable::encode(value).into()))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeQuotedPrintable;
+
+impl Function for EncodeQuotedPrintable {
+    fn identifier(&self) -> &'static str {
+        "encode_quoted_printable"
+    }
+
+    fn summary(&self) -> &'static str {
+        "encode a string to Quoted-Printable"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Encodes the `value` with the Quoted-Printable transfer encoding of RFC 2045, as
+            used in email bodies.
+
+            Bytes outside printable ASCII, and `=` itself, are written as `=XX` hex escapes.
+            CRLF line breaks are kept, while lone CR and LF characters are escaped. Lines are
+            wrapped at 76 characters with soft line breaks, a `=` at the end of a line.
+        "}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(EncodeQuotedPrintableFn { value }.as_expr())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "encode string",
+                source: r#"encode_quoted_printable("Grüße = Greetings")"#,
+                result: Ok("Gr=C3=BC=C3=9Fe =3D Greetings"),
+            },
+            Example {
+                title: "round trip",
+                source: r#"decode_quoted_printable(encode_quoted_printable("Grüße = Greetings"))"#,
+                result: Ok("Grüße = Greetings"),
+            },
+        ]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodeQuotedPrintableFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for EncodeQuotedPrintableFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        encode_quoted_printable(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        encode_quoted_printable => EncodeQuotedPrintable;
+
+        escapes {
+            args: func_args![value: value!("caf\u{e9} = 5\u{20ac}\r\nnext\nline")],
+            want: Ok(value!("caf=C3=A9 =3D 5=E2=82=AC\r\nnext=0Aline")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        trailing_whitespace {
+            args: func_args![value: value!("trailing \r\ntab\t\r\nend")],
+            want: Ok(value!("trailing=20\r\ntab=09\r\nend")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        soft_line_breaks {
+            args: func_args![value: value!("The quick brown fox jumps over the lazy dog, then the lazy dog jumps over the quick brown fox.")],
+            want: Ok(value!("The quick brown fox jumps over the lazy dog, then the lazy dog jumps over t=\r\nhe quick brown fox.")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
@@ -1,14 +1,23 @@
 use crate::compiler::prelude::*;
 use snap::raw::Encoder;
+use snap::read::FrameEncoder;
+use std::io::Read;
 
-fn encode_snappy(value: Value) -> Resolved {
+fn encode_snappy(value: Value, framed: bool) -> Resolved {
     let value = value.try_bytes()?;
-    let mut encoder = Encoder::new();
-    let result = encoder.compress_vec(&value);
+    let result = if framed {
+        let mut buf = Vec::new();
+        FrameEncoder::new(&value[..])
+            .read_to_end(&mut buf)
+            .map(|_| buf)
+            .map_err(|_| ())
+    } else {
+        Encoder::new().compress_vec(&value).map_err(|_| ())
+    };
 
     match result {
         Ok(buf) => Ok(Value::Bytes(buf.into())),
-        Err(_) => Err("unable to encode value with Snappy encoder".into()),
+        Err(()) => Err("unable to encode value with Snappy encoder".into()),
     }
 }
 
@@ -21,11 +30,18 @@ impl Function for EncodeSnappy {
     }
 
     fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "demo string",
-            source: r#"encode_base64(encode_snappy!("The quick brown fox jumps over 13 lazy dogs."))"#,
-            result: Ok("LKxUaGUgcXVpY2sgYnJvd24gZm94IGp1bXBzIG92ZXIgMTMgbGF6eSBkb2dzLg=="),
-        }]
+        &[
+            Example {
+                title: "demo string",
+                source: r#"encode_base64(encode_snappy!("The quick brown fox jumps over 13 lazy dogs."))"#,
+                result: Ok("LKxUaGUgcXVpY2sgYnJvd24gZm94IGp1bXBzIG92ZXIgMTMgbGF6eSBkb2dzLg=="),
+            },
+            Example {
+                title: "framed round trip",
+                source: r#"decode_snappy!(encode_snappy!("The quick brown fox jumps over 13 lazy dogs.", framed: true), framed: true)"#,
+                result: Ok("The quick brown fox jumps over 13 lazy dogs."),
+            },
+        ]
     }
 
     fn compile(
@@ -35,29 +51,39 @@ impl Function for EncodeSnappy {
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
+        let framed = arguments.optional("framed").unwrap_or_else(|| expr!(false));
 
-        Ok(EncodeSnappyFn { value }.as_expr())
+        Ok(EncodeSnappyFn { value, framed }.as_expr())
     }
 
     fn parameters(&self) -> &'static [Parameter] {
-        &[Parameter {
-            keyword: "value",
-            kind: kind::BYTES,
-            required: true,
-        }]
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "framed",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
     }
 }
 
 #[derive(Clone, Debug)]
 struct EncodeSnappyFn {
     value: Box<dyn Expression>,
+    framed: Box<dyn Expression>,
 }
 
 impl FunctionExpression for EncodeSnappyFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
+        let framed = self.framed.resolve(ctx)?.try_boolean()?;
 
-        encode_snappy(value)
+        encode_snappy(value, framed)
     }
 
     fn type_def(&self, _state: &state::TypeState) -> TypeDef {
@@ -90,5 +116,29 @@ mod test {
             want: Ok(value!(decode_base64("LKxUaGUgcXVpY2sgYnJvd24gZm94IGp1bXBzIG92ZXIgMTMgbGF6eSBkb2dzLg==").as_bytes())),
             tdef: TypeDef::bytes().fallible(),
         }
+
+        raw_explicit {
+            args: func_args![value: value!("The quick brown fox jumps over 13 lazy dogs."), framed: false],
+            want: Ok(value!(decode_base64("LKxUaGUgcXVpY2sgYnJvd24gZm94IGp1bXBzIG92ZXIgMTMgbGF6eSBkb2dzLg==").as_bytes())),
+            tdef: TypeDef::bytes().fallible(),
+        }
     ];
+
+    #[test]
+    fn framed_round_trip() {
+        let input = "The quick brown fox jumps over 13 lazy dogs.";
+        let encoded = match encode_snappy(value!(input), true) {
+            Ok(Value::Bytes(bytes)) => bytes,
+            other => panic!("unexpected result: {other:?}"),
+        };
+
+        // Every framed stream starts with the stream identifier chunk.
+        assert!(encoded.starts_with(b"\xff\x06\x00\x00sNaPpY"));
+
+        let mut decoded = Vec::new();
+        snap::read::FrameDecoder::new(encoded.as_bytes())
+            .read_to_end(&mut decoded)
+            .expect("valid snappy frame");
+        assert_eq!(decoded, input.as_bytes());
+    }
 }
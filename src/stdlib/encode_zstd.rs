@@ -86,3 +86,33 @@ impl FunctionExpression for EncodeZstdFn {
         TypeDef::bytes().infallible()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        encode_zstd => EncodeZstd;
+
+        with_defaults {
+            args: func_args![value: value!("encode_me")],
+            want: Ok(value!(b"\x28\xb5\x2f\xfd\x00\x58\x49\x00\x00encode_me")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+
+    #[test]
+    fn round_trip() {
+        let input = "you_have_successfully_decoded_me.congratulations.you_are_breathtaking.";
+
+        for level in [1, 3, 19] {
+            let encoded = match encode_zstd(value!(input), Some(value!(level))) {
+                Ok(Value::Bytes(bytes)) => bytes,
+                other => panic!("unexpected result: {other:?}"),
+            };
+            let decoded = zstd::decode_all(encoded.as_bytes()).expect("valid zstd frame");
+            assert_eq!(decoded, input.as_bytes());
+        }
+    }
+}
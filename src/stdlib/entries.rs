@@ -0,0 +1,101 @@
+use std::collections::BTreeMap;
+
+use crate::compiler::prelude::*;
+
+fn entries(value: Value) -> Resolved {
+    let object = value.try_object()?;
+    let entries = object
+        .into_iter()
+        .map(|(key, value)| Value::Array(vec![Value::from(key), value]));
+    Ok(Value::Array(entries.collect()))
+}
+
+#[derive(Debug)]
+pub struct Entries;
+
+impl Function for Entries {
+    fn identifier(&self) -> &'static str {
+        "entries"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "get entries",
+            source: r#"entries({"key1": "val1", "key2": 2})"#,
+            result: Ok(r#"[["key1", "val1"], ["key2", 2]]"#),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        Ok(EntriesFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct EntriesFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for EntriesFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        entries(self.value.resolve(ctx)?)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        let value_kind = self
+            .value
+            .type_def(state)
+            .kind()
+            .as_object()
+            .unwrap()
+            .reduced_kind();
+        let pair = BTreeMap::from([
+            (Index::from(0), Kind::bytes()),
+            (Index::from(1), value_kind),
+        ]);
+
+        TypeDef::array(Collection::empty().with_unknown(Kind::array(pair))).infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        entries => Entries;
+
+        object {
+            args: func_args![value: value!({"a": 1, "b": {"c": true}})],
+            want: Ok(value!([["a", 1], ["b", {"c": true}]])),
+            tdef: TypeDef::array(Collection::empty().with_unknown(Kind::array(BTreeMap::from([
+                (Index::from(0), Kind::bytes()),
+                (Index::from(1), Kind::integer() | Kind::object(BTreeMap::from([("c".into(), Kind::boolean())]))),
+            ])))),
+        }
+
+        empty {
+            args: func_args![value: value!({})],
+            want: Ok(value!([])),
+            tdef: TypeDef::array(Collection::empty().with_unknown(Kind::array(BTreeMap::from([
+                (Index::from(0), Kind::bytes()),
+                (Index::from(1), Kind::never()),
+            ])))),
+        }
+    ];
+}
@@ -1,4 +1,5 @@
-use std::collections::btree_map;
+use std::borrow::Cow;
+use std::collections::{btree_map, BTreeMap};
 
 use crate::compiler::prelude::*;
 
@@ -99,11 +100,76 @@ impl FunctionExpression for FlattenFn {
         let td = self.value.type_def(state);
 
         if td.is_array() {
-            TypeDef::array(Collection::any())
-        } else {
-            TypeDef::object(Collection::any())
+            return TypeDef::array(Collection::any());
+        }
+
+        // The flattened keys can only be known when the separator is.
+        let separator = self
+            .separator
+            .resolve_constant(state)
+            .and_then(|separator| separator.as_str().map(Cow::into_owned));
+        match (td.kind().as_object(), separator) {
+            (Some(object), Some(separator)) if td.is_object() => {
+                TypeDef::object(flatten_collection(object, &separator))
+            }
+            _ => TypeDef::object(Collection::any()),
+        }
+    }
+}
+
+/// Computes the type of a flattened object from the type of the object.
+fn flatten_collection(collection: &Collection<Field>, separator: &str) -> Collection<Field> {
+    let mut known = BTreeMap::new();
+    let mut unknown = Kind::undefined();
+    flatten_fields(collection, None, separator, &mut known, &mut unknown);
+
+    Collection::from_parts(known, unknown)
+}
+
+fn flatten_fields(
+    collection: &Collection<Field>,
+    parent: Option<&str>,
+    separator: &str,
+    known: &mut BTreeMap<Field, Kind>,
+    unknown: &mut Kind,
+) {
+    for (field, kind) in collection.known() {
+        let key = match parent {
+            None => field.to_string(),
+            Some(parent) => format!("{parent}{separator}{field}"),
+        };
+        let optional = kind.contains_undefined();
+
+        match kind.as_object() {
+            // A field that is always an object is replaced by its own flattened fields, which
+            // may be missing when the field itself may be.
+            Some(object) if kind.without_undefined().is_object() => {
+                let mut nested = BTreeMap::new();
+                flatten_fields(object, Some(&key), separator, &mut nested, unknown);
+                known.extend(nested.into_iter().map(|(field, kind)| {
+                    let kind = if optional { kind.or_undefined() } else { kind };
+                    (field, kind)
+                }));
+            }
+            // A field that may be an object keeps its key when it isn't one, and adds keys
+            // that can't be known when it is.
+            Some(_) => {
+                known.insert(key.into(), kind.without_object().or_undefined());
+                *unknown = Kind::any();
+            }
+            None => {
+                known.insert(key.into(), kind.clone());
+            }
         }
     }
+
+    // Fields of unknown names that are objects are flattened into more unknown keys.
+    let unknown_kind = collection.unknown_kind();
+    *unknown = if unknown_kind.contains_object() {
+        Kind::any()
+    } else {
+        unknown.union(unknown_kind)
+    };
 }
 
 /// An iterator to walk over maps allowing us to flatten nested maps to a single level.
@@ -260,19 +326,33 @@ mod test {
         map {
             args: func_args![value: value!({parent: "child"})],
             want: Ok(value!({parent: "child"})),
-            tdef: TypeDef::object(Collection::any()),
+            tdef: TypeDef::object(BTreeMap::from([("parent".into(), Kind::bytes())])),
         }
 
         nested_map {
             args: func_args![value: value!({parent: {child1: 1, child2: 2}, key: "val"})],
             want: Ok(value!({"parent.child1": 1, "parent.child2": 2, key: "val"})),
-            tdef: TypeDef::object(Collection::any()),
+            tdef: TypeDef::object(BTreeMap::from([
+                ("parent.child1".into(), Kind::integer()),
+                ("parent.child2".into(), Kind::integer()),
+                ("key".into(), Kind::bytes()),
+            ])),
         }
 
         nested_map_with_separator {
             args: func_args![value: value!({parent: {child1: 1, child2: 2}, key: "val"}), separator: "_"],
             want: Ok(value!({"parent_child1": 1, "parent_child2": 2, key: "val"})),
-            tdef: TypeDef::object(Collection::any()),
+            tdef: TypeDef::object(BTreeMap::from([
+                ("parent_child1".into(), Kind::integer()),
+                ("parent_child2".into(), Kind::integer()),
+                ("key".into(), Kind::bytes()),
+            ])),
+        }
+
+        nested_empty_map {
+            args: func_args![value: value!({parent: {}, key: true})],
+            want: Ok(value!({key: true})),
+            tdef: TypeDef::object(BTreeMap::from([("key".into(), Kind::boolean())])),
         }
 
         double_nested_map {
@@ -289,7 +369,12 @@ mod test {
                 "parent.child2.grandchild2": 2,
                 key: "val",
             })),
-            tdef: TypeDef::object(Collection::any()),
+            tdef: TypeDef::object(BTreeMap::from([
+                ("parent.child1".into(), Kind::integer()),
+                ("parent.child2.grandchild1".into(), Kind::integer()),
+                ("parent.child2.grandchild2".into(), Kind::integer()),
+                ("key".into(), Kind::bytes()),
+            ])),
         }
 
         map_and_array {
@@ -306,7 +391,12 @@ mod test {
                 "parent.child2.grandchild2": [1, [2, 3], 4],
                 key: "val",
             })),
-            tdef: TypeDef::object(Collection::any()),
+            tdef: TypeDef::object(BTreeMap::from([
+                ("parent.child1".into(), Kind::from(value!([1, [2, 3]]))),
+                ("parent.child2.grandchild1".into(), Kind::integer()),
+                ("parent.child2.grandchild2".into(), Kind::from(value!([1, [2, 3], 4]))),
+                ("key".into(), Kind::bytes()),
+            ])),
         }
 
         map_and_array_with_separator {
@@ -323,7 +413,12 @@ mod test {
                 "parent_child2_grandchild2": [1, [2, 3], 4],
                 key: "val",
             })),
-            tdef: TypeDef::object(Collection::any()),
+            tdef: TypeDef::object(BTreeMap::from([
+                ("parent_child1".into(), Kind::from(value!([1, [2, 3]]))),
+                ("parent_child2_grandchild1".into(), Kind::integer()),
+                ("parent_child2_grandchild2".into(), Kind::from(value!([1, [2, 3], 4]))),
+                ("key".into(), Kind::bytes()),
+            ])),
         }
 
         // If the root object is an array, child maps are not flattened.
@@ -357,7 +452,68 @@ mod test {
                 "parent1.child2.grandchild3": 3,
                 parent2: 4,
             })),
-            tdef: TypeDef::object(Collection::any()),
+            tdef: TypeDef::object(BTreeMap::from([
+                ("parent1.child1.grandchild1".into(), Kind::integer()),
+                ("parent1.child2.grandchild2".into(), Kind::integer()),
+                ("parent1.child2.grandchild3".into(), Kind::integer()),
+                ("parent2".into(), Kind::integer()),
+            ])),
         }
     ];
+
+    #[test]
+    fn flatten_optional_object() {
+        let collection = Collection::from(BTreeMap::from([
+            (
+                "request".into(),
+                Kind::object(BTreeMap::from([("status".into(), Kind::integer())])).or_undefined(),
+            ),
+            ("host".into(), Kind::bytes()),
+        ]));
+
+        assert_eq!(
+            flatten_collection(&collection, "."),
+            Collection::from(BTreeMap::from([
+                ("request.status".into(), Kind::integer().or_undefined()),
+                ("host".into(), Kind::bytes()),
+            ]))
+        );
+    }
+
+    #[test]
+    fn flatten_maybe_object() {
+        let collection = Collection::from(BTreeMap::from([
+            (
+                "request".into(),
+                Kind::object(BTreeMap::from([("status".into(), Kind::integer())])).or_null(),
+            ),
+            ("host".into(), Kind::bytes()),
+        ]));
+
+        assert_eq!(
+            flatten_collection(&collection, "."),
+            Collection::from(BTreeMap::from([
+                ("request".into(), Kind::null().or_undefined()),
+                ("host".into(), Kind::bytes()),
+            ]))
+            .with_unknown(Kind::any())
+        );
+    }
+
+    #[test]
+    fn flatten_unknown_fields() {
+        let collection = Collection::from_parts(
+            BTreeMap::from([("host".into(), Kind::bytes())]),
+            Kind::integer(),
+        );
+
+        assert_eq!(
+            flatten_collection(&collection, "."),
+            Collection::from_parts(
+                BTreeMap::from([("host".into(), Kind::bytes())]),
+                Kind::integer().or_undefined()
+            )
+        );
+        assert!(flatten_collection(&Collection::any(), ".").is_any());
+    }
 }
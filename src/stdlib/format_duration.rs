@@ -0,0 +1,264 @@
+use crate::compiler::prelude::*;
+use crate::value;
+
+use super::parse_duration::UNITS;
+
+/// Components a duration is broken into, as (milliseconds, short suffix, long name).
+const COMPONENTS: [(u128, &str, &str); 5] = [
+    (86_400_000, "d", "day"),
+    (3_600_000, "h", "hour"),
+    (60_000, "m", "minute"),
+    (1_000, "s", "second"),
+    (1, "ms", "millisecond"),
+];
+
+fn format_duration(value: Value, unit: Value, style: &Bytes, max_units: Option<Value>) -> Resolved {
+    let amount = match value {
+        Value::Integer(v) => v as f64,
+        Value::Float(v) => v.into_inner(),
+        value => {
+            return Err(ValueError::Expected {
+                got: value.kind(),
+                expected: Kind::integer() | Kind::float(),
+            }
+            .into())
+        }
+    };
+
+    let conversion_factor = {
+        let bytes = unit.try_bytes()?;
+        let string = String::from_utf8_lossy(&bytes);
+
+        *UNITS
+            .get(string.as_ref())
+            .ok_or(format!("unknown unit format: '{string}'"))?
+    };
+
+    let max_units = match max_units {
+        Some(max_units) => match max_units.try_integer()? {
+            n if n < 1 => return Err("max_units must be at least 1".into()),
+            n => n as usize,
+        },
+        None => COMPONENTS.len(),
+    };
+
+    let millis = (amount * conversion_factor.as_secs_f64() * 1_000.0).round();
+    let mut remaining = millis.abs() as u128;
+    let long = style.as_ref() == b"long";
+
+    let parts: Vec<String> = COMPONENTS
+        .iter()
+        .filter_map(|&(size, short_name, long_name)| {
+            let count = remaining / size;
+            remaining %= size;
+            (count > 0).then(|| match (long, count) {
+                (false, _) => format!("{count}{short_name}"),
+                (true, 1) => format!("{count} {long_name}"),
+                (true, _) => format!("{count} {long_name}s"),
+            })
+        })
+        .take(max_units)
+        .collect();
+
+    let formatted = match (parts.is_empty(), long) {
+        (true, false) => "0s".to_owned(),
+        (true, true) => "0 seconds".to_owned(),
+        (false, false) => parts.concat(),
+        (false, true) => parts.join(" "),
+    };
+
+    if millis < 0.0 && !parts.is_empty() {
+        Ok(format!("-{formatted}").into())
+    } else {
+        Ok(formatted.into())
+    }
+}
+
+fn styles() -> Vec<Value> {
+    vec![value!("short"), value!("long")]
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct FormatDuration;
+
+impl Function for FormatDuration {
+    fn identifier(&self) -> &'static str {
+        "format_duration"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "short style",
+                source: "format_duration!(5400)",
+                result: Ok("1h30m"),
+            },
+            Example {
+                title: "long style",
+                source: r#"format_duration!(5400, style: "long")"#,
+                result: Ok("1 hour 30 minutes"),
+            },
+            Example {
+                title: "limited units",
+                source: r#"format_duration!(93784500, unit: "ms", max_units: 2)"#,
+                result: Ok("1d2h"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let unit = arguments.optional("unit").unwrap_or_else(|| expr!("s"));
+        let style = arguments
+            .optional_enum("style", &styles(), state)?
+            .unwrap_or_else(|| value!("short"))
+            .try_bytes()
+            .expect("style not bytes");
+        let max_units = arguments.optional("max_units");
+
+        Ok(FormatDurationFn {
+            value,
+            unit,
+            style,
+            max_units,
+        }
+        .as_expr())
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::INTEGER | kind::FLOAT,
+                required: true,
+            },
+            Parameter {
+                keyword: "unit",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "style",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "max_units",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FormatDurationFn {
+    value: Box<dyn Expression>,
+    unit: Box<dyn Expression>,
+    style: Bytes,
+    max_units: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for FormatDurationFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let unit = self.unit.resolve(ctx)?;
+        let max_units = self
+            .max_units
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        format_duration(value, unit, &self.style, max_units)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        format_duration => FormatDuration;
+
+        short_style {
+            args: func_args![value: 5400],
+            want: Ok("1h30m"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        long_style {
+            args: func_args![value: 5400, style: "long"],
+            want: Ok("1 hour 30 minutes"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        long_style_singular {
+            args: func_args![value: 90061, style: "long"],
+            want: Ok("1 day 1 hour 1 minute 1 second"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        all_components {
+            args: func_args![value: 93_784_005, unit: "ms"],
+            want: Ok("1d2h3m4s5ms"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        fractional_input {
+            args: func_args![value: 1.5, unit: "m"],
+            want: Ok("1m30s"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        max_units_short {
+            args: func_args![value: 93_784, max_units: 2],
+            want: Ok("1d2h"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        max_units_long {
+            args: func_args![value: 5_430, style: "long", max_units: 1],
+            want: Ok("1 hour"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        zero_short {
+            args: func_args![value: 0],
+            want: Ok("0s"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        zero_long {
+            args: func_args![value: 0, style: "long"],
+            want: Ok("0 seconds"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        negative {
+            args: func_args![value: -90],
+            want: Ok("-1m30s"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_max_units {
+            args: func_args![value: 60, max_units: 0],
+            want: Err("max_units must be at least 1"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        unknown_unit {
+            args: func_args![value: 60, unit: "w"],
+            want: Err("unknown unit format: 'w'"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -4,17 +4,27 @@ use chrono::{
     format::{strftime::StrftimeItems, Item},
     DateTime, Utc,
 };
+use std::borrow::Cow;
 
-fn format_timestamp_with_tz(ts: Value, format: Value, timezone: Option<Value>) -> Resolved {
+fn format_timestamp_with_tz(ts: Value, items: &[Item], timezone: Option<Value>) -> Resolved {
     let ts: DateTime<Utc> = ts.try_timestamp()?;
 
-    let format_bytes = format.try_bytes()?;
-    let format = String::from_utf8_lossy(&format_bytes);
-
     let timezone_bytes = timezone.map(VrlValueConvert::try_bytes).transpose()?;
     let timezone = timezone_bytes.as_ref().map(|b| String::from_utf8_lossy(b));
 
-    try_format_with_timezone(ts, &format, timezone.as_deref()).map(Into::into)
+    try_format_with_timezone(ts, items, timezone.as_deref()).map(Into::into)
+}
+
+/// Parses the strftime format, returning `None` if it is invalid.
+fn format_items(format: &str) -> Option<Vec<Item<'static>>> {
+    StrftimeItems::new(format).parse_to_owned().ok()
+}
+
+/// The format, parsed at compile time when it is constant.
+#[derive(Debug, Clone)]
+enum Format {
+    Items(Vec<Item<'static>>),
+    Expr(Box<dyn Expression>),
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -47,12 +57,23 @@ impl Function for FormatTimestamp {
 
     fn compile(
         &self,
-        _state: &state::TypeState,
+        state: &state::TypeState,
         _ctx: &mut FunctionCompileContext,
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
         let format = arguments.required("format");
+        let format = match format.resolve_constant(state) {
+            Some(format) => {
+                let items = format.as_str().and_then(|format| format_items(&format));
+                Format::Items(items.ok_or_else(|| function::Error::InvalidArgument {
+                    keyword: "format",
+                    value: format,
+                    error: "invalid format",
+                })?)
+            }
+            None => Format::Expr(format),
+        };
         let timezone = arguments.optional("timezone");
 
         Ok(FormatTimestampFn {
@@ -82,13 +103,19 @@ impl Function for FormatTimestamp {
 #[derive(Debug, Clone)]
 struct FormatTimestampFn {
     value: Box<dyn Expression>,
-    format: Box<dyn Expression>,
+    format: Format,
     timezone: Option<Box<dyn Expression>>,
 }
 
 impl FunctionExpression for FormatTimestampFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
-        let bytes = self.format.resolve(ctx)?;
+        let items = match &self.format {
+            Format::Items(items) => Cow::Borrowed(items.as_slice()),
+            Format::Expr(format) => {
+                let bytes = format.resolve(ctx)?.try_bytes()?;
+                Cow::Owned(format_items(&String::from_utf8_lossy(&bytes)).ok_or("invalid format")?)
+            }
+        };
         let ts = self.value.resolve(ctx)?;
         let tz = self
             .timezone
@@ -96,7 +123,7 @@ impl FunctionExpression for FormatTimestampFn {
             .map(|tz| tz.resolve(ctx))
             .transpose()?;
 
-        format_timestamp_with_tz(ts, bytes, tz)
+        format_timestamp_with_tz(ts, &items, tz)
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
@@ -106,16 +133,9 @@ impl FunctionExpression for FormatTimestampFn {
 
 fn try_format_with_timezone(
     dt: DateTime<Utc>,
-    format: &str,
+    items: &[Item],
     timezone: Option<&str>,
 ) -> ExpressionResult<String> {
-    let items = StrftimeItems::new(format)
-        .map(|item| match item {
-            Item::Error => Err("invalid format".into()),
-            _ => Ok(item),
-        })
-        .collect::<ExpressionResult<Vec<_>>>()?;
-
     let timezone = timezone
         .map(|timezone| {
             TimeZone::parse(timezone).ok_or(format!("unable to parse timezone: {timezone}"))
@@ -125,13 +145,13 @@ fn try_format_with_timezone(
     match timezone {
         Some(TimeZone::Named(tz)) => Ok(dt
             .with_timezone(&tz)
-            .format_with_items(items.into_iter())
+            .format_with_items(items.iter())
             .to_string()),
         Some(TimeZone::Local) => Ok(dt
             .with_timezone(&chrono::Local)
-            .format_with_items(items.into_iter())
+            .format_with_items(items.iter())
             .to_string()),
-        None => Ok(dt.format_with_items(items.into_iter()).to_string()),
+        None => Ok(dt.format_with_items(items.iter()).to_string()),
     }
 }
 
@@ -147,7 +167,7 @@ mod tests {
         invalid {
             args: func_args![value: Utc.timestamp_opt(10, 0).single().expect("invalid timestamp"),
                              format: "%Q INVALID"],
-            want: Err("invalid format"),
+            want: Err("invalid argument"),
             tdef: TypeDef::bytes().fallible(),
         }
 
@@ -189,4 +209,43 @@ mod tests {
             tdef: TypeDef::bytes().fallible(),
         }
     ];
+
+    #[test]
+    fn invalid_literal_format_fails_compilation() {
+        let result = crate::compiler::compile(
+            r#"format_timestamp!(now(), format: "%Q INVALID")"#,
+            &crate::stdlib::all(),
+        );
+
+        let Err(diagnostics) = result else {
+            panic!("invalid format compiled");
+        };
+        let error = diagnostics.errors()[0];
+        assert_eq!(error.code, 610);
+        assert!(error
+            .labels
+            .iter()
+            .any(|label| label.message.contains("invalid format")));
+    }
+
+    #[test]
+    fn invalid_dynamic_format_fails_at_runtime() {
+        use crate::compiler::{compile, runtime::Runtime, TargetValue};
+        use crate::value::Secrets;
+
+        let program = compile(
+            "format_timestamp!(now(), format: string!(.format))",
+            &crate::stdlib::all(),
+        )
+        .unwrap()
+        .program;
+        let mut target = TargetValue {
+            value: value!({ format: "%Q INVALID" }),
+            metadata: value!({}),
+            secrets: Secrets::new(),
+        };
+
+        let result = Runtime::default().resolve(&mut target, &program, &Default::default());
+        assert!(result.unwrap_err().to_string().contains("invalid format"));
+    }
 }
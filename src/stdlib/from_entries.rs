@@ -0,0 +1,157 @@
+use crate::compiler::prelude::*;
+
+fn from_entries(value: Value) -> Resolved {
+    let mut object = ObjectMap::new();
+    for entry in value.try_array()? {
+        let (key, value) = entry_pair(entry)?;
+        // Later duplicates overwrite earlier ones.
+        object.insert(key, value);
+    }
+    Ok(Value::Object(object))
+}
+
+/// Splits an entry, either a `[key, value]` array or a `{"key": .., "value": ..}` object,
+/// into its key and value.
+fn entry_pair(entry: Value) -> ExpressionResult<(KeyString, Value)> {
+    let (key, value) = match entry {
+        Value::Array(pair) => {
+            let [key, value]: [Value; 2] = pair
+                .try_into()
+                .map_err(|_| "entry arrays must have exactly two elements")?;
+            (key, value)
+        }
+        Value::Object(mut pair) => {
+            let key = pair
+                .remove("key")
+                .ok_or("entry objects must have a \"key\" field")?;
+            (key, pair.remove("value").unwrap_or(Value::Null))
+        }
+        entry => {
+            return Err(format!(
+                "expected entry to be an array or object, got {}",
+                entry.kind()
+            )
+            .into())
+        }
+    };
+
+    match key {
+        Value::Bytes(key) => Ok((String::from_utf8_lossy(&key).into(), value)),
+        key => Err(format!("entry keys must be strings, got {}", key.kind()).into()),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct FromEntries;
+
+impl Function for FromEntries {
+    fn identifier(&self) -> &'static str {
+        "from_entries"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "from key/value arrays",
+                source: r#"from_entries!([["key1", "val1"], ["key2", 2]])"#,
+                result: Ok(r#"{"key1": "val1", "key2": 2}"#),
+            },
+            Example {
+                title: "from key/value objects",
+                source: r#"from_entries!([{"key": "key1", "value": "val1"}, {"key": "key1", "value": "val2"}])"#,
+                result: Ok(r#"{"key1": "val2"}"#),
+            },
+            Example {
+                title: "round trip",
+                source: r#"from_entries!(map_values(entries({"a": 1, "b": 2})) -> |pair| { [pair[0], pair[1] * 10] })"#,
+                result: Ok(r#"{"a": 10, "b": 20}"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(FromEntriesFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FromEntriesFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for FromEntriesFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        from_entries(self.value.resolve(ctx)?)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(Collection::any()).fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        from_entries => FromEntries;
+
+        arrays {
+            args: func_args![value: value!([["a", 1], ["b", {"c": true}]])],
+            want: Ok(value!({"a": 1, "b": {"c": true}})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        objects {
+            args: func_args![value: value!([{"key": "a", "value": 1}, {"key": "b"}])],
+            want: Ok(value!({"a": 1, "b": null})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        mixed_duplicates_last_wins {
+            args: func_args![value: value!([["a", 1], {"key": "a", "value": 2}])],
+            want: Ok(value!({"a": 2})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        empty {
+            args: func_args![value: value!([])],
+            want: Ok(value!({})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        non_string_key {
+            args: func_args![value: value!([[1, "a"]])],
+            want: Err("entry keys must be strings, got integer"),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        wrong_pair_length {
+            args: func_args![value: value!([["a", 1, 2]])],
+            want: Err("entry arrays must have exactly two elements"),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        missing_key_field {
+            args: func_args![value: value!([{"value": 1}])],
+            want: Err(r#"entry objects must have a "key" field"#),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+    ];
+}
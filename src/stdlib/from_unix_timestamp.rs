@@ -216,6 +216,30 @@ mod tests {
             tdef: TypeDef::timestamp().fallible(),
         }
 
+        subsecond_milliseconds {
+            args: func_args![value: 1_609_459_200_123_i64, unit: "milliseconds"],
+            want: Ok(chrono::Utc.ymd(2021, 1, 1).and_hms_milli(0,0,0,123)),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        subsecond_microseconds {
+            args: func_args![value: 1_609_459_200_123_000_i64, unit: "microseconds"],
+            want: Ok(chrono::Utc.ymd(2021, 1, 1).and_hms_milli(0,0,0,123)),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        subsecond_nanoseconds {
+            args: func_args![value: 1_609_459_200_123_000_000_i64, unit: "nanoseconds"],
+            want: Ok(chrono::Utc.ymd(2021, 1, 1).and_hms_milli(0,0,0,123)),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        negative_milliseconds {
+            args: func_args![value: -1_500_i64, unit: "milliseconds"],
+            want: Ok(chrono::Utc.ymd(1969, 12, 31).and_hms_milli(23,59,58,500)),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
         float_type_invalid {
             args: func_args![value: 5.123],
             want: Err("unable to coerce float into timestamp"),
@@ -0,0 +1,336 @@
+use std::collections::HashMap;
+use std::env;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError, Weak};
+
+use maxminddb::{MaxMindDBError, Reader};
+use once_cell::sync::Lazy;
+
+use crate::compiler::prelude::*;
+
+/// Controls whether `geoip_lookup` may open MaxMind databases from the filesystem.
+///
+/// Access is denied by default, so that programs can't read arbitrary files unless the
+/// embedder trusts them. Embedders grant it by registering `GeoipDatabaseAccess::Allowed`
+/// with `CompileConfig::set_custom`; otherwise `geoip_lookup` calls fail to compile.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GeoipDatabaseAccess {
+    Allowed,
+    #[default]
+    Denied,
+}
+
+// This needs to be static because the database path needs to be a literal.
+static EXAMPLE_GEOIP_LOOKUP_EXPR: Lazy<&str> = Lazy::new(|| {
+    let path = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap())
+        .join("tests/data/geoip/test.mmdb")
+        .display()
+        .to_string();
+
+    Box::leak(
+        format!(r#"geoip_lookup!("8.8.8.8", "{path}", fields: ["country.iso_code"])"#)
+            .into_boxed_str(),
+    )
+});
+
+static EXAMPLES: Lazy<Vec<Example>> = Lazy::new(|| {
+    vec![Example {
+        title: "selected fields",
+        source: &EXAMPLE_GEOIP_LOOKUP_EXPR,
+        result: Ok(r#"{ "country.iso_code": "US" }"#),
+    }]
+});
+
+/// Opens the database at `path`, sharing the reader with every call site using the same
+/// path for as long as any of them is alive.
+fn open_reader(path: &str) -> Result<Arc<Reader<Vec<u8>>>, MaxMindDBError> {
+    type Readers = HashMap<String, Weak<Reader<Vec<u8>>>>;
+    static READERS: OnceLock<Mutex<Readers>> = OnceLock::new();
+
+    let mut readers = READERS
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    if let Some(reader) = readers.get(path).and_then(Weak::upgrade) {
+        return Ok(reader);
+    }
+
+    let reader = Arc::new(Reader::open_readfile(path)?);
+    readers.retain(|_, reader| reader.strong_count() > 0);
+    readers.insert(path.to_owned(), Arc::downgrade(&reader));
+    Ok(reader)
+}
+
+fn geoip_lookup(reader: &Reader<Vec<u8>>, value: Value, fields: Option<Value>) -> Resolved {
+    let ip = value.try_bytes_utf8_lossy()?;
+    let ip: IpAddr = ip
+        .parse()
+        .map_err(|err| format!("unable to parse IP address: {err}"))?;
+
+    let record = match reader.lookup::<Value>(ip) {
+        Ok(record) => record,
+        Err(MaxMindDBError::AddressNotFoundError(_)) => return Ok(Value::Null),
+        Err(err) => return Err(format!("unable to look up IP address: {err}").into()),
+    };
+
+    let Some(fields) = fields else {
+        return Ok(record);
+    };
+
+    let mut map = ObjectMap::new();
+    for field in fields.try_array()? {
+        let field = field.try_bytes_utf8_lossy()?.into_owned();
+        let value = lookup_field(&record, &field)
+            .cloned()
+            .unwrap_or(Value::Null);
+        map.insert(field.into(), value);
+    }
+
+    Ok(map.into())
+}
+
+/// Walks a dotted field path such as `country.iso_code` through a database record.
+fn lookup_field<'a>(record: &'a Value, field: &str) -> Option<&'a Value> {
+    field
+        .split('.')
+        .try_fold(record, |value, segment| match value {
+            Value::Object(map) => map.get(segment),
+            _ => None,
+        })
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GeoipLookup;
+
+impl Function for GeoipLookup {
+    fn identifier(&self) -> &'static str {
+        "geoip_lookup"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "ip",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "database_path",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "fields",
+                kind: kind::ARRAY,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES.as_slice()
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("ip");
+        let database_path = arguments
+            .required_literal("database_path", state)?
+            .try_bytes_utf8_lossy()
+            .map_err(|_| function::Error::InvalidArgument {
+                keyword: "database_path",
+                value: Value::Null,
+                error: "database_path should be a string",
+            })?
+            .into_owned();
+        let fields = arguments.optional("fields");
+
+        let access = ctx
+            .get_external_context::<GeoipDatabaseAccess>()
+            .copied()
+            .unwrap_or_default();
+        if access == GeoipDatabaseAccess::Denied {
+            return Err(function::Error::InvalidArgument {
+                keyword: "database_path",
+                value: database_path.into(),
+                error: "access to MaxMind databases is denied",
+            }
+            .into());
+        }
+
+        // The database is opened once here so every resolve shares the same reader.
+        let reader = open_reader(&database_path).map_err(|_| function::Error::InvalidArgument {
+            keyword: "database_path",
+            value: database_path.clone().into(),
+            error: "Unable to open MaxMind database",
+        })?;
+
+        Ok(GeoipLookupFn {
+            value,
+            reader,
+            fields,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GeoipLookupFn {
+    value: Box<dyn Expression>,
+    reader: Arc<Reader<Vec<u8>>>,
+    fields: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for GeoipLookupFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let fields = self
+            .fields
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        geoip_lookup(&self.reader, value, fields)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(Collection::any()).or_null().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{CompileConfig, TimeZone};
+    use crate::diagnostic::Span;
+    use crate::value;
+
+    fn test_database() -> String {
+        PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap())
+            .join("tests/data/geoip/test.mmdb")
+            .to_str()
+            .unwrap()
+            .to_owned()
+    }
+
+    fn compile(
+        args: HashMap<&'static str, Value>,
+        access: GeoipDatabaseAccess,
+    ) -> Result<Box<dyn Expression>, String> {
+        let mut config = CompileConfig::default();
+        config.set_custom(access);
+        let mut compile_ctx = FunctionCompileContext::new(Span::new(0, 0), config);
+
+        GeoipLookup
+            .compile(&TypeState::default(), &mut compile_ctx, args.into())
+            .map_err(|err| err.message())
+    }
+
+    fn lookup(args: HashMap<&'static str, Value>) -> Result<Value, String> {
+        let expr = compile(args, GeoipDatabaseAccess::Allowed)?;
+        assert_eq!(
+            expr.type_def(&TypeState::default()),
+            TypeDef::object(Collection::any()).or_null().fallible()
+        );
+
+        let mut target = value!({});
+        let mut runtime_state = state::RuntimeState::default();
+        let tz = TimeZone::default();
+        let mut ctx = Context::new(&mut target, &mut runtime_state, &tz);
+
+        expr.resolve(&mut ctx).map_err(|err| err.to_string())
+    }
+
+    #[test]
+    fn full_record() {
+        assert_eq!(
+            lookup(func_args![ip: "1.1.1.1", database_path: test_database()]),
+            Ok(value!({
+                autonomous_system_number: 13335,
+                autonomous_system_organization: "Cloudflare",
+                country: { iso_code: "AU", names: { en: "Australia" } },
+            }))
+        );
+    }
+
+    #[test]
+    fn selected_fields() {
+        assert_eq!(
+            lookup(func_args![
+                ip: "8.8.8.8",
+                database_path: test_database(),
+                fields: vec!["country.iso_code", "autonomous_system_number"],
+            ]),
+            Ok(value!({
+                "country.iso_code": "US",
+                autonomous_system_number: 15169,
+            }))
+        );
+    }
+
+    #[test]
+    fn missing_field() {
+        assert_eq!(
+            lookup(func_args![
+                ip: "8.8.8.8",
+                database_path: test_database(),
+                fields: vec!["city.names.en"],
+            ]),
+            Ok(value!({ "city.names.en": null }))
+        );
+    }
+
+    #[test]
+    fn address_not_found() {
+        assert_eq!(
+            lookup(func_args![ip: "9.9.9.9", database_path: test_database()]),
+            Ok(value!(null))
+        );
+    }
+
+    #[test]
+    fn invalid_ip() {
+        assert_eq!(
+            lookup(func_args![ip: "not an ip", database_path: test_database()]),
+            Err("unable to parse IP address: invalid IP address syntax".to_owned())
+        );
+    }
+
+    #[test]
+    fn missing_database() {
+        assert_eq!(
+            lookup(func_args![ip: "1.1.1.1", database_path: "/nonexistent/test.mmdb"]),
+            Err("invalid argument".to_owned())
+        );
+    }
+
+    #[test]
+    fn access_denied() {
+        let args = || func_args![ip: "1.1.1.1", database_path: test_database()];
+
+        assert_eq!(
+            compile(args(), GeoipDatabaseAccess::Denied).unwrap_err(),
+            "invalid argument"
+        );
+        assert!(GeoipLookup
+            .compile(
+                &TypeState::default(),
+                &mut FunctionCompileContext::new(Span::new(0, 0), CompileConfig::default()),
+                args().into(),
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn readers_are_shared() {
+        let path = test_database();
+        let reader = open_reader(&path).unwrap();
+
+        assert!(Arc::ptr_eq(&reader, &open_reader(&path).unwrap()));
+    }
+}
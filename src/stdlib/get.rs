@@ -167,5 +167,17 @@ mod tests {
             want: Ok(42),
             tdef: TypeDef::any().fallible(),
         }
+
+        nested_object_and_array {
+            args: func_args![value: value!({"a": [{"b": 1}, {"b": 2}]}), path: value!(["a", 1, "b"])],
+            want: Ok(2),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        out_of_bounds {
+            args: func_args![value: value!({"a": [1]}), path: value!(["a", 5, "b"])],
+            want: Ok(value!(null)),
+            tdef: TypeDef::any().fallible(),
+        }
     ];
 }
@@ -0,0 +1,148 @@
+use crate::compiler::prelude::*;
+
+/// Controls whether `get_env` and `get_env_var` may read the process environment.
+///
+/// Access is denied by default, so that programs can't read secrets from the environment
+/// unless the embedder trusts them. Embedders grant it by registering
+/// `EnvironmentAccess::Allowed` with `CompileConfig::set_custom`; otherwise every `get_env`
+/// and `get_env_var` call errors instead of exposing the environment.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EnvironmentAccess {
+    Allowed,
+    #[default]
+    Denied,
+}
+
+fn get_env(name: Value) -> Resolved {
+    let name = name.try_bytes_utf8_lossy()?;
+    Ok(std::env::var(name.as_ref()).map_or(Value::Null, Into::into))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GetEnv;
+
+impl Function for GetEnv {
+    fn identifier(&self) -> &'static str {
+        "get_env"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "name",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "set variable",
+                source: r#"get_env("HOME") != null"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "unset variable",
+                source: r#"get_env("VRL_UNSET_VARIABLE")"#,
+                result: Ok("null"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let name = arguments.required("name");
+        let access = ctx
+            .get_external_context::<EnvironmentAccess>()
+            .copied()
+            .unwrap_or_default();
+
+        Ok(GetEnvFn { name, access }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GetEnvFn {
+    name: Box<dyn Expression>,
+    access: EnvironmentAccess,
+}
+
+impl FunctionExpression for GetEnvFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let name = self.name.resolve(ctx)?;
+
+        match self.access {
+            EnvironmentAccess::Allowed => get_env(name),
+            EnvironmentAccess::Denied => Err("environment access is denied".into()),
+        }
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes()
+            .or_null()
+            .maybe_fallible(self.access == EnvironmentAccess::Denied)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{CompileConfig, TimeZone};
+    use crate::diagnostic::Span;
+    use crate::value;
+
+    fn resolve(name: &str, access: Option<EnvironmentAccess>) -> (Resolved, TypeDef) {
+        let mut config = CompileConfig::default();
+        if let Some(access) = access {
+            config.set_custom(access);
+        }
+        let mut compile_ctx = FunctionCompileContext::new(Span::new(0, 0), config);
+        let state = TypeState::default();
+        let expr = GetEnv
+            .compile(&state, &mut compile_ctx, func_args![name: name].into())
+            .unwrap();
+
+        let mut target = value!({});
+        let mut runtime_state = state::RuntimeState::default();
+        let tz = TimeZone::default();
+        let mut ctx = Context::new(&mut target, &mut runtime_state, &tz);
+
+        (expr.resolve(&mut ctx), expr.type_def(&state))
+    }
+
+    #[test]
+    fn set() {
+        std::env::set_var("VRL_GET_ENV_SET", "value");
+        let (result, tdef) = resolve("VRL_GET_ENV_SET", Some(EnvironmentAccess::Allowed));
+
+        assert_eq!(result, Ok(value!("value")));
+        assert_eq!(tdef, TypeDef::bytes().or_null().infallible());
+    }
+
+    #[test]
+    fn unset() {
+        let (result, tdef) = resolve("VRL_GET_ENV_UNSET", Some(EnvironmentAccess::Allowed));
+
+        assert_eq!(result, Ok(value!(null)));
+        assert_eq!(tdef, TypeDef::bytes().or_null().infallible());
+    }
+
+    #[test]
+    fn permission_denied() {
+        std::env::set_var("VRL_GET_ENV_DENIED", "secret");
+
+        for access in [None, Some(EnvironmentAccess::Denied)] {
+            let (result, tdef) = resolve("VRL_GET_ENV_DENIED", access);
+
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                "environment access is denied"
+            );
+            assert!(tdef.is_fallible());
+        }
+    }
+}
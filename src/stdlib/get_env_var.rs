@@ -1,3 +1,4 @@
+use super::EnvironmentAccess;
 use crate::compiler::prelude::*;
 
 fn get_env_var(value: Value) -> Resolved {
@@ -34,24 +35,33 @@ impl Function for GetEnvVar {
     fn compile(
         &self,
         _state: &state::TypeState,
-        _ctx: &mut FunctionCompileContext,
+        ctx: &mut FunctionCompileContext,
         arguments: ArgumentList,
     ) -> Compiled {
         let name = arguments.required("name");
+        let access = ctx
+            .get_external_context::<EnvironmentAccess>()
+            .copied()
+            .unwrap_or_default();
 
-        Ok(GetEnvVarFn { name }.as_expr())
+        Ok(GetEnvVarFn { name, access }.as_expr())
     }
 }
 
 #[derive(Debug, Clone)]
 struct GetEnvVarFn {
     name: Box<dyn Expression>,
+    access: EnvironmentAccess,
 }
 
 impl FunctionExpression for GetEnvVarFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.name.resolve(ctx)?;
-        get_env_var(value)
+
+        match self.access {
+            EnvironmentAccess::Allowed => get_env_var(value),
+            EnvironmentAccess::Denied => Err("environment access is denied".into()),
+        }
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
@@ -62,43 +72,63 @@ impl FunctionExpression for GetEnvVarFn {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::compiler::{CompileConfig, TimeZone};
+    use crate::diagnostic::Span;
     use crate::value;
 
-    test_function![
-        get_env_var => GetEnvVar;
-
-        before_each => {
-            std::env::set_var("VAR2", "var");
+    fn resolve(name: &str, access: Option<EnvironmentAccess>) -> Resolved {
+        let mut config = CompileConfig::default();
+        if let Some(access) = access {
+            config.set_custom(access);
         }
+        let mut compile_ctx = FunctionCompileContext::new(Span::new(0, 0), config);
+        let state = TypeState::default();
+        let expr = GetEnvVar
+            .compile(&state, &mut compile_ctx, func_args![name: name].into())
+            .unwrap();
+        assert_eq!(expr.type_def(&state), TypeDef::bytes().fallible());
+
+        let mut target = value!({});
+        let mut runtime_state = state::RuntimeState::default();
+        let tz = TimeZone::default();
+        let mut ctx = Context::new(&mut target, &mut runtime_state, &tz);
+
+        expr.resolve(&mut ctx)
+    }
 
-        doesnt_exist {
-            args: func_args![name: "VAR1"],
-            want: Err("environment variable not found"),
-            tdef: TypeDef::bytes().fallible(),
-        }
+    #[test]
+    fn exists() {
+        std::env::set_var("VRL_GET_ENV_VAR_SET", "var");
 
-        exists {
-            args: func_args![name: "VAR2"],
-            want: Ok(value!("var")),
-            tdef: TypeDef::bytes().fallible(),
-        }
-
-        invalid1 {
-            args: func_args![name: "="],
-            want: Err("environment variable not found"),
-            tdef: TypeDef::bytes().fallible(),
-        }
+        assert_eq!(
+            resolve("VRL_GET_ENV_VAR_SET", Some(EnvironmentAccess::Allowed)),
+            Ok(value!("var"))
+        );
+    }
 
-        invalid2 {
-            args: func_args![name: ""],
-            want: Err("environment variable not found"),
-            tdef: TypeDef::bytes().fallible(),
+    #[test]
+    fn doesnt_exist() {
+        for name in ["VRL_GET_ENV_VAR_UNSET", "=", "", "a=b"] {
+            assert_eq!(
+                resolve(name, Some(EnvironmentAccess::Allowed))
+                    .unwrap_err()
+                    .to_string(),
+                "environment variable not found"
+            );
         }
+    }
 
-        invalid3 {
-            args: func_args![name: "a=b"],
-            want: Err("environment variable not found"),
-            tdef: TypeDef::bytes().fallible(),
+    #[test]
+    fn permission_denied() {
+        std::env::set_var("VRL_GET_ENV_VAR_DENIED", "secret");
+
+        for access in [None, Some(EnvironmentAccess::Denied)] {
+            assert_eq!(
+                resolve("VRL_GET_ENV_VAR_DENIED", access)
+                    .unwrap_err()
+                    .to_string(),
+                "environment access is denied"
+            );
         }
-    ];
+    }
 }
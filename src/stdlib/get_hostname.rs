@@ -1,11 +1,34 @@
 use crate::compiler::prelude::*;
+#[cfg(not(target_arch = "wasm32"))]
+use once_cell::sync::OnceCell;
+
+/// Controls whether `get_hostname` and `hostname` may read the hostname of the machine.
+///
+/// Like `EnvironmentAccess`, access is denied by default, so untrusted programs can't
+/// fingerprint the infrastructure they run on. Embedders allow it by registering
+/// `HostnameAccess::Allowed` with `CompileConfig::set_custom`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HostnameAccess {
+    Allowed,
+    #[default]
+    Denied,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+static HOSTNAME: OnceCell<String> = OnceCell::new();
 
 #[cfg(not(target_arch = "wasm32"))]
 fn get_hostname() -> Resolved {
-    Ok(hostname::get()
-        .map_err(|error| format!("failed to get hostname: {error}"))?
-        .to_string_lossy()
-        .into())
+    // The hostname is looked up once per process, as it rarely changes and the lookup is a
+    // system call.
+    HOSTNAME
+        .get_or_try_init(|| {
+            hostname::get()
+                .map(|hostname| hostname.to_string_lossy().into_owned())
+                .map_err(|error| format!("failed to get hostname: {error}"))
+        })
+        .map(|hostname| hostname.as_str().into())
+        .map_err(Into::into)
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -20,10 +43,15 @@ impl Function for GetHostname {
     fn compile(
         &self,
         _state: &state::TypeState,
-        _ctx: &mut FunctionCompileContext,
+        ctx: &mut FunctionCompileContext,
         _: ArgumentList,
     ) -> Compiled {
-        Ok(GetHostnameFn.as_expr())
+        let access = ctx
+            .get_external_context::<HostnameAccess>()
+            .copied()
+            .unwrap_or_default();
+
+        Ok(GetHostnameFn { access }.as_expr())
     }
 
     #[cfg(target_arch = "wasm32")]
@@ -45,17 +73,101 @@ impl Function for GetHostname {
     }
 }
 
+/// The same as `get_hostname`, under the name most languages use.
+#[derive(Clone, Copy, Debug)]
+pub struct Hostname;
+
+impl Function for Hostname {
+    fn identifier(&self) -> &'static str {
+        "hostname"
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        GetHostname.compile(state, ctx, arguments)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "valid",
+            source: r"hostname!() == get_hostname!()",
+            result: Ok("true"),
+        }]
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 #[derive(Debug, Clone)]
-struct GetHostnameFn;
+struct GetHostnameFn {
+    access: HostnameAccess,
+}
 
 #[cfg(not(target_arch = "wasm32"))]
 impl FunctionExpression for GetHostnameFn {
     fn resolve(&self, _: &mut Context) -> Resolved {
-        get_hostname()
+        match self.access {
+            HostnameAccess::Allowed => get_hostname(),
+            HostnameAccess::Denied => Err("hostname access is denied".into()),
+        }
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
         TypeDef::bytes().fallible()
     }
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::compiler::{CompileConfig, TimeZone};
+    use crate::diagnostic::Span;
+    use crate::value;
+
+    fn resolve(access: Option<HostnameAccess>) -> Resolved {
+        let mut config = CompileConfig::default();
+        if let Some(access) = access {
+            config.set_custom(access);
+        }
+        let mut compile_ctx = FunctionCompileContext::new(Span::new(0, 0), config);
+        let state = TypeState::default();
+        let expr = GetHostname
+            .compile(&state, &mut compile_ctx, func_args![].into())
+            .unwrap();
+
+        let mut target = value!({});
+        let mut runtime_state = state::RuntimeState::default();
+        let tz = TimeZone::default();
+        let mut ctx = Context::new(&mut target, &mut runtime_state, &tz);
+
+        expr.resolve(&mut ctx)
+    }
+
+    #[test]
+    fn permission_allowed() {
+        let hostname = resolve(Some(HostnameAccess::Allowed)).unwrap();
+        assert!(!hostname.as_bytes().unwrap().is_empty());
+        assert_eq!(resolve(Some(HostnameAccess::Allowed)).unwrap(), hostname);
+    }
+
+    #[test]
+    fn permission_denied() {
+        assert_eq!(
+            resolve(Some(HostnameAccess::Denied))
+                .unwrap_err()
+                .to_string(),
+            "hostname access is denied"
+        );
+    }
+
+    #[test]
+    fn denied_by_default() {
+        assert_eq!(
+            resolve(None).unwrap_err().to_string(),
+            "hostname access is denied"
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use crate::compiler::prelude::*;
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use super::util::date_in_timezone;
+
+/// Parses the optional `holidays` argument, an array of `YYYY-MM-DD` date strings.
+pub(super) fn parse_holidays(holidays: Option<Value>) -> Result<Vec<NaiveDate>, ExpressionError> {
+    let Some(holidays) = holidays else {
+        return Ok(Vec::new());
+    };
+
+    holidays
+        .try_array()?
+        .into_iter()
+        .map(|holiday| {
+            let holiday = holiday.try_bytes_utf8_lossy()?;
+            NaiveDate::parse_from_str(&holiday, "%Y-%m-%d").map_err(|_| {
+                format!("invalid holiday date '{holiday}': expected YYYY-MM-DD").into()
+            })
+        })
+        .collect()
+}
+
+/// A business day is any weekday that is not listed in `holidays`.
+pub(super) fn is_business_date(date: NaiveDate, holidays: &[NaiveDate]) -> bool {
+    !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !holidays.contains(&date)
+}
+
+fn is_business_day(value: Value, holidays: Option<Value>, timezone: Option<Value>) -> Resolved {
+    let date = date_in_timezone(value.try_timestamp()?, timezone)?;
+    let holidays = parse_holidays(holidays)?;
+
+    Ok(is_business_date(date, &holidays).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct IsBusinessDay;
+
+impl Function for IsBusinessDay {
+    fn identifier(&self) -> &'static str {
+        "is_business_day"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::TIMESTAMP,
+                required: true,
+            },
+            Parameter {
+                keyword: "holidays",
+                kind: kind::ARRAY,
+                required: false,
+            },
+            Parameter {
+                keyword: "timezone",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "weekday",
+                source: "is_business_day!(t'2021-02-10T12:00:00Z')",
+                result: Ok("true"),
+            },
+            Example {
+                title: "weekend",
+                source: "is_business_day!(t'2021-02-13T12:00:00Z')",
+                result: Ok("false"),
+            },
+            Example {
+                title: "holiday",
+                source: r#"is_business_day!(t'2021-12-24T12:00:00Z', holidays: ["2021-12-24"])"#,
+                result: Ok("false"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let holidays = arguments.optional("holidays");
+        let timezone = arguments.optional("timezone");
+
+        Ok(IsBusinessDayFn {
+            value,
+            holidays,
+            timezone,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IsBusinessDayFn {
+    value: Box<dyn Expression>,
+    holidays: Option<Box<dyn Expression>>,
+    timezone: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for IsBusinessDayFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let holidays = self
+            .holidays
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+        let timezone = self
+            .timezone
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        is_business_day(value, holidays, timezone)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::boolean().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    test_function![
+        is_business_day => IsBusinessDay;
+
+        friday {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 2, 12, 12, 0, 0).unwrap()],
+            want: Ok(true),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        saturday {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 2, 13, 12, 0, 0).unwrap()],
+            want: Ok(false),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        holiday {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 2, 15, 12, 0, 0).unwrap(),
+                             holidays: vec!["2021-01-01", "2021-02-15"]],
+            want: Ok(false),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        timezone_shifts_into_weekend {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 2, 12, 23, 0, 0).unwrap(),
+                             timezone: "Asia/Tokyo"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        invalid_holiday {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 2, 12, 12, 0, 0).unwrap(),
+                             holidays: vec!["15/02/2021"]],
+            want: Err("invalid holiday date '15/02/2021': expected YYYY-MM-DD"),
+            tdef: TypeDef::boolean().fallible(),
+        }
+    ];
+}
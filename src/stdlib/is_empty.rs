@@ -5,12 +5,14 @@ fn is_empty(value: Value) -> Resolved {
         Value::Object(v) => v.is_empty(),
         Value::Array(v) => v.is_empty(),
         Value::Bytes(v) => v.is_empty(),
+        Value::Null => true,
         value => {
             return Err(ValueError::Expected {
                 got: value.kind(),
                 expected: Kind::array(Collection::any())
                     | Kind::object(Collection::any())
-                    | Kind::bytes(),
+                    | Kind::bytes()
+                    | Kind::null(),
             }
             .into())
         }
@@ -27,10 +29,18 @@ impl Function for IsEmpty {
         "is_empty"
     }
 
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Returns whether a string, array or object has no characters, elements or fields.
+            `null` is considered empty. Other types, such as numbers and booleans, have no
+            notion of emptiness and are rejected.
+        "}
+    }
+
     fn parameters(&self) -> &'static [Parameter] {
         &[Parameter {
             keyword: "value",
-            kind: kind::OBJECT | kind::ARRAY | kind::BYTES,
+            kind: kind::OBJECT | kind::ARRAY | kind::BYTES | kind::NULL,
             required: true,
         }]
     }
@@ -52,6 +62,11 @@ impl Function for IsEmpty {
                 source: "is_empty({})",
                 result: Ok("true"),
             },
+            Example {
+                title: "null",
+                source: "is_empty(null)",
+                result: Ok("true"),
+            },
             Example {
                 title: "non-empty array",
                 source: "is_empty([1,2,3])",
@@ -131,5 +146,17 @@ mod tests {
             want: Ok(value!(false)),
             tdef: TypeDef::boolean().infallible(),
         }
+
+        null {
+            args: func_args![value: value!(null)],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        integer {
+            args: func_args![value: 0],
+            want: Err("expected string, null, array or object, got integer"),
+            tdef: TypeDef::boolean().infallible(),
+        }
     ];
 }
@@ -0,0 +1,101 @@
+use crate::compiler::prelude::*;
+
+fn is_valid_utf8(value: Value) -> Resolved {
+    let bytes = value.try_bytes()?;
+
+    Ok(std::str::from_utf8(&bytes).is_ok().into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct IsValidUtf8;
+
+impl Function for IsValidUtf8 {
+    fn identifier(&self) -> &'static str {
+        "is_valid_utf8"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "valid",
+                source: r#"is_valid_utf8("ñandú")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "invalid",
+                source: r#"is_valid_utf8(decode_base16!("62ff6172"))"#,
+                result: Ok("false"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(IsValidUtf8Fn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IsValidUtf8Fn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for IsValidUtf8Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        is_valid_utf8(value)
+    }
+
+    fn type_def(&self, _state: &state::TypeState) -> TypeDef {
+        TypeDef::boolean().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        is_valid_utf8 => IsValidUtf8;
+
+        ascii {
+            args: func_args![value: value!("bar")],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        multi_byte {
+            args: func_args![value: value!("ñandú")],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        invalid {
+            args: func_args![value: value!(b"b\xFFar")],
+            want: Ok(value!(false)),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        truncated_sequence {
+            args: func_args![value: value!(b"caf\xC3")],
+            want: Ok(value!(false)),
+            tdef: TypeDef::boolean().infallible(),
+        }
+    ];
+}
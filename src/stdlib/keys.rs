@@ -34,6 +34,11 @@ impl Function for Keys {
                 source: r#"keys({"key1": "val1", "key2": {"nestedkey1": "val3", "nestedkey2": "val4"}})"#,
                 result: Ok(r#"["key1", "key2"]"#),
             },
+            Example {
+                title: "get keys from an empty object",
+                source: "keys({})",
+                result: Ok("[]"),
+            },
         ]
     }
 
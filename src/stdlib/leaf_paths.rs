@@ -0,0 +1,118 @@
+use crate::compiler::prelude::*;
+
+fn leaf_paths(value: &Value) -> Value {
+    let mut leaves = Vec::new();
+    collect_leaves(value, "", &mut leaves);
+    Value::Array(leaves.into_iter().map(|(path, _)| path.into()).collect())
+}
+
+/// Collects every scalar leaf beneath `value` along with its path, written as `a[0].b`.
+/// Empty objects and arrays hold no leaves and so contribute nothing.
+pub(super) fn collect_leaves(value: &Value, prefix: &str, leaves: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let path = if prefix.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                collect_leaves(value, &path, leaves);
+            }
+        }
+        Value::Array(array) => {
+            for (index, value) in array.iter().enumerate() {
+                collect_leaves(value, &format!("{prefix}[{index}]"), leaves);
+            }
+        }
+        value => leaves.push((prefix.to_owned(), value.clone())),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct LeafPaths;
+
+impl Function for LeafPaths {
+    fn identifier(&self) -> &'static str {
+        "leaf_paths"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "nested object",
+                source: r#"leaf_paths({"a": {"b": 1, "c": [true, {"d": null}]}})"#,
+                result: Ok(r#"["a.b", "a.c[0]", "a.c[1].d"]"#),
+            },
+            Example {
+                title: "array",
+                source: "leaf_paths([1, [2]])",
+                result: Ok(r#"["[0]", "[1][0]"]"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(LeafPathsFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct LeafPathsFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for LeafPathsFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        Ok(leaf_paths(&value))
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::array(Collection::from_unknown(Kind::bytes())).infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        leaf_paths => LeafPaths;
+
+        nested_object_with_array {
+            args: func_args![value: value!({"a": {"b": 1, "c": [{"d": "x"}, {"d": "y", "e": [true]}]}, "f": null})],
+            want: Ok(value!(["a.b", "a.c[0].d", "a.c[1].d", "a.c[1].e[0]", "f"])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())).infallible(),
+        }
+
+        top_level_array {
+            args: func_args![value: value!([1, [2, 3]])],
+            want: Ok(value!(["[0]", "[1][0]", "[1][1]"])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())).infallible(),
+        }
+
+        empty_containers {
+            args: func_args![value: value!({"a": {}, "b": []})],
+            want: Ok(value!([])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())).infallible(),
+        }
+    ];
+}
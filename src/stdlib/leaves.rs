@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+
+use super::leaf_paths::collect_leaves;
+use crate::compiler::prelude::*;
+
+fn leaves(value: &Value) -> Value {
+    let mut leaves = Vec::new();
+    collect_leaves(value, "", &mut leaves);
+    Value::Array(
+        leaves
+            .into_iter()
+            .map(|(path, value)| Value::Array(vec![path.into(), value]))
+            .collect(),
+    )
+}
+
+fn pair_kind() -> Kind {
+    Kind::array(BTreeMap::from([
+        (Index::from(0), Kind::bytes()),
+        (Index::from(1), Kind::any().without_array().without_object()),
+    ]))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Leaves;
+
+impl Function for Leaves {
+    fn identifier(&self) -> &'static str {
+        "leaves"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "nested object",
+            source: r#"leaves({"a": {"b": 1, "c": [true, {"d": null}]}})"#,
+            result: Ok(r#"[["a.b", 1], ["a.c[0]", true], ["a.c[1].d", null]]"#),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(LeavesFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct LeavesFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for LeavesFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        Ok(leaves(&value))
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::array(Collection::from_unknown(pair_kind())).infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        leaves => Leaves;
+
+        nested_object_with_array {
+            args: func_args![value: value!({"a": {"b": 1, "c": [{"d": "x"}, {"e": [true]}]}})],
+            want: Ok(value!([["a.b", 1], ["a.c[0].d", "x"], ["a.c[1].e[0]", true]])),
+            tdef: TypeDef::array(Collection::from_unknown(pair_kind())).infallible(),
+        }
+
+        empty {
+            args: func_args![value: value!({})],
+            want: Ok(value!([])),
+            tdef: TypeDef::array(Collection::from_unknown(pair_kind())).infallible(),
+        }
+    ];
+}
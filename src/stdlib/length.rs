@@ -1,10 +1,15 @@
 use crate::compiler::prelude::*;
+use crate::value;
 
-fn length(value: Value) -> Resolved {
+fn length(value: Value, unit: &Bytes) -> Resolved {
     match value {
         Value::Array(v) => Ok(v.len().into()),
         Value::Object(v) => Ok(v.len().into()),
-        Value::Bytes(v) => Ok(v.len().into()),
+        Value::Bytes(v) => match unit.as_ref() {
+            b"bytes" => Ok(v.len().into()),
+            b"chars" => Ok(String::from_utf8_lossy(&v).chars().count().into()),
+            _ => unreachable!("enum invariant"),
+        },
         value => Err(ValueError::Expected {
             got: value.kind(),
             expected: Kind::array(Collection::any())
@@ -15,6 +20,10 @@ fn length(value: Value) -> Resolved {
     }
 }
 
+fn units() -> Vec<Value> {
+    vec![value!("bytes"), value!("chars")]
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct Length;
 
@@ -24,11 +33,18 @@ impl Function for Length {
     }
 
     fn parameters(&self) -> &'static [Parameter] {
-        &[Parameter {
-            keyword: "value",
-            kind: kind::ARRAY | kind::OBJECT | kind::BYTES,
-            required: true,
-        }]
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ARRAY | kind::OBJECT | kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "unit",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
     }
 
     fn examples(&self) -> &'static [Example] {
@@ -48,31 +64,42 @@ impl Function for Length {
                 source: r#"length("foobar")"#,
                 result: Ok("6"),
             },
+            Example {
+                title: "string in characters",
+                source: r#"length("café", unit: "chars")"#,
+                result: Ok("4"),
+            },
         ]
     }
 
     fn compile(
         &self,
-        _state: &state::TypeState,
+        state: &state::TypeState,
         _ctx: &mut FunctionCompileContext,
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
+        let unit = arguments
+            .optional_enum("unit", &units(), state)?
+            .unwrap_or_else(|| value!("bytes"))
+            .try_bytes()
+            .expect("unit not bytes");
 
-        Ok(LengthFn { value }.as_expr())
+        Ok(LengthFn { value, unit }.as_expr())
     }
 }
 
 #[derive(Debug, Clone)]
 struct LengthFn {
     value: Box<dyn Expression>,
+    unit: Bytes,
 }
 
 impl FunctionExpression for LengthFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
 
-        length(value)
+        length(value, &self.unit)
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
@@ -83,7 +110,6 @@ impl FunctionExpression for LengthFn {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::value;
 
     test_function![
         length => Length;
@@ -123,5 +149,29 @@ mod tests {
             want: Ok(value!(11)),
             tdef: TypeDef::integer().infallible(),
         }
+
+        multi_byte_string_bytes {
+            args: func_args![value: value!("München"), unit: "bytes"],
+            want: Ok(value!(8)),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        multi_byte_string_chars {
+            args: func_args![value: value!("München"), unit: "chars"],
+            want: Ok(value!(7)),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        multi_byte_string_default_unit {
+            args: func_args![value: value!("café")],
+            want: Ok(value!(5)),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        array_ignores_unit {
+            args: func_args![value: value!(["café", "München"]), unit: "chars"],
+            want: Ok(value!(2)),
+            tdef: TypeDef::integer().infallible(),
+        }
     ];
 }
@@ -264,6 +264,30 @@ pub(crate) fn log_fields(
         .into())
 }
 
+/// Attempts to extract log fields from each of the list of regexes, keeping every field as
+/// the string it was captured as.
+pub(crate) fn parse_message_raw(
+    regexes: &Vec<Regex>,
+    message: &str,
+    log_type: &str,
+) -> std::result::Result<Value, String> {
+    for regex in regexes {
+        if let Some(captures) = regex.captures(message) {
+            return Ok(regex
+                .capture_names()
+                .flatten()
+                .filter_map(|name| {
+                    captures
+                        .name(name)
+                        .map(|value| (name.to_string().into(), value.as_str().into()))
+                })
+                .collect::<ObjectMap>()
+                .into());
+        }
+    }
+    Err(format!("failed parsing {log_type} log line"))
+}
+
 /// Attempts to extract log fields from each of the list of regexes
 pub(crate) fn parse_message(
     regexes: &Vec<Regex>,
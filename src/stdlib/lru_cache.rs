@@ -0,0 +1,222 @@
+//! A bounded least-recently-used cache for functions that reuse work across events.
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex, TryLockError};
+
+/// Marks the end of the list of entries.
+const NIL: usize = usize::MAX;
+
+/// A least-recently-used cache holding at most `capacity` entries, with constant time lookups,
+/// insertions and evictions.
+///
+/// Entries live in a slab and are linked from the most to the least recently used one, so a
+/// hit only relinks its entry and an eviction reuses the slot of the last one.
+#[derive(Debug)]
+pub(crate) struct LruCache<K, V> {
+    capacity: usize,
+    slots: HashMap<K, usize>,
+    entries: Vec<Entry<K, V>>,
+    /// The most recently used entry.
+    head: usize,
+    /// The least recently used entry, the next one to be evicted.
+    tail: usize,
+}
+
+#[derive(Debug)]
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    prev: usize,
+    next: usize,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be positive");
+
+        Self {
+            capacity,
+            slots: HashMap::new(),
+            entries: Vec::new(),
+            head: NIL,
+            tail: NIL,
+        }
+    }
+
+    /// Returns the value cached for `key`, marking it as the most recently used.
+    pub(crate) fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let slot = *self.slots.get(key)?;
+        self.unlink(slot);
+        self.push_front(slot);
+        Some(&self.entries[slot].value)
+    }
+
+    /// Caches `value` for `key`, evicting the least recently used entry when full.
+    pub(crate) fn insert(&mut self, key: K, value: V) {
+        if let Some(&slot) = self.slots.get(&key) {
+            self.entries[slot].value = value;
+            self.unlink(slot);
+            self.push_front(slot);
+            return;
+        }
+
+        let slot = if self.entries.len() < self.capacity {
+            self.entries.push(Entry {
+                key: key.clone(),
+                value,
+                prev: NIL,
+                next: NIL,
+            });
+            self.entries.len() - 1
+        } else {
+            let slot = self.tail;
+            self.unlink(slot);
+            let entry = &mut self.entries[slot];
+            self.slots.remove(&entry.key);
+            entry.key = key.clone();
+            entry.value = value;
+            slot
+        };
+
+        self.slots.insert(key, slot);
+        self.push_front(slot);
+    }
+
+    fn unlink(&mut self, slot: usize) {
+        let Entry { prev, next, .. } = self.entries[slot];
+        match prev {
+            NIL => self.head = next,
+            prev => self.entries[prev].next = next,
+        }
+        match next {
+            NIL => self.tail = prev,
+            next => self.entries[next].prev = prev,
+        }
+    }
+
+    fn push_front(&mut self, slot: usize) {
+        let head = self.head;
+        self.entries[slot].prev = NIL;
+        self.entries[slot].next = head;
+        match head {
+            NIL => self.tail = slot,
+            head => self.entries[head].prev = slot,
+        }
+        self.head = slot;
+    }
+}
+
+/// An [`LruCache`] shared by the clones of a function call site, and so by every thread
+/// running its program.
+///
+/// Calls never wait for each other: when another thread is using the cache, lookups miss and
+/// insertions are dropped, so the caller does the work uncached instead of blocking.
+#[derive(Debug)]
+pub(crate) struct SharedLruCache<K, V>(Arc<Mutex<LruCache<K, V>>>);
+
+impl<K, V> Clone for SharedLruCache<K, V> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> SharedLruCache<K, V> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self(Arc::new(Mutex::new(LruCache::new(capacity))))
+    }
+
+    pub(crate) fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.try_with(|cache| cache.get(key).cloned()).flatten()
+    }
+
+    pub(crate) fn insert(&self, key: K, value: V) {
+        self.try_with(|cache| cache.insert(key, value));
+    }
+
+    fn try_with<T>(&self, f: impl FnOnce(&mut LruCache<K, V>) -> T) -> Option<T> {
+        match self.0.try_lock() {
+            Ok(mut cache) => Some(f(&mut cache)),
+            // The cache is always left consistent, so a panic elsewhere doesn't spoil it.
+            Err(TryLockError::Poisoned(poisoned)) => Some(f(&mut poisoned.into_inner())),
+            Err(TryLockError::WouldBlock) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        cache.insert("a".to_owned(), 1);
+        cache.insert("b".to_owned(), 2);
+        assert_eq!(cache.get("a"), Some(&1));
+
+        cache.insert("c".to_owned(), 3);
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.get("a"), Some(&1));
+        assert_eq!(cache.get("c"), Some(&3));
+
+        cache.insert("a".to_owned(), 4);
+        cache.insert("d".to_owned(), 5);
+        assert_eq!(cache.get("c"), None);
+        assert_eq!(cache.get("a"), Some(&4));
+        assert_eq!(cache.get("d"), Some(&5));
+    }
+
+    #[test]
+    fn matches_reference_model() {
+        let capacity = 8;
+        let mut cache = LruCache::new(capacity);
+        // The keys from the least to the most recently used.
+        let mut model: Vec<(u64, u64)> = Vec::new();
+
+        let mut seed = 42_u64;
+        for i in 0..10_000 {
+            seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+            let key = (seed >> 33) % 16;
+
+            if seed & 1 == 0 {
+                let want = model.iter().position(|(k, _)| *k == key).map(|index| {
+                    let entry = model.remove(index);
+                    model.push(entry);
+                    entry.1
+                });
+                assert_eq!(cache.get(&key).copied(), want);
+            } else {
+                cache.insert(key, i);
+                model.retain(|(k, _)| *k != key);
+                if model.len() == capacity {
+                    model.remove(0);
+                }
+                model.push((key, i));
+            }
+        }
+    }
+
+    #[test]
+    fn shared_cache_skips_while_locked() {
+        let cache = SharedLruCache::new(2);
+        cache.insert("a".to_owned(), 1);
+
+        let guard = cache.0.lock().unwrap();
+        assert_eq!(cache.clone().get("a"), None);
+        cache.insert("b".to_owned(), 2);
+        drop(guard);
+
+        assert_eq!(cache.get("a"), Some(1));
+        assert_eq!(cache.get("b"), None);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::compiler::prelude::*;
+
+fn luhn_check(value: Value, strict: bool) -> Resolved {
+    let value = value.try_bytes()?;
+
+    let mut digits = Vec::with_capacity(value.len());
+    for byte in &value {
+        match byte {
+            b'0'..=b'9' => digits.push(u32::from(byte - b'0')),
+            _ if strict => {
+                return Err(format!("invalid character {:?} in value", char::from(*byte)).into())
+            }
+            _ => {}
+        }
+    }
+
+    Ok(is_luhn_valid(&digits).into())
+}
+
+/// Whether the digits end with a valid Luhn check digit.
+pub(crate) fn is_luhn_valid(digits: &[u32]) -> bool {
+    // A single digit is only ever its own check digit, so it isn't a meaningful number.
+    if digits.len() < 2 {
+        return false;
+    }
+
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(idx, &digit)| {
+            if idx % 2 == 1 {
+                let doubled = digit * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                digit
+            }
+        })
+        .sum();
+
+    sum % 10 == 0
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct LuhnCheck;
+
+impl Function for LuhnCheck {
+    fn identifier(&self) -> &'static str {
+        "luhn_check"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "strict",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "valid card number",
+                source: r#"luhn_check("4111 1111 1111 1111")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "invalid card number",
+                source: r#"luhn_check("4111 1111 1111 1112")"#,
+                result: Ok("false"),
+            },
+            Example {
+                title: "strict mode",
+                source: r#"luhn_check!("4111-1111-1111-1111", strict: true)"#,
+                result: Err(
+                    r#"function call error for "luhn_check" at (0:48): invalid character '-' in value"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let strict = arguments.optional("strict").unwrap_or_else(|| expr!(false));
+
+        Ok(LuhnCheckFn { value, strict }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct LuhnCheckFn {
+    value: Box<dyn Expression>,
+    strict: Box<dyn Expression>,
+}
+
+impl FunctionExpression for LuhnCheckFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let strict = self.strict.resolve(ctx)?.try_boolean()?;
+
+        luhn_check(value, strict)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        // Only strict mode rejects values, so the call can't fail when it's known to be off.
+        let lenient = matches!(
+            self.strict.resolve_constant(state),
+            Some(Value::Boolean(false))
+        );
+
+        TypeDef::boolean().maybe_fallible(!lenient)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        luhn_check => LuhnCheck;
+
+        valid {
+            args: func_args![value: "4111111111111111"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        valid_with_separators {
+            args: func_args![value: "5500-0000-0000-0004"],
+            want: Ok(true),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        invalid {
+            args: func_args![value: "4111111111111112"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        too_short {
+            args: func_args![value: "0"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        no_digits {
+            args: func_args![value: "card"],
+            want: Ok(false),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        strict_valid {
+            args: func_args![value: "79927398713", strict: true],
+            want: Ok(true),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        explicitly_lenient {
+            args: func_args![value: "4111 1111 1111 1111", strict: false],
+            want: Ok(true),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        strict_rejects_separators {
+            args: func_args![value: "4111 1111 1111 1111", strict: true],
+            want: Err("invalid character ' ' in value"),
+            tdef: TypeDef::boolean().fallible(),
+        }
+    ];
+}
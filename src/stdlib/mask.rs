@@ -0,0 +1,228 @@
+use crate::compiler::prelude::*;
+
+fn mask(
+    value: Value,
+    keep_start: Value,
+    keep_end: Value,
+    mask_char: Value,
+    mask_short: Value,
+) -> Resolved {
+    let value = value.try_bytes_utf8_lossy()?;
+    let keep_start = usize::try_from(keep_start.try_integer()?)
+        .map_err(|_| "keep_start must be a non-negative integer")?;
+    let keep_end = usize::try_from(keep_end.try_integer()?)
+        .map_err(|_| "keep_end must be a non-negative integer")?;
+    let mask_char = mask_char.try_bytes_utf8_lossy()?;
+    let mask_short = mask_short.try_boolean()?;
+
+    let mut chars = mask_char.chars();
+    let (Some(mask_char), None) = (chars.next(), chars.next()) else {
+        return Err("mask_char must be a single character".into());
+    };
+
+    let len = value.chars().count();
+    if len <= keep_start.saturating_add(keep_end) {
+        // Nothing would be left to mask, so either leave the string alone or hide all of it.
+        let masked = if mask_short {
+            std::iter::repeat(mask_char).take(len).collect()
+        } else {
+            value.into_owned()
+        };
+        return Ok(masked.into());
+    }
+
+    let masked: String = value
+        .chars()
+        .enumerate()
+        .map(|(idx, c)| {
+            if idx < keep_start || idx >= len - keep_end {
+                c
+            } else {
+                mask_char
+            }
+        })
+        .collect();
+
+    Ok(masked.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Mask;
+
+impl Function for Mask {
+    fn identifier(&self) -> &'static str {
+        "mask"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "keep_start",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "keep_end",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "mask_char",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "mask_short",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "card number",
+                source: r#"mask!("4111111111111111", keep_start: 4, keep_end: 4)"#,
+                result: Ok("4111********1111"),
+            },
+            Example {
+                title: "custom mask character",
+                source: r#"mask!("secret-token", keep_end: 2, mask_char: "x")"#,
+                result: Ok("xxxxxxxxxxen"),
+            },
+            Example {
+                title: "short string",
+                source: r#"mask!("1234", keep_start: 4, keep_end: 4, mask_short: true)"#,
+                result: Ok("****"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let keep_start = arguments.optional("keep_start").unwrap_or(expr!(0));
+        let keep_end = arguments.optional("keep_end").unwrap_or(expr!(0));
+        let mask_char = arguments.optional("mask_char").unwrap_or(expr!("*"));
+        let mask_short = arguments.optional("mask_short").unwrap_or(expr!(false));
+
+        Ok(MaskFn {
+            value,
+            keep_start,
+            keep_end,
+            mask_char,
+            mask_short,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MaskFn {
+    value: Box<dyn Expression>,
+    keep_start: Box<dyn Expression>,
+    keep_end: Box<dyn Expression>,
+    mask_char: Box<dyn Expression>,
+    mask_short: Box<dyn Expression>,
+}
+
+impl FunctionExpression for MaskFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let keep_start = self.keep_start.resolve(ctx)?;
+        let keep_end = self.keep_end.resolve(ctx)?;
+        let mask_char = self.mask_char.resolve(ctx)?;
+        let mask_short = self.mask_short.resolve(ctx)?;
+
+        mask(value, keep_start, keep_end, mask_char, mask_short)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        mask => Mask;
+
+        mask_all {
+            args: func_args![value: "secret"],
+            want: Ok("******"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        keep_start_and_end {
+            args: func_args![value: "4111111111111111", keep_start: 4, keep_end: 4],
+            want: Ok("4111********1111"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        keep_end_only {
+            args: func_args![value: "4111111111111111", keep_end: 4],
+            want: Ok("************1111"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        custom_mask_char {
+            args: func_args![value: "password", keep_start: 1, mask_char: "x"],
+            want: Ok("pxxxxxxx"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        multi_byte_by_character {
+            args: func_args![value: "Müllerstraße", keep_start: 2, keep_end: 2, mask_char: "•"],
+            want: Ok("Mü••••••••ße"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        short_string_unmasked {
+            args: func_args![value: "1234", keep_start: 4, keep_end: 4],
+            want: Ok("1234"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        short_string_masked {
+            args: func_args![value: "1234", keep_start: 4, keep_end: 4, mask_short: true],
+            want: Ok("****"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        exactly_kept_length {
+            args: func_args![value: "abcd", keep_start: 2, keep_end: 2],
+            want: Ok("abcd"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        negative_keep_start {
+            args: func_args![value: "abcd", keep_start: -2],
+            want: Err("keep_start must be a non-negative integer"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        negative_keep_end {
+            args: func_args![value: "abcd", keep_end: -1],
+            want: Err("keep_end must be a non-negative integer"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_mask_char {
+            args: func_args![value: "abcd", mask_char: "**"],
+            want: Err("mask_char must be a single character"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -1,11 +1,38 @@
+use std::sync::Arc;
+
+use super::lru_cache::SharedLruCache;
 use crate::compiler::prelude::*;
 use regex::bytes::RegexSet;
 
+/// The number of regex sets kept by each `match_any` call site with runtime patterns.
+const CACHE_CAPACITY: usize = 64;
+
+/// Regex sets built from runtime patterns, keyed by the sources of the patterns.
+type RegexSetCache = SharedLruCache<Vec<String>, Arc<RegexSet>>;
+
 fn match_any(value: Value, pattern: &RegexSet) -> Resolved {
     let bytes = value.try_bytes()?;
     Ok(pattern.is_match(&bytes).into())
 }
 
+/// Builds a [`RegexSet`] from regex values that are only known at runtime, such as those
+/// produced by `to_regex`, reusing the set built for the same patterns by an earlier event.
+fn regex_set(patterns: Value, cache: &RegexSetCache) -> Result<Arc<RegexSet>, ExpressionError> {
+    let mut re_strings = Vec::new();
+    for pattern in patterns.try_array()? {
+        re_strings.push(pattern.try_regex()?.to_string());
+    }
+    if let Some(regex_set) = cache.get(&re_strings) {
+        return Ok(regex_set);
+    }
+
+    let regex_set = RegexSet::new(&re_strings)
+        .map(Arc::new)
+        .map_err(|err| format!("could not create regex set: {err}"))?;
+    cache.insert(re_strings, Arc::clone(&regex_set));
+    Ok(regex_set)
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct MatchAny;
 
@@ -41,6 +68,11 @@ impl Function for MatchAny {
                 source: r#"match_any("My name is John Doe", patterns: [r'\d+', r'Jane'])"#,
                 result: Ok("false"),
             },
+            Example {
+                title: "runtime patterns",
+                source: r#"match_any!("FOO BAR", patterns: [to_regex!("baz"), to_regex!("foo", flags: "i")])"#,
+                result: Ok("true"),
+            },
         ]
     }
 
@@ -51,18 +83,21 @@ impl Function for MatchAny {
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
-        let patterns = arguments.required_array("patterns")?;
-
-        let mut re_strings = Vec::with_capacity(patterns.len());
-        for expr in patterns {
-            let value =
-                expr.resolve_constant(state)
-                    .ok_or(function::Error::ExpectedStaticExpression {
-                        keyword: "patterns",
-                        expr,
-                    })?;
-
-            let re = value
+        let patterns = arguments.required("patterns");
+
+        // Patterns that aren't all literals, e.g. built with `to_regex`, are compiled at runtime.
+        let Some(constant) = patterns.resolve_constant(state) else {
+            return Ok(MatchAnyDynamicFn {
+                value,
+                patterns,
+                cache: RegexSetCache::new(CACHE_CAPACITY),
+            }
+            .as_expr());
+        };
+
+        let mut re_strings = Vec::new();
+        for pattern in constant.try_array().expect("patterns is an array") {
+            let re = pattern
                 .try_regex()
                 .map_err(|e| Box::new(e) as Box<dyn DiagnosticMessage>)?;
             re_strings.push(re.to_string());
@@ -91,6 +126,27 @@ impl FunctionExpression for MatchAnyFn {
     }
 }
 
+#[derive(Clone, Debug)]
+struct MatchAnyDynamicFn {
+    value: Box<dyn Expression>,
+    patterns: Box<dyn Expression>,
+    cache: RegexSetCache,
+}
+
+impl FunctionExpression for MatchAnyDynamicFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let patterns = self.patterns.resolve(ctx)?;
+
+        let regex_set = regex_set(patterns, &self.cache)?;
+        match_any(value, &regex_set)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::boolean().fallible()
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::trivial_regex)]
 mod tests {
@@ -122,4 +178,35 @@ mod tests {
             tdef: TypeDef::boolean().infallible(),
         }
     ];
+
+    #[test]
+    fn runtime_patterns() {
+        let patterns = Value::Array(vec![
+            Value::Regex(Regex::new("baz").unwrap().into()),
+            Value::Regex(Regex::new("(?i)foo").unwrap().into()),
+        ]);
+
+        let cache = RegexSetCache::new(CACHE_CAPACITY);
+
+        let regex_set = regex_set(patterns.clone(), &cache).unwrap();
+        assert_eq!(
+            match_any("FOO BAR".into(), &regex_set).unwrap(),
+            value!(true)
+        );
+        // The same patterns reuse the set built for the first event.
+        assert!(Arc::ptr_eq(
+            &regex_set,
+            &super::regex_set(patterns, &cache).unwrap()
+        ));
+    }
+
+    #[test]
+    fn runtime_patterns_not_regex() {
+        assert_eq!(
+            regex_set(value!(["foo"]), &RegexSetCache::new(CACHE_CAPACITY))
+                .unwrap_err()
+                .to_string(),
+            "expected regex, got string"
+        );
+    }
 }
@@ -0,0 +1,131 @@
+use crate::compiler::prelude::*;
+
+/// Applies an RFC 7396 merge patch: objects merge recursively, `null` deletes a key, and any
+/// other patch value replaces the target outright.
+fn merge_patch(target: Value, patch: Value) -> Value {
+    let Value::Object(patch) = patch else {
+        return patch;
+    };
+
+    let mut target = match target {
+        Value::Object(target) => target,
+        _ => ObjectMap::new(),
+    };
+
+    for (key, value) in patch {
+        if value.is_null() {
+            target.remove(&key);
+        } else {
+            let existing = target.remove(&key).unwrap_or(Value::Null);
+            target.insert(key, merge_patch(existing, value));
+        }
+    }
+
+    Value::Object(target)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct MergePatch;
+
+impl Function for MergePatch {
+    fn identifier(&self) -> &'static str {
+        "merge_patch"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "target",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "patch",
+                kind: kind::ANY,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "merge patch",
+            source: r#"merge_patch({"a": {"b": 1, "c": 2}, "d": true}, {"a": {"c": null, "e": 3}, "d": [1]})"#,
+            result: Ok(r#"{"a": {"b": 1, "e": 3}, "d": [1]}"#),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let target = arguments.required("target");
+        let patch = arguments.required("patch");
+
+        Ok(MergePatchFn { target, patch }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MergePatchFn {
+    target: Box<dyn Expression>,
+    patch: Box<dyn Expression>,
+}
+
+impl FunctionExpression for MergePatchFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let target = self.target.resolve(ctx)?;
+        let patch = self.patch.resolve(ctx)?;
+
+        Ok(merge_patch(target, patch))
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::any().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        merge_patch => MergePatch;
+
+        nested_merge {
+            args: func_args![
+                target: value!({"a": {"b": {"c": 1, "d": 2}}, "e": "x"}),
+                patch: value!({"a": {"b": {"d": 3, "f": 4}}}),
+            ],
+            want: Ok(value!({"a": {"b": {"c": 1, "d": 3, "f": 4}}, "e": "x"})),
+            tdef: TypeDef::any().infallible(),
+        }
+
+        delete_with_null {
+            args: func_args![target: value!({"a": 1, "b": {"c": 2, "d": 3}}), patch: value!({"a": null, "b": {"c": null}, "z": null})],
+            want: Ok(value!({"b": {"d": 3}})),
+            tdef: TypeDef::any().infallible(),
+        }
+
+        arrays_replace {
+            args: func_args![target: value!({"a": [1, 2]}), patch: value!({"a": [3]})],
+            want: Ok(value!({"a": [3]})),
+            tdef: TypeDef::any().infallible(),
+        }
+
+        object_patch_over_scalar {
+            args: func_args![target: value!({"a": "x"}), patch: value!({"a": {"b": null, "c": 1}})],
+            want: Ok(value!({"a": {"c": 1}})),
+            tdef: TypeDef::any().infallible(),
+        }
+
+        non_object_patch {
+            args: func_args![target: value!({"a": 1}), patch: value!("replaced")],
+            want: Ok("replaced"),
+            tdef: TypeDef::any().infallible(),
+        }
+    ];
+}
@@ -32,6 +32,7 @@ pub use wasm_unsupported_function::WasmUnsupportedFunction;
 use crate::compiler::Function;
 
 mod json_utils;
+mod lru_cache;
 mod string_utils;
 mod util;
 mod wasm_unsupported_function;
@@ -39,48 +40,68 @@ mod wasm_unsupported_function;
 cfg_if::cfg_if! {
     if #[cfg(feature = "stdlib")] {
         mod abs;
+        mod add_business_days;
+        mod add_duration;
         mod append;
+        mod apply_patch;
         mod array;
+        mod array_difference;
+        mod array_intersection;
+        mod array_union;
         mod assert;
         mod assert_eq;
+        mod assert_type;
         mod boolean;
         mod ceil;
+        mod cached;
         mod casing;
         mod chunks;
         mod compact;
         mod contains;
         mod contains_all;
+        mod contains_any;
         mod crc;
+        mod date_diff;
+        mod day_of_year;
         mod decode_base16;
         mod decode_base64;
         mod decode_charset;
         mod decode_gzip;
+        mod decode_mime_header;
         mod decode_mime_q;
         mod decode_percent;
         mod decode_punycode;
+        mod decode_quoted_printable;
         mod decode_snappy;
         mod decode_zlib;
         mod decode_zstd;
         mod decrypt;
         mod del;
         mod dns_lookup;
+        mod domain_parts;
         mod downcase;
+        mod emit_log;
         mod emit_metric;
         mod encode_base16;
         mod encode_base64;
         mod encode_charset;
         mod encode_gzip;
+        mod encode_influxdb_line_protocol;
         mod encode_json;
+        mod encode_json_canonical;
         mod encode_key_value;
         mod encode_logfmt;
         mod encode_percent;
+        mod encode_prometheus;
         mod encode_proto;
         mod encode_punycode;
+        mod encode_quoted_printable;
         mod encode_snappy;
         mod encode_zlib;
         mod encode_zstd;
         mod encrypt;
         mod ends_with;
+        mod entries;
         mod exists;
         mod filter;
         mod find;
@@ -88,11 +109,15 @@ cfg_if::cfg_if! {
         mod float;
         mod floor;
         mod for_each;
+        mod format_duration;
         mod format_int;
         mod format_number;
         mod format_timestamp;
+        mod from_entries;
         mod from_unix_timestamp;
+        mod geoip_lookup;
         mod get;
+        mod get_env;
         mod get_env_var;
         mod get_hostname;
         mod get_timezone_name;
@@ -109,6 +134,7 @@ cfg_if::cfg_if! {
         mod ipv6_to_ipv4;
         mod is_array;
         mod is_boolean;
+        mod is_business_day;
         mod is_empty;
         mod is_float;
         mod is_integer;
@@ -121,39 +147,57 @@ cfg_if::cfg_if! {
         mod is_regex;
         mod is_string;
         mod is_timestamp;
+        mod is_valid_utf8;
         mod join;
         mod keys;
+        mod leaf_paths;
+        mod leaves;
         mod length;
         mod log;
         mod log_util;
+        mod luhn_check;
         mod map_keys;
         mod map_values;
+        mod mask;
         mod r#match;
         mod match_any;
         mod match_array;
         mod match_datadog_query;
         mod md5;
         mod merge;
+        mod merge_patch;
         mod mod_func;
+        mod normalize_unicode;
+        mod normalize_url;
         mod now;
         mod object;
+        mod object_diff;
         mod object_from_array;
+        mod omit;
         mod parse_apache_log;
         mod parse_aws_alb_log;
+        mod parse_aws_cloudtrail;
         mod parse_aws_cloudwatch_log_subscription_message;
+        mod parse_aws_cloudwatch_metric_stream;
         mod parse_aws_vpc_flow_log;
         mod parse_bytes;
         mod parse_cef;
         mod parse_cbor;
         mod parse_common_log;
         mod parse_csv;
+        mod parse_dns_message;
         mod parse_duration;
+        mod parse_duration_iso8601;
+        mod parse_envoy_access_log;
         mod parse_etld;
+        mod parse_fixed_width;
         mod parse_float;
         mod parse_glog;
         mod parse_grok;
         mod parse_groks;
+        mod parse_haproxy_log;
         mod parse_influxdb;
+        mod parse_influxdb_line_protocol;
         mod parse_int;
         mod parse_json;
         mod parse_key_value;
@@ -161,17 +205,22 @@ cfg_if::cfg_if! {
         mod parse_linux_authorization;
         mod parse_logfmt;
         mod parse_nginx_log;
+        mod parse_postgresql_log;
+        mod parse_prometheus;
         mod parse_proto;
         mod parse_query_string;
         mod parse_regex;
         mod parse_regex_all;
         mod parse_ruby_hash;
+        mod parse_statsd;
         mod parse_syslog;
         mod parse_timestamp;
+        mod parse_timestamp_auto;
         mod parse_tokens;
         mod parse_url;
         mod parse_user_agent;
         mod parse_xml;
+        mod pick;
         mod push;
         mod random_bool;
         mod random_bytes;
@@ -179,8 +228,11 @@ cfg_if::cfg_if! {
         mod random_int;
         mod redact;
         mod remove;
+        mod remove_accents;
+        mod rename_keys;
         mod replace;
         mod replace_with;
+        mod reverse;
         mod reverse_dns;
         mod round;
         mod seahash;
@@ -196,6 +248,7 @@ cfg_if::cfg_if! {
         mod strip_ansi_escape_codes;
         mod strip_whitespace;
         mod strlen;
+        mod subtract_duration;
         mod tag_types_externally;
         mod tally;
         mod tally_value;
@@ -209,8 +262,12 @@ cfg_if::cfg_if! {
         mod to_syslog_level;
         mod to_syslog_severity;
         mod to_unix_timestamp;
+        mod to_valid_utf8;
+        mod coalesce;
+        mod coerce;
         mod community_id;
         mod truncate;
+        mod type_name;
         mod unflatten;
         mod type_def;
         mod unique;
@@ -219,39 +276,60 @@ cfg_if::cfg_if! {
         mod uuid_from_friendly_id;
         mod uuid_v4;
         mod uuid_v7;
+        mod validate_json_schema;
         mod values;
+        mod week_of_year;
+        mod weekday;
         mod zip;
 
         // -----------------------------------------------------------------------------
 
         pub use self::hmac::Hmac;
         pub use abs::Abs;
+        pub use add_business_days::AddBusinessDays;
+        pub use add_duration::AddDuration;
         pub use append::Append;
+        pub use apply_patch::ApplyPatch;
+        pub use array_difference::ArrayDifference;
+        pub use array_intersection::ArrayIntersection;
+        pub use array_union::ArrayUnion;
         pub use assert::Assert;
         pub use assert_eq::AssertEq;
+        pub use assert_type::AssertType;
         pub use boolean::Boolean;
+        pub use cached::Cached;
         pub use ceil::Ceil;
         pub use chunks::Chunks;
+        pub use coalesce::Coalesce;
+        pub use coerce::Coerce;
         pub use compact::Compact;
         pub use contains::Contains;
         pub use contains_all::ContainsAll;
+        pub use contains_any::ContainsAny;
+        pub use date_diff::DateDiff;
+        pub use day_of_year::DayOfYear;
         pub use decode_base16::DecodeBase16;
         pub use decode_base64::DecodeBase64;
         pub use decode_charset::DecodeCharset;
         pub use decode_gzip::DecodeGzip;
+        pub use decode_mime_header::DecodeMimeHeader;
         pub use decode_mime_q::DecodeMimeQ;
         pub use decode_percent::DecodePercent;
         pub use decode_punycode::DecodePunycode;
+        pub use decode_quoted_printable::DecodeQuotedPrintable;
         pub use decode_snappy::DecodeSnappy;
         pub use decode_zlib::DecodeZlib;
         pub use decode_zstd::DecodeZstd;
         pub use decrypt::Decrypt;
         pub use del::Del;
         pub use dns_lookup::DnsLookup;
+        pub use domain_parts::DomainParts;
         pub use downcase::Downcase;
-        pub use emit_metric::EmitMetric;
+        pub use emit_log::EmitLog;
+        pub use emit_metric::{EmitMetric, MetricEmission};
 
         pub use casing::camelcase::Camelcase;
+        pub use casing::convert_keys::ConvertKeys;
         pub use casing::pascalcase::Pascalcase;
         pub use casing::snakecase::Snakecase;
         pub use casing::screamingsnakecase::ScreamingSnakecase;
@@ -260,17 +338,22 @@ cfg_if::cfg_if! {
         pub use encode_base64::EncodeBase64;
         pub use encode_charset::EncodeCharset;
         pub use encode_gzip::EncodeGzip;
+        pub use encode_influxdb_line_protocol::EncodeInfluxdbLineProtocol;
         pub use encode_json::EncodeJson;
+        pub use encode_json_canonical::EncodeJsonCanonical;
         pub use encode_key_value::EncodeKeyValue;
         pub use encode_logfmt::EncodeLogfmt;
         pub use encode_percent::EncodePercent;
+        pub use encode_prometheus::EncodePrometheus;
         pub use encode_proto::EncodeProto;
         pub use encode_punycode::EncodePunycode;
+        pub use encode_quoted_printable::EncodeQuotedPrintable;
         pub use encode_snappy::EncodeSnappy;
         pub use encode_zlib::EncodeZlib;
         pub use encode_zstd::EncodeZstd;
         pub use encrypt::Encrypt;
         pub use ends_with::EndsWith;
+        pub use entries::Entries;
         pub use exists::Exists;
         pub use filter::Filter;
         pub use find::Find;
@@ -278,14 +361,18 @@ cfg_if::cfg_if! {
         pub use float::Float;
         pub use floor::Floor;
         pub use for_each::ForEach;
+        pub use format_duration::FormatDuration;
         pub use format_int::FormatInt;
         pub use format_number::FormatNumber;
         pub use format_timestamp::FormatTimestamp;
+        pub use from_entries::FromEntries;
         pub use from_unix_timestamp::FromUnixTimestamp;
+        pub use geoip_lookup::{GeoipDatabaseAccess, GeoipLookup};
         pub use self::community_id::CommunityID;
         pub use get::Get;
+        pub use get_env::{EnvironmentAccess, GetEnv};
         pub use get_env_var::GetEnvVar;
-        pub use get_hostname::GetHostname;
+        pub use get_hostname::{GetHostname, Hostname, HostnameAccess};
         pub use get_timezone_name::GetTimezoneName;
         pub use get_timezone_name::get_name_for_timezone;
         pub use includes::Includes;
@@ -300,6 +387,7 @@ cfg_if::cfg_if! {
         pub use ipv6_to_ipv4::Ipv6ToIpV4;
         pub use is_array::IsArray;
         pub use is_boolean::IsBoolean;
+        pub use is_business_day::IsBusinessDay;
         pub use is_empty::IsEmpty;
         pub use is_float::IsFloat;
         pub use is_integer::IsInteger;
@@ -312,36 +400,54 @@ cfg_if::cfg_if! {
         pub use is_regex::IsRegex;
         pub use is_string::IsString;
         pub use is_timestamp::IsTimestamp;
+        pub use is_valid_utf8::IsValidUtf8;
         pub use join::Join;
         pub use keys::Keys;
+        pub use leaf_paths::LeafPaths;
+        pub use leaves::Leaves;
         pub use length::Length;
         pub use log::Log;
+        pub use luhn_check::LuhnCheck;
         pub use map_keys::MapKeys;
         pub use map_values::MapValues;
+        pub use mask::Mask;
         pub use match_any::MatchAny;
         pub use match_array::MatchArray;
         pub use match_datadog_query::MatchDatadogQuery;
         pub use merge::Merge;
+        pub use merge_patch::MergePatch;
         pub use mod_func::Mod;
+        pub use normalize_unicode::NormalizeUnicode;
+        pub use normalize_url::NormalizeUrl;
         pub use now::Now;
         pub use object::Object;
+        pub use object_diff::ObjectDiff;
         pub use object_from_array::ObjectFromArray;
+        pub use omit::Omit;
         pub use parse_apache_log::ParseApacheLog;
         pub use parse_aws_alb_log::ParseAwsAlbLog;
+        pub use parse_aws_cloudtrail::ParseAwsCloudTrail;
         pub use parse_aws_cloudwatch_log_subscription_message::ParseAwsCloudWatchLogSubscriptionMessage;
+        pub use parse_aws_cloudwatch_metric_stream::ParseAwsCloudWatchMetricStream;
         pub use parse_aws_vpc_flow_log::ParseAwsVpcFlowLog;
         pub use parse_bytes::ParseBytes;
         pub use parse_cbor::ParseCbor;
         pub use parse_cef::ParseCef;
         pub use parse_common_log::ParseCommonLog;
         pub use parse_csv::ParseCsv;
+        pub use parse_dns_message::ParseDnsMessage;
         pub use parse_duration::ParseDuration;
+        pub use parse_duration_iso8601::ParseDurationIso8601;
+        pub use parse_fixed_width::ParseFixedWidth;
         pub use parse_float::ParseFloat;
+        pub use parse_envoy_access_log::ParseEnvoyAccessLog;
         pub use parse_etld::ParseEtld;
         pub use parse_glog::ParseGlog;
         pub use parse_grok::ParseGrok;
         pub use parse_groks::ParseGroks;
+        pub use parse_haproxy_log::ParseHaproxyLog;
         pub use parse_influxdb::ParseInfluxDB;
+        pub use parse_influxdb_line_protocol::ParseInfluxdbLineProtocol;
         pub use parse_int::ParseInt;
         pub use parse_json::ParseJson;
         pub use parse_key_value::ParseKeyValue;
@@ -349,17 +455,22 @@ cfg_if::cfg_if! {
         pub use parse_linux_authorization::ParseLinuxAuthorization;
         pub use parse_logfmt::ParseLogFmt;
         pub use parse_nginx_log::ParseNginxLog;
+        pub use parse_postgresql_log::ParsePostgresqlLog;
+        pub use parse_prometheus::ParsePrometheus;
         pub use parse_proto::ParseProto;
         pub use parse_query_string::ParseQueryString;
         pub use parse_regex::ParseRegex;
         pub use parse_regex_all::ParseRegexAll;
         pub use parse_ruby_hash::ParseRubyHash;
+        pub use parse_statsd::ParseStatsd;
         pub use parse_syslog::ParseSyslog;
         pub use parse_timestamp::ParseTimestamp;
+        pub use parse_timestamp_auto::ParseTimestampAuto;
         pub use parse_tokens::ParseTokens;
         pub use parse_url::ParseUrl;
         pub use parse_user_agent::ParseUserAgent;
         pub use parse_xml::ParseXml;
+        pub use pick::Pick;
         pub use push::Push;
         pub use r#match::Match;
         pub use random_bool::RandomBool;
@@ -368,8 +479,11 @@ cfg_if::cfg_if! {
         pub use random_int::RandomInt;
         pub use redact::Redact;
         pub use remove::Remove;
+        pub use remove_accents::RemoveAccents;
+        pub use rename_keys::RenameKeys;
         pub use replace::Replace;
         pub use replace_with::ReplaceWith;
+        pub use reverse::Reverse;
         pub use reverse_dns::ReverseDns;
         pub use round::Round;
         pub use set::Set;
@@ -383,6 +497,7 @@ cfg_if::cfg_if! {
         pub use strip_ansi_escape_codes::StripAnsiEscapeCodes;
         pub use strip_whitespace::StripWhitespace;
         pub use strlen::Strlen;
+        pub use subtract_duration::SubtractDuration;
         pub use tag_types_externally::TagTypesExternally;
         pub use tally::Tally;
         pub use tally_value::TallyValue;
@@ -396,8 +511,10 @@ cfg_if::cfg_if! {
         pub use to_syslog_level::ToSyslogLevel;
         pub use to_syslog_severity::ToSyslogSeverity;
         pub use to_unix_timestamp::ToUnixTimestamp;
+        pub use to_valid_utf8::ToValidUtf8;
         pub use truncate::Truncate;
         pub use type_def::TypeDef;
+        pub use type_name::TypeName;
         pub use unflatten::Unflatten;
         pub use unique::Unique;
         pub use unnest::Unnest;
@@ -405,7 +522,10 @@ cfg_if::cfg_if! {
         pub use uuid_from_friendly_id::UuidFromFriendlyId;
         pub use uuid_v4::UuidV4;
         pub use uuid_v7::UuidV7;
+        pub use validate_json_schema::ValidateJsonSchema;
         pub use values::Values;
+        pub use week_of_year::WeekOfYear;
+        pub use weekday::Weekday;
         pub use zip::Zip;
         pub use self::array::Array;
         pub use self::md5::Md5;
@@ -420,47 +540,70 @@ cfg_if::cfg_if! {
 pub fn all() -> Vec<Box<dyn Function>> {
     vec![
         Box::new(Abs),
+        Box::new(AddBusinessDays),
+        Box::new(AddDuration),
         Box::new(Append),
         Box::new(Array),
+        Box::new(ApplyPatch),
+        Box::new(ArrayDifference),
+        Box::new(ArrayIntersection),
+        Box::new(ArrayUnion),
         Box::new(Assert),
         Box::new(AssertEq),
+        Box::new(AssertType),
         Box::new(Boolean),
         Box::new(Camelcase),
+        Box::new(Cached),
         Box::new(Ceil),
         Box::new(Chunks),
+        Box::new(Coalesce),
+        Box::new(Coerce),
         Box::new(Compact),
         Box::new(Contains),
         Box::new(ContainsAll),
+        Box::new(ConvertKeys),
         Box::new(Crc),
+        Box::new(ContainsAny),
+        Box::new(DateDiff),
+        Box::new(DayOfYear),
         Box::new(DecodeBase16),
         Box::new(DecodeBase64),
         Box::new(DecodeCharset),
         Box::new(DecodeGzip),
         Box::new(DecodePercent),
         Box::new(DecodePunycode),
+        Box::new(DecodeMimeHeader),
         Box::new(DecodeMimeQ),
+        Box::new(DecodeQuotedPrintable),
         Box::new(DecodeSnappy),
         Box::new(DecodeZlib),
         Box::new(DecodeZstd),
         Box::new(Decrypt),
         Box::new(Del),
         Box::new(DnsLookup),
+        Box::new(DomainParts),
         Box::new(Downcase),
+        Box::new(EmitLog),
         Box::new(EncodeBase16),
         Box::new(EncodeBase64),
         Box::new(EncodeCharset),
         Box::new(EncodeGzip),
+        Box::new(EncodeInfluxdbLineProtocol),
         Box::new(EncodeJson),
+        Box::new(EncodeJsonCanonical),
         Box::new(EncodeKeyValue),
         Box::new(EncodeLogfmt),
         Box::new(EncodePercent),
+        Box::new(EncodePrometheus),
         Box::new(EncodeProto),
         Box::new(EncodePunycode),
+        Box::new(EncodeQuotedPrintable),
         Box::new(EncodeSnappy),
         Box::new(EncodeZlib),
         Box::new(EncodeZstd),
         Box::new(Encrypt),
         Box::new(EndsWith),
+        Box::new(Entries),
         Box::new(Exists),
         Box::new(Filter),
         Box::new(Find),
@@ -468,15 +611,20 @@ pub fn all() -> Vec<Box<dyn Function>> {
         Box::new(Float),
         Box::new(Floor),
         Box::new(ForEach),
+        Box::new(FormatDuration),
         Box::new(FormatInt),
         Box::new(FormatNumber),
         Box::new(FormatTimestamp),
+        Box::new(FromEntries),
         Box::new(FromUnixTimestamp),
+        Box::new(GeoipLookup),
         Box::new(Get),
+        Box::new(GetEnv),
         Box::new(GetEnvVar),
         Box::new(GetHostname),
         Box::new(GetTimezoneName),
         Box::new(Hmac),
+        Box::new(Hostname),
         Box::new(Includes),
         Box::new(Integer),
         Box::new(IpAton),
@@ -489,6 +637,7 @@ pub fn all() -> Vec<Box<dyn Function>> {
         Box::new(Ipv6ToIpV4),
         Box::new(IsArray),
         Box::new(IsBoolean),
+        Box::new(IsBusinessDay),
         Box::new(IsEmpty),
         Box::new(IsFloat),
         Box::new(IsInteger),
@@ -501,39 +650,57 @@ pub fn all() -> Vec<Box<dyn Function>> {
         Box::new(IsRegex),
         Box::new(IsString),
         Box::new(IsTimestamp),
+        Box::new(IsValidUtf8),
         Box::new(Join),
         Box::new(Kebabcase),
         Box::new(Keys),
+        Box::new(LeafPaths),
+        Box::new(Leaves),
         Box::new(Length),
         Box::new(Log),
+        Box::new(LuhnCheck),
         Box::new(MapKeys),
         Box::new(MapValues),
+        Box::new(Mask),
         Box::new(Match),
         Box::new(MatchAny),
         Box::new(MatchArray),
         Box::new(MatchDatadogQuery),
         Box::new(Md5),
         Box::new(Merge),
+        Box::new(MergePatch),
         Box::new(Mod),
+        Box::new(NormalizeUnicode),
+        Box::new(NormalizeUrl),
         Box::new(Now),
         Box::new(Object),
+        Box::new(ObjectDiff),
         Box::new(ObjectFromArray),
+        Box::new(Omit),
         Box::new(ParseApacheLog),
         Box::new(ParseAwsAlbLog),
+        Box::new(ParseAwsCloudTrail),
         Box::new(ParseAwsCloudWatchLogSubscriptionMessage),
+        Box::new(ParseAwsCloudWatchMetricStream),
         Box::new(ParseAwsVpcFlowLog),
         Box::new(ParseBytes),
         Box::new(ParseCbor),
         Box::new(ParseCef),
         Box::new(ParseCommonLog),
         Box::new(ParseCsv),
+        Box::new(ParseDnsMessage),
         Box::new(ParseDuration),
+        Box::new(ParseDurationIso8601),
+        Box::new(ParseFixedWidth),
         Box::new(ParseFloat),
+        Box::new(ParseEnvoyAccessLog),
         Box::new(ParseEtld),
         Box::new(ParseGlog),
         Box::new(ParseGrok),
         Box::new(ParseGroks),
+        Box::new(ParseHaproxyLog),
         Box::new(ParseInfluxDB),
+        Box::new(ParseInfluxdbLineProtocol),
         Box::new(ParseInt),
         Box::new(ParseJson),
         Box::new(ParseKeyValue),
@@ -541,18 +708,23 @@ pub fn all() -> Vec<Box<dyn Function>> {
         Box::new(ParseLinuxAuthorization),
         Box::new(ParseLogFmt),
         Box::new(ParseNginxLog),
+        Box::new(ParsePostgresqlLog),
+        Box::new(ParsePrometheus),
         Box::new(ParseProto),
         Box::new(ParseQueryString),
         Box::new(ParseRegex),
         Box::new(ParseRegexAll),
         Box::new(ParseRubyHash),
+        Box::new(ParseStatsd),
         Box::new(ParseSyslog),
         Box::new(ParseTimestamp),
+        Box::new(ParseTimestampAuto),
         Box::new(ParseTokens),
         Box::new(ParseUrl),
         Box::new(ParseUserAgent),
         Box::new(ParseXml),
         Box::new(Pascalcase),
+        Box::new(Pick),
         Box::new(Push),
         Box::new(RandomBool),
         Box::new(RandomBytes),
@@ -560,8 +732,11 @@ pub fn all() -> Vec<Box<dyn Function>> {
         Box::new(RandomInt),
         Box::new(Redact),
         Box::new(Remove),
+        Box::new(RemoveAccents),
+        Box::new(RenameKeys),
         Box::new(Replace),
         Box::new(ReplaceWith),
+        Box::new(Reverse),
         Box::new(ReverseDns),
         Box::new(Round),
         Box::new(Seahash),
@@ -581,6 +756,7 @@ pub fn all() -> Vec<Box<dyn Function>> {
         Box::new(Strlen),
         Box::new(Tally),
         Box::new(TallyValue),
+        Box::new(SubtractDuration),
         Box::new(TagTypesExternally),
         Box::new(Timestamp),
         Box::new(ToBool),
@@ -592,9 +768,11 @@ pub fn all() -> Vec<Box<dyn Function>> {
         Box::new(ToSyslogLevel),
         Box::new(ToSyslogSeverity),
         Box::new(ToUnixTimestamp),
+        Box::new(ToValidUtf8),
         Box::new(CommunityID),
         Box::new(Truncate),
         Box::new(TypeDef),
+        Box::new(TypeName),
         Box::new(Unflatten),
         Box::new(Unique),
         Box::new(Unnest),
@@ -602,7 +780,10 @@ pub fn all() -> Vec<Box<dyn Function>> {
         Box::new(UuidFromFriendlyId),
         Box::new(UuidV4),
         Box::new(UuidV7),
+        Box::new(ValidateJsonSchema),
         Box::new(Values),
+        Box::new(WeekOfYear),
+        Box::new(Weekday),
         Box::new(Zip),
     ]
 }
@@ -0,0 +1,154 @@
+use crate::compiler::prelude::*;
+use crate::value;
+use unicode_normalization::UnicodeNormalization;
+
+fn normalize_unicode(value: Value, form: &Bytes) -> Resolved {
+    let string = value.try_bytes_utf8_lossy()?;
+    let normalized: String = match form.as_ref() {
+        b"NFC" => string.nfc().collect(),
+        b"NFD" => string.nfd().collect(),
+        b"NFKC" => string.nfkc().collect(),
+        b"NFKD" => string.nfkd().collect(),
+        _ => unreachable!("enum invariant"),
+    };
+
+    Ok(normalized.into())
+}
+
+fn forms() -> Vec<Value> {
+    vec![value!("NFC"), value!("NFD"), value!("NFKC"), value!("NFKD")]
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct NormalizeUnicode;
+
+impl Function for NormalizeUnicode {
+    fn identifier(&self) -> &'static str {
+        "normalize_unicode"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "form",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "compose a combining accent",
+                source: r#"normalize_unicode(decode_base16!("63616665cc81"))"#,
+                result: Ok("café"),
+            },
+            Example {
+                title: "compatibility decomposition",
+                source: r#"normalize_unicode("ﬁ", form: "NFKD")"#,
+                result: Ok("fi"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let form = arguments
+            .optional_enum("form", &forms(), state)?
+            .unwrap_or_else(|| value!("NFC"))
+            .try_bytes()
+            .expect("form not bytes");
+
+        Ok(NormalizeUnicodeFn { value, form }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct NormalizeUnicodeFn {
+    value: Box<dyn Expression>,
+    form: Bytes,
+}
+
+impl FunctionExpression for NormalizeUnicodeFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        normalize_unicode(value, &self.form)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // "café" with a precomposed "é" (U+00E9) and with "e" followed by a combining acute accent (U+0301).
+    const COMPOSED: &str = "caf\u{e9}";
+    const DECOMPOSED: &str = "cafe\u{301}";
+
+    test_function![
+        normalize_unicode => NormalizeUnicode;
+
+        default_form_composes {
+            args: func_args![value: DECOMPOSED],
+            want: Ok(COMPOSED),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        nfc_composed_unchanged {
+            args: func_args![value: COMPOSED, form: "NFC"],
+            want: Ok(COMPOSED),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        nfd_decomposes {
+            args: func_args![value: COMPOSED, form: "NFD"],
+            want: Ok(DECOMPOSED),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        nfd_decomposed_unchanged {
+            args: func_args![value: DECOMPOSED, form: "NFD"],
+            want: Ok(DECOMPOSED),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        nfkc_ligature {
+            args: func_args![value: "\u{fb01}le", form: "NFKC"],
+            want: Ok("file"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        nfkd_ligature_and_accent {
+            args: func_args![value: "\u{fb01}anc\u{e9}", form: "NFKD"],
+            want: Ok("fiance\u{301}"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+
+    #[test]
+    fn canonically_equal_strings_normalize_identically() {
+        for form in forms() {
+            let form = form.try_bytes().unwrap();
+            assert_eq!(
+                normalize_unicode(value!(COMPOSED), &form).unwrap(),
+                normalize_unicode(value!(DECOMPOSED), &form).unwrap(),
+                "{form:?}",
+            );
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use crate::compiler::prelude::*;
+use url::Url;
+
+fn normalize_url(value: Value, sort_query: bool, strip_fragment: bool) -> Resolved {
+    let string = value.try_bytes_utf8_lossy()?;
+    // Parsing already lowercases the scheme and host of special schemes such as `http`,
+    // removes their default port and resolves `.` and `..` path segments.
+    let mut url = Url::parse(&string).map_err(|e| format!("unable to parse url: {e}"))?;
+
+    if sort_query {
+        if let Some(query) = url.query() {
+            // Pairs are sorted by key only, and left encoded as they are, so the order of
+            // repeated keys, which may be significant, is preserved.
+            let mut pairs = query
+                .split('&')
+                .filter(|pair| !pair.is_empty())
+                .collect::<Vec<_>>();
+            pairs.sort_by_key(|pair| pair.split_once('=').map_or(*pair, |(key, _)| key));
+            let query = pairs.join("&");
+            url.set_query((!query.is_empty()).then_some(&query));
+        }
+    }
+    if strip_fragment {
+        url.set_fragment(None);
+    }
+
+    Ok(String::from(url).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct NormalizeUrl;
+
+impl Function for NormalizeUrl {
+    fn identifier(&self) -> &'static str {
+        "normalize_url"
+    }
+
+    fn summary(&self) -> &'static str {
+        "normalize a URL into a canonical form"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Normalizes a URL so that equivalent URLs compare equal, for use in cache keys or for
+            deduplication.
+
+            The scheme and host are lowercased, default ports such as `:443` for `https` are
+            removed and `.` and `..` path segments are resolved. These are always applied, as
+            they are part of parsing the URL. Query parameters are sorted by key, keeping the
+            order of repeated keys, unless `sort_query` is `false`, and the fragment is removed
+            when `strip_fragment` is `true`.
+        "}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "sort_query",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "strip_fragment",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "normalize url",
+                source: r#"normalize_url!("HTTPS://Vector.dev:443/docs/./about/../setup?b=2&a=1#install")"#,
+                result: Ok("https://vector.dev/docs/setup?a=1&b=2#install"),
+            },
+            Example {
+                title: "strip fragment",
+                source: r#"normalize_url!("https://vector.dev/docs#install", strip_fragment: true)"#,
+                result: Ok("https://vector.dev/docs"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let sort_query = arguments
+            .optional("sort_query")
+            .unwrap_or_else(|| expr!(true));
+        let strip_fragment = arguments
+            .optional("strip_fragment")
+            .unwrap_or_else(|| expr!(false));
+
+        Ok(NormalizeUrlFn {
+            value,
+            sort_query,
+            strip_fragment,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct NormalizeUrlFn {
+    value: Box<dyn Expression>,
+    sort_query: Box<dyn Expression>,
+    strip_fragment: Box<dyn Expression>,
+}
+
+impl FunctionExpression for NormalizeUrlFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let sort_query = self.sort_query.resolve(ctx)?.try_boolean()?;
+        let strip_fragment = self.strip_fragment.resolve(ctx)?.try_boolean()?;
+
+        normalize_url(value, sort_query, strip_fragment)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        normalize_url => NormalizeUrl;
+
+        equivalent_urls {
+            args: func_args![value: value!("HTTP://Example.COM:80/a/b/../c/./d?z=1&y=2&z=0")],
+            want: Ok(value!("http://example.com/a/c/d?y=2&z=1&z=0")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        equivalent_urls_canonical {
+            args: func_args![value: value!("http://example.com/a/c/d?y=2&z=1&z=0")],
+            want: Ok(value!("http://example.com/a/c/d?y=2&z=1&z=0")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        query_unsorted {
+            args: func_args![value: value!("https://example.com/?z=1&y=2"), sort_query: false],
+            want: Ok(value!("https://example.com/?z=1&y=2")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        empty_query {
+            args: func_args![value: value!("https://example.com/search?&")],
+            want: Ok(value!("https://example.com/search")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        fragment_kept {
+            args: func_args![value: value!("https://example.com:8443/#top")],
+            want: Ok(value!("https://example.com:8443/#top")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        fragment_stripped {
+            args: func_args![value: value!("https://example.com:8443/#top"), strip_fragment: true],
+            want: Ok(value!("https://example.com:8443/")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid {
+            args: func_args![value: value!("example.com/path")],
+            want: Err("unable to parse url: relative URL without a base"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -1,5 +1,27 @@
 use crate::compiler::prelude::*;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+
+use super::add_duration::shift_timestamp;
+
+fn now(offset: Option<Value>) -> Resolved {
+    let now = Utc::now();
+    match offset {
+        Some(offset) => apply_offset(now, &offset.try_bytes_utf8_lossy()?),
+        None => Ok(now.into()),
+    }
+}
+
+/// Shifts `timestamp` by a signed duration string such as `-1h` or `+30m`, using the same
+/// grammar as `parse_duration`.
+fn apply_offset(timestamp: DateTime<Utc>, offset: &str) -> Resolved {
+    let offset = offset.trim_start();
+    let (negative, duration) = match offset.strip_prefix('-') {
+        Some(duration) => (true, duration),
+        None => (false, offset.strip_prefix('+').unwrap_or(offset)),
+    };
+
+    shift_timestamp(timestamp.into(), duration.into(), negative)
+}
 
 #[derive(Clone, Copy, Debug)]
 pub struct Now;
@@ -9,33 +31,111 @@ impl Function for Now {
         "now"
     }
 
-    fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "now",
-            source: r#"now() != """#,
-            result: Ok("true"),
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "offset",
+            kind: kind::BYTES,
+            required: false,
         }]
     }
 
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "now",
+                source: r#"now() != """#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "an hour ago",
+                source: r#"now!("-1h") < now()"#,
+                result: Ok("true"),
+            },
+        ]
+    }
+
     fn compile(
         &self,
         _state: &state::TypeState,
         _ctx: &mut FunctionCompileContext,
-        _: ArgumentList,
+        arguments: ArgumentList,
     ) -> Compiled {
-        Ok(NowFn.as_expr())
+        let offset = arguments.optional("offset");
+
+        Ok(NowFn { offset }.as_expr())
     }
 }
 
 #[derive(Debug, Clone)]
-struct NowFn;
+struct NowFn {
+    offset: Option<Box<dyn Expression>>,
+}
 
 impl FunctionExpression for NowFn {
-    fn resolve(&self, _: &mut Context) -> Resolved {
-        Ok(Utc::now().into())
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let offset = self
+            .offset
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        now(offset)
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
-        TypeDef::timestamp()
+        TypeDef::timestamp().maybe_fallible(self.offset.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeDelta, TimeZone};
+
+    fn timestamp() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2021, 1, 1, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn negative_offset() {
+        assert_eq!(
+            apply_offset(timestamp(), "-1h").unwrap(),
+            Value::from(Utc.with_ymd_and_hms(2021, 1, 1, 11, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn positive_offset() {
+        assert_eq!(
+            apply_offset(timestamp(), "+1h30m").unwrap(),
+            Value::from(Utc.with_ymd_and_hms(2021, 1, 1, 13, 30, 0).unwrap())
+        );
+        assert_eq!(
+            apply_offset(timestamp(), "2d").unwrap(),
+            Value::from(Utc.with_ymd_and_hms(2021, 1, 3, 12, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn invalid_offset() {
+        let error = apply_offset(timestamp(), "-1 fortnight").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("unable to parse duration: 'unknown time unit \"fortnight\""));
+    }
+
+    #[test]
+    fn now_with_offset() {
+        let before = Utc::now();
+        let Value::Timestamp(past) = now(Some("-1h".into())).unwrap() else {
+            panic!("expected timestamp");
+        };
+        let Value::Timestamp(future) = now(Some("1h".into())).unwrap() else {
+            panic!("expected timestamp");
+        };
+        let after = Utc::now();
+
+        assert!(past >= before - TimeDelta::hours(1) && past <= after - TimeDelta::hours(1));
+        assert!(future >= before + TimeDelta::hours(1) && future <= after + TimeDelta::hours(1));
     }
 }
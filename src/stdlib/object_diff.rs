@@ -0,0 +1,194 @@
+use std::collections::BTreeMap;
+
+use crate::compiler::prelude::*;
+
+fn object_diff(old: Value, new: Value) -> Resolved {
+    let old = old.try_object()?;
+    let new = new.try_object()?;
+
+    let mut diff = Diff::default();
+    diff.compare(&old, &new, "");
+
+    Ok(Value::from(ObjectMap::from([
+        ("added".into(), diff.added.into()),
+        ("removed".into(), diff.removed.into()),
+        ("changed".into(), diff.changed.into()),
+    ])))
+}
+
+/// Differences between two objects, keyed by the dotted path of each differing field.
+#[derive(Default)]
+struct Diff {
+    added: ObjectMap,
+    removed: ObjectMap,
+    changed: ObjectMap,
+}
+
+impl Diff {
+    /// Nested objects present on both sides are compared field by field; any other
+    /// values, including arrays, are compared as a whole.
+    fn compare(&mut self, old: &ObjectMap, new: &ObjectMap, prefix: &str) {
+        for (key, old_value) in old {
+            let path = field_path(prefix, key);
+            match (old_value, new.get(key)) {
+                (_, None) => {
+                    self.removed.insert(path.into(), old_value.clone());
+                }
+                (Value::Object(old_map), Some(Value::Object(new_map))) => {
+                    self.compare(old_map, new_map, &path);
+                }
+                (_, Some(new_value)) if old_value != new_value => {
+                    let change = ObjectMap::from([
+                        ("old".into(), old_value.clone()),
+                        ("new".into(), new_value.clone()),
+                    ]);
+                    self.changed.insert(path.into(), change.into());
+                }
+                _ => {}
+            }
+        }
+
+        for (key, new_value) in new {
+            if !old.contains_key(key) {
+                self.added
+                    .insert(field_path(prefix, key).into(), new_value.clone());
+            }
+        }
+    }
+}
+
+fn field_path(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{prefix}.{key}")
+    }
+}
+
+fn diff_kind() -> Kind {
+    let change = Kind::object(BTreeMap::from([
+        ("old".into(), Kind::any()),
+        ("new".into(), Kind::any()),
+    ]));
+
+    Kind::object(BTreeMap::from([
+        ("added".into(), Kind::object(Collection::any())),
+        ("removed".into(), Kind::object(Collection::any())),
+        (
+            "changed".into(),
+            Kind::object(Collection::from_unknown(change)),
+        ),
+    ]))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ObjectDiff;
+
+impl Function for ObjectDiff {
+    fn identifier(&self) -> &'static str {
+        "object_diff"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "old",
+                kind: kind::OBJECT,
+                required: true,
+            },
+            Parameter {
+                keyword: "new",
+                kind: kind::OBJECT,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "diff objects",
+            source: r#"object_diff({"a": 1, "b": {"c": 2}, "d": true}, {"a": 1, "b": {"c": 3}, "e": "x"})"#,
+            result: Ok(
+                r#"{"added": {"e": "x"}, "removed": {"d": true}, "changed": {"b.c": {"old": 2, "new": 3}}}"#,
+            ),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let old = arguments.required("old");
+        let new = arguments.required("new");
+
+        Ok(ObjectDiffFn { old, new }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ObjectDiffFn {
+    old: Box<dyn Expression>,
+    new: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ObjectDiffFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let old = self.old.resolve(ctx)?;
+        let new = self.new.resolve(ctx)?;
+
+        object_diff(old, new)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::from(diff_kind()).infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        object_diff => ObjectDiff;
+
+        added_key {
+            args: func_args![old: value!({"a": 1}), new: value!({"a": 1, "b": 2})],
+            want: Ok(value!({"added": {"b": 2}, "removed": {}, "changed": {}})),
+            tdef: TypeDef::from(diff_kind()).infallible(),
+        }
+
+        removed_key {
+            args: func_args![old: value!({"a": 1, "b": {"c": 2}}), new: value!({"a": 1})],
+            want: Ok(value!({"added": {}, "removed": {"b": {"c": 2}}, "changed": {}})),
+            tdef: TypeDef::from(diff_kind()).infallible(),
+        }
+
+        changed_nested_value {
+            args: func_args![
+                old: value!({"a": {"b": {"c": 1, "d": "x"}}}),
+                new: value!({"a": {"b": {"c": 2, "d": "x", "e": null}}}),
+            ],
+            want: Ok(value!({
+                "added": {"a.b.e": null},
+                "removed": {},
+                "changed": {"a.b.c": {"old": 1, "new": 2}},
+            })),
+            tdef: TypeDef::from(diff_kind()).infallible(),
+        }
+
+        changed_type {
+            args: func_args![old: value!({"a": {"b": 1}}), new: value!({"a": [1]})],
+            want: Ok(value!({"added": {}, "removed": {}, "changed": {"a": {"old": {"b": 1}, "new": [1]}}})),
+            tdef: TypeDef::from(diff_kind()).infallible(),
+        }
+
+        identical {
+            args: func_args![old: value!({"a": [1, 2]}), new: value!({"a": [1, 2]})],
+            want: Ok(value!({"added": {}, "removed": {}, "changed": {}})),
+            tdef: TypeDef::from(diff_kind()).infallible(),
+        }
+    ];
+}
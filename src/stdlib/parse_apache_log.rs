@@ -1,6 +1,7 @@
 use super::log_util;
 use crate::compiler::prelude::*;
 use crate::value;
+use std::borrow::Cow;
 use std::collections::BTreeMap;
 
 fn parse_apache_log(
@@ -10,9 +11,9 @@ fn parse_apache_log(
     ctx: &Context,
 ) -> Resolved {
     let message = bytes.try_bytes_utf8_lossy()?;
-    let timestamp_format = match timestamp_format {
-        None => "%d/%b/%Y:%T %z".to_owned(),
-        Some(timestamp_format) => timestamp_format.try_bytes_utf8_lossy()?.to_string(),
+    let timestamp_format = match &timestamp_format {
+        None => Cow::Borrowed("%d/%b/%Y:%T %z"),
+        Some(timestamp_format) => timestamp_format.try_bytes_utf8_lossy()?,
     };
     let regexes = match format.as_ref() {
         b"common" => &*log_util::REGEX_APACHE_COMMON_LOG,
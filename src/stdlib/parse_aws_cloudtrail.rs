@@ -0,0 +1,293 @@
+use crate::compiler::prelude::*;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AwsCloudTrailUserIdentity {
+    #[serde(rename = "type")]
+    identity_type: Option<String>,
+    arn: Option<String>,
+    account_id: Option<String>,
+    principal_id: Option<String>,
+    access_key_id: Option<String>,
+    user_name: Option<String>,
+}
+
+/// The commonly-used fields of a single CloudTrail event record.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AwsCloudTrailEvent {
+    event_name: String,
+    event_source: String,
+    event_time: DateTime<Utc>,
+    aws_region: Option<String>,
+    #[serde(rename = "sourceIPAddress")]
+    source_ip_address: Option<String>,
+    user_agent: Option<String>,
+    #[serde(default)]
+    user_identity: AwsCloudTrailUserIdentity,
+    recipient_account_id: Option<String>,
+    error_code: Option<String>,
+    error_message: Option<String>,
+    #[serde(rename = "requestID")]
+    request_id: Option<String>,
+    #[serde(rename = "eventID")]
+    event_id: Option<String>,
+    read_only: Option<bool>,
+}
+
+fn parse_aws_cloudtrail(bytes: Value) -> Resolved {
+    let bytes = bytes.try_bytes()?;
+    let json = serde_json::from_slice::<serde_json::Value>(&bytes)
+        .map_err(|e| format!("unable to parse: {e}"))?;
+    let event =
+        AwsCloudTrailEvent::deserialize(&json).map_err(|e| format!("unable to parse: {e}"))?;
+    let identity = event.user_identity;
+
+    Ok(Value::from(BTreeMap::from([
+        (KeyString::from("event_name"), Value::from(event.event_name)),
+        (
+            KeyString::from("event_source"),
+            Value::from(event.event_source),
+        ),
+        (KeyString::from("event_time"), Value::from(event.event_time)),
+        (KeyString::from("aws_region"), Value::from(event.aws_region)),
+        (
+            KeyString::from("source_ip_address"),
+            Value::from(event.source_ip_address),
+        ),
+        (KeyString::from("user_agent"), Value::from(event.user_agent)),
+        (
+            KeyString::from("user_type"),
+            Value::from(identity.identity_type),
+        ),
+        (KeyString::from("user_arn"), Value::from(identity.arn)),
+        (
+            KeyString::from("user_name"),
+            Value::from(identity.user_name),
+        ),
+        (
+            KeyString::from("account_id"),
+            Value::from(identity.account_id),
+        ),
+        (
+            KeyString::from("principal_id"),
+            Value::from(identity.principal_id),
+        ),
+        (
+            KeyString::from("access_key_id"),
+            Value::from(identity.access_key_id),
+        ),
+        (
+            KeyString::from("recipient_account_id"),
+            Value::from(event.recipient_account_id),
+        ),
+        (KeyString::from("error_code"), Value::from(event.error_code)),
+        (
+            KeyString::from("error_message"),
+            Value::from(event.error_message),
+        ),
+        (KeyString::from("request_id"), Value::from(event.request_id)),
+        (KeyString::from("event_id"), Value::from(event.event_id)),
+        (KeyString::from("read_only"), Value::from(event.read_only)),
+        (KeyString::from("record"), Value::from(json)),
+    ])))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseAwsCloudTrail;
+
+impl Function for ParseAwsCloudTrail {
+    fn identifier(&self) -> &'static str {
+        "parse_aws_cloudtrail"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "valid",
+            source: indoc! {r#"
+                parse_aws_cloudtrail!(s'{
+                    "eventVersion": "1.08",
+                    "userIdentity": {
+                        "type": "IAMUser",
+                        "arn": "arn:aws:iam::111111111111:user/alice",
+                        "accountId": "111111111111",
+                        "userName": "alice"
+                    },
+                    "eventTime": "2021-01-29T14:14:58Z",
+                    "eventSource": "s3.amazonaws.com",
+                    "eventName": "ListBuckets",
+                    "awsRegion": "us-east-1",
+                    "sourceIPAddress": "192.0.2.1",
+                    "readOnly": true
+                }').user_arn
+            "#},
+            result: Ok(r#""arn:aws:iam::111111111111:user/alice""#),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(ParseAwsCloudTrailFn { value }.as_expr())
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseAwsCloudTrailFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ParseAwsCloudTrailFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let bytes = self.value.resolve(ctx)?;
+        parse_aws_cloudtrail(bytes)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible(/* record parsing error */)
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        (Field::from("event_name"), Kind::bytes()),
+        (Field::from("event_source"), Kind::bytes()),
+        (Field::from("event_time"), Kind::timestamp()),
+        (Field::from("aws_region"), Kind::bytes().or_null()),
+        (Field::from("source_ip_address"), Kind::bytes().or_null()),
+        (Field::from("user_agent"), Kind::bytes().or_null()),
+        (Field::from("user_type"), Kind::bytes().or_null()),
+        (Field::from("user_arn"), Kind::bytes().or_null()),
+        (Field::from("user_name"), Kind::bytes().or_null()),
+        (Field::from("account_id"), Kind::bytes().or_null()),
+        (Field::from("principal_id"), Kind::bytes().or_null()),
+        (Field::from("access_key_id"), Kind::bytes().or_null()),
+        (Field::from("recipient_account_id"), Kind::bytes().or_null()),
+        (Field::from("error_code"), Kind::bytes().or_null()),
+        (Field::from("error_message"), Kind::bytes().or_null()),
+        (Field::from("request_id"), Kind::bytes().or_null()),
+        (Field::from("event_id"), Kind::bytes().or_null()),
+        (Field::from("read_only"), Kind::boolean().or_null()),
+        (Field::from("record"), Kind::object(Collection::any())),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+    use crate::value;
+
+    test_function![
+        parse_aws_cloudtrail => ParseAwsCloudTrail;
+
+        record {
+            args: func_args![value: r#"
+     {
+         "eventVersion": "1.08",
+         "userIdentity": {
+             "type": "AssumedRole",
+             "principalId": "AROAEXAMPLE:alice",
+             "arn": "arn:aws:sts::111111111111:assumed-role/Admin/alice",
+             "accountId": "111111111111",
+             "accessKeyId": "ASIAEXAMPLE"
+         },
+         "eventTime": "2021-01-29T14:14:58Z",
+         "eventSource": "ec2.amazonaws.com",
+         "eventName": "TerminateInstances",
+         "awsRegion": "us-east-1",
+         "sourceIPAddress": "192.0.2.1",
+         "userAgent": "aws-cli/2.0.0",
+         "errorCode": "Client.UnauthorizedOperation",
+         "errorMessage": "You are not authorized to perform this operation.",
+         "requestParameters": {
+             "instancesSet": {"items": [{"instanceId": "i-123456789012"}]}
+         },
+         "responseElements": null,
+         "requestID": "a1b2c3d4",
+         "eventID": "e5f6a7b8",
+         "readOnly": false,
+         "eventType": "AwsApiCall",
+         "recipientAccountId": "111111111111"
+     }
+     "#],
+            want: Ok(value!({
+                "event_name": "TerminateInstances",
+                "event_source": "ec2.amazonaws.com",
+                "event_time": (Utc.timestamp_opt(1_611_929_698, 0).single().expect("invalid timestamp")),
+                "aws_region": "us-east-1",
+                "source_ip_address": "192.0.2.1",
+                "user_agent": "aws-cli/2.0.0",
+                "user_type": "AssumedRole",
+                "user_arn": "arn:aws:sts::111111111111:assumed-role/Admin/alice",
+                "user_name": null,
+                "account_id": "111111111111",
+                "principal_id": "AROAEXAMPLE:alice",
+                "access_key_id": "ASIAEXAMPLE",
+                "recipient_account_id": "111111111111",
+                "error_code": "Client.UnauthorizedOperation",
+                "error_message": "You are not authorized to perform this operation.",
+                "request_id": "a1b2c3d4",
+                "event_id": "e5f6a7b8",
+                "read_only": false,
+                "record": {
+                    "eventVersion": "1.08",
+                    "userIdentity": {
+                        "type": "AssumedRole",
+                        "principalId": "AROAEXAMPLE:alice",
+                        "arn": "arn:aws:sts::111111111111:assumed-role/Admin/alice",
+                        "accountId": "111111111111",
+                        "accessKeyId": "ASIAEXAMPLE",
+                    },
+                    "eventTime": "2021-01-29T14:14:58Z",
+                    "eventSource": "ec2.amazonaws.com",
+                    "eventName": "TerminateInstances",
+                    "awsRegion": "us-east-1",
+                    "sourceIPAddress": "192.0.2.1",
+                    "userAgent": "aws-cli/2.0.0",
+                    "errorCode": "Client.UnauthorizedOperation",
+                    "errorMessage": "You are not authorized to perform this operation.",
+                    "requestParameters": {
+                        "instancesSet": {"items": [{"instanceId": "i-123456789012"}]},
+                    },
+                    "responseElements": null,
+                    "requestID": "a1b2c3d4",
+                    "eventID": "e5f6a7b8",
+                    "readOnly": false,
+                    "eventType": "AwsApiCall",
+                    "recipientAccountId": "111111111111",
+                },
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        missing_field {
+            args: func_args![value: r#"{"eventName": "ListBuckets", "eventTime": "2021-01-29T14:14:58Z"}"#],
+            want: Err("unable to parse: missing field `eventSource`"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        invalid_value {
+            args: func_args![value: "{ INVALID }"],
+            want: Err("unable to parse: key must be a string at line 1 column 3"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
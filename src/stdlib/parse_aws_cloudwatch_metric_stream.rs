@@ -0,0 +1,259 @@
+use crate::compiler::prelude::*;
+use chrono::{serde::ts_milliseconds, DateTime, Utc};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Deserialize)]
+struct AwsCloudWatchMetricStats {
+    max: f64,
+    min: f64,
+    sum: f64,
+    count: f64,
+    /// Percentile statistics such as `p99`, present when the stream is configured with
+    /// additional statistics.
+    #[serde(flatten)]
+    percentiles: BTreeMap<String, f64>,
+}
+
+/// A single record of a CloudWatch metric stream in JSON output format.
+#[derive(Debug, Deserialize)]
+struct AwsCloudWatchMetricStreamRecord {
+    metric_stream_name: String,
+    account_id: String,
+    region: String,
+    namespace: String,
+    metric_name: String,
+    dimensions: BTreeMap<String, String>,
+    #[serde(with = "ts_milliseconds")]
+    timestamp: DateTime<Utc>,
+    value: AwsCloudWatchMetricStats,
+    unit: String,
+}
+
+fn parse_aws_cloudwatch_metric_stream(bytes: Value) -> Resolved {
+    let bytes = bytes.try_bytes()?;
+    let record = serde_json::from_slice::<AwsCloudWatchMetricStreamRecord>(&bytes)
+        .map_err(|e| format!("unable to parse: {e}"))?;
+
+    let mut stats = ObjectMap::from([
+        (
+            KeyString::from("max"),
+            Value::from_f64_or_zero(record.value.max),
+        ),
+        (
+            KeyString::from("min"),
+            Value::from_f64_or_zero(record.value.min),
+        ),
+        (
+            KeyString::from("sum"),
+            Value::from_f64_or_zero(record.value.sum),
+        ),
+        (
+            KeyString::from("count"),
+            Value::from_f64_or_zero(record.value.count),
+        ),
+    ]);
+    for (statistic, value) in record.value.percentiles {
+        stats.insert(statistic.into(), Value::from_f64_or_zero(value));
+    }
+
+    let dimensions = record
+        .dimensions
+        .into_iter()
+        .map(|(name, value)| (KeyString::from(name), Value::from(value)))
+        .collect::<ObjectMap>();
+
+    Ok(Value::from(BTreeMap::from([
+        (
+            KeyString::from("metric_stream_name"),
+            Value::from(record.metric_stream_name),
+        ),
+        (
+            KeyString::from("account_id"),
+            Value::from(record.account_id),
+        ),
+        (KeyString::from("region"), Value::from(record.region)),
+        (KeyString::from("namespace"), Value::from(record.namespace)),
+        (
+            KeyString::from("metric_name"),
+            Value::from(record.metric_name),
+        ),
+        (KeyString::from("dimensions"), Value::from(dimensions)),
+        (KeyString::from("timestamp"), Value::from(record.timestamp)),
+        (KeyString::from("value"), Value::from(stats)),
+        (KeyString::from("unit"), Value::from(record.unit)),
+    ])))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseAwsCloudWatchMetricStream;
+
+impl Function for ParseAwsCloudWatchMetricStream {
+    fn identifier(&self) -> &'static str {
+        "parse_aws_cloudwatch_metric_stream"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "valid",
+            source: indoc! {r#"
+                parse_aws_cloudwatch_metric_stream!(s'{
+                    "metric_stream_name": "MyMetricStream",
+                    "account_id": "111111111111",
+                    "region": "us-east-1",
+                    "namespace": "AWS/EC2",
+                    "metric_name": "DiskWriteOps",
+                    "dimensions": {
+                        "InstanceId": "i-123456789012"
+                    },
+                    "timestamp": 1611929698000,
+                    "value": {
+                        "max": 3.0,
+                        "min": 0.0,
+                        "sum": 5.0,
+                        "count": 3.0
+                    },
+                    "unit": "Seconds"
+                }')
+            "#},
+            result: Ok(indoc! {r#"{
+                "account_id": "111111111111",
+                "dimensions": {"InstanceId": "i-123456789012"},
+                "metric_name": "DiskWriteOps",
+                "metric_stream_name": "MyMetricStream",
+                "namespace": "AWS/EC2",
+                "region": "us-east-1",
+                "timestamp": "2021-01-29T14:14:58Z",
+                "unit": "Seconds",
+                "value": {"count": 3.0, "max": 3.0, "min": 0.0, "sum": 5.0}
+            }"#}),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(ParseAwsCloudWatchMetricStreamFn { value }.as_expr())
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseAwsCloudWatchMetricStreamFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ParseAwsCloudWatchMetricStreamFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let bytes = self.value.resolve(ctx)?;
+        parse_aws_cloudwatch_metric_stream(bytes)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible(/* record parsing error */)
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        (Field::from("metric_stream_name"), Kind::bytes()),
+        (Field::from("account_id"), Kind::bytes()),
+        (Field::from("region"), Kind::bytes()),
+        (Field::from("namespace"), Kind::bytes()),
+        (Field::from("metric_name"), Kind::bytes()),
+        (
+            Field::from("dimensions"),
+            Kind::object(Collection::from_unknown(Kind::bytes())),
+        ),
+        (Field::from("timestamp"), Kind::timestamp()),
+        (
+            Field::from("value"),
+            Kind::object(Collection::from_parts(
+                BTreeMap::from([
+                    (Field::from("max"), Kind::float()),
+                    (Field::from("min"), Kind::float()),
+                    (Field::from("sum"), Kind::float()),
+                    (Field::from("count"), Kind::float()),
+                ]),
+                Kind::float(),
+            )),
+        ),
+        (Field::from("unit"), Kind::bytes()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+    use crate::value;
+
+    test_function![
+        parse_aws_cloudwatch_metric_stream => ParseAwsCloudWatchMetricStream;
+
+        record {
+            args: func_args![value: r#"
+     {
+         "metric_stream_name": "MyMetricStream",
+         "account_id": "111111111111",
+         "region": "us-east-1",
+         "namespace": "AWS/ApplicationELB",
+         "metric_name": "TargetResponseTime",
+         "dimensions": {
+             "LoadBalancer": "app/my-alb/50dc6c495c0c9188",
+             "AvailabilityZone": "us-east-1a"
+         },
+         "timestamp": 1611929698000,
+         "value": {
+             "max": 0.25,
+             "min": 0.005,
+             "sum": 1.5,
+             "count": 12.0,
+             "p99": 0.24
+         },
+         "unit": "Seconds"
+     }
+     "#],
+            want: Ok(value!({
+                "metric_stream_name": "MyMetricStream",
+                "account_id": "111111111111",
+                "region": "us-east-1",
+                "namespace": "AWS/ApplicationELB",
+                "metric_name": "TargetResponseTime",
+                "dimensions": {
+                    "AvailabilityZone": "us-east-1a",
+                    "LoadBalancer": "app/my-alb/50dc6c495c0c9188",
+                },
+                "timestamp": (Utc.timestamp_opt(1_611_929_698, 0).single().expect("invalid timestamp")),
+                "value": {"max": 0.25, "min": 0.005, "sum": 1.5, "count": 12.0, "p99": 0.24},
+                "unit": "Seconds",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        missing_field {
+            args: func_args![value: r#"{"metric_stream_name": "MyMetricStream"}"#],
+            want: Err("unable to parse: missing field `account_id` at line 1 column 40"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        invalid_value {
+            args: func_args![value: "{ INVALID }"],
+            want: Err("unable to parse: key must be a string at line 1 column 3"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
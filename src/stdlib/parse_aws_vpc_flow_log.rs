@@ -43,6 +43,15 @@ impl Function for ParseAwsVpcFlowLog {
                     "log_status":"NODATA"
                 }"# }),
             },
+            Example {
+                title: "AWS format string",
+                source: r#"parse_aws_vpc_flow_log!("eni-1235b8ca123456789 ACCEPT OK", "${interface-id} ${action} ${log-status}")"#,
+                result: Ok(indoc! { r#"{
+                    "interface_id": "eni-1235b8ca123456789",
+                    "action": "ACCEPT",
+                    "log_status": "OK"
+                }"# }),
+            },
             Example {
                 title: "custom format",
                 source: r#"parse_aws_vpc_flow_log!("- eni-1235b8ca123456789 10.0.1.5 10.0.0.220 10.0.1.5 203.0.113.5", "instance_id interface_id srcaddr dstaddr pkt_srcaddr pkt_dstaddr")"#,
@@ -188,6 +197,16 @@ macro_rules! create_match {
     };
 }
 
+/// Accepts field names either as listed in the parsed output (`account_id`) or as written
+/// in an AWS log format string (`${account-id}`).
+fn normalize_field(field: &str) -> String {
+    field
+        .strip_prefix("${")
+        .and_then(|field| field.strip_suffix('}'))
+        .unwrap_or(field)
+        .replace('-', "_")
+}
+
 fn parse_log(input: &str, format: Option<&str>) -> ParseResult<Value> {
     let mut log = BTreeMap::new();
 
@@ -199,8 +218,9 @@ fn parse_log(input: &str, format: Option<&str>) -> ParseResult<Value> {
     loop {
         return match (format.next(), input.next()) {
             (Some(key), Some(value)) => {
+                let key = normalize_field(key);
                 create_match!(
-                    log, key, value,
+                    log, key.as_str(), value,
                     "account_id" => identity,
                     "action" => identity,
                     "az_id" => identity,
@@ -357,5 +377,44 @@ mod tests {
              })),
              tdef: TypeDef::object(inner_kind()).fallible(),
          }
+
+        custom_subset_and_order {
+             args: func_args![value: "ACCEPT 443 10.0.0.62 52.213.180.42 OK",
+                              format: "action dstport srcaddr dstaddr log_status"],
+             want: Ok(value!({
+                 "action": "ACCEPT",
+                 "dstaddr": "52.213.180.42",
+                 "dstport": 443,
+                 "log_status": "OK",
+                 "srcaddr": "10.0.0.62",
+             })),
+             tdef: TypeDef::object(inner_kind()).fallible(),
+         }
+
+        aws_format_syntax {
+             args: func_args![value: "eni-1235b8ca123456789 123456789010 - egress",
+                              format: "${interface-id} ${account-id} ${pkt-src-aws-service} ${flow-direction}"],
+             want: Ok(value!({
+                 "account_id": "123456789010",
+                 "flow_direction": "egress",
+                 "interface_id": "eni-1235b8ca123456789",
+                 "pkt_src_aws_service": null,
+             })),
+             tdef: TypeDef::object(inner_kind()).fallible(),
+         }
+
+        unknown_field {
+             args: func_args![value: "ACCEPT 443",
+                              format: "action destination_port"],
+             want: Err("unknown key: `destination_port`"),
+             tdef: TypeDef::object(inner_kind()).fallible(),
+         }
+
+        too_few_fields {
+             args: func_args![value: "ACCEPT",
+                              format: "action dstport"],
+             want: Err("no item for key: `dstport`"),
+             tdef: TypeDef::object(inner_kind()).fallible(),
+         }
     ];
 }
@@ -2,21 +2,42 @@ use super::log_util;
 use crate::compiler::prelude::*;
 use std::collections::BTreeMap;
 
-fn parse_common_log(bytes: Value, timestamp_format: Option<Value>, ctx: &Context) -> Resolved {
+fn parse_common_log(
+    bytes: Value,
+    timestamp_format: Option<Value>,
+    raw: bool,
+    ctx: &Context,
+) -> Resolved {
     let message = bytes.try_bytes_utf8_lossy()?;
+    // `raw` skips the typing of `status`, `size` and `timestamp` and returns every matched
+    // field as the string it was captured as, with `-` fields dropped.
+    if raw {
+        return log_util::parse_message_raw(&log_util::REGEX_APACHE_COMMON_LOG, &message, "common")
+            .map_err(Into::into);
+    }
+
     let timestamp_format = match timestamp_format {
         None => "%d/%b/%Y:%T %z".to_owned(),
         Some(timestamp_format) => timestamp_format.try_bytes_utf8_lossy()?.to_string(),
     };
 
-    log_util::parse_message(
+    let mut log = log_util::parse_message(
         &log_util::REGEX_APACHE_COMMON_LOG,
         &message,
         &timestamp_format,
         ctx.timezone(),
         "common",
-    )
-    .map_err(Into::into)
+    )?;
+    // A response with a `-` size sent no body, which is kept as `null` rather than dropped.
+    // The regex only leaves `size` out next to a `status` when the size was a literal `-`, so
+    // lines without a response, such as one made up only of `-` fields, are left as they are.
+    if let Value::Object(fields) = &mut log {
+        if fields.contains_key("status") && !fields.contains_key("size") {
+            fields.insert("size".into(), Value::Null);
+        }
+    }
+
+    Ok(log)
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -39,31 +60,48 @@ impl Function for ParseCommonLog {
                 kind: kind::BYTES,
                 required: false,
             },
+            Parameter {
+                keyword: "raw",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
         ]
     }
 
     fn compile(
         &self,
-        _state: &state::TypeState,
+        state: &state::TypeState,
         _ctx: &mut FunctionCompileContext,
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
         let timestamp_format = arguments.optional("timestamp_format");
+        let raw = match arguments.optional_literal("raw", state)? {
+            Some(raw) => raw
+                .as_boolean()
+                .ok_or_else(|| function::Error::InvalidArgument {
+                    keyword: "raw",
+                    value: raw,
+                    error: "raw must be a boolean",
+                })?,
+            None => false,
+        };
 
         Ok(ParseCommonLogFn {
             value,
             timestamp_format,
+            raw,
         }
         .as_expr())
     }
 
     fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "parse common log",
-            source: r#"parse_common_log!(s'127.0.0.1 bob frank [10/Oct/2000:13:55:36 -0700] "GET /apache_pb.gif HTTP/1.0" 200 2326')"#,
-            result: Ok(indoc! {
-                r#"{
+        &[
+            Example {
+                title: "parse common log",
+                source: r#"parse_common_log!(s'127.0.0.1 bob frank [10/Oct/2000:13:55:36 -0700] "GET /apache_pb.gif HTTP/1.0" 200 2326')"#,
+                result: Ok(indoc! {
+                    r#"{
                     "host":"127.0.0.1",
                     "identity":"bob",
                     "message":"GET /apache_pb.gif HTTP/1.0",
@@ -75,8 +113,26 @@ impl Function for ParseCommonLog {
                     "timestamp":"2000-10-10T20:55:36Z",
                     "user":"frank"
                 }"#
-            }),
-        }]
+                }),
+            },
+            Example {
+                title: "parse common log without typing fields",
+                source: r#"parse_common_log!(s'127.0.0.1 bob frank [10/Oct/2000:13:55:36 -0700] "GET /apache_pb.gif HTTP/1.0" 200 -', raw: true)"#,
+                result: Ok(indoc! {
+                    r#"{
+                    "host":"127.0.0.1",
+                    "identity":"bob",
+                    "message":"GET /apache_pb.gif HTTP/1.0",
+                    "method":"GET",
+                    "path":"/apache_pb.gif",
+                    "protocol":"HTTP/1.0",
+                    "status":"200",
+                    "timestamp":"10/Oct/2000:13:55:36 -0700",
+                    "user":"frank"
+                }"#
+                }),
+            },
+        ]
     }
 }
 
@@ -84,6 +140,7 @@ impl Function for ParseCommonLog {
 struct ParseCommonLogFn {
     value: Box<dyn Expression>,
     timestamp_format: Option<Box<dyn Expression>>,
+    raw: bool,
 }
 
 impl FunctionExpression for ParseCommonLogFn {
@@ -95,11 +152,15 @@ impl FunctionExpression for ParseCommonLogFn {
             .map(|expr| expr.resolve(ctx))
             .transpose()?;
 
-        parse_common_log(bytes, timestamp_format, ctx)
+        parse_common_log(bytes, timestamp_format, self.raw, ctx)
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
-        TypeDef::object(inner_kind()).fallible()
+        if self.raw {
+            TypeDef::object(raw_inner_kind()).fallible()
+        } else {
+            TypeDef::object(inner_kind()).fallible()
+        }
     }
 }
 
@@ -118,6 +179,13 @@ fn inner_kind() -> BTreeMap<Field, Kind> {
     ])
 }
 
+fn raw_inner_kind() -> BTreeMap<Field, Kind> {
+    inner_kind()
+        .into_keys()
+        .map(|field| (field, Kind::bytes() | Kind::null()))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::btreemap;
@@ -147,13 +215,13 @@ mod tests {
 
         log_line_valid_empty {
             args: func_args![value: "- - - - - - -"],
-            want: Ok(BTreeMap::new()),
+            want: Ok(btreemap! {}),
             tdef: TypeDef::object(inner_kind()).fallible(),
         }
 
         log_line_valid_empty_variant {
             args: func_args![value: r#"- - - [-] "-" - -"#],
-            want: Ok(BTreeMap::new()),
+            want: Ok(btreemap! {}),
             tdef: TypeDef::object(inner_kind()).fallible(),
         }
 
@@ -167,6 +235,37 @@ mod tests {
             tdef: TypeDef::object(inner_kind()).fallible(),
         }
 
+        log_line_typed_status_and_missing_size {
+            args: func_args![value: r#"10.0.0.5 - - [10/Oct/2000:13:55:36 -0700] "HEAD /health HTTP/1.1" 304 -"#],
+            want: Ok(btreemap! {
+                "host" => "10.0.0.5",
+                "timestamp" => Value::Timestamp(DateTime::parse_from_rfc3339("2000-10-10T20:55:36Z").unwrap().into()),
+                "message" => "HEAD /health HTTP/1.1",
+                "method" => "HEAD",
+                "path" => "/health",
+                "protocol" => "HTTP/1.1",
+                "status" => 304,
+                "size" => Value::Null,
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        log_line_raw {
+            args: func_args![value: r#"10.0.0.5 - - [10/Oct/2000:13:55:36 -0700] "HEAD /health HTTP/1.1" 304 -"#,
+                             raw: true,
+            ],
+            want: Ok(btreemap! {
+                "host" => "10.0.0.5",
+                "timestamp" => "10/Oct/2000:13:55:36 -0700",
+                "message" => "HEAD /health HTTP/1.1",
+                "method" => "HEAD",
+                "path" => "/health",
+                "protocol" => "HTTP/1.1",
+                "status" => "304",
+            }),
+            tdef: TypeDef::object(raw_inner_kind()).fallible(),
+        }
+
         log_line_invalid {
             args: func_args![value: "not a common log line"],
             want: Err("failed parsing common log line"),
@@ -1,36 +1,232 @@
 use crate::compiler::prelude::*;
-use quick_csv::Csv;
+use bytes::BytesMut;
+use quick_csv::{Csv, Row};
 use std::io::Cursor;
+use std::sync::{Arc, Mutex, TryLockError};
 
-fn parse_csv(csv_string: Value, delimiter: Value) -> Resolved {
+/// The key holding columns beyond the header, when `flexible` is set.
+const OVERFLOW_KEY: &str = "_overflow";
+
+#[derive(Debug, Clone, Copy)]
+struct CsvOptions {
+    delimiter: u8,
+    lenient: bool,
+    trim: bool,
+    flexible: bool,
+}
+
+/// Buffers kept between calls, so that they aren't allocated afresh for every event.
+#[derive(Debug, Default)]
+struct Scratch {
+    /// The input without its comments.
+    stripped: Vec<u8>,
+    /// Whether each column of the record being read is quoted.
+    quoted: Vec<bool>,
+}
+
+/// Returns the first line of the input as a single element array.
+fn raw_line(csv_string: &[u8]) -> Value {
+    let line = csv_string.split(|b| *b == b'\n').next().unwrap_or_default();
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    Value::Array(vec![Bytes::copy_from_slice(line).into()])
+}
+
+/// Splits the input into its first record, without the line terminator, and the rest.
+fn split_record(csv_string: &[u8]) -> (&[u8], &[u8]) {
+    let mut in_quotes = false;
+    for (i, byte) in csv_string.iter().enumerate() {
+        match *byte {
+            b'"' => in_quotes = !in_quotes,
+            b'\n' if !in_quotes => {
+                let record = &csv_string[..i];
+                let record = record.strip_suffix(b"\r").unwrap_or(record);
+                return (record, &csv_string[i + 1..]);
+            }
+            _ => {}
+        }
+    }
+    (csv_string, &[])
+}
+
+/// Returns whether each column of a record is quoted, since `quick_csv` strips the quotes
+/// before handing columns out.
+fn quoted_columns(record: &[u8], delimiter: u8, quoted: &mut Vec<bool>) {
+    quoted.push(record.first() == Some(&b'"'));
+    let mut in_quotes = false;
+    for (i, byte) in record.iter().enumerate() {
+        match *byte {
+            b'"' => in_quotes = !in_quotes,
+            byte if byte == delimiter && !in_quotes => {
+                quoted.push(record.get(i + 1) == Some(&b'"'));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn columns<'a>(
+    row: &'a Row,
+    raw: &[u8],
+    options: &CsvOptions,
+    quoted: &'a mut Vec<bool>,
+) -> impl Iterator<Item = Bytes> + 'a {
+    // Quoted columns keep their whitespace, as it was put there on purpose.
+    quoted.clear();
+    if options.trim {
+        quoted_columns(raw, options.delimiter, quoted);
+    }
+    let quoted = &*quoted;
+    let trim = options.trim;
+
+    // The columns of a record share one allocation, which they are split off from. Sharing it
+    // costs an allocation of its own, so it only pays off from three columns on.
+    let mut buffer = (row.len() > 2).then(|| BytesMut::with_capacity(raw.len()));
+    row.bytes_columns().enumerate().map(move |(i, x)| {
+        let x = if trim && !quoted.get(i).copied().unwrap_or_default() {
+            x.trim_ascii()
+        } else {
+            x
+        };
+        match &mut buffer {
+            Some(buffer) => {
+                buffer.extend_from_slice(x);
+                buffer.split().freeze()
+            }
+            None => Bytes::copy_from_slice(x),
+        }
+    })
+}
+
+/// Reads the next record, returning it along with its raw bytes or, when `lenient` is set and
+/// the record is malformed, the raw record as a single element array.
+fn next_record<'a, E: std::fmt::Display>(
+    records: &mut impl Iterator<Item = Result<Row, E>>,
+    input: &mut &'a [u8],
+    options: &CsvOptions,
+) -> Result<Option<(Row, &'a [u8])>, Resolved> {
+    let start = *input;
+    let (raw, rest) = split_record(start);
+    *input = rest;
+
+    match records.next().transpose() {
+        Ok(row) => Ok(row.map(|row| (row, raw))),
+        // Hand the malformed record back untouched so it can be routed elsewhere, rather than
+        // failing the whole event. Its quotes can't be trusted to find where it ends, so only
+        // its first line is kept.
+        Err(_) if options.lenient => Err(Ok(raw_line(start))),
+        Err(err) => Err(Err(format!("invalid csv record: {err}").into())),
+    }
+}
+
+/// Maps the record onto the header. Short records are padded with `null` and the columns of
+/// long records that have no header are kept under `_overflow`.
+fn record_object(
+    header: Vec<KeyString>,
+    record: Option<(Row, &[u8])>,
+    options: &CsvOptions,
+    quoted: &mut Vec<bool>,
+) -> Value {
+    let Some((row, raw)) = record else {
+        return header
+            .into_iter()
+            .map(|key| (key, Value::Null))
+            .collect::<ObjectMap>()
+            .into();
+    };
+
+    let overflow_len = row.len().saturating_sub(header.len());
+    let mut record = columns(&row, raw, options, quoted).map(Value::from);
+    let mut object = header
+        .into_iter()
+        .map(|key| (key, record.next().unwrap_or(Value::Null)))
+        .collect::<ObjectMap>();
+    if overflow_len > 0 {
+        let mut overflow = Vec::with_capacity(overflow_len);
+        overflow.extend(record);
+        object.insert(OVERFLOW_KEY.into(), overflow.into());
+    }
+    object.into()
+}
+
+/// Drops the lines starting with `comment`, which quick_csv has no notion of. Only the first
+/// `records` records are kept, as the rest would never be read.
+fn strip_comments(mut csv_string: &[u8], comment: u8, records: usize, stripped: &mut Vec<u8>) {
+    stripped.clear();
+    let mut kept = 0;
+    while !csv_string.is_empty() && kept < records {
+        // Quotes in comments are free text, so comments end at the first newline.
+        if csv_string.first() == Some(&comment) {
+            let end = csv_string.iter().position(|b| *b == b'\n');
+            csv_string = end.map_or(&[], |end| &csv_string[end + 1..]);
+            continue;
+        }
+
+        let (record, rest) = split_record(csv_string);
+        stripped.extend_from_slice(record);
+        stripped.push(b'\n');
+        csv_string = rest;
+        kept += 1;
+    }
+}
+
+fn single_byte(value: Value, keyword: &str) -> Result<u8, ExpressionError> {
+    match value.try_bytes()?.as_ref() {
+        [byte] => Ok(*byte),
+        _ => Err(format!("{keyword} must be a single character").into()),
+    }
+}
+
+fn parse_csv(
+    csv_string: Value,
+    delimiter: Value,
+    comment: Option<Value>,
+    headers: bool,
+    options: CsvOptions,
+    scratch: &mut Scratch,
+) -> Resolved {
     let csv_string = csv_string.try_bytes()?;
-    let delimiter = delimiter.try_bytes()?;
-    if delimiter.len() != 1 {
-        return Err("delimiter must be a single character".into());
+    let options = CsvOptions {
+        delimiter: single_byte(delimiter, "delimiter")?,
+        ..options
+    };
+    let mut input = match comment {
+        Some(comment) => {
+            let comment = single_byte(comment, "comment")?;
+            let records = if headers { 2 } else { 1 };
+            strip_comments(&csv_string, comment, records, &mut scratch.stripped);
+            &*scratch.stripped
+        }
+        None => &*csv_string,
+    };
+
+    // Without `flexible`, quick_csv rejects records whose length differs from the first one.
+    let mut records = Csv::from_reader(Cursor::new(input))
+        .delimiter(options.delimiter)
+        .flexible(options.flexible);
+
+    let first = match next_record(&mut records, &mut input, &options) {
+        Ok(Some((row, raw))) => {
+            if !headers {
+                let mut values = Vec::with_capacity(row.len());
+                values.extend(columns(&row, raw, &options, &mut scratch.quoted).map(Value::from));
+                return Ok(values.into());
+            }
+            let mut header = Vec::with_capacity(row.len());
+            header.extend(
+                columns(&row, raw, &options, &mut scratch.quoted)
+                    .map(|key| KeyString::from(String::from_utf8_lossy(&key).as_ref())),
+            );
+            header
+        }
+        Ok(None) if !headers => return Ok(Value::Array(Vec::new())),
+        Ok(None) => Vec::new(),
+        Err(resolved) => return resolved,
+    };
+
+    match next_record(&mut records, &mut input, &options) {
+        Ok(record) => Ok(record_object(first, record, &options, &mut scratch.quoted)),
+        Err(resolved) => resolved,
     }
-    let delimiter = delimiter[0];
-
-    let csv = Csv::from_reader(Cursor::new(&*csv_string))
-        .delimiter(delimiter);
-
-    let result = csv.into_iter()
-        .next()
-        .transpose()
-        .map_err(|err| format!("invalid csv record: {err}").into())
-        .map(|record| {
-            record
-                .map(|record| {
-                    // Use byte_columns() to get an iterator over byte slices
-                    record
-                        .bytes_columns()
-                        .map(|x| Bytes::copy_from_slice(x).into())
-                        .collect::<Vec<Value>>()
-                })
-                .unwrap_or_default()
-                .into()
-        });
-
-    result
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -42,22 +238,77 @@ impl Function for ParseCsv {
     }
 
     fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "parse a single CSV formatted row",
-            source: r#"parse_csv!(s'foo,bar,"foo "", bar"')"#,
-            result: Ok(r#"["foo", "bar", "foo \", bar"]"#),
-        }]
+        &[
+            Example {
+                title: "parse a single CSV formatted row",
+                source: r#"parse_csv!(s'foo,bar,"foo "", bar"')"#,
+                result: Ok(r#"["foo", "bar", "foo \", bar"]"#),
+            },
+            Example {
+                title: "trim unquoted fields",
+                source: r#"parse_csv!(s' foo ," bar "', trim: true)"#,
+                result: Ok(r#"["foo", " bar "]"#),
+            },
+            Example {
+                title: "keep a malformed row",
+                source: r#"parse_csv!(s'foo,b"ar', lenient: true)"#,
+                result: Ok(r#"["foo,b\"ar"]"#),
+            },
+            Example {
+                title: "skip comment lines",
+                source: r##"parse_csv!("# generated by exporter\nfoo,bar", comment: "#")"##,
+                result: Ok(r#"["foo", "bar"]"#),
+            },
+            Example {
+                title: "parse a row with a header",
+                source: r#"parse_csv!("host,status,bytes\nweb-1,200,512,gzip", headers: true, flexible: true)"#,
+                result: Ok(
+                    r#"{"host": "web-1", "status": "200", "bytes": "512", "_overflow": ["gzip"]}"#,
+                ),
+            },
+        ]
+    }
+
+    fn fold(&self) -> bool {
+        true
     }
 
     fn compile(
         &self,
-        _state: &state::TypeState,
+        state: &state::TypeState,
         _ctx: &mut FunctionCompileContext,
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
         let delimiter = arguments.optional("delimiter").unwrap_or(expr!(","));
-        Ok(ParseCsvFn { value, delimiter }.as_expr())
+        let lenient = arguments.optional("lenient").unwrap_or(expr!(false));
+        let trim = arguments.optional("trim").unwrap_or(expr!(false));
+        let flexible = arguments.optional("flexible").unwrap_or(expr!(false));
+        let comment = arguments.optional("comment");
+        // This changes the return type, so it has to be known at compile time.
+        let headers = match arguments.optional_literal("headers", state)? {
+            Some(headers) => {
+                headers
+                    .as_boolean()
+                    .ok_or_else(|| function::Error::InvalidArgument {
+                        keyword: "headers",
+                        value: headers,
+                        error: "headers must be a boolean",
+                    })?
+            }
+            None => false,
+        };
+        Ok(ParseCsvFn {
+            value,
+            delimiter,
+            lenient,
+            trim,
+            flexible,
+            comment,
+            headers,
+            scratch: Arc::default(),
+        }
+        .as_expr())
     }
 
     fn parameters(&self) -> &'static [Parameter] {
@@ -72,6 +323,31 @@ impl Function for ParseCsv {
                 kind: kind::BYTES,
                 required: false,
             },
+            Parameter {
+                keyword: "lenient",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "trim",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "headers",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "flexible",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "comment",
+                kind: kind::BYTES,
+                required: false,
+            },
         ]
     }
 }
@@ -80,18 +356,60 @@ impl Function for ParseCsv {
 struct ParseCsvFn {
     value: Box<dyn Expression>,
     delimiter: Box<dyn Expression>,
+    lenient: Box<dyn Expression>,
+    trim: Box<dyn Expression>,
+    flexible: Box<dyn Expression>,
+    comment: Option<Box<dyn Expression>>,
+    headers: bool,
+    scratch: Arc<Mutex<Scratch>>,
 }
 
 impl FunctionExpression for ParseCsvFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let csv_string = self.value.resolve(ctx)?;
         let delimiter = self.delimiter.resolve(ctx)?;
-
-        parse_csv(csv_string, delimiter)
+        let options = CsvOptions {
+            delimiter: b',',
+            lenient: self.lenient.resolve(ctx)?.try_boolean()?,
+            trim: self.trim.resolve(ctx)?.try_boolean()?,
+            flexible: self.flexible.resolve(ctx)?.try_boolean()?,
+        };
+
+        let comment = self
+            .comment
+            .as_ref()
+            .map(|comment| comment.resolve(ctx))
+            .transpose()?;
+
+        // Calls running at the same time on other threads use buffers of their own rather than
+        // waiting for these ones.
+        let mut scratch = match self.scratch.try_lock() {
+            Ok(scratch) => Some(scratch),
+            Err(TryLockError::Poisoned(poisoned)) => Some(poisoned.into_inner()),
+            Err(TryLockError::WouldBlock) => None,
+        };
+        let mut fallback = Scratch::default();
+        let scratch = scratch.as_deref_mut().unwrap_or(&mut fallback);
+
+        parse_csv(
+            csv_string,
+            delimiter,
+            comment,
+            self.headers,
+            options,
+            scratch,
+        )
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
-        TypeDef::array(inner_kind()).fallible()
+        if self.headers {
+            // A malformed record is still returned as an array when `lenient` is set.
+            TypeDef::object(header_kind())
+                .or_array(inner_kind())
+                .fallible()
+        } else {
+            TypeDef::array(inner_kind()).fallible()
+        }
     }
 }
 
@@ -102,9 +420,16 @@ fn inner_kind() -> Collection<Index> {
     v
 }
 
+fn header_kind() -> Collection<Field> {
+    Collection::from_unknown(
+        Kind::bytes() | Kind::null() | Kind::array(Collection::from_unknown(Kind::bytes())),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::compiler::{function::ArgumentList, CompileConfig};
     use crate::value;
 
     test_function![
@@ -194,6 +519,78 @@ mod tests {
            tdef: TypeDef::array(inner_kind()).fallible(),
        }
 
+       whitespace_trimmed {
+           args: func_args![value: value!(" field1 ,\tfield2 ,field3 "), trim: true],
+           want: Ok(value!(["field1", "field2", "field3"])),
+           tdef: TypeDef::array(inner_kind()).fallible(),
+       }
+
+       quoted_whitespace_trimmed {
+           args: func_args![value: value!("\" field1 \", field2 ,\" field, 3 \"\n next "), trim: true],
+           want: Ok(value!([" field1 ", "field2", " field, 3 "])),
+           tdef: TypeDef::array(inner_kind()).fallible(),
+       }
+
+       header {
+           args: func_args![value: value!("host,status\r\nweb-1,200\nweb-2,500"), headers: true],
+           want: Ok(value!({"host": "web-1", "status": "200"})),
+           tdef: TypeDef::object(header_kind()).or_array(inner_kind()).fallible(),
+       }
+
+       header_only {
+           args: func_args![value: value!("host,status"), headers: true],
+           want: Ok(value!({"host": null, "status": null})),
+           tdef: TypeDef::object(header_kind()).or_array(inner_kind()).fallible(),
+       }
+
+       header_short_row {
+           args: func_args![value: value!("host,status,bytes\nweb-1,200"), headers: true],
+           want: Err("invalid csv record: Expectiong 3 columns, found 2"),
+           tdef: TypeDef::object(header_kind()).or_array(inner_kind()).fallible(),
+       }
+
+       header_short_row_flexible {
+           args: func_args![value: value!("host,status,bytes\nweb-1,200"), headers: true, flexible: true],
+           want: Ok(value!({"host": "web-1", "status": "200", "bytes": null})),
+           tdef: TypeDef::object(header_kind()).or_array(inner_kind()).fallible(),
+       }
+
+       header_long_row_flexible {
+           args: func_args![value: value!("host,status\nweb-1,200,512,gzip"), headers: true, flexible: true],
+           want: Ok(value!({"host": "web-1", "status": "200", "_overflow": ["512", "gzip"]})),
+           tdef: TypeDef::object(header_kind()).or_array(inner_kind()).fallible(),
+       }
+
+       header_trimmed_lenient {
+           args: func_args![value: value!(" host , status \n web-1 ,\" 200 \"\nbad,ro\"w"), headers: true, trim: true, lenient: true],
+           want: Ok(value!({"host": "web-1", "status": " 200 "})),
+           tdef: TypeDef::object(header_kind()).or_array(inner_kind()).fallible(),
+       }
+
+       header_malformed_lenient {
+           args: func_args![value: value!("host,status\nweb\"-1,200"), headers: true, lenient: true],
+           want: Ok(value!(["web\"-1,200"])),
+           tdef: TypeDef::object(header_kind()).or_array(inner_kind()).fallible(),
+       }
+
+       comment_lines_skipped {
+           args: func_args![value: value!("# exported \"daily\"\r\n#host,status\nhost,status\n# web-0,000\nweb-1,200"), headers: true, comment: "#"],
+           want: Ok(value!({"host": "web-1", "status": "200"})),
+           tdef: TypeDef::object(header_kind()).or_array(inner_kind()).fallible(),
+       }
+
+       comment_in_quoted_field {
+           args: func_args![value: value!("\"multi\n#line\",field2"), comment: "#"],
+           want: Ok(value!(["multi\n#line", "field2"])),
+           tdef: TypeDef::array(inner_kind()).fallible(),
+       }
+
+       invalid_comment {
+           args: func_args![value: value!("foo,bar"), comment: "//"],
+           want: Err("comment must be a single character"),
+           tdef: TypeDef::array(inner_kind()).fallible(),
+       }
+
        newlines_in_quoted_fields {
            args: func_args![value: value!("\"field\nwith\nnewlines\",normal")],
            want: Ok(value!(["field\nwith\nnewlines", "normal"])),
@@ -261,6 +658,18 @@ mod tests {
            tdef: TypeDef::array(inner_kind()).fallible(),
        }
 
+       malformed_quotes_embedded_lenient {
+           args: func_args![value: value!("field1,fie\"ld2,field3\r\nnext,line"), lenient: true],
+           want: Ok(value!(["field1,fie\"ld2,field3"])),
+           tdef: TypeDef::array(inner_kind()).fallible(),
+       }
+
+       valid_lenient {
+           args: func_args![value: value!("field1,field2"), lenient: true],
+           want: Ok(value!(["field1", "field2"])),
+           tdef: TypeDef::array(inner_kind()).fallible(),
+       }
+
        empty_delimiter {
            args: func_args![value: value!("foo,bar"), delimiter: value!("")],
            want: Err("delimiter must be a single character"),
@@ -292,4 +701,75 @@ mod tests {
        }
 
     ];
+
+    /// A header of `columns` columns followed by `rows` commented records, each one column
+    /// longer than the header.
+    fn large_csv(columns: usize, rows: usize) -> String {
+        let header = (0..columns).map(|i| format!("col{i}")).collect::<Vec<_>>();
+        let record = (0..=columns).map(|i| format!("val{i}")).collect::<Vec<_>>();
+        let record = format!("\n# comment\n{}", record.join(","));
+        header.join(",") + &record.repeat(rows)
+    }
+
+    fn resolve(args: ArgumentList) -> Resolved {
+        let state = state::TypeState::default();
+        let mut ctx = FunctionCompileContext::new(Span::default(), CompileConfig::default());
+        let expression = ParseCsv.compile(&state, &mut ctx, args).unwrap();
+        let mut target = Value::Object(ObjectMap::new());
+        let mut runtime_state = state::RuntimeState::default();
+        let tz = TimeZone::default();
+        expression.resolve(&mut Context::new(&mut target, &mut runtime_state, &tz))
+    }
+
+    #[test]
+    fn large_csv_with_headers() {
+        let csv = large_csv(1_000, 10_000);
+        let got = resolve(ArgumentList::from(
+            func_args![value: csv, headers: true, flexible: true, comment: "#"],
+        ))
+        .unwrap();
+
+        let object = got.as_object().unwrap();
+        assert_eq!(object.len(), 1_001);
+        assert_eq!(object.get("col0"), Some(&value!("val0")));
+        assert_eq!(object.get("col999"), Some(&value!("val999")));
+        assert_eq!(object.get(OVERFLOW_KEY), Some(&value!(["val1000"])));
+    }
+
+    #[test]
+    fn large_csv_without_headers() {
+        let csv = large_csv(1_000, 10_000);
+        let got = resolve(ArgumentList::from(func_args![value: csv, comment: "#"])).unwrap();
+
+        let array = got.as_array().unwrap();
+        assert_eq!(array.len(), 1_000);
+        assert_eq!(array.last(), Some(&value!("col999")));
+    }
+
+    #[test]
+    fn scratch_reused_between_calls() {
+        let options = CsvOptions {
+            delimiter: b',',
+            lenient: false,
+            trim: true,
+            flexible: true,
+        };
+        let mut scratch = Scratch::default();
+        let mut parse = |csv: &str| {
+            parse_csv(
+                value!(csv),
+                value!(","),
+                Some(value!("#")),
+                true,
+                options,
+                &mut scratch,
+            )
+        };
+
+        assert_eq!(
+            parse("# first\n a ,\" b \",c\n 1 , 2 ,3,4"),
+            Ok(value!({a: "1", " b ": "2", c: "3", _overflow: ["4"]}))
+        );
+        assert_eq!(parse("x\n\" y \""), Ok(value!({x: " y "})));
+    }
 }
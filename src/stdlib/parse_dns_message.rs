@@ -0,0 +1,194 @@
+use crate::compiler::prelude::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+fn parse_dns_message(value: Value) -> Resolved {
+    use domain::base::Message;
+
+    let message = Message::from_octets(value.try_bytes()?)
+        .map_err(|err| format!("unable to parse DNS message: {err}"))?;
+
+    Ok(super::dns_lookup::parse_message(&message)?.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseDnsMessage;
+
+impl Function for ParseDnsMessage {
+    fn identifier(&self) -> &'static str {
+        "parse_dns_message"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "A record response",
+            source: r#"parse_dns_message!(decode_base64!("EjSBgAABAAEAAAAAB2V4YW1wbGUDY29tAAABAAHADAABAAEAAAEsAARduNgi")).answers"#,
+            result: Ok(indoc! {r#"[
+                {
+                    "class": "IN",
+                    "domainName": "example.com",
+                    "rData": "93.184.216.34",
+                    "recordType": "A",
+                    "recordTypeId": 1,
+                    "ttl": 300
+                }
+            ]"#}),
+        }]
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(ParseDnsMessageFn { value }.as_expr())
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        ctx: &mut FunctionCompileContext,
+        _arguments: ArgumentList,
+    ) -> Compiled {
+        Ok(super::WasmUnsupportedFunction::new(
+            ctx.span(),
+            TypeDef::object(Collection::any()).fallible(),
+        )
+        .as_expr())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone)]
+struct ParseDnsMessageFn {
+    value: Box<dyn Expression>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FunctionExpression for ParseDnsMessageFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        parse_dns_message(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(super::dns_lookup::inner_kind()).fallible(/* malformed message */)
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use crate::stdlib::dns_lookup::inner_kind;
+    use crate::value;
+
+    const QUERY: &[u8] = b"\x12\x34\x01\x00\x00\x01\x00\x00\x00\x00\x00\x00\
+        \x07example\x03com\x00\x00\x01\x00\x01";
+
+    const RESPONSE: &[u8] = b"\x12\x34\x81\x80\x00\x01\x00\x01\x00\x00\x00\x00\
+        \x07example\x03com\x00\x00\x01\x00\x01\
+        \xc0\x0c\x00\x01\x00\x01\x00\x00\x01\x2c\x00\x04\x5d\xb8\xd8\x22";
+
+    test_function![
+        parse_dns_message => ParseDnsMessage;
+
+        a_record_query {
+            args: func_args![value: Bytes::from_static(QUERY)],
+            want: Ok(value!({
+                "fullRcode": 0,
+                "rcodeName": "NOERROR",
+                "header": {
+                    "aa": false,
+                    "ad": false,
+                    "anCount": 0,
+                    "arCount": 0,
+                    "cd": false,
+                    "nsCount": 0,
+                    "opcode": 0,
+                    "qdCount": 1,
+                    "qr": false,
+                    "ra": false,
+                    "rcode": 0,
+                    "rd": true,
+                    "tc": false,
+                },
+                "question": [{
+                    "class": "IN",
+                    "domainName": "example.com",
+                    "questionType": "A",
+                    "questionTypeId": 1,
+                }],
+                "answers": [],
+                "authority": [],
+                "additional": [],
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        a_record_response {
+            args: func_args![value: Bytes::from_static(RESPONSE)],
+            want: Ok(value!({
+                "fullRcode": 0,
+                "rcodeName": "NOERROR",
+                "header": {
+                    "aa": false,
+                    "ad": false,
+                    "anCount": 1,
+                    "arCount": 0,
+                    "cd": false,
+                    "nsCount": 0,
+                    "opcode": 0,
+                    "qdCount": 1,
+                    "qr": true,
+                    "ra": true,
+                    "rcode": 0,
+                    "rd": true,
+                    "tc": false,
+                },
+                "question": [{
+                    "class": "IN",
+                    "domainName": "example.com",
+                    "questionType": "A",
+                    "questionTypeId": 1,
+                }],
+                "answers": [{
+                    "class": "IN",
+                    "domainName": "example.com",
+                    "rData": "93.184.216.34",
+                    "recordType": "A",
+                    "recordTypeId": 1,
+                    "ttl": 300,
+                }],
+                "authority": [],
+                "additional": [],
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        short_header {
+            args: func_args![value: Bytes::from_static(b"\x12\x34\x81")],
+            want: Err("unable to parse DNS message: short message"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+
+    #[test]
+    fn truncated_answer() {
+        let truncated = Bytes::from_static(&RESPONSE[..RESPONSE.len() - 2]);
+
+        assert!(parse_dns_message(truncated.into()).is_err());
+    }
+}
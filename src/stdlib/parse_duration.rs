@@ -4,13 +4,19 @@ use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::time::Duration;
 
-fn parse_duration(bytes: Value, unit: Value) -> Resolved {
-    let bytes = bytes.try_bytes()?;
-    let value = String::from_utf8_lossy(&bytes);
+/// Parses a duration string such as `1h30m` or `1s 500ms` into a [`Duration`].
+pub(super) fn parse_duration_str(value: &str) -> Result<Duration, String> {
     // Remove all spaces and replace the micro symbol with the ASCII equivalent
     // since the `humantime` does not support them.
     let trimmed_value = value.replace(' ', "").replace("µs", "us");
 
+    ht_parse_duration(&trimmed_value).map_err(|e| format!("unable to parse duration: '{e}'"))
+}
+
+fn parse_duration(bytes: Value, unit: Value) -> Resolved {
+    let bytes = bytes.try_bytes()?;
+    let value = String::from_utf8_lossy(&bytes);
+
     let conversion_factor = {
         let bytes = unit.try_bytes()?;
         let string = String::from_utf8_lossy(&bytes);
@@ -19,14 +25,13 @@ fn parse_duration(bytes: Value, unit: Value) -> Resolved {
             .get(string.as_ref())
             .ok_or(format!("unknown unit format: '{string}'"))?
     };
-    let duration = ht_parse_duration(&trimmed_value)
-        .map_err(|e| format!("unable to parse duration: '{e}'"))?;
+    let duration = parse_duration_str(&value)?;
     let number = duration.div_duration_f64(conversion_factor);
 
     Ok(Value::from_f64_or_zero(number))
 }
 
-static UNITS: Lazy<HashMap<String, Duration>> = Lazy::new(|| {
+pub(super) static UNITS: Lazy<HashMap<String, Duration>> = Lazy::new(|| {
     vec![
         ("ns", Duration::from_nanos(1)),
         ("us", Duration::from_micros(1)),
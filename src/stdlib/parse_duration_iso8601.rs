@@ -0,0 +1,271 @@
+use crate::compiler::prelude::*;
+
+use super::parse_duration::UNITS;
+
+/// ISO-8601 designators in the order they must appear, paired with whether they belong
+/// to the time part of the duration. Each may appear at most once.
+const ORDER: [(char, bool); 7] = [
+    ('Y', false),
+    ('M', false),
+    ('W', false),
+    ('D', false),
+    ('H', true),
+    ('M', true),
+    ('S', true),
+];
+
+/// Seconds represented by each designator. Days and weeks use fixed 24 hour days, while
+/// years and months have no fixed length and are rejected.
+fn designator_seconds(designator: char, in_time: bool) -> Result<f64, String> {
+    match (designator, in_time) {
+        ('Y', false) => Err("years are not supported in ISO-8601 durations".to_owned()),
+        ('M', false) => Err("months are not supported in ISO-8601 durations".to_owned()),
+        ('W', false) => Ok(604_800.0),
+        ('D', false) => Ok(86_400.0),
+        ('H', true) => Ok(3_600.0),
+        ('M', true) => Ok(60.0),
+        ('S', true) => Ok(1.0),
+        _ => Err(format!("unexpected designator '{designator}'")),
+    }
+}
+
+/// Parses an ISO-8601 duration such as `PT1H30M` or `P3DT4H` into a number of seconds.
+fn parse_iso8601_seconds(value: &str) -> Result<f64, String> {
+    let rest = value
+        .strip_prefix('P')
+        .ok_or("duration must start with 'P'")?;
+
+    let mut position = 0;
+    let mut in_time = false;
+    let mut components = 0;
+    let mut time_components = 0;
+    let mut number = String::new();
+    let mut seconds = 0.0;
+
+    for c in rest.chars() {
+        match c {
+            'T' if !in_time && number.is_empty() => in_time = true,
+            '0'..='9' | '.' | ',' => number.push(if c == ',' { '.' } else { c }),
+            designator => {
+                if number.is_empty() {
+                    return Err(format!("missing value before designator '{designator}'"));
+                }
+                let index = ORDER[position..]
+                    .iter()
+                    .position(|&entry| entry == (designator, in_time))
+                    .map(|offset| position + offset)
+                    .ok_or_else(|| format!("unexpected designator '{designator}'"))?;
+                let amount: f64 = number
+                    .parse()
+                    .map_err(|_| format!("invalid number '{number}'"))?;
+
+                seconds += amount * designator_seconds(designator, in_time)?;
+                position = index + 1;
+                components += 1;
+                if in_time {
+                    time_components += 1;
+                }
+                number.clear();
+            }
+        }
+    }
+
+    if !number.is_empty() {
+        return Err(format!("missing designator after '{number}'"));
+    }
+    if components == 0 || (in_time && time_components == 0) {
+        return Err("duration has no components".to_owned());
+    }
+
+    Ok(seconds)
+}
+
+fn parse_duration_iso8601(value: Value, unit: Value) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let value = String::from_utf8_lossy(&bytes);
+
+    let conversion_factor = {
+        let bytes = unit.try_bytes()?;
+        let string = String::from_utf8_lossy(&bytes);
+
+        *UNITS
+            .get(string.as_ref())
+            .ok_or(format!("unknown unit format: '{string}'"))?
+    };
+    let seconds = parse_iso8601_seconds(value.trim())
+        .map_err(|e| format!("unable to parse duration: '{e}'"))?;
+
+    Ok(Value::from_f64_or_zero(
+        seconds / conversion_factor.as_secs_f64(),
+    ))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseDurationIso8601;
+
+impl Function for ParseDurationIso8601 {
+    fn identifier(&self) -> &'static str {
+        "parse_duration_iso8601"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "minutes",
+                source: r#"parse_duration_iso8601!("PT90M", unit: "s")"#,
+                result: Ok("5400.0"),
+            },
+            Example {
+                title: "days and hours",
+                source: r#"parse_duration_iso8601!("P3DT4H", unit: "h")"#,
+                result: Ok("76.0"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let unit = arguments.required("unit");
+
+        Ok(ParseDurationIso8601Fn { value, unit }.as_expr())
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "unit",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseDurationIso8601Fn {
+    value: Box<dyn Expression>,
+    unit: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ParseDurationIso8601Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let unit = self.unit.resolve(ctx)?;
+
+        parse_duration_iso8601(value, unit)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::float().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        parse_duration_iso8601 => ParseDurationIso8601;
+
+        minutes_to_seconds {
+            args: func_args![value: "PT90M",
+                             unit: "s"],
+            want: Ok(5400.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        hours_and_minutes {
+            args: func_args![value: "PT1H30M",
+                             unit: "m"],
+            want: Ok(90.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        days_and_hours {
+            args: func_args![value: "P3DT4H",
+                             unit: "h"],
+            want: Ok(76.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        weeks {
+            args: func_args![value: "P2W",
+                             unit: "d"],
+            want: Ok(14.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        fractional_seconds {
+            args: func_args![value: "PT1.5S",
+                             unit: "ms"],
+            want: Ok(1500.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        comma_decimal {
+            args: func_args![value: "PT0,5H",
+                             unit: "m"],
+            want: Ok(30.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        years_rejected {
+            args: func_args![value: "P1Y",
+                             unit: "s"],
+            want: Err("unable to parse duration: 'years are not supported in ISO-8601 durations'"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        months_rejected {
+            args: func_args![value: "P1M",
+                             unit: "s"],
+            want: Err("unable to parse duration: 'months are not supported in ISO-8601 durations'"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        missing_prefix {
+            args: func_args![value: "1H30M",
+                             unit: "s"],
+            want: Err("unable to parse duration: 'duration must start with 'P''"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        out_of_order {
+            args: func_args![value: "PT30M1H",
+                             unit: "s"],
+            want: Err("unable to parse duration: 'unexpected designator 'H''"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        empty_time_part {
+            args: func_args![value: "P1DT",
+                             unit: "s"],
+            want: Err("unable to parse duration: 'duration has no components'"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        trailing_number {
+            args: func_args![value: "PT5",
+                             unit: "s"],
+            want: Err("unable to parse duration: 'missing designator after '5''"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        unknown_unit {
+            args: func_args![value: "PT1S",
+                             unit: "w"],
+            want: Err("unknown unit format: 'w'"),
+            tdef: TypeDef::float().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,385 @@
+use std::collections::BTreeMap;
+
+use chrono::DateTime;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::compiler::prelude::*;
+
+// Envoy's default access log format, as documented at
+// https://www.envoyproxy.io/docs/envoy/latest/configuration/observability/access_log/usage#default-format-string
+// Newer releases insert the response code details between the flags and the byte counts and
+// append the upstream and downstream addresses, so both parts are optional.
+static REGEX_ENVOY_ACCESS_LOG: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?x)
+        ^\s*
+        \[(?P<start_time>[^\]]+)\]\s+
+        "(?P<method>\S+)\s+(?P<path>\S+)\s+(?P<protocol>\S+)"\s+
+        (?P<response_code>\d+)\s+
+        (?P<response_flags>\S+)\s+
+        (?:
+            (?P<response_code_details>\S+)\s+
+            (?P<connection_termination_details>\S+)\s+
+            "(?P<upstream_transport_failure_reason>[^"]*)"\s+
+        )?
+        (?P<bytes_received>\d+)\s+
+        (?P<bytes_sent>\d+)\s+
+        (?P<duration>\d+|-)\s+
+        (?P<upstream_service_time>\d+|-)\s+
+        "(?P<x_forwarded_for>[^"]*)"\s+
+        "(?P<user_agent>[^"]*)"\s+
+        "(?P<request_id>[^"]*)"\s+
+        "(?P<authority>[^"]*)"\s+
+        "(?P<upstream_host>[^"]*)"
+        (?:\s+
+            (?P<upstream_cluster>\S+)\s+
+            (?P<upstream_local_address>\S+)\s+
+            (?P<downstream_local_address>\S+)\s+
+            (?P<downstream_remote_address>\S+)
+            (?:\s+(?P<requested_server_name>\S+))?
+            (?:\s+(?P<route_name>\S+))?
+        )?
+        \s*$
+    "#,
+    )
+    .expect("failed compiling regex for Envoy access log")
+});
+
+const INTEGER_FIELDS: &[&str] = &[
+    "response_code",
+    "bytes_received",
+    "bytes_sent",
+    "duration",
+    "upstream_service_time",
+];
+
+/// Returns the description of a response flag, as listed in Envoy's access log documentation.
+fn response_flag_description(flag: &str) -> Option<&'static str> {
+    Some(match flag {
+        "UH" => "no healthy upstream hosts",
+        "UF" => "upstream connection failure",
+        "UO" => "upstream overflow",
+        "NR" => "no route configured",
+        "URX" => "upstream retry limit exceeded",
+        "NC" => "upstream cluster not found",
+        "DT" => "max duration exceeded",
+        "DC" => "downstream connection termination",
+        "LH" => "local service failed health check",
+        "UT" => "upstream request timeout",
+        "LR" => "connection local reset",
+        "UR" => "upstream remote reset",
+        "UC" => "upstream connection termination",
+        "DI" => "delayed via fault injection",
+        "FI" => "aborted via fault injection",
+        "RL" => "rate limited",
+        "UAEX" => "unauthorized external service",
+        "RLSE" => "rate limit service error",
+        "IH" => "invalid header value",
+        "SI" => "stream idle timeout",
+        "DPE" => "downstream protocol error",
+        "UPE" => "upstream protocol error",
+        "UMSDR" => "upstream max stream duration reached",
+        "OM" => "overload manager",
+        "DF" => "DNS resolution failure",
+        "DO" => "drop overload",
+        _ => return None,
+    })
+}
+
+fn response_flags(value: &str, decode: bool) -> Value {
+    if value == "-" {
+        return Value::Array(vec![]);
+    }
+
+    value
+        .split(',')
+        .map(|flag| {
+            let description = decode.then(|| response_flag_description(flag)).flatten();
+            Value::from(description.unwrap_or(flag))
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
+
+fn capture_value(name: &str, value: &str, decode_flags: bool) -> Result<Value, String> {
+    // Envoy logs `-` for any value that is not available, such as the request line of a TCP
+    // connection.
+    if value == "-" && name != "response_flags" {
+        return Ok(Value::Null);
+    }
+
+    Ok(match name {
+        "start_time" => DateTime::parse_from_rfc3339(value)
+            .map_err(|err| format!("failed parsing start_time {value}: {err}"))?
+            .with_timezone(&chrono::Utc)
+            .into(),
+        "response_flags" => response_flags(value, decode_flags),
+        name if INTEGER_FIELDS.contains(&name) => value
+            .parse::<i64>()
+            .map_err(|_| format!("failed parsing {name}"))?
+            .into(),
+        _ => value.into(),
+    })
+}
+
+fn parse_envoy_access_log(value: Value, decode_flags: bool) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let line = String::from_utf8_lossy(&bytes);
+    let captures = REGEX_ENVOY_ACCESS_LOG
+        .captures(&line)
+        .ok_or("failed parsing Envoy access log line")?;
+
+    let mut log = ObjectMap::new();
+    for name in REGEX_ENVOY_ACCESS_LOG.capture_names().flatten() {
+        if let Some(capture) = captures.name(name) {
+            log.insert(
+                name.into(),
+                capture_value(name, capture.as_str(), decode_flags)?,
+            );
+        }
+    }
+
+    Ok(log.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseEnvoyAccessLog;
+
+impl Function for ParseEnvoyAccessLog {
+    fn identifier(&self) -> &'static str {
+        "parse_envoy_access_log"
+    }
+
+    fn summary(&self) -> &'static str {
+        "parse an Envoy access log line"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Parses a line in Envoy's default access log format into an object. The response code
+            details and the upstream and downstream addresses logged by newer Envoy releases (and
+            by Istio) are included when present.
+
+            Status codes, byte counts and timings are returned as integers and `-` values as
+            `null`. Response flags are returned as an array of codes, such as `UH` or `URX`,
+            which is empty when no flag is set. Set `decode_response_flags` to `true` to return
+            their descriptions instead.
+        "}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "decode_response_flags",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "default format",
+                source: r#"parse_envoy_access_log!(s'[2024-03-08T12:00:00.123Z] "GET /api/users HTTP/1.1" 200 - 0 512 23 21 "10.0.0.1" "curl/8.4.0" "9b0a3c1e-5e8a-4d5c-9d3e-0f2f5c3b7a11" "api.example.com" "10.0.2.15:8080"')"#,
+                result: Ok(indoc! {r#"{
+                    "authority": "api.example.com",
+                    "bytes_received": 0,
+                    "bytes_sent": 512,
+                    "duration": 23,
+                    "method": "GET",
+                    "path": "/api/users",
+                    "protocol": "HTTP/1.1",
+                    "request_id": "9b0a3c1e-5e8a-4d5c-9d3e-0f2f5c3b7a11",
+                    "response_code": 200,
+                    "response_flags": [],
+                    "start_time": "2024-03-08T12:00:00.123Z",
+                    "upstream_host": "10.0.2.15:8080",
+                    "upstream_service_time": 21,
+                    "user_agent": "curl/8.4.0",
+                    "x_forwarded_for": "10.0.0.1"
+                }"#}),
+            },
+            Example {
+                title: "decoded response flags",
+                source: r#"parse_envoy_access_log!(s'[2024-03-08T12:00:00.123Z] "GET /api/users HTTP/1.1" 503 UF,URX 0 91 3000 - "-" "curl/8.4.0" "9b0a3c1e-5e8a-4d5c-9d3e-0f2f5c3b7a11" "api.example.com" "10.0.2.15:8080"', decode_response_flags: true).response_flags"#,
+                result: Ok(r#"["upstream connection failure", "upstream retry limit exceeded"]"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let decode_response_flags =
+            match arguments.optional_literal("decode_response_flags", state)? {
+                Some(decode) => {
+                    decode
+                        .as_boolean()
+                        .ok_or_else(|| function::Error::InvalidArgument {
+                            keyword: "decode_response_flags",
+                            value: decode,
+                            error: "decode_response_flags must be a boolean",
+                        })?
+                }
+                None => false,
+            };
+
+        Ok(ParseEnvoyAccessLogFn {
+            value,
+            decode_response_flags,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ParseEnvoyAccessLogFn {
+    value: Box<dyn Expression>,
+    decode_response_flags: bool,
+}
+
+impl FunctionExpression for ParseEnvoyAccessLogFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_envoy_access_log(value, self.decode_response_flags)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    let optional = [
+        "response_code_details",
+        "connection_termination_details",
+        "upstream_transport_failure_reason",
+        "upstream_cluster",
+        "upstream_local_address",
+        "downstream_local_address",
+        "downstream_remote_address",
+        "requested_server_name",
+        "route_name",
+    ];
+
+    REGEX_ENVOY_ACCESS_LOG
+        .capture_names()
+        .flatten()
+        .map(|name| {
+            let kind = match name {
+                "start_time" => Kind::timestamp(),
+                "response_flags" => Kind::array(Collection::from_unknown(Kind::bytes())),
+                "response_code" | "bytes_received" | "bytes_sent" => Kind::integer(),
+                "duration" | "upstream_service_time" => Kind::integer().or_null(),
+                name if optional.contains(&name) => Kind::bytes().or_null().or_undefined(),
+                _ => Kind::bytes().or_null(),
+            };
+            (Field::from(name.to_owned()), kind)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone as _, Utc};
+
+    use super::*;
+    use crate::value;
+
+    test_function![
+        parse_envoy_access_log => ParseEnvoyAccessLog;
+
+        response_flags {
+            args: func_args![value: r#"[2024-03-08T12:00:00.123Z] "POST /checkout HTTP/2" 503 UF,URX 128 91 3001 - "203.0.113.7" "Mozilla/5.0" "d7e8f9a0-1b2c-4d3e-8f4a-5b6c7d8e9f00" "shop.example.com" "10.0.2.15:8080""#],
+            want: Ok(value!({
+                "start_time": (Utc.with_ymd_and_hms(2024, 3, 8, 12, 0, 0).unwrap() + chrono::Duration::milliseconds(123)),
+                "method": "POST",
+                "path": "/checkout",
+                "protocol": "HTTP/2",
+                "response_code": 503,
+                "response_flags": ["UF", "URX"],
+                "bytes_received": 128,
+                "bytes_sent": 91,
+                "duration": 3001,
+                "upstream_service_time": null,
+                "x_forwarded_for": "203.0.113.7",
+                "user_agent": "Mozilla/5.0",
+                "request_id": "d7e8f9a0-1b2c-4d3e-8f4a-5b6c7d8e9f00",
+                "authority": "shop.example.com",
+                "upstream_host": "10.0.2.15:8080",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        decoded_response_flags {
+            args: func_args![value: r#"[2024-03-08T12:00:00.123Z] "GET / HTTP/1.1" 0 DC,XYZ 0 0 12 - "-" "-" "-" "-" "-""#, decode_response_flags: true],
+            want: Ok(value!({
+                "start_time": (Utc.with_ymd_and_hms(2024, 3, 8, 12, 0, 0).unwrap() + chrono::Duration::milliseconds(123)),
+                "method": "GET",
+                "path": "/",
+                "protocol": "HTTP/1.1",
+                "response_code": 0,
+                "response_flags": ["downstream connection termination", "XYZ"],
+                "bytes_received": 0,
+                "bytes_sent": 0,
+                "duration": 12,
+                "upstream_service_time": null,
+                "x_forwarded_for": null,
+                "user_agent": null,
+                "request_id": null,
+                "authority": null,
+                "upstream_host": null,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        extended_format {
+            args: func_args![value: r#"[2024-03-08T12:00:00.000Z] "- - -" 0 UH no_healthy_upstream - "-" 0 0 1 - "-" "-" "-" "-" "-" outbound|5432||db.default.svc.cluster.local - 10.96.0.12:5432 10.244.1.5:45678 - -"#],
+            want: Ok(value!({
+                "start_time": (Utc.with_ymd_and_hms(2024, 3, 8, 12, 0, 0).unwrap()),
+                "method": null,
+                "path": null,
+                "protocol": null,
+                "response_code": 0,
+                "response_flags": ["UH"],
+                "response_code_details": "no_healthy_upstream",
+                "connection_termination_details": null,
+                "upstream_transport_failure_reason": null,
+                "bytes_received": 0,
+                "bytes_sent": 0,
+                "duration": 1,
+                "upstream_service_time": null,
+                "x_forwarded_for": null,
+                "user_agent": null,
+                "request_id": null,
+                "authority": null,
+                "upstream_host": null,
+                "upstream_cluster": "outbound|5432||db.default.svc.cluster.local",
+                "upstream_local_address": null,
+                "downstream_local_address": "10.96.0.12:5432",
+                "downstream_remote_address": "10.244.1.5:45678",
+                "requested_server_name": null,
+                "route_name": null,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        invalid_line {
+            args: func_args![value: "GET /index.html 200"],
+            want: Err("failed parsing Envoy access log line"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
@@ -234,6 +234,26 @@ mod tests {
             tdef: TypeDef::object(inner_kind()).fallible(),
         }
 
+        multi_level_suffix {
+            args: func_args![value: value!("www.example.co.uk")],
+            want: Ok(value!({
+                etld: "co.uk",
+                etld_plus: "co.uk",
+                known_suffix: true,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        multi_level_suffix_plus_one {
+            args: func_args![value: value!("www.example.co.uk"), plus_parts: 1],
+            want: Ok(value!({
+                etld: "co.uk",
+                etld_plus: "example.co.uk",
+                known_suffix: true,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
         short_plus {
             args: func_args![value: value!("vector.dev"), plus_parts: 10],
             want: Ok(value!({
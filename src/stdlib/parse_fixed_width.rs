@@ -0,0 +1,227 @@
+use crate::compiler::prelude::*;
+
+fn widths(value: Value) -> Result<Vec<usize>, ExpressionError> {
+    value
+        .try_array()?
+        .into_iter()
+        .map(|width| {
+            let width = width.try_integer()?;
+            usize::try_from(width)
+                .ok()
+                .filter(|width| *width > 0)
+                .ok_or_else(|| format!("widths must be positive integers, got {width}").into())
+        })
+        .collect()
+}
+
+fn parse_fixed_width(
+    value: Value,
+    widths: Vec<usize>,
+    names: Option<Value>,
+    trim: bool,
+) -> Resolved {
+    let line = value.try_bytes_utf8_lossy()?;
+    let line = line.trim_end_matches(['\r', '\n']);
+
+    // Widths count characters rather than bytes, so a multi-byte character takes up a single
+    // column, as it does in the text editors and tools that produce these files.
+    let mut chars = line.chars();
+    let fields = widths
+        .iter()
+        .map(|width| {
+            let field = chars.by_ref().take(*width).collect::<String>();
+            if field.is_empty() {
+                return Value::Null;
+            }
+            if trim {
+                field.trim_end_matches(' ').into()
+            } else {
+                field.into()
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let Some(names) = names else {
+        return Ok(fields.into());
+    };
+    let names = names.try_array()?;
+    if names.len() != fields.len() {
+        return Err("names must have the same length as widths".into());
+    }
+
+    names
+        .into_iter()
+        .zip(fields)
+        .map(|(name, field)| Ok((name.try_bytes_utf8_lossy()?.into(), field)))
+        .collect::<Result<ObjectMap, ExpressionError>>()
+        .map(Into::into)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseFixedWidth;
+
+impl Function for ParseFixedWidth {
+    fn identifier(&self) -> &'static str {
+        "parse_fixed_width"
+    }
+
+    fn summary(&self) -> &'static str {
+        "parse a fixed-width record"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Parses a record made of fixed-width columns, as exported by mainframes and other legacy
+            systems, by slicing it into fields of the given `widths`.
+
+            Widths are counted in characters rather than bytes, so a multi-byte UTF-8 character
+            takes up a single column. Trailing spaces used to pad each field are removed unless
+            `trim` is `false`. Columns past the end of a short record are `null`, and characters
+            past the last column are ignored.
+
+            The fields are returned as an array, or as an object when `names` is given.
+        "}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "widths",
+                kind: kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "names",
+                kind: kind::ARRAY,
+                required: false,
+            },
+            Parameter {
+                keyword: "trim",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "fixed-width record",
+                source: r#"parse_fixed_width!("00042JOHN      SMITH     ", widths: [5, 10, 10])"#,
+                result: Ok(r#"["00042", "JOHN", "SMITH"]"#),
+            },
+            Example {
+                title: "named fields",
+                source: r#"parse_fixed_width!("00042JOHN      SMITH     ", widths: [5, 10, 10], names: ["id", "first", "last"])"#,
+                result: Ok(r#"{"id": "00042", "first": "JOHN", "last": "SMITH"}"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let widths = arguments.required("widths");
+        let names = arguments.optional("names");
+        let trim = arguments.optional("trim").unwrap_or(expr!(true));
+
+        Ok(ParseFixedWidthFn {
+            value,
+            widths,
+            names,
+            trim,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ParseFixedWidthFn {
+    value: Box<dyn Expression>,
+    widths: Box<dyn Expression>,
+    names: Option<Box<dyn Expression>>,
+    trim: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ParseFixedWidthFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let widths = widths(self.widths.resolve(ctx)?)?;
+        let names = self
+            .names
+            .as_ref()
+            .map(|names| names.resolve(ctx))
+            .transpose()?;
+        let trim = self.trim.resolve(ctx)?.try_boolean()?;
+
+        parse_fixed_width(value, widths, names, trim)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        type_def(self.names.is_some())
+    }
+}
+
+fn type_def(named: bool) -> TypeDef {
+    let field = Kind::bytes().or_null();
+    if named {
+        TypeDef::object(Collection::from_unknown(field)).fallible()
+    } else {
+        TypeDef::array(Collection::from_unknown(field)).fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        parse_fixed_width => ParseFixedWidth;
+
+        simple {
+            args: func_args![value: "00042JOHN      SMITH       19.99\n", widths: value!([5, 10, 10, 7])],
+            want: Ok(value!(["00042", "JOHN", "SMITH", "  19.99"])),
+            tdef: type_def(false),
+        }
+
+        untrimmed {
+            args: func_args![value: "AB  CD  ", widths: value!([4, 4]), trim: false],
+            want: Ok(value!(["AB  ", "CD  "])),
+            tdef: type_def(false),
+        }
+
+        named {
+            args: func_args![value: "0001Zoë  Lyon", widths: value!([4, 5, 10]), names: value!(["id", "name", "city"])],
+            want: Ok(value!({"id": "0001", "name": "Zoë", "city": "Lyon"})),
+            tdef: type_def(true),
+        }
+
+        short_record {
+            args: func_args![value: "0001", widths: value!([4, 5])],
+            want: Ok(value!(["0001", null])),
+            tdef: type_def(false),
+        }
+
+        names_length_mismatch {
+            args: func_args![value: "0001", widths: value!([4]), names: value!(["id", "name"])],
+            want: Err("names must have the same length as widths"),
+            tdef: type_def(true),
+        }
+
+        invalid_width {
+            args: func_args![value: "0001", widths: value!([4, 0])],
+            want: Err("widths must be positive integers, got 0"),
+            tdef: type_def(false),
+        }
+    ];
+}
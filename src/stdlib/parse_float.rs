@@ -1,8 +1,70 @@
 use crate::compiler::prelude::*;
 use crate::stdlib::to_float::bytes_to_float;
 
-fn parse_float(value: Value) -> Resolved {
-    bytes_to_float(value.try_bytes()?)
+/// Parses `value` as a float.
+///
+/// By default `inf`/`infinity` literals (and values too large to represent) are accepted
+/// and trailing characters are an error. NaN is always rejected, as VRL floats cannot be NaN.
+fn parse_float(value: Value, allow_infinity: Value, lenient: Value) -> Resolved {
+    let mut bytes = value.try_bytes()?;
+
+    if lenient.try_boolean()? {
+        let string = String::from_utf8_lossy(&bytes);
+        let prefix = numeric_prefix(&string);
+        if prefix.is_empty() {
+            return Err(format!("no leading number in {string:?}").into());
+        }
+        bytes = Bytes::copy_from_slice(prefix.as_bytes());
+    }
+
+    let float = bytes_to_float(bytes)?;
+    if !allow_infinity.try_boolean()? && float.as_float().is_some_and(|f| f.is_infinite()) {
+        return Err("infinite number not allowed".into());
+    }
+
+    Ok(float)
+}
+
+/// Returns the longest leading part of `value` that forms a number, so that lenient parsing
+/// can ignore trailing characters such as units (`"1.5abc"` becomes `"1.5"`).
+fn numeric_prefix(value: &str) -> &str {
+    let bytes = value.as_bytes();
+    let digits_from = |mut i: usize| {
+        while bytes.get(i).is_some_and(u8::is_ascii_digit) {
+            i += 1;
+        }
+        i
+    };
+
+    let mut end = usize::from(matches!(bytes.first(), Some(b'+' | b'-')));
+
+    let rest = value[end..].to_ascii_lowercase();
+    for special in ["infinity", "inf", "nan"] {
+        if rest.starts_with(special) {
+            return &value[..end + special.len()];
+        }
+    }
+
+    let integer_end = digits_from(end);
+    let mut mantissa_end = integer_end;
+    if bytes.get(integer_end) == Some(&b'.') {
+        mantissa_end = digits_from(integer_end + 1);
+    }
+    if integer_end == end && mantissa_end <= integer_end + 1 {
+        // Neither an integer part nor a fraction, e.g. "abc" or ".".
+        return "";
+    }
+    end = mantissa_end;
+
+    if matches!(bytes.get(end), Some(b'e' | b'E')) {
+        let sign = usize::from(matches!(bytes.get(end + 1), Some(b'+' | b'-')));
+        let exponent_end = digits_from(end + 1 + sign);
+        if exponent_end > end + 1 + sign {
+            end = exponent_end;
+        }
+    }
+
+    &value[..end]
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -14,11 +76,23 @@ impl Function for ParseFloat {
     }
 
     fn parameters(&self) -> &'static [Parameter] {
-        &[Parameter {
-            keyword: "value",
-            kind: kind::BYTES,
-            required: true,
-        }]
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "allow_infinity",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "lenient",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
     }
 
     fn examples(&self) -> &'static [Example] {
@@ -38,6 +112,18 @@ impl Function for ParseFloat {
                 source: r#"parse_float!("2.5e3")"#,
                 result: Ok("2500.0"),
             },
+            Example {
+                title: "lenient trailing characters",
+                source: r#"parse_float!("1.5abc", lenient: true)"#,
+                result: Ok("1.5"),
+            },
+            Example {
+                title: "reject infinity",
+                source: r#"parse_float!("inf", allow_infinity: false)"#,
+                result: Err(
+                    r#"function call error for "parse_float" at (0:42): infinite number not allowed"#,
+                ),
+            },
         ]
     }
 
@@ -48,21 +134,36 @@ impl Function for ParseFloat {
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
+        let allow_infinity = arguments
+            .optional("allow_infinity")
+            .unwrap_or_else(|| expr!(true));
+        let lenient = arguments
+            .optional("lenient")
+            .unwrap_or_else(|| expr!(false));
 
-        Ok(ParseFloatFn { value }.as_expr())
+        Ok(ParseFloatFn {
+            value,
+            allow_infinity,
+            lenient,
+        }
+        .as_expr())
     }
 }
 
 #[derive(Debug, Clone)]
 struct ParseFloatFn {
     value: Box<dyn Expression>,
+    allow_infinity: Box<dyn Expression>,
+    lenient: Box<dyn Expression>,
 }
 
 impl FunctionExpression for ParseFloatFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
+        let allow_infinity = self.allow_infinity.resolve(ctx)?;
+        let lenient = self.lenient.resolve(ctx)?;
 
-        parse_float(value)
+        parse_float(value, allow_infinity, lenient)
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
@@ -148,5 +249,53 @@ mod tests {
             want: Ok(f64::INFINITY),
             tdef: TypeDef::float().fallible(),
         }
+
+        strict_trailing_characters {
+            args: func_args![value: "1.5abc"],
+            want: Err("Invalid floating point number \"1.5abc\": invalid float literal"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        lenient_trailing_characters {
+            args: func_args![value: "1.5abc", lenient: true],
+            want: Ok(1.5),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        lenient_exponent {
+            args: func_args![value: "-2.5e3ms", lenient: true],
+            want: Ok(-2500.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        lenient_dangling_exponent {
+            args: func_args![value: "3e", lenient: true],
+            want: Ok(3.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        lenient_no_number {
+            args: func_args![value: "abc", lenient: true],
+            want: Err("no leading number in \"abc\""),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        lenient_nan {
+            args: func_args![value: "NaN%", lenient: true],
+            want: Err("NaN number not supported \"NaN\"".to_string()),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        infinity_rejected {
+            args: func_args![value: "-inf", allow_infinity: false],
+            want: Err("infinite number not allowed"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        finite_with_infinity_rejected {
+            args: func_args![value: "42.38", allow_infinity: false],
+            want: Ok(42.38),
+            tdef: TypeDef::float().fallible(),
+        }
     ];
 }
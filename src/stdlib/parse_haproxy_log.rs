@@ -0,0 +1,440 @@
+use std::collections::BTreeMap;
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+use crate::compiler::prelude::*;
+use crate::value;
+
+const ACCEPT_DATE_FORMAT: &str = "%d/%b/%Y:%H:%M:%S%.f";
+
+// Both formats may be preceded by a syslog header ending in `haproxy[pid]: `, so the
+// patterns are only anchored at the end of the line.
+static REGEX_HAPROXY_HTTP_LOG: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?x)
+        (?:(?P<process_name>[\w.-]+)\[(?P<pid>\d+)\]:\s+)?
+        (?P<client_ip>[0-9a-fA-F.:]+):(?P<client_port>\d+)\s+
+        \[(?P<accept_date>[^\]]+)\]\s+
+        (?P<frontend_name>\S+)\s+
+        (?P<backend_name>[^\s/]+)/(?P<server_name>\S+)\s+
+        (?P<time_request>-?\d+)/(?P<time_queue>-?\d+)/(?P<time_connect>-?\d+)/(?P<time_response>-?\d+)/(?P<time_active>\+?-?\d+)\s+
+        (?P<status_code>-?\d+)\s+
+        (?P<bytes_read>\+?\d+)\s+
+        (?P<captured_request_cookie>\S+)\s+
+        (?P<captured_response_cookie>\S+)\s+
+        (?P<termination_state>\S{4})\s+
+        (?P<actconn>\d+)/(?P<feconn>\d+)/(?P<beconn>\d+)/(?P<srv_conn>\d+)/(?P<retries>\+?\d+)\s+
+        (?P<srv_queue>\d+)/(?P<backend_queue>\d+)\s+
+        (?:\{(?P<captured_request_headers>[^}]*)\}\s+)?
+        (?:\{(?P<captured_response_headers>[^}]*)\}\s+)?
+        "(?P<http_request>[^"]*)"?
+        \s*$
+    "#,
+    )
+    .expect("failed compiling regex for HAProxy HTTP log")
+});
+
+static REGEX_HAPROXY_TCP_LOG: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?x)
+        (?:(?P<process_name>[\w.-]+)\[(?P<pid>\d+)\]:\s+)?
+        (?P<client_ip>[0-9a-fA-F.:]+):(?P<client_port>\d+)\s+
+        \[(?P<accept_date>[^\]]+)\]\s+
+        (?P<frontend_name>\S+)\s+
+        (?P<backend_name>[^\s/]+)/(?P<server_name>\S+)\s+
+        (?P<time_queue>-?\d+)/(?P<time_connect>-?\d+)/(?P<time_total>\+?-?\d+)\s+
+        (?P<bytes_read>\+?\d+)\s+
+        (?P<termination_state>\S{2})\s+
+        (?P<actconn>\d+)/(?P<feconn>\d+)/(?P<beconn>\d+)/(?P<srv_conn>\d+)/(?P<retries>\+?\d+)\s+
+        (?P<srv_queue>\d+)/(?P<backend_queue>\d+)
+        \s*$
+    ",
+    )
+    .expect("failed compiling regex for HAProxy TCP log")
+});
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Http,
+    Tcp,
+}
+
+impl Format {
+    fn regex(self) -> &'static Regex {
+        match self {
+            Self::Http => &REGEX_HAPROXY_HTTP_LOG,
+            Self::Tcp => &REGEX_HAPROXY_TCP_LOG,
+        }
+    }
+}
+
+fn formats() -> Vec<Value> {
+    vec![value!("http"), value!("tcp")]
+}
+
+fn is_integer_field(name: &str) -> bool {
+    matches!(
+        name,
+        "pid"
+            | "client_port"
+            | "time_request"
+            | "time_queue"
+            | "time_connect"
+            | "time_response"
+            | "time_active"
+            | "time_total"
+            | "status_code"
+            | "bytes_read"
+            | "actconn"
+            | "feconn"
+            | "beconn"
+            | "srv_conn"
+            | "retries"
+            | "srv_queue"
+            | "backend_queue"
+    )
+}
+
+fn capture_value(name: &str, value: &str, timezone: &TimeZone) -> Result<Value, String> {
+    Ok(match name {
+        "accept_date" => timezone
+            .datetime_from_str(value, ACCEPT_DATE_FORMAT)
+            .map_err(|err| format!("failed parsing accept_date {value}: {err}"))?
+            .into(),
+        // Captured headers are separated by `|`, in the order they are configured.
+        "captured_request_headers" | "captured_response_headers" => {
+            value.split('|').map(Value::from).collect::<Vec<_>>().into()
+        }
+        "captured_request_cookie" | "captured_response_cookie" if value == "-" => Value::Null,
+        // A leading `+` marks values logged before the session ended, or a redispatch.
+        name if is_integer_field(name) => value
+            .parse::<i64>()
+            .map_err(|_| format!("failed parsing {name}"))?
+            .into(),
+        _ => value.into(),
+    })
+}
+
+/// Splits the captured request line, such as `GET /index.html HTTP/1.1`, into its parts.
+fn request_fields(request: &str, log: &mut ObjectMap) {
+    let mut parts = request.split_whitespace();
+    for key in ["http_method", "http_path", "http_version"] {
+        if let Some(part) = parts.next() {
+            log.insert(key.into(), part.into());
+        }
+    }
+}
+
+fn parse_haproxy_log(value: Value, format: Format, timezone: &TimeZone) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let line = String::from_utf8_lossy(&bytes);
+    let regex = format.regex();
+    let captures: Captures = regex
+        .captures(&line)
+        .ok_or("failed parsing HAProxy log line")?;
+
+    let mut log = ObjectMap::new();
+    for name in regex.capture_names().flatten() {
+        if let Some(capture) = captures.name(name) {
+            log.insert(
+                name.into(),
+                capture_value(name, capture.as_str(), timezone)?,
+            );
+        }
+    }
+    if let Some(request) = captures.name("http_request") {
+        request_fields(request.as_str(), &mut log);
+    }
+
+    Ok(log.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseHaproxyLog;
+
+impl Function for ParseHaproxyLog {
+    fn identifier(&self) -> &'static str {
+        "parse_haproxy_log"
+    }
+
+    fn summary(&self) -> &'static str {
+        "parse an HAProxy HTTP or TCP log line"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Parses a line in HAProxy's default HTTP (`option httplog`) or TCP (`option tcplog`)
+            log format, selected with `format`, into a typed object. A leading syslog header is
+            ignored, apart from the `haproxy[pid]: ` tag which is returned as `process_name`
+            and `pid`.
+
+            Timers, counters, the status code and byte counts are returned as integers, with
+            `-1` kept for aborted timers. The accept date is parsed in the configured timezone.
+            For HTTP logs, the request line is also split into `http_method`, `http_path` and
+            `http_version`, captured headers are returned as arrays and `-` cookies as `null`.
+        "}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "format",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "HTTP log",
+                source: r#"parse_haproxy_log!(s'haproxy[14389]: 10.0.1.2:33317 [06/Feb/2009:12:14:14.655] http-in static/srv1 10/0/30/69/109 200 2750 - - ---- 1/1/1/1/0 0/0 "GET /index.html HTTP/1.1"')"#,
+                result: Ok(indoc! {r#"{
+                    "accept_date": "2009-02-06T12:14:14.655Z",
+                    "actconn": 1,
+                    "backend_name": "static",
+                    "backend_queue": 0,
+                    "beconn": 1,
+                    "bytes_read": 2750,
+                    "captured_request_cookie": null,
+                    "captured_response_cookie": null,
+                    "client_ip": "10.0.1.2",
+                    "client_port": 33317,
+                    "feconn": 1,
+                    "frontend_name": "http-in",
+                    "http_method": "GET",
+                    "http_path": "/index.html",
+                    "http_request": "GET /index.html HTTP/1.1",
+                    "http_version": "HTTP/1.1",
+                    "pid": 14389,
+                    "process_name": "haproxy",
+                    "retries": 0,
+                    "server_name": "srv1",
+                    "srv_conn": 1,
+                    "srv_queue": 0,
+                    "status_code": 200,
+                    "termination_state": "----",
+                    "time_active": 109,
+                    "time_connect": 30,
+                    "time_queue": 0,
+                    "time_request": 10,
+                    "time_response": 69
+                }"#}),
+            },
+            Example {
+                title: "TCP log",
+                source: r#"parse_haproxy_log!("10.0.1.2:33313 [06/Feb/2009:12:12:51.443] fnt bck/srv1 0/0/5007 212 -- 0/0/0/0/3 0/0", format: "tcp")"#,
+                result: Ok(indoc! {r#"{
+                    "accept_date": "2009-02-06T12:12:51.443Z",
+                    "actconn": 0,
+                    "backend_name": "bck",
+                    "backend_queue": 0,
+                    "beconn": 0,
+                    "bytes_read": 212,
+                    "client_ip": "10.0.1.2",
+                    "client_port": 33313,
+                    "feconn": 0,
+                    "frontend_name": "fnt",
+                    "retries": 3,
+                    "server_name": "srv1",
+                    "srv_conn": 0,
+                    "srv_queue": 0,
+                    "termination_state": "--",
+                    "time_connect": 0,
+                    "time_queue": 0,
+                    "time_total": 5007
+                }"#}),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let format = match arguments
+            .optional_enum("format", &formats(), state)?
+            .as_ref()
+            .and_then(Value::as_bytes)
+            .map(AsRef::as_ref)
+        {
+            Some(b"tcp") => Format::Tcp,
+            _ => Format::Http,
+        };
+
+        Ok(ParseHaproxyLogFn { value, format }.as_expr())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ParseHaproxyLogFn {
+    value: Box<dyn Expression>,
+    format: Format,
+}
+
+impl FunctionExpression for ParseHaproxyLogFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_haproxy_log(value, self.format, ctx.timezone())
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(inner_kind(self.format)).fallible()
+    }
+}
+
+fn inner_kind(format: Format) -> BTreeMap<Field, Kind> {
+    let mut kind = BTreeMap::new();
+    for name in format.regex().capture_names().flatten() {
+        let field_kind = match name {
+            "process_name" => Kind::bytes().or_undefined(),
+            "pid" => Kind::integer().or_undefined(),
+            "accept_date" => Kind::timestamp(),
+            "captured_request_headers" | "captured_response_headers" => {
+                Kind::array(Collection::from_unknown(Kind::bytes())).or_undefined()
+            }
+            "captured_request_cookie" | "captured_response_cookie" => Kind::bytes().or_null(),
+            "http_request" => Kind::bytes(),
+            name if is_integer_field(name) => Kind::integer(),
+            _ => Kind::bytes(),
+        };
+        kind.insert(Field::from(name.to_owned()), field_kind);
+    }
+    if format == Format::Http {
+        for name in ["http_method", "http_path", "http_version"] {
+            kind.insert(Field::from(name), Kind::bytes().or_undefined());
+        }
+    }
+    kind
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone as _, Utc};
+
+    use super::*;
+
+    test_function![
+        parse_haproxy_log => ParseHaproxyLog;
+
+        http_log {
+            args: func_args![value: r#"Feb  6 12:14:14 localhost haproxy[14389]: 10.0.1.2:33317 [06/Feb/2009:12:14:14.655] http-in~ static/srv1 10/0/30/69/109 302 2750 SESSIONID=abc - --NI 4/3/2/1/+1 0/0 {1wt.eu|Mozilla/5.0} {} "GET /login?next=/ HTTP/1.1""#],
+            want: Ok(value!({
+                "process_name": "haproxy",
+                "pid": 14389,
+                "client_ip": "10.0.1.2",
+                "client_port": 33317,
+                "accept_date": (Utc.with_ymd_and_hms(2009, 2, 6, 12, 14, 14).unwrap() + chrono::Duration::milliseconds(655)),
+                "frontend_name": "http-in~",
+                "backend_name": "static",
+                "server_name": "srv1",
+                "time_request": 10,
+                "time_queue": 0,
+                "time_connect": 30,
+                "time_response": 69,
+                "time_active": 109,
+                "status_code": 302,
+                "bytes_read": 2750,
+                "captured_request_cookie": "SESSIONID=abc",
+                "captured_response_cookie": null,
+                "termination_state": "--NI",
+                "actconn": 4,
+                "feconn": 3,
+                "beconn": 2,
+                "srv_conn": 1,
+                "retries": 1,
+                "srv_queue": 0,
+                "backend_queue": 0,
+                "captured_request_headers": ["1wt.eu", "Mozilla/5.0"],
+                "captured_response_headers": [""],
+                "http_request": "GET /login?next=/ HTTP/1.1",
+                "http_method": "GET",
+                "http_path": "/login?next=/",
+                "http_version": "HTTP/1.1",
+            })),
+            tdef: TypeDef::object(inner_kind(Format::Http)).fallible(),
+        }
+
+        http_log_aborted {
+            args: func_args![value: r#"192.168.1.5:51234 [06/Feb/2009:12:14:14.655] http-in www/<NOSRV> 15/-1/-1/-1/15 400 187 - - PR-- 1/1/0/0/0 0/0 "<BADREQ>""#, format: "http"],
+            want: Ok(value!({
+                "client_ip": "192.168.1.5",
+                "client_port": 51234,
+                "accept_date": (Utc.with_ymd_and_hms(2009, 2, 6, 12, 14, 14).unwrap() + chrono::Duration::milliseconds(655)),
+                "frontend_name": "http-in",
+                "backend_name": "www",
+                "server_name": "<NOSRV>",
+                "time_request": 15,
+                "time_queue": (-1),
+                "time_connect": (-1),
+                "time_response": (-1),
+                "time_active": 15,
+                "status_code": 400,
+                "bytes_read": 187,
+                "captured_request_cookie": null,
+                "captured_response_cookie": null,
+                "termination_state": "PR--",
+                "actconn": 1,
+                "feconn": 1,
+                "beconn": 0,
+                "srv_conn": 0,
+                "retries": 0,
+                "srv_queue": 0,
+                "backend_queue": 0,
+                "http_request": "<BADREQ>",
+                "http_method": "<BADREQ>",
+            })),
+            tdef: TypeDef::object(inner_kind(Format::Http)).fallible(),
+        }
+
+        tcp_log {
+            args: func_args![value: "haproxy[14387]: 10.0.1.2:33313 [06/Feb/2009:12:12:51.443] fnt bck/srv1 0/0/5007 212 -- 0/0/0/0/3 0/0", format: "tcp"],
+            want: Ok(value!({
+                "process_name": "haproxy",
+                "pid": 14387,
+                "client_ip": "10.0.1.2",
+                "client_port": 33313,
+                "accept_date": (Utc.with_ymd_and_hms(2009, 2, 6, 12, 12, 51).unwrap() + chrono::Duration::milliseconds(443)),
+                "frontend_name": "fnt",
+                "backend_name": "bck",
+                "server_name": "srv1",
+                "time_queue": 0,
+                "time_connect": 0,
+                "time_total": 5007,
+                "bytes_read": 212,
+                "termination_state": "--",
+                "actconn": 0,
+                "feconn": 0,
+                "beconn": 0,
+                "srv_conn": 0,
+                "retries": 3,
+                "srv_queue": 0,
+                "backend_queue": 0,
+            })),
+            tdef: TypeDef::object(inner_kind(Format::Tcp)).fallible(),
+        }
+
+        http_line_as_tcp {
+            args: func_args![value: r#"10.0.1.2:33317 [06/Feb/2009:12:14:14.655] http-in static/srv1 10/0/30/69/109 200 2750 - - ---- 1/1/1/1/0 0/0 "GET / HTTP/1.1""#, format: "tcp"],
+            want: Err("failed parsing HAProxy log line"),
+            tdef: TypeDef::object(inner_kind(Format::Tcp)).fallible(),
+        }
+
+        invalid_format {
+            args: func_args![value: "", format: "udp"],
+            want: Err(r#"invalid enum variant""#),
+            tdef: TypeDef::object(inner_kind(Format::Http)).fallible(),
+        }
+    ];
+}
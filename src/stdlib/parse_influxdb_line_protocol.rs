@@ -0,0 +1,215 @@
+use std::collections::BTreeMap;
+
+use chrono::DateTime;
+use influxdb_line_protocol::{FieldValue, ParsedLine};
+
+use crate::btreemap;
+use crate::compiler::prelude::*;
+
+fn field_value(key: &str, value: FieldValue) -> Result<Value, ExpressionError> {
+    Ok(match value {
+        FieldValue::I64(v) => v.into(),
+        FieldValue::U64(v) => i64::try_from(v)
+            .map_err(|_| format!("unsigned field {key:?} is out of range: {v}"))?
+            .into(),
+        FieldValue::F64(v) => Value::from_f64_or_zero(v),
+        FieldValue::Boolean(v) => v.into(),
+        FieldValue::String(v) => v.to_string().into(),
+    })
+}
+
+fn line_to_object(line: ParsedLine) -> Resolved {
+    let ParsedLine {
+        series,
+        field_set,
+        timestamp,
+    } = line;
+
+    let tags: ObjectMap = series
+        .tag_set
+        .iter()
+        .flatten()
+        .map(|(key, value)| (key.to_string().into(), value.to_string().into()))
+        .collect();
+
+    let fields = field_set
+        .into_iter()
+        .map(|(key, value)| {
+            let key = key.to_string();
+            let value = field_value(&key, value)?;
+            Ok((key.into(), value))
+        })
+        .collect::<Result<ObjectMap, ExpressionError>>()?;
+
+    Ok(Value::from(btreemap! {
+        "measurement" => series.measurement.to_string(),
+        "tags" => tags,
+        "fields" => fields,
+        "timestamp" => timestamp.map(DateTime::from_timestamp_nanos),
+    }))
+}
+
+fn parse_influxdb_line_protocol(bytes: Value) -> Resolved {
+    let bytes = bytes.try_bytes()?;
+    let input = String::from_utf8_lossy(&bytes);
+
+    let mut lines = influxdb_line_protocol::parse_lines(&input);
+    let line = match lines.next() {
+        Some(line) => line?,
+        None => return Err("no line protocol entry found".into()),
+    };
+    if lines.next().is_some() {
+        return Err("expected a single line protocol entry".into());
+    }
+    line_to_object(line)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseInfluxdbLineProtocol;
+
+impl Function for ParseInfluxdbLineProtocol {
+    fn identifier(&self) -> &'static str {
+        "parse_influxdb_line_protocol"
+    }
+
+    fn summary(&self) -> &'static str {
+        "parse a single InfluxDB line protocol entry into its components"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Parses a single InfluxDB line protocol entry into its measurement, tags, fields and
+            timestamp. Escaped commas, spaces and equals signs are unescaped, and field values
+            keep their line protocol types: `i` and `u` suffixed values become integers, quoted
+            values become strings and `t`/`true`/`f`/`false` become booleans.
+
+            Use `parse_influxdb` instead to convert entries into metrics.
+        "}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "parse line protocol entry",
+            source: r#"parse_influxdb_line_protocol!(s'weather,location=us\,west temperature=82i,humidity=71.5,raining=false,note="light wind" 1465839830100400200')"#,
+            result: Ok(indoc! {r#"{
+                "measurement": "weather",
+                "tags": {"location": "us,west"},
+                "fields": {
+                    "humidity": 71.5,
+                    "note": "light wind",
+                    "raining": false,
+                    "temperature": 82
+                },
+                "timestamp": "2016-06-13T17:43:50.100400200Z"
+            }"#}),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(ParseInfluxdbLineProtocolFn { value }.as_expr())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ParseInfluxdbLineProtocolFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ParseInfluxdbLineProtocolFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_influxdb_line_protocol(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    btreemap! {
+        "measurement" => Kind::bytes(),
+        "tags" => Kind::object(Collection::from_unknown(Kind::bytes())),
+        "fields" => Kind::object(Collection::from_unknown(
+            Kind::integer() | Kind::float() | Kind::boolean() | Kind::bytes(),
+        )),
+        "timestamp" => Kind::timestamp() | Kind::null(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        parse_influxdb_line_protocol => ParseInfluxdbLineProtocol;
+
+        typed_fields {
+            args: func_args![value: r#"cpu,host=A usage=64i,free=10u,load=0.5,up=true,state="ok" 1590488773254420000"#],
+            want: Ok(value!({
+                "measurement": "cpu",
+                "tags": {"host": "A"},
+                "fields": {"usage": 64, "free": 10, "load": 0.5, "up": true, "state": "ok"},
+                "timestamp": (DateTime::from_timestamp_nanos(1_590_488_773_254_420_000)),
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        escaped_keys_and_values {
+            args: func_args![value: r"my\ measurement,tag\ key=a\,b,host\=name=x\ y field\,key=1i"],
+            want: Ok(value!({
+                "measurement": "my measurement",
+                "tags": {"tag key": "a,b", "host=name": "x y"},
+                "fields": {"field,key": 1},
+                "timestamp": null,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        no_tags {
+            args: func_args![value: "cpu value=1i"],
+            want: Ok(value!({
+                "measurement": "cpu",
+                "tags": {},
+                "fields": {"value": 1},
+                "timestamp": null,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        unsigned_out_of_range {
+            args: func_args![value: "cpu value=18446744073709551615u"],
+            want: Err(r#"unsigned field "value" is out of range: 18446744073709551615"#),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        multiple_lines {
+            args: func_args![value: "cpu value=1i\ncpu value=2i"],
+            want: Err("expected a single line protocol entry"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        empty {
+            args: func_args![value: ""],
+            want: Err("no line protocol entry found"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
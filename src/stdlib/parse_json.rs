@@ -187,6 +187,10 @@ impl Function for ParseJson {
         ]
     }
 
+    fn fold(&self) -> bool {
+        true
+    }
+
     fn compile(
         &self,
         _state: &state::TypeState,
@@ -258,6 +262,7 @@ impl FunctionExpression for ParseJsonMaxDepthFn {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::compiler::compile;
     use crate::value;
 
     test_function![
@@ -369,4 +374,57 @@ mod tests {
             tdef: json_type_def(),
         }
     ];
+
+    /// Compiles a single call, returning its value if it was folded at compile time.
+    fn folded(source: &str) -> Option<Value> {
+        let program = compile(source, &crate::stdlib::all()).unwrap().program;
+        let state = program.initial_type_state();
+
+        program.expressions.into_inner()[0].resolve_constant(&state)
+    }
+
+    #[test]
+    fn folds_constant_arguments() {
+        assert_eq!(
+            folded(r#"parse_json!(s'{"a": [1, true]}')"#),
+            Some(value!({ a: [1, true] }))
+        );
+    }
+
+    #[test]
+    fn folded_type_is_narrowed() {
+        let program = compile(r#"parse_json!(s'{"a": 1}')"#, &crate::stdlib::all())
+            .unwrap()
+            .program;
+
+        assert_eq!(
+            program.final_type_info().result,
+            TypeDef::object(std::collections::BTreeMap::from([(
+                Field::from("a"),
+                Kind::integer()
+            )]))
+        );
+    }
+
+    #[test]
+    fn folded_call_stays_fallible() {
+        // The error still has to be handled, even though the constant parses.
+        assert!(compile(r#"parse_json(s'{"a": 1}')"#, &crate::stdlib::all()).is_err());
+        assert!(compile(r#"parse_json(s'{"a": 1}') ?? {}"#, &crate::stdlib::all()).is_ok());
+    }
+
+    #[test]
+    fn invalid_constant_not_folded() {
+        assert_eq!(folded(r#"parse_json!("{ INVALID }")"#), None);
+    }
+
+    #[test]
+    fn folded_call_can_be_coerced() {
+        for source in [
+            r#"array!(parse_json!(s'["bar", true]'))"#,
+            r#"object!(parse_json!(s'{"foo": "bar"}'))"#,
+        ] {
+            assert!(compile(source, &crate::stdlib::all()).is_ok(), "{source}");
+        }
+    }
 }
@@ -1,10 +1,10 @@
 use crate::compiler::prelude::*;
-use chrono::{offset::TimeZone, Datelike, Utc};
+use chrono::{offset::TimeZone, DateTime, Datelike, Utc};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::collections::BTreeMap;
 
-fn parse_klog(bytes: Value) -> Resolved {
+fn parse_klog(bytes: Value, now: DateTime<Utc>) -> Resolved {
     let bytes = bytes.try_bytes()?;
     let message = String::from_utf8_lossy(&bytes);
     let mut log = ObjectMap::new();
@@ -24,7 +24,7 @@ fn parse_klog(bytes: Value) -> Resolved {
     }
     if let Some(timestamp) = captures.name("timestamp").map(|capture| capture.as_str()) {
         let month = captures.name("month").map(|capture| capture.as_str());
-        let year = resolve_year(month);
+        let year = resolve_year(month, now);
         log.insert(
             "timestamp".into(),
             Value::Timestamp(
@@ -79,10 +79,11 @@ impl Function for ParseKlog {
     }
 
     fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "valid",
-            source: r#"parse_klog!("I0505 17:59:40.692994   28133 klog.go:70] hello from klog")"#,
-            result: Ok(indoc! { r#"{
+        &[
+            Example {
+                title: "valid",
+                source: r#"parse_klog!("I0505 17:59:40.692994   28133 klog.go:70] hello from klog", now: t'2025-06-01T00:00:00Z')"#,
+                result: Ok(indoc! { r#"{
                     "file": "klog.go",
                     "id": 28133,
                     "level": "info",
@@ -90,7 +91,20 @@ impl Function for ParseKlog {
                     "message": "hello from klog",
                     "timestamp": "2025-05-05T17:59:40.692994Z"
                 }"#}),
-        }]
+            },
+            Example {
+                title: "previous year",
+                source: r#"parse_klog!("E1231 23:59:59.123456    1 leaderelection.go:330] error retrieving resource lock", now: t'2026-01-01T00:00:02Z')"#,
+                result: Ok(indoc! { r#"{
+                    "file": "leaderelection.go",
+                    "id": 1,
+                    "level": "error",
+                    "line": 330,
+                    "message": "error retrieving resource lock",
+                    "timestamp": "2025-12-31T23:59:59.123456Z"
+                }"#}),
+            },
+        ]
     }
 
     fn compile(
@@ -100,28 +114,41 @@ impl Function for ParseKlog {
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
+        let now = arguments.optional("now");
 
-        Ok(ParseKlogFn { value }.as_expr())
+        Ok(ParseKlogFn { value, now }.as_expr())
     }
 
     fn parameters(&self) -> &'static [Parameter] {
-        &[Parameter {
-            keyword: "value",
-            kind: kind::BYTES,
-            required: true,
-        }]
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "now",
+                kind: kind::TIMESTAMP,
+                required: false,
+            },
+        ]
     }
 }
 
 #[derive(Debug, Clone)]
 struct ParseKlogFn {
     value: Box<dyn Expression>,
+    now: Option<Box<dyn Expression>>,
 }
 
 impl FunctionExpression for ParseKlogFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let bytes = self.value.resolve(ctx)?;
-        parse_klog(bytes)
+        let now = match &self.now {
+            Some(now) => now.resolve(ctx)?.try_timestamp()?,
+            None => Utc::now(),
+        };
+        parse_klog(bytes, now)
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
@@ -130,11 +157,12 @@ impl FunctionExpression for ParseKlogFn {
 }
 
 // same logic as our handling of RFC3164 syslog messages: since we don't know the year, we look at
-// the month to guess the year based on the current month
-fn resolve_year(month: Option<&str>) -> i32 {
-    let now = Utc::now();
+// the month to guess the year based on the current month. A January message seen in December is
+// also moved to the next year, to allow for clocks running slightly ahead of ours.
+fn resolve_year(month: Option<&str>, now: DateTime<Utc>) -> i32 {
     match (month, now.month()) {
         (Some("12"), 1) => now.year() - 1,
+        (Some("01"), 12) => now.year() + 1,
         (_, _) => now.year(),
     }
 }
@@ -153,7 +181,6 @@ fn inner_kind() -> BTreeMap<Field, Kind> {
 #[cfg(test)]
 mod tests {
     use crate::btreemap;
-    use chrono::DateTime;
 
     use super::*;
 
@@ -186,6 +213,54 @@ mod tests {
             tdef: TypeDef::object(inner_kind()).fallible(),
         }
 
+        log_line_previous_year {
+            args: func_args![
+                value: "W1231 23:59:58.000001       7 reflector.go:424] watch of *v1.Pod ended",
+                now: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 3).unwrap(),
+            ],
+            want: Ok(btreemap! {
+                "level" => "warning",
+                "timestamp" => Value::Timestamp(DateTime::parse_from_rfc3339("2023-12-31T23:59:58.000001Z").unwrap().into()),
+                "id" => 7,
+                "file" => "reflector.go",
+                "line" => 424,
+                "message" => "watch of *v1.Pod ended",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        log_line_next_year {
+            args: func_args![
+                value: "I0101 00:00:01.500000       7 reflector.go:424] watch of *v1.Pod ended",
+                now: Utc.with_ymd_and_hms(2023, 12, 31, 23, 59, 59).unwrap(),
+            ],
+            want: Ok(btreemap! {
+                "level" => "info",
+                "timestamp" => Value::Timestamp(DateTime::parse_from_rfc3339("2024-01-01T00:00:01.5Z").unwrap().into()),
+                "id" => 7,
+                "file" => "reflector.go",
+                "line" => 424,
+                "message" => "watch of *v1.Pod ended",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        log_line_same_year {
+            args: func_args![
+                value: "I1230 10:00:00.000000       7 reflector.go:424] watch of *v1.Pod ended",
+                now: Utc.with_ymd_and_hms(2023, 12, 31, 23, 59, 59).unwrap(),
+            ],
+            want: Ok(btreemap! {
+                "level" => "info",
+                "timestamp" => Value::Timestamp(DateTime::parse_from_rfc3339("2023-12-30T10:00:00Z").unwrap().into()),
+                "id" => 7,
+                "file" => "reflector.go",
+                "line" => 424,
+                "message" => "watch of *v1.Pod ended",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
         log_line_invalid {
             args: func_args![value: "not a klog line"],
             want: Err("failed parsing klog message"),
@@ -1,6 +1,7 @@
 use crate::compiler::prelude::*;
 use crate::value;
 use regex::Regex;
+use std::borrow::Cow;
 use std::collections::BTreeMap;
 
 use super::log_util;
@@ -12,9 +13,9 @@ fn parse_nginx_log(
     ctx: &Context,
 ) -> Resolved {
     let message = bytes.try_bytes_utf8_lossy()?;
-    let timestamp_format = match timestamp_format {
-        None => time_format_for_format(format.as_ref()),
-        Some(timestamp_format) => timestamp_format.try_bytes_utf8_lossy()?.to_string(),
+    let timestamp_format = match &timestamp_format {
+        None => Cow::Borrowed(time_format_for_format(format.as_ref())),
+        Some(timestamp_format) => timestamp_format.try_bytes_utf8_lossy()?,
     };
     let regex = regex_for_format(format.as_ref());
     let captures = regex.captures(&message).ok_or("failed parsing log line")?;
@@ -119,12 +120,12 @@ fn regex_for_format(format: &[u8]) -> &Regex {
     }
 }
 
-fn time_format_for_format(format: &[u8]) -> String {
+fn time_format_for_format(format: &[u8]) -> &'static str {
     match format {
-        b"combined" => "%d/%b/%Y:%T %z".to_owned(),
-        b"ingress_upstreaminfo" => "%d/%b/%Y:%T %z".to_owned(),
-        b"main" => "%d/%b/%Y:%T %z".to_owned(),
-        b"error" => "%Y/%m/%d %H:%M:%S".to_owned(),
+        b"combined" => "%d/%b/%Y:%T %z",
+        b"ingress_upstreaminfo" => "%d/%b/%Y:%T %z",
+        b"main" => "%d/%b/%Y:%T %z",
+        b"error" => "%Y/%m/%d %H:%M:%S",
         _ => unreachable!(),
     }
 }
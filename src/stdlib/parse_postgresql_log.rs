@@ -0,0 +1,374 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write as _;
+
+use chrono::{DateTime, FixedOffset, NaiveDateTime, TimeZone as _, Utc};
+use regex::Regex;
+
+use crate::compiler::prelude::*;
+
+/// The `log_line_prefix` PostgreSQL uses by default since version 10.
+const DEFAULT_PREFIX: &str = "%m [%p] ";
+
+const LEVELS: &str =
+    "DEBUG[1-5]?|INFO|NOTICE|WARNING|ERROR|LOG|FATAL|PANIC|DETAIL|HINT|QUERY|CONTEXT|STATEMENT|LOCATION";
+
+const TIMESTAMP: &str = r"\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}";
+
+/// Returns the field name and pattern for a `log_line_prefix` escape, or `None` when the
+/// escape isn't supported.
+fn escape_pattern(escape: char) -> Option<(&'static str, String)> {
+    Some(match escape {
+        'a' => ("application_name", ".*?".to_owned()),
+        'u' => ("user", ".*?".to_owned()),
+        'd' => ("database", ".*?".to_owned()),
+        'r' | 'h' => ("remote_host", r"\S*".to_owned()),
+        'b' => ("backend_type", ".*?".to_owned()),
+        'p' => ("pid", r"\d+".to_owned()),
+        'P' => ("parallel_leader_pid", r"\d*".to_owned()),
+        't' => ("timestamp", format!(r"{TIMESTAMP} \S+")),
+        'm' => ("timestamp", format!(r"{TIMESTAMP}\.\d+ \S+")),
+        'n' => ("timestamp", r"\d+\.\d+".to_owned()),
+        'i' => ("command_tag", ".*?".to_owned()),
+        'e' => ("sql_state", "[0-9A-Z]{5}".to_owned()),
+        'c' => ("session_id", r"[0-9a-f]+\.[0-9a-f]+".to_owned()),
+        'l' => ("line_number", r"\d+".to_owned()),
+        's' => ("session_start", format!(r"{TIMESTAMP} \S+")),
+        'v' => ("virtual_transaction_id", r"\S*".to_owned()),
+        'x' => ("transaction_id", r"\d+".to_owned()),
+        'Q' => ("query_id", r"-?\d+".to_owned()),
+        _ => return None,
+    })
+}
+
+/// Builds a regex matching log lines written with the given `log_line_prefix`.
+fn prefix_regex(prefix: &str) -> Result<Regex, &'static str> {
+    let mut pattern = String::from("(?s)^");
+    let mut seen = HashSet::new();
+    let mut chars = prefix.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            pattern.push_str(&regex::escape(&c.to_string()));
+            continue;
+        }
+
+        match chars.next() {
+            Some('%') => pattern.push('%'),
+            // `%q` only marks where non-session processes stop printing the prefix.
+            Some('q') => {}
+            Some(escape) => {
+                let (name, escape_pattern) =
+                    escape_pattern(escape).ok_or("unsupported log_line_prefix escape")?;
+                // Only the first occurrence of a field is captured.
+                if seen.insert(name) {
+                    write!(pattern, "(?P<{name}>{escape_pattern})").expect("write to String");
+                } else {
+                    write!(pattern, "(?:{escape_pattern})").expect("write to String");
+                }
+            }
+            None => return Err("log_line_prefix ends with an incomplete escape"),
+        }
+    }
+    write!(pattern, r"(?P<level>{LEVELS}):\s+(?P<message>.*?)\s*$").expect("write to String");
+
+    Regex::new(&pattern).map_err(|_| "unable to build pattern from log_line_prefix")
+}
+
+/// Parses `%t`, `%m` and `%s` timestamps. PostgreSQL writes the zone as an abbreviation,
+/// so numeric offsets and zones known to the TZ database are honoured and any other zone
+/// falls back to the configured timezone.
+fn parse_timestamp(value: &str, timezone: &TimeZone) -> Result<DateTime<Utc>, String> {
+    if let Some((seconds, millis)) = value.split_once('.').filter(|_| !value.contains(' ')) {
+        let seconds = seconds.parse().ok();
+        let nanos = format!("{millis:0<9}").parse::<u32>().ok();
+        return seconds
+            .zip(nanos)
+            .and_then(|(seconds, nanos)| DateTime::from_timestamp(seconds, nanos))
+            .ok_or_else(|| format!("invalid timestamp {value:?}"));
+    }
+
+    let invalid = || format!("invalid timestamp {value:?}");
+    let (datetime, zone) = value.rsplit_once(' ').ok_or_else(invalid)?;
+    let datetime =
+        NaiveDateTime::parse_from_str(datetime, "%Y-%m-%d %H:%M:%S%.f").map_err(|_| invalid())?;
+
+    let offset = zone
+        .parse::<i32>()
+        .ok()
+        .filter(|_| zone.starts_with(['+', '-']))
+        .and_then(|hours| FixedOffset::east_opt(hours * 3600));
+    if let Some(offset) = offset {
+        return offset
+            .from_local_datetime(&datetime)
+            .single()
+            .map(|datetime| datetime.with_timezone(&Utc))
+            .ok_or_else(invalid);
+    }
+
+    let timezone = TimeZone::parse(zone).unwrap_or(*timezone);
+    timezone
+        .datetime_from_str(&datetime.to_string(), "%Y-%m-%d %H:%M:%S%.f")
+        .map_err(|_| invalid())
+}
+
+fn capture_value(name: &str, value: &str, timezone: &TimeZone) -> Result<Value, String> {
+    Ok(match name {
+        "timestamp" | "session_start" => parse_timestamp(value, timezone)?.into(),
+        "level" | "message" => value.into(),
+        _ if value.is_empty() => Value::Null,
+        "pid" | "parallel_leader_pid" | "line_number" | "transaction_id" | "query_id" => value
+            .parse::<i64>()
+            .map_err(|_| format!("invalid {name} {value:?}"))?
+            .into(),
+        _ => value.into(),
+    })
+}
+
+fn capture_kind(name: &str) -> Kind {
+    match name {
+        "timestamp" | "session_start" => Kind::timestamp(),
+        "pid" | "line_number" | "transaction_id" => Kind::integer(),
+        "parallel_leader_pid" | "query_id" => Kind::integer() | Kind::null(),
+        "level" | "message" => Kind::bytes(),
+        _ => Kind::bytes() | Kind::null(),
+    }
+}
+
+fn parse_postgresql_log(value: Value, regex: &Regex, timezone: &TimeZone) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let line = String::from_utf8_lossy(&bytes);
+    let captures = regex
+        .captures(&line)
+        .ok_or("unable to parse PostgreSQL log line")?;
+
+    let mut log = ObjectMap::new();
+    for name in regex.capture_names().flatten() {
+        if let Some(capture) = captures.name(name) {
+            log.insert(
+                name.into(),
+                capture_value(name, capture.as_str(), timezone)?,
+            );
+        }
+    }
+
+    Ok(log.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParsePostgresqlLog;
+
+impl Function for ParsePostgresqlLog {
+    fn identifier(&self) -> &'static str {
+        "parse_postgresql_log"
+    }
+
+    fn summary(&self) -> &'static str {
+        "parse a PostgreSQL server log line"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {r#"
+            Parses a PostgreSQL server log line into the fields of its `log_line_prefix`, the
+            severity `level` and the `message`.
+
+            The `prefix` argument takes the server's `log_line_prefix` setting and defaults to
+            `"%m [%p] "`. Supported escapes are captured as `application_name` (`%a`), `user`
+            (`%u`), `database` (`%d`), `remote_host` (`%r`, `%h`), `backend_type` (`%b`),
+            `pid` (`%p`), `parallel_leader_pid` (`%P`), `timestamp` (`%t`, `%m`, `%n`),
+            `command_tag` (`%i`), `sql_state` (`%e`), `session_id` (`%c`), `line_number`
+            (`%l`), `session_start` (`%s`), `virtual_transaction_id` (`%v`), `transaction_id`
+            (`%x`) and `query_id` (`%Q`). Empty values are returned as `null`.
+
+            Timestamps with a zone abbreviation that isn't in the TZ database are interpreted
+            in the configured timezone.
+        "#}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "prefix",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "default prefix",
+                source: r#"parse_postgresql_log!("2024-01-15 10:23:45.123 UTC [4242] LOG:  database system is ready to accept connections")"#,
+                result: Ok(indoc! {r#"{
+                    "level": "LOG",
+                    "message": "database system is ready to accept connections",
+                    "pid": 4242,
+                    "timestamp": "2024-01-15T10:23:45.123Z"
+                }"#}),
+            },
+            Example {
+                title: "custom prefix",
+                source: r#"parse_postgresql_log!("2024-01-15 10:23:45 UTC [4242]: user=app,db=shop ERROR:  relation \"users\" does not exist", prefix: "%t [%p]: user=%u,db=%d ")"#,
+                result: Ok(indoc! {r#"{
+                    "database": "shop",
+                    "level": "ERROR",
+                    "message": "relation \"users\" does not exist",
+                    "pid": 4242,
+                    "timestamp": "2024-01-15T10:23:45Z",
+                    "user": "app"
+                }"#}),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let prefix = match arguments.optional_literal("prefix", state)? {
+            Some(prefix) => prefix
+                .try_bytes_utf8_lossy()
+                .expect("prefix not bytes")
+                .into_owned(),
+            None => DEFAULT_PREFIX.to_owned(),
+        };
+        let regex = prefix_regex(&prefix).map_err(|error| function::Error::InvalidArgument {
+            keyword: "prefix",
+            value: prefix.clone().into(),
+            error,
+        })?;
+
+        Ok(ParsePostgresqlLogFn { value, regex }.as_expr())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ParsePostgresqlLogFn {
+    value: Box<dyn Expression>,
+    regex: Regex,
+}
+
+impl FunctionExpression for ParsePostgresqlLogFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_postgresql_log(value, &self.regex, ctx.timezone())
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(inner_kind(&self.regex)).fallible()
+    }
+}
+
+fn inner_kind(regex: &Regex) -> BTreeMap<Field, Kind> {
+    regex
+        .capture_names()
+        .flatten()
+        .map(|name| (Field::from(name.to_owned()), capture_kind(name)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone as _;
+
+    use super::*;
+    use crate::value;
+
+    fn tdef(prefix: &str) -> TypeDef {
+        TypeDef::object(inner_kind(&prefix_regex(prefix).unwrap())).fallible()
+    }
+
+    test_function![
+        parse_postgresql_log => ParsePostgresqlLog;
+
+        default_prefix {
+            args: func_args![value: "2024-01-15 10:23:45.123 UTC [12345] ERROR:  duplicate key value violates unique constraint \"users_pkey\""],
+            want: Ok(value!({
+                "timestamp": (Utc.with_ymd_and_hms(2024, 1, 15, 10, 23, 45).unwrap() + chrono::Duration::milliseconds(123)),
+                "pid": 12345,
+                "level": "ERROR",
+                "message": "duplicate key value violates unique constraint \"users_pkey\"",
+            })),
+            tdef: tdef(DEFAULT_PREFIX),
+        }
+
+        multiline_message {
+            args: func_args![value: "2024-01-15 10:23:45.123 UTC [12345] STATEMENT:  SELECT *\n\tFROM users\n"],
+            want: Ok(value!({
+                "timestamp": (Utc.with_ymd_and_hms(2024, 1, 15, 10, 23, 45).unwrap() + chrono::Duration::milliseconds(123)),
+                "pid": 12345,
+                "level": "STATEMENT",
+                "message": "SELECT *\n\tFROM users",
+            })),
+            tdef: tdef(DEFAULT_PREFIX),
+        }
+
+        custom_prefix {
+            args: func_args![
+                value: "2024-01-15 11:23:45 +01 [12345]: [3-1] user=app,db=shop,app=[unknown] LOG:  connection authorized",
+                prefix: "%t [%p]: [%l-1] user=%u,db=%d,app=%a ",
+            ],
+            want: Ok(value!({
+                "timestamp": (Utc.with_ymd_and_hms(2024, 1, 15, 10, 23, 45).unwrap()),
+                "pid": 12345,
+                "line_number": 3,
+                "user": "app",
+                "database": "shop",
+                "application_name": "[unknown]",
+                "level": "LOG",
+                "message": "connection authorized",
+            })),
+            tdef: tdef("%t [%p]: [%l-1] user=%u,db=%d,app=%a "),
+        }
+
+        empty_fields {
+            args: func_args![
+                value: "1705314225.500 [99] user=,db= LOG:  checkpoint starting: time",
+                prefix: "%n [%p] user=%u,db=%d ",
+            ],
+            want: Ok(value!({
+                "timestamp": (Utc.with_ymd_and_hms(2024, 1, 15, 10, 23, 45).unwrap() + chrono::Duration::milliseconds(500)),
+                "pid": 99,
+                "user": null,
+                "database": null,
+                "level": "LOG",
+                "message": "checkpoint starting: time",
+            })),
+            tdef: tdef("%n [%p] user=%u,db=%d "),
+        }
+
+        named_zone {
+            args: func_args![
+                value: "2024-01-15 11:23:45 CET [1] LOG:  shutting down",
+                prefix: "%t [%p] ",
+            ],
+            want: Ok(value!({
+                "timestamp": (Utc.with_ymd_and_hms(2024, 1, 15, 10, 23, 45).unwrap()),
+                "pid": 1,
+                "level": "LOG",
+                "message": "shutting down",
+            })),
+            tdef: tdef("%t [%p] "),
+        }
+
+        no_match {
+            args: func_args![value: "not a postgres line"],
+            want: Err("unable to parse PostgreSQL log line"),
+            tdef: tdef(DEFAULT_PREFIX),
+        }
+
+        unsupported_escape {
+            args: func_args![value: "", prefix: "%z "],
+            want: Err("invalid argument"),
+            tdef: tdef(DEFAULT_PREFIX),
+        }
+    ];
+}
@@ -0,0 +1,496 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Utc};
+use indexmap::IndexMap;
+
+use crate::compiler::prelude::*;
+
+const METRIC_TYPES: [&str; 5] = ["counter", "gauge", "histogram", "summary", "untyped"];
+
+/// The part a sample plays in its metric family.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Role {
+    Value,
+    Bucket,
+    Quantile,
+    Sum,
+    Count,
+}
+
+/// All samples sharing a family name and label set.
+#[derive(Debug, Default)]
+struct Series {
+    value: Option<f64>,
+    buckets: Vec<Value>,
+    quantiles: Vec<Value>,
+    sum: Option<f64>,
+    count: Option<f64>,
+    timestamp: Option<DateTime<Utc>>,
+}
+
+struct Sample<'a> {
+    name: &'a str,
+    labels: BTreeMap<String, String>,
+    value: f64,
+    timestamp: Option<DateTime<Utc>>,
+}
+
+/// Converts a sample value, which may be `NaN` for quantiles without observations.
+fn float(value: f64) -> Value {
+    NotNan::new(value).map_or(Value::Null, Value::Float)
+}
+
+fn parse_float(value: &str) -> Result<f64, String> {
+    match value {
+        "+Inf" | "Inf" => Ok(f64::INFINITY),
+        "-Inf" => Ok(f64::NEG_INFINITY),
+        "NaN" => Ok(f64::NAN),
+        _ => value
+            .parse()
+            .map_err(|_| format!("invalid sample value {value:?}")),
+    }
+}
+
+fn parse_label_value(input: &str) -> Result<(String, &str), String> {
+    let mut value = String::new();
+    let mut chars = input.char_indices();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '"' => return Ok((value, &input[idx + 1..])),
+            '\\' => match chars.next() {
+                Some((_, 'n')) => value.push('\n'),
+                Some((_, c)) => value.push(c),
+                None => break,
+            },
+            c => value.push(c),
+        }
+    }
+
+    Err("unterminated label value".to_owned())
+}
+
+fn parse_labels(mut input: &str) -> Result<(BTreeMap<String, String>, &str), String> {
+    let mut labels = BTreeMap::new();
+    loop {
+        input = input.trim_start();
+        if let Some(rest) = input.strip_prefix('}') {
+            return Ok((labels, rest));
+        }
+
+        let (name, rest) = input
+            .split_once('=')
+            .ok_or_else(|| "expected label name".to_owned())?;
+        let rest = rest
+            .trim_start()
+            .strip_prefix('"')
+            .ok_or_else(|| "expected quoted label value".to_owned())?;
+        let (value, rest) = parse_label_value(rest)?;
+        labels.insert(name.trim().to_owned(), value);
+
+        input = rest.trim_start();
+        input = input.strip_prefix(',').unwrap_or(input);
+    }
+}
+
+fn parse_sample(line: &str) -> Result<Sample<'_>, String> {
+    let name_end = line
+        .find(|c: char| c == '{' || c.is_whitespace())
+        .ok_or_else(|| "missing sample value".to_owned())?;
+    let (name, rest) = line.split_at(name_end);
+
+    let (labels, rest) = match rest.strip_prefix('{') {
+        Some(rest) => parse_labels(rest)?,
+        None => (BTreeMap::new(), rest),
+    };
+
+    let mut parts = rest.split_whitespace();
+    let value = parse_float(parts.next().ok_or("missing sample value")?)?;
+    let timestamp = parts
+        .next()
+        .map(|ts| {
+            ts.parse()
+                .ok()
+                .and_then(DateTime::from_timestamp_millis)
+                .ok_or_else(|| format!("invalid timestamp {ts:?}"))
+        })
+        .transpose()?;
+    if parts.next().is_some() {
+        return Err("unexpected trailing data".to_owned());
+    }
+
+    Ok(Sample {
+        name,
+        labels,
+        value,
+        timestamp,
+    })
+}
+
+/// Works out which family a sample belongs to, so histogram and summary samples such as
+/// `http_request_duration_seconds_bucket` are grouped under their family name.
+fn classify<'a>(sample: &Sample<'a>, types: &HashMap<String, String>) -> (&'a str, Role) {
+    let name = sample.name;
+    match types.get(name).map(String::as_str) {
+        Some("summary") if sample.labels.contains_key("quantile") => (name, Role::Quantile),
+        Some(_) => (name, Role::Value),
+        None => {
+            for (suffix, role) in [
+                ("_bucket", Role::Bucket),
+                ("_sum", Role::Sum),
+                ("_count", Role::Count),
+            ] {
+                let Some(family) = name.strip_suffix(suffix) else {
+                    continue;
+                };
+                match types.get(family).map(String::as_str) {
+                    Some("histogram") => return (family, role),
+                    Some("summary") if role != Role::Bucket => return (family, role),
+                    _ => {}
+                }
+            }
+            (name, Role::Value)
+        }
+    }
+}
+
+/// Moves the `le` or `quantile` label of a sample into a `{ key: bound, value_key: value }`
+/// pair. The `+Inf` bucket always matches `count`, so only finite buckets are kept.
+fn bound_pair(
+    labels: &mut BTreeMap<String, String>,
+    label: &str,
+    key: &str,
+    value_key: &str,
+    value: f64,
+) -> Result<Option<Value>, String> {
+    let bound = labels
+        .remove(label)
+        .ok_or_else(|| format!("missing {label:?} label"))?;
+    let bound = parse_float(&bound)?;
+    if label == "le" && bound.is_infinite() {
+        return Ok(None);
+    }
+
+    Ok(Some(Value::from(ObjectMap::from([
+        (KeyString::from(key), float(bound)),
+        (KeyString::from(value_key), float(value)),
+    ]))))
+}
+
+fn series_to_value(
+    name: String,
+    labels: BTreeMap<String, String>,
+    series: Series,
+    metric_type: &str,
+    help: Option<&String>,
+) -> Value {
+    let mut metric = ObjectMap::from([
+        (KeyString::from("name"), Value::from(name)),
+        (KeyString::from("type"), Value::from(metric_type)),
+        (KeyString::from("help"), Value::from(help.cloned())),
+        (
+            KeyString::from("labels"),
+            labels
+                .into_iter()
+                .map(|(key, value)| (KeyString::from(key), Value::from(value)))
+                .collect::<ObjectMap>()
+                .into(),
+        ),
+        (KeyString::from("timestamp"), Value::from(series.timestamp)),
+    ]);
+
+    match metric_type {
+        "histogram" | "summary" => {
+            let (key, values) = if metric_type == "histogram" {
+                ("buckets", series.buckets)
+            } else {
+                ("quantiles", series.quantiles)
+            };
+            metric.insert(key.into(), values.into());
+            metric.insert("sum".into(), series.sum.map_or(Value::Null, float));
+            metric.insert("count".into(), series.count.map_or(Value::Null, float));
+        }
+        _ => {
+            metric.insert("value".into(), series.value.map_or(Value::Null, float));
+        }
+    }
+
+    metric.into()
+}
+
+fn parse_prometheus(value: Value) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let input = String::from_utf8_lossy(&bytes);
+
+    let mut help = HashMap::new();
+    let mut types = HashMap::new();
+    let mut series: IndexMap<(String, BTreeMap<String, String>), Series> = IndexMap::new();
+
+    for (idx, line) in input.lines().enumerate() {
+        let line = line.trim();
+        let line_error = |error: String| format!("unable to parse line {}: {error}", idx + 1);
+
+        if let Some(comment) = line.strip_prefix('#') {
+            let mut parts = comment.trim_start().splitn(3, char::is_whitespace);
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some("HELP"), Some(name), text) => {
+                    let text = text
+                        .unwrap_or_default()
+                        .replace("\\n", "\n")
+                        .replace("\\\\", "\\");
+                    help.insert(name.to_owned(), text);
+                }
+                (Some("TYPE"), Some(name), Some(metric_type)) => {
+                    let metric_type = metric_type.trim();
+                    if !METRIC_TYPES.contains(&metric_type) {
+                        return Err(
+                            line_error(format!("unknown metric type {metric_type:?}")).into()
+                        );
+                    }
+                    types.insert(name.to_owned(), metric_type.to_owned());
+                }
+                _ => {}
+            }
+            continue;
+        }
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut sample = parse_sample(line).map_err(line_error)?;
+        let (family, role) = classify(&sample, &types);
+        let value = sample.value;
+        let pair = match role {
+            Role::Bucket => bound_pair(&mut sample.labels, "le", "upper_limit", "count", value),
+            Role::Quantile => {
+                bound_pair(&mut sample.labels, "quantile", "quantile", "value", value)
+            }
+            _ => Ok(None),
+        }
+        .map_err(line_error)?;
+
+        let entry = series
+            .entry((family.to_owned(), sample.labels))
+            .or_default();
+        entry.timestamp = sample.timestamp.or(entry.timestamp);
+        match role {
+            Role::Value => entry.value = Some(value),
+            Role::Sum => entry.sum = Some(value),
+            Role::Count => entry.count = Some(value),
+            Role::Bucket => entry.buckets.extend(pair),
+            Role::Quantile => entry.quantiles.extend(pair),
+        }
+    }
+
+    Ok(series
+        .into_iter()
+        .map(|((name, labels), series)| {
+            let metric_type = types.get(&name).map_or("untyped", String::as_str);
+            let help = help.get(&name);
+            series_to_value(name, labels, series, metric_type, help)
+        })
+        .collect::<Vec<_>>()
+        .into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParsePrometheus;
+
+impl Function for ParsePrometheus {
+    fn identifier(&self) -> &'static str {
+        "parse_prometheus"
+    }
+
+    fn summary(&self) -> &'static str {
+        "parse Prometheus exposition format text into metrics"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Parses Prometheus text exposition format into an array of metric objects with the
+            metric `name`, `type`, `help` text, `labels` and `timestamp`.
+
+            Counters, gauges and untyped metrics carry a single `value`. The samples of a
+            histogram or summary are grouped into one object per label set, holding `buckets`
+            or `quantiles` together with `sum` and `count`. The `+Inf` bucket is left out as
+            it always equals `count`. `NaN` sample values are returned as `null`.
+        "}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "counter",
+            source: indoc! {r#"
+                parse_prometheus!(s'# HELP http_requests_total Total requests.
+                # TYPE http_requests_total counter
+                http_requests_total{method="post",code="200"} 1027 1395066363000
+                ')
+            "#},
+            result: Ok(indoc! {r#"[{
+                "name": "http_requests_total",
+                "type": "counter",
+                "help": "Total requests.",
+                "labels": {"code": "200", "method": "post"},
+                "timestamp": "2014-03-17T14:26:03Z",
+                "value": 1027.0
+            }]"#}),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(ParsePrometheusFn { value }.as_expr())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ParsePrometheusFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ParsePrometheusFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_prometheus(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::array(Collection::from_unknown(Kind::object(Collection::any()))).fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    fn tdef() -> TypeDef {
+        TypeDef::array(Collection::from_unknown(Kind::object(Collection::any()))).fallible()
+    }
+
+    test_function![
+        parse_prometheus => ParsePrometheus;
+
+        counter_with_labels {
+            args: func_args![value: indoc! {r#"
+                # HELP http_requests_total The total number of HTTP requests.
+                # TYPE http_requests_total counter
+                http_requests_total{method="post",code="200"} 1027
+                http_requests_total{method="post",code="400"}    3
+            "#}],
+            want: Ok(value!([
+                {
+                    "name": "http_requests_total",
+                    "type": "counter",
+                    "help": "The total number of HTTP requests.",
+                    "labels": {"code": "200", "method": "post"},
+                    "timestamp": null,
+                    "value": 1027.0,
+                },
+                {
+                    "name": "http_requests_total",
+                    "type": "counter",
+                    "help": "The total number of HTTP requests.",
+                    "labels": {"code": "400", "method": "post"},
+                    "timestamp": null,
+                    "value": 3.0,
+                },
+            ])),
+            tdef: tdef(),
+        }
+
+        histogram {
+            args: func_args![value: indoc! {r#"
+                # HELP request_duration_seconds Request latency.
+                # TYPE request_duration_seconds histogram
+                request_duration_seconds_bucket{path="/",le="0.05"} 24054
+                request_duration_seconds_bucket{path="/",le="0.5"} 129389
+                request_duration_seconds_bucket{path="/",le="+Inf"} 144320
+                request_duration_seconds_sum{path="/"} 53423
+                request_duration_seconds_count{path="/"} 144320
+            "#}],
+            want: Ok(value!([{
+                "name": "request_duration_seconds",
+                "type": "histogram",
+                "help": "Request latency.",
+                "labels": {"path": "/"},
+                "timestamp": null,
+                "buckets": [
+                    {"upper_limit": 0.05, "count": 24054.0},
+                    {"upper_limit": 0.5, "count": 129_389.0},
+                ],
+                "sum": 53423.0,
+                "count": 144_320.0,
+            }])),
+            tdef: tdef(),
+        }
+
+        summary_and_untyped {
+            args: func_args![value: indoc! {r#"
+                # TYPE rpc_duration_seconds summary
+                rpc_duration_seconds{quantile="0.5"} 4773
+                rpc_duration_seconds{quantile="0.99"} NaN
+                rpc_duration_seconds_sum 17560473
+                rpc_duration_seconds_count 2693
+                # A plain comment.
+                temperature{room="a \"big\" one"} -3.5 1395066363000
+            "#}],
+            want: Ok(value!([
+                {
+                    "name": "rpc_duration_seconds",
+                    "type": "summary",
+                    "help": null,
+                    "labels": {},
+                    "timestamp": null,
+                    "quantiles": [
+                        {"quantile": 0.5, "value": 4773.0},
+                        {"quantile": 0.99, "value": null},
+                    ],
+                    "sum": 17_560_473.0,
+                    "count": 2693.0,
+                },
+                {
+                    "name": "temperature",
+                    "type": "untyped",
+                    "help": null,
+                    "labels": {"room": "a \"big\" one"},
+                    "timestamp": (DateTime::from_timestamp_millis(1_395_066_363_000).unwrap()),
+                    "value": (-3.5),
+                },
+            ])),
+            tdef: tdef(),
+        }
+
+        unknown_type {
+            args: func_args![value: "# TYPE foo enum\nfoo 1"],
+            want: Err(r#"unable to parse line 1: unknown metric type "enum""#),
+            tdef: tdef(),
+        }
+
+        bucket_without_le {
+            args: func_args![value: "# TYPE foo histogram\nfoo_bucket 1"],
+            want: Err(r#"unable to parse line 2: missing "le" label"#),
+            tdef: tdef(),
+        }
+
+        invalid_value {
+            args: func_args![value: "foo{a=\"b\"} one"],
+            want: Err(r#"unable to parse line 1: invalid sample value "one""#),
+            tdef: tdef(),
+        }
+    ];
+}
@@ -1,5 +1,10 @@
 use crate::compiler::prelude::*;
-use crate::parsing::query_string::parse_query_string;
+use crate::parsing::query_string::{parse_query_string, Repeated};
+use crate::value;
+
+fn repeated_policies() -> Vec<Value> {
+    vec![value!("array"), value!("first"), value!("last")]
+}
 
 #[derive(Clone, Copy, Debug)]
 pub struct ParseQueryString;
@@ -9,62 +14,118 @@ impl Function for ParseQueryString {
         "parse_query_string"
     }
 
+    fn usage(&self) -> &'static str {
+        indoc! {r#"
+            Parses a query string, such as `?foo=1&bar=2`, into an object.
+
+            When a key appears more than once, `repeated` decides what is kept: `"array"` (the
+            default) collects all of its values into an array, while `"first"` and `"last"` keep
+            a single value. Keys and values are percent-decoded unless `decode` is `false`.
+        "#}
+    }
+
     fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "parse query string",
-            source: r#"parse_query_string("foo=1&bar=2")"#,
-            result: Ok(r#"
+        &[
+            Example {
+                title: "parse query string",
+                source: r#"parse_query_string("foo=1&bar=2")"#,
+                result: Ok(r#"
                 {
                     "foo": "1",
                     "bar": "2"
                 }
             "#),
-        }]
+            },
+            Example {
+                title: "last value wins",
+                source: r#"parse_query_string("a=1&a=2", repeated: "last")"#,
+                result: Ok(r#"{ "a": "2" }"#),
+            },
+            Example {
+                title: "without decoding",
+                source: r#"parse_query_string("q=hello%20world", decode: false)"#,
+                result: Ok(r#"{ "q": "hello%20world" }"#),
+            },
+        ]
     }
 
     fn compile(
         &self,
-        _state: &state::TypeState,
+        state: &state::TypeState,
         _ctx: &mut FunctionCompileContext,
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
-        Ok(ParseQueryStringFn { value }.as_expr())
+        let repeated = match arguments
+            .optional_enum("repeated", &repeated_policies(), state)?
+            .as_ref()
+            .and_then(Value::as_bytes)
+            .map(AsRef::as_ref)
+        {
+            Some(b"first") => Repeated::First,
+            Some(b"last") => Repeated::Last,
+            _ => Repeated::Array,
+        };
+        let decode = arguments.optional("decode").unwrap_or(expr!(true));
+
+        Ok(ParseQueryStringFn {
+            value,
+            repeated,
+            decode,
+        }
+        .as_expr())
     }
 
     fn parameters(&self) -> &'static [Parameter] {
-        &[Parameter {
-            keyword: "value",
-            kind: kind::BYTES,
-            required: true,
-        }]
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "repeated",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "decode",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
     }
 }
 
 #[derive(Debug, Clone)]
 struct ParseQueryStringFn {
     value: Box<dyn Expression>,
+    repeated: Repeated,
+    decode: Box<dyn Expression>,
 }
 
 impl FunctionExpression for ParseQueryStringFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let bytes = self.value.resolve(ctx)?.try_bytes()?;
-        parse_query_string(&bytes, false)
+        let decode = self.decode.resolve(ctx)?.try_boolean()?;
+        parse_query_string(&bytes, false, self.repeated, decode)
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
-        TypeDef::object(inner_kind())
+        TypeDef::object(inner_kind(self.repeated))
     }
 }
 
-fn inner_kind() -> Collection<Field> {
-    Collection::from_unknown(Kind::bytes().or_array(Collection::any()))
+fn inner_kind(repeated: Repeated) -> Collection<Field> {
+    match repeated {
+        Repeated::Array => Collection::from_unknown(Kind::bytes().or_array(Collection::any())),
+        Repeated::First | Repeated::Last => Collection::from_unknown(Kind::bytes()),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::value;
 
     test_function![
         parse_query_string => ParseQueryString;
@@ -77,7 +138,7 @@ mod tests {
                 xyz: "",
                 abc: "",
             })),
-            tdef: TypeDef::object(inner_kind()),
+            tdef: TypeDef::object(inner_kind(Repeated::Array)),
         }
 
         multiple_values {
@@ -85,7 +146,7 @@ mod tests {
             want: Ok(value!({
                 foo: ["bar", "xyz"],
             })),
-            tdef: TypeDef::object(inner_kind()),
+            tdef: TypeDef::object(inner_kind(Repeated::Array)),
         }
 
         ruby_on_rails_multiple_values {
@@ -93,7 +154,7 @@ mod tests {
             want: Ok(value!({
                 "foo[]": ["bar", "xyz"],
             })),
-            tdef: TypeDef::object(inner_kind()),
+            tdef: TypeDef::object(inner_kind(Repeated::Array)),
         }
 
         empty_key {
@@ -101,7 +162,7 @@ mod tests {
             want: Ok(value!({
                 "": ["", ""],
             })),
-            tdef: TypeDef::object(inner_kind()),
+            tdef: TypeDef::object(inner_kind(Repeated::Array)),
         }
 
         single_key {
@@ -109,13 +170,52 @@ mod tests {
             want: Ok(value!({
                 foo: "",
             })),
-            tdef: TypeDef::object(inner_kind()),
+            tdef: TypeDef::object(inner_kind(Repeated::Array)),
         }
 
         empty {
             args: func_args![value: value!("")],
             want: Ok(value!({})),
-            tdef: TypeDef::object(inner_kind()),
+            tdef: TypeDef::object(inner_kind(Repeated::Array)),
+        }
+
+        repeated_array {
+            args: func_args![value: value!("a=1&a=2"), repeated: "array"],
+            want: Ok(value!({
+                a: ["1", "2"],
+            })),
+            tdef: TypeDef::object(inner_kind(Repeated::Array)),
+        }
+
+        repeated_first {
+            args: func_args![value: value!("a=1&a=2"), repeated: "first"],
+            want: Ok(value!({
+                a: "1",
+            })),
+            tdef: TypeDef::object(inner_kind(Repeated::First)),
+        }
+
+        repeated_last {
+            args: func_args![value: value!("a=1&a=2"), repeated: "last"],
+            want: Ok(value!({
+                a: "2",
+            })),
+            tdef: TypeDef::object(inner_kind(Repeated::Last)),
+        }
+
+        invalid_repeated {
+            args: func_args![value: value!("a=1&a=2"), repeated: "merge"],
+            want: Err(r#"invalid enum variant""#),
+            tdef: TypeDef::object(inner_kind(Repeated::Array)),
+        }
+
+        not_decoded {
+            args: func_args![value: value!("redirect=%2Fhome%3Fx%3D1&name=a+b"), decode: false],
+            want: Ok(value!({
+                redirect: "%2Fhome%3Fx%3D1",
+                name: "a+b",
+            })),
+            tdef: TypeDef::object(inner_kind(Repeated::Array)),
         }
 
         starts_with_question_mark {
@@ -123,7 +223,7 @@ mod tests {
             want: Ok(value!({
                 foo: "bar",
             })),
-            tdef: TypeDef::object(inner_kind()),
+            tdef: TypeDef::object(inner_kind(Repeated::Array)),
         }
     ];
 }
@@ -1,16 +1,41 @@
 use crate::compiler::prelude::*;
 use regex::Regex;
+use std::collections::BTreeMap;
 
 use super::util;
 
-fn parse_regex(value: Value, numeric_groups: bool, pattern: &Regex) -> Resolved {
+fn parse_regex(value: Value, numeric_groups: bool, numbered: bool, pattern: &Regex) -> Resolved {
     let bytes = value.try_bytes()?;
     let value = String::from_utf8_lossy(&bytes);
-    let parsed = pattern
+    let capture = pattern
         .captures(&value)
-        .map(|capture| util::capture_regex_to_map(pattern, &capture, numeric_groups))
         .ok_or("could not find any pattern matches")?;
-    Ok(parsed.into())
+
+    if numbered {
+        // Every group keeps its position, with `null` for groups that did not participate.
+        return Ok(capture
+            .iter()
+            .map(|group| group.map(|group| group.as_str()).into())
+            .collect::<Vec<Value>>()
+            .into());
+    }
+
+    Ok(util::capture_regex_to_map(pattern, &capture, numeric_groups).into())
+}
+
+/// Array type for `numbered` results: group 0 always matches, the others may be `null`.
+fn numbered_kind(pattern: &Regex) -> Collection<Index> {
+    (0..pattern.captures_len())
+        .map(|idx| {
+            let kind = if idx == 0 {
+                Kind::bytes()
+            } else {
+                Kind::bytes() | Kind::null()
+            };
+            (Index::from(idx), kind)
+        })
+        .collect::<BTreeMap<_, _>>()
+        .into()
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -38,6 +63,11 @@ impl Function for ParseRegex {
                 kind: kind::BOOLEAN,
                 required: false,
             },
+            Parameter {
+                keyword: "numbered",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
         ]
     }
 
@@ -52,11 +82,15 @@ impl Function for ParseRegex {
         let numeric_groups = arguments
             .optional("numeric_groups")
             .unwrap_or_else(|| expr!(false));
+        let numbered = arguments
+            .optional("numbered")
+            .unwrap_or_else(|| expr!(false));
 
         Ok(ParseRegexFn {
             value,
             pattern,
             numeric_groups,
+            numbered,
         }
         .as_expr())
     }
@@ -82,6 +116,11 @@ impl Function for ParseRegex {
                 "user": "zorp"
             }"# }),
             },
+            Example {
+                title: "numbered groups",
+                source: r#"parse_regex!("8.7.6.5 - zorp", r'^(?P<host>[\w\.]+) - ([\w]+)', numbered: true)"#,
+                result: Ok(r#"["8.7.6.5 - zorp", "8.7.6.5", "zorp"]"#),
+            },
             Example {
                 title: "match with variable",
                 source: r#"
@@ -101,19 +140,37 @@ pub(crate) struct ParseRegexFn {
     value: Box<dyn Expression>,
     pattern: Regex,
     numeric_groups: Box<dyn Expression>,
+    numbered: Box<dyn Expression>,
 }
 
 impl FunctionExpression for ParseRegexFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
         let numeric_groups = self.numeric_groups.resolve(ctx)?;
+        let numbered = self.numbered.resolve(ctx)?;
         let pattern = &self.pattern;
 
-        parse_regex(value, numeric_groups.try_boolean()?, pattern)
+        parse_regex(
+            value,
+            numeric_groups.try_boolean()?,
+            numbered.try_boolean()?,
+            pattern,
+        )
     }
 
-    fn type_def(&self, _: &state::TypeState) -> TypeDef {
-        TypeDef::object(util::regex_kind(&self.pattern)).fallible()
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        let object = TypeDef::object(util::regex_kind(&self.pattern)).fallible();
+        let array = TypeDef::array(numbered_kind(&self.pattern)).fallible();
+
+        match self
+            .numbered
+            .resolve_constant(state)
+            .and_then(|numbered| numbered.as_boolean())
+        {
+            Some(true) => array,
+            Some(false) => object,
+            None => object.union(array),
+        }
     }
 }
 
@@ -212,5 +269,52 @@ mod tests {
                     Field::from("8") => Kind::bytes() | Kind::null(),
                 }).fallible(),
         }
+
+        numbered_mixed_groups {
+            args: func_args! [
+                value: "GET /index.html 200",
+                pattern: Regex::new(r"^(?P<method>\w+) (\S+) (?P<status>\d+)$").unwrap(),
+                numbered: true,
+            ],
+            want: Ok(value!(["GET /index.html 200", "GET", "/index.html", "200"])),
+            tdef: TypeDef::array(btreemap! {
+                    Index::from(0) => Kind::bytes(),
+                    Index::from(1) => Kind::bytes() | Kind::null(),
+                    Index::from(2) => Kind::bytes() | Kind::null(),
+                    Index::from(3) => Kind::bytes() | Kind::null(),
+                }).fallible(),
+        }
+
+        numbered_unmatched_group {
+            args: func_args! [
+                value: "GET 200",
+                pattern: Regex::new(r"^(?P<method>\w+)(?: (/\S*))? (\d+)$").unwrap(),
+                numbered: true,
+            ],
+            want: Ok(value!(["GET 200", "GET", null, "200"])),
+            tdef: TypeDef::array(btreemap! {
+                    Index::from(0) => Kind::bytes(),
+                    Index::from(1) => Kind::bytes() | Kind::null(),
+                    Index::from(2) => Kind::bytes() | Kind::null(),
+                    Index::from(3) => Kind::bytes() | Kind::null(),
+                }).fallible(),
+        }
+
+        numbered_false {
+            args: func_args! [
+                value: "GET /index.html 200",
+                pattern: Regex::new(r"^(?P<method>\w+) (\S+) (?P<status>\d+)$").unwrap(),
+                numbered: false,
+            ],
+            want: Ok(value!({"method": "GET", "status": "200"})),
+            tdef: TypeDef::object(btreemap! {
+                    Field::from("method") => Kind::bytes(),
+                    Field::from("status") => Kind::bytes(),
+                    Field::from("0") => Kind::bytes() | Kind::null(),
+                    Field::from("1") => Kind::bytes() | Kind::null(),
+                    Field::from("2") => Kind::bytes() | Kind::null(),
+                    Field::from("3") => Kind::bytes() | Kind::null(),
+                }).fallible(),
+        }
     ];
 }
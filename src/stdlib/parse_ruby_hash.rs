@@ -1,8 +1,13 @@
 use crate::compiler::prelude::*;
+use crate::parsing::ruby_hash::Options;
 
-fn parse_ruby_hash(value: Value) -> Resolved {
+fn parse_ruby_hash(value: Value, strip_symbol_colon: bool) -> Resolved {
     let input = value.try_bytes_utf8_lossy()?;
-    crate::parsing::ruby_hash::parse_ruby_hash(&input)
+    let options = Options {
+        strip_symbol_colon,
+        integers: true,
+    };
+    crate::parsing::ruby_hash::parse_ruby_hash(&input, options)
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -14,10 +19,11 @@ impl Function for ParseRubyHash {
     }
 
     fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "parse ruby hash",
-            source: r#"parse_ruby_hash!(s'{ "test" => "value", "testNum" => 0.2, "testObj" => { "testBool" => true, "testNull" => nil } }')"#,
-            result: Ok(r#"
+        &[
+            Example {
+                title: "parse ruby hash",
+                source: r#"parse_ruby_hash!(s'{ "test" => "value", "testNum" => 0.2, "testObj" => { "testBool" => true, "testNull" => nil } }')"#,
+                result: Ok(r#"
                 {
                     "test": "value",
                     "testNum": 0.2,
@@ -27,7 +33,13 @@ impl Function for ParseRubyHash {
                     }
                 }
             "#),
-        }]
+            },
+            Example {
+                title: "keep symbol colons",
+                source: "parse_ruby_hash!(s'{ :status => 200, :level => :warn }', strip_symbol_colon: false)",
+                result: Ok(r#"{ ":level": ":warn", ":status": 200 }"#),
+            },
+        ]
     }
 
     fn compile(
@@ -37,27 +49,43 @@ impl Function for ParseRubyHash {
         arguments: ArgumentList,
     ) -> Compiled {
         let value = arguments.required("value");
-        Ok(ParseRubyHashFn { value }.as_expr())
+        let strip_symbol_colon = arguments
+            .optional("strip_symbol_colon")
+            .unwrap_or_else(|| expr!(true));
+        Ok(ParseRubyHashFn {
+            value,
+            strip_symbol_colon,
+        }
+        .as_expr())
     }
 
     fn parameters(&self) -> &'static [Parameter] {
-        &[Parameter {
-            keyword: "value",
-            kind: kind::BYTES,
-            required: true,
-        }]
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "strip_symbol_colon",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
     }
 }
 
 #[derive(Debug, Clone)]
 struct ParseRubyHashFn {
     value: Box<dyn Expression>,
+    strip_symbol_colon: Box<dyn Expression>,
 }
 
 impl FunctionExpression for ParseRubyHashFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
-        parse_ruby_hash(value)
+        let strip_symbol_colon = self.strip_symbol_colon.resolve(ctx)?.try_boolean()?;
+        parse_ruby_hash(value, strip_symbol_colon)
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
@@ -68,6 +96,7 @@ impl FunctionExpression for ParseRubyHashFn {
 fn inner_kinds() -> Kind {
     Kind::null()
         | Kind::bytes()
+        | Kind::integer()
         | Kind::float()
         | Kind::boolean()
         | Kind::array(Collection::any())
@@ -93,5 +122,26 @@ mod tests {
             })),
             tdef: TypeDef::object(Collection::from_unknown(inner_kinds())).fallible(),
         }
+
+        typed_values {
+            args: func_args![value: value!(r#"{ :user_id => 42, :role => :admin, "deleted_at" => nil, "score" => 9.5 }"#)],
+            want: Ok(value!({
+                user_id: 42,
+                role: "admin",
+                deleted_at: null,
+                score: 9.5
+            })),
+            tdef: TypeDef::object(Collection::from_unknown(inner_kinds())).fallible(),
+        }
+
+        symbol_colon_kept {
+            args: func_args![value: value!(r#"{ :user_id => 42, :role => :admin, "deleted_at" => nil }"#), strip_symbol_colon: false],
+            want: Ok(value!({
+                ":user_id": 42,
+                ":role": ":admin",
+                deleted_at: null
+            })),
+            tdef: TypeDef::object(Collection::from_unknown(inner_kinds())).fallible(),
+        }
     ];
 }
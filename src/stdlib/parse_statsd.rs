@@ -0,0 +1,326 @@
+use std::collections::BTreeMap;
+
+use crate::compiler::prelude::*;
+
+fn metric_type(code: &str) -> Result<&'static str, String> {
+    match code {
+        "c" => Ok("counter"),
+        "g" => Ok("gauge"),
+        "ms" | "h" | "d" => Ok("timing"),
+        "s" => Ok("set"),
+        _ => Err(format!("unknown metric type {code:?}")),
+    }
+}
+
+fn parse_tags(tags: &str) -> ObjectMap {
+    tags.split(',')
+        .filter(|tag| !tag.is_empty())
+        .map(|tag| match tag.split_once(':') {
+            Some((key, value)) => (key.into(), value.into()),
+            None => (tag.into(), Value::Null),
+        })
+        .collect()
+}
+
+/// Parses a single `name:value|type|@rate|#tags` metric.
+fn parse_metric(line: &str) -> Result<Value, String> {
+    let (name, rest) = line.split_once(':').ok_or("missing value")?;
+    if name.is_empty() {
+        return Err("missing name".to_owned());
+    }
+
+    let mut sections = rest.split('|');
+    let raw_value = sections.next().unwrap_or_default();
+    let metric_type = metric_type(sections.next().ok_or("missing type")?)?;
+
+    let mut sample_rate = 1.0;
+    let mut tags = ObjectMap::new();
+    for section in sections {
+        if let Some(rate) = section.strip_prefix('@') {
+            sample_rate = rate
+                .parse::<f64>()
+                .ok()
+                .filter(|rate| *rate > 0.0 && *rate <= 1.0)
+                .ok_or_else(|| format!("invalid sample rate {rate:?}"))?;
+        } else if let Some(section) = section.strip_prefix('#') {
+            tags.extend(parse_tags(section));
+        }
+        // Other extension sections, such as DogStatsD container IDs, are ignored.
+    }
+
+    // A leading sign on a gauge means the value is a change to the current value rather
+    // than a replacement.
+    let relative = metric_type == "gauge" && raw_value.starts_with(['+', '-']);
+    let value = if metric_type == "set" {
+        Value::from(raw_value)
+    } else {
+        raw_value
+            .parse::<f64>()
+            .ok()
+            .and_then(|value| NotNan::new(value).ok())
+            .map(Value::Float)
+            .ok_or_else(|| format!("invalid value {raw_value:?}"))?
+    };
+
+    Ok(Value::from(BTreeMap::from([
+        (KeyString::from("name"), Value::from(name)),
+        (KeyString::from("value"), value),
+        (KeyString::from("type"), Value::from(metric_type)),
+        (KeyString::from("relative"), Value::from(relative)),
+        (
+            KeyString::from("sample_rate"),
+            Value::from_f64_or_zero(sample_rate),
+        ),
+        (KeyString::from("tags"), Value::from(tags)),
+    ])))
+}
+
+fn parse_statsd(value: Value, multiline: bool) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let input = String::from_utf8_lossy(&bytes);
+    let mut metrics = input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            parse_metric(line).map_err(|err| format!("unable to parse statsd metric: {err}"))
+        });
+
+    if multiline {
+        return Ok(metrics.collect::<Result<Vec<_>, _>>()?.into());
+    }
+
+    match (metrics.next(), metrics.next()) {
+        (Some(metric), None) => Ok(metric?),
+        (None, _) => Err("unable to parse statsd metric: empty input".into()),
+        (Some(_), Some(_)) => {
+            Err("found multiple statsd metrics, set `multiline` to parse them".into())
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseStatsd;
+
+impl Function for ParseStatsd {
+    fn identifier(&self) -> &'static str {
+        "parse_statsd"
+    }
+
+    fn summary(&self) -> &'static str {
+        "parse a StatsD metric"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Parses a StatsD metric in the `name:value|type|@sample_rate|#tags` format, including
+            DogStatsD tags.
+
+            The type is mapped to `counter`, `gauge`, `timing` (for `ms`, `h` and `d`) or
+            `set`. Set values are returned as strings and all other values as floats. A gauge
+            value with a leading `+` or `-` sign is a change to the current value, which is
+            indicated by `relative`. Tags without a value are returned as `null`.
+
+            Packets holding several newline-separated metrics can be parsed into an array by
+            setting `multiline` to `true`.
+        "}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "multiline",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "tagged counter",
+                source: r#"parse_statsd!("page.views:1|c|@0.5|#env:prod,canary")"#,
+                result: Ok(indoc! {r#"{
+                    "name": "page.views",
+                    "value": 1.0,
+                    "type": "counter",
+                    "relative": false,
+                    "sample_rate": 0.5,
+                    "tags": {"canary": null, "env": "prod"}
+                }"#}),
+            },
+            Example {
+                title: "multiple metrics",
+                source: r#"parse_statsd!("users:alice|s\nqueue.depth:-4|g", multiline: true)"#,
+                result: Ok(indoc! {r#"[
+                    {"name": "users", "value": "alice", "type": "set", "relative": false, "sample_rate": 1.0, "tags": {}},
+                    {"name": "queue.depth", "value": -4.0, "type": "gauge", "relative": true, "sample_rate": 1.0, "tags": {}}
+                ]"#}),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let multiline = match arguments.optional_literal("multiline", state)? {
+            Some(multiline) => {
+                multiline
+                    .as_boolean()
+                    .ok_or_else(|| function::Error::InvalidArgument {
+                        keyword: "multiline",
+                        value: multiline,
+                        error: "multiline must be a boolean",
+                    })?
+            }
+            None => false,
+        };
+
+        Ok(ParseStatsdFn { value, multiline }.as_expr())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ParseStatsdFn {
+    value: Box<dyn Expression>,
+    multiline: bool,
+}
+
+impl FunctionExpression for ParseStatsdFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_statsd(value, self.multiline)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        type_def(self.multiline)
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        (Field::from("name"), Kind::bytes()),
+        (Field::from("value"), Kind::float() | Kind::bytes()),
+        (Field::from("type"), Kind::bytes()),
+        (Field::from("relative"), Kind::boolean()),
+        (Field::from("sample_rate"), Kind::float()),
+        (
+            Field::from("tags"),
+            Kind::object(Collection::from_unknown(Kind::bytes() | Kind::null())),
+        ),
+    ])
+}
+
+fn type_def(multiline: bool) -> TypeDef {
+    if multiline {
+        TypeDef::array(Collection::from_unknown(Kind::object(inner_kind()))).fallible()
+    } else {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        parse_statsd => ParseStatsd;
+
+        tagged_counter {
+            args: func_args![value: "api.requests:3|c|@0.1|#region:us-east-1,endpoint:/users"],
+            want: Ok(value!({
+                "name": "api.requests",
+                "value": 3.0,
+                "type": "counter",
+                "relative": false,
+                "sample_rate": 0.1,
+                "tags": {"endpoint": "/users", "region": "us-east-1"},
+            })),
+            tdef: type_def(false),
+        }
+
+        signed_gauge {
+            args: func_args![value: "pool.size:+10|g"],
+            want: Ok(value!({
+                "name": "pool.size",
+                "value": 10.0,
+                "type": "gauge",
+                "relative": true,
+                "sample_rate": 1.0,
+                "tags": {},
+            })),
+            tdef: type_def(false),
+        }
+
+        absolute_gauge {
+            args: func_args![value: "pool.size:7.5|g"],
+            want: Ok(value!({
+                "name": "pool.size",
+                "value": 7.5,
+                "type": "gauge",
+                "relative": false,
+                "sample_rate": 1.0,
+                "tags": {},
+            })),
+            tdef: type_def(false),
+        }
+
+        timing {
+            args: func_args![value: "db.query:320|ms"],
+            want: Ok(value!({
+                "name": "db.query",
+                "value": 320.0,
+                "type": "timing",
+                "relative": false,
+                "sample_rate": 1.0,
+                "tags": {},
+            })),
+            tdef: type_def(false),
+        }
+
+        multiline {
+            args: func_args![value: "a:1|c\n\nb:-2|g\n", multiline: true],
+            want: Ok(value!([
+                {"name": "a", "value": 1.0, "type": "counter", "relative": false, "sample_rate": 1.0, "tags": {}},
+                {"name": "b", "value": (-2.0), "type": "gauge", "relative": true, "sample_rate": 1.0, "tags": {}},
+            ])),
+            tdef: type_def(true),
+        }
+
+        multiple_without_multiline {
+            args: func_args![value: "a:1|c\nb:2|c"],
+            want: Err("found multiple statsd metrics, set `multiline` to parse them"),
+            tdef: type_def(false),
+        }
+
+        unknown_type {
+            args: func_args![value: "a:1|x"],
+            want: Err(r#"unable to parse statsd metric: unknown metric type "x""#),
+            tdef: type_def(false),
+        }
+
+        invalid_value {
+            args: func_args![value: "a:one|c"],
+            want: Err(r#"unable to parse statsd metric: invalid value "one""#),
+            tdef: type_def(false),
+        }
+
+        invalid_sample_rate {
+            args: func_args![value: "a:1|c|@2"],
+            want: Err(r#"unable to parse statsd metric: invalid sample rate "2""#),
+            tdef: type_def(false),
+        }
+    ];
+}
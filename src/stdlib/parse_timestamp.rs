@@ -10,8 +10,6 @@ fn parse_timestamp(
 ) -> Resolved {
     match value {
         Value::Bytes(v) => {
-            let format = format.try_bytes_utf8_lossy()?;
-
             let timezone_bytes = timezone.map(VrlValueConvert::try_bytes).transpose()?;
             let timezone = timezone_bytes.as_ref().map(|b| String::from_utf8_lossy(b));
             let timezone = timezone
@@ -22,16 +20,45 @@ fn parse_timestamp(
                 .transpose()?
                 .unwrap_or(*ctx.timezone());
 
-            Conversion::parse(format!("timestamp|{format}"), timezone)
-                .map_err(|e| e.to_string())?
-                .convert(v)
-                .map_err(|e| e.to_string().into())
+            let Value::Array(formats) = format else {
+                let format = format.try_bytes_utf8_lossy()?;
+                return parse_with_format(v, &format, timezone).map_err(Into::into);
+            };
+
+            let mut candidates = Vec::with_capacity(formats.len());
+            for format in &formats {
+                candidates.push(format.try_bytes_utf8_lossy()?.into_owned());
+            }
+            if candidates.is_empty() {
+                return Err("at least one format is required".into());
+            }
+
+            // Candidate formats are tried in order, returning the first successful parse.
+            for format in &candidates {
+                if let Ok(timestamp) = parse_with_format(v.clone(), format, timezone) {
+                    return Ok(timestamp);
+                }
+            }
+
+            let tried = candidates
+                .iter()
+                .map(|format| format!("{format:?}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            Err(format!("unable to parse timestamp with any of the formats: {tried}").into())
         }
         Value::Timestamp(_) => Ok(value),
         _ => Err("unable to convert value to timestamp".into()),
     }
 }
 
+fn parse_with_format(value: Bytes, format: &str, timezone: TimeZone) -> Result<Value, String> {
+    Conversion::parse(format!("timestamp|{format}"), timezone)
+        .map_err(|e| e.to_string())?
+        .convert(value)
+        .map_err(|e| e.to_string())
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct ParseTimestamp;
 
@@ -47,6 +74,11 @@ impl Function for ParseTimestamp {
                 source: r#"parse_timestamp!("11-Feb-2021 16:00 +00:00", format: "%v %R %z")"#,
                 result: Ok("t'2021-02-11T16:00:00Z'"),
             },
+            Example {
+                title: "first matching format",
+                source: r#"parse_timestamp!("2021-02-11 16:00:00 +0000", format: ["%d/%m/%Y %H:%M:%S %z", "%Y-%m-%d %H:%M:%S %z"])"#,
+                result: Ok("t'2021-02-11T16:00:00Z'"),
+            },
             Example {
                 title: "valid with timezone",
                 source: r#"parse_timestamp!("16/10/2019 12:00:00", format: "%d/%m/%Y %H:%M:%S", timezone: "Europe/Paris")"#,
@@ -82,7 +114,7 @@ impl Function for ParseTimestamp {
             },
             Parameter {
                 keyword: "format",
-                kind: kind::BYTES,
+                kind: kind::BYTES | kind::ARRAY,
                 required: true,
             },
             Parameter {
@@ -212,5 +244,53 @@ mod tests {
             tdef: TypeDef::timestamp().fallible(),
             tz: TimeZone::default(),
         }
+
+        format_list_second_matches {
+            args: func_args![
+                value: "2019-10-16T12:00:00+0000",
+                format: vec!["%d/%m/%Y:%H:%M:%S %z", "%Y-%m-%dT%H:%M:%S%z"]
+            ],
+            want: Ok(value!(
+                DateTime::parse_from_rfc2822("Wed, 16 Oct 2019 12:00:00 +0000")
+                    .unwrap()
+                    .with_timezone(&Utc)
+            )),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: TimeZone::default(),
+        }
+
+        format_list_first_match_wins {
+            args: func_args![
+                value: "16/10/2019:12:00:00",
+                format: vec!["%d/%m/%Y:%H:%M:%S", "%m/%d/%Y:%H:%M:%S"]
+            ],
+            want: Ok(value!(
+                DateTime::parse_from_rfc2822("Wed, 16 Oct 2019 12:00:00 +0000")
+                    .unwrap()
+                    .with_timezone(&Utc)
+            )),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: TimeZone::Named(chrono_tz::UTC),
+        }
+
+        format_list_none_match {
+            args: func_args![
+                value: "not a timestamp",
+                format: vec!["%d/%m/%Y", "%Y-%m-%d"]
+            ],
+            want: Err(r#"unable to parse timestamp with any of the formats: "%d/%m/%Y", "%Y-%m-%d""#),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: TimeZone::default(),
+        }
+
+        format_list_empty {
+            args: func_args![
+                value: "16/10/2019:12:00:00",
+                format: Vec::<Value>::new()
+            ],
+            want: Err("at least one format is required"),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: TimeZone::default(),
+        }
     ];
 }
@@ -0,0 +1,251 @@
+use crate::compiler::conversion::{parse_local_formats, parse_tz_formats};
+use crate::compiler::prelude::*;
+use crate::compiler::TimeZone;
+use chrono::{DateTime, TimeZone as _, Utc};
+
+/// Formats with an offset tried, in order, after RFC 3339 and RFC 2822.
+const COMMON_TZ_FORMATS: &[&str] = &[
+    "%Y-%m-%d %H:%M:%S%.f %z",
+    "%Y-%m-%d %H:%M:%S%.f%z",
+    "%d/%b/%Y:%H:%M:%S %z",
+];
+
+/// Formats without an offset tried, in order, last. These are interpreted in the `timezone`
+/// argument, or the runtime timezone when it isn't given.
+const COMMON_LOCAL_FORMATS: &[&str] = &[
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y/%m/%d %H:%M:%S%.f",
+    "%b %d %Y %H:%M:%S",
+    "%a %b %e %H:%M:%S %Y",
+];
+
+/// Converts a Unix epoch integer into a timestamp, guessing the unit from its magnitude:
+///
+/// - below 10^11 (year 5138 in seconds): seconds
+/// - below 10^14 (year 5138 in milliseconds): milliseconds
+/// - below 10^17 (year 5138 in microseconds): microseconds
+/// - anything larger: nanoseconds
+///
+/// This means millisecond epochs before March 1973 are read as seconds.
+fn from_epoch(epoch: i64) -> Option<DateTime<Utc>> {
+    match epoch.unsigned_abs() {
+        0..100_000_000_000 => Utc.timestamp_opt(epoch, 0).single(),
+        100_000_000_000..100_000_000_000_000 => Utc.timestamp_millis_opt(epoch).single(),
+        100_000_000_000_000..100_000_000_000_000_000 => DateTime::from_timestamp_micros(epoch),
+        _ => Some(Utc.timestamp_nanos(epoch)),
+    }
+}
+
+fn detect(value: &str, timezone: TimeZone) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+
+    if let Ok(epoch) = value.parse::<i64>() {
+        return from_epoch(epoch);
+    }
+
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Some(timestamp.with_timezone(&Utc));
+    }
+
+    if let Ok(timestamp) = DateTime::parse_from_rfc2822(value) {
+        return Some(timestamp.with_timezone(&Utc));
+    }
+
+    parse_tz_formats(value, COMMON_TZ_FORMATS)
+        .or_else(|| parse_local_formats(timezone, value, COMMON_LOCAL_FORMATS))
+}
+
+fn parse_timestamp_auto(value: Value, timezone: Option<Value>, ctx: &Context) -> Resolved {
+    let timezone = match timezone {
+        Some(timezone) => {
+            let timezone = timezone.try_bytes_utf8_lossy()?;
+            TimeZone::parse(&timezone)
+                .ok_or_else(|| format!("unable to parse timezone: {timezone}"))?
+        }
+        None => *ctx.timezone(),
+    };
+
+    match value {
+        Value::Bytes(bytes) => {
+            let value = String::from_utf8_lossy(&bytes);
+            detect(&value, timezone)
+                .map(Into::into)
+                .ok_or_else(|| format!("unable to detect timestamp format of '{value}'").into())
+        }
+        Value::Integer(epoch) => from_epoch(epoch)
+            .map(Into::into)
+            .ok_or_else(|| ValueError::OutOfRange(Kind::timestamp()).into()),
+        Value::Timestamp(_) => Ok(value),
+        _ => Err("unable to convert value to timestamp".into()),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseTimestampAuto;
+
+impl Function for ParseTimestampAuto {
+    fn identifier(&self) -> &'static str {
+        "parse_timestamp_auto"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "RFC 3339",
+                source: r#"parse_timestamp_auto!("2021-02-11T16:00:00+01:00")"#,
+                result: Ok("t'2021-02-11T15:00:00Z'"),
+            },
+            Example {
+                title: "epoch seconds",
+                source: r#"parse_timestamp_auto!("1613059200")"#,
+                result: Ok("t'2021-02-11T16:00:00Z'"),
+            },
+            Example {
+                title: "epoch milliseconds",
+                source: "parse_timestamp_auto!(1613059200123)",
+                result: Ok("t'2021-02-11T16:00:00.123Z'"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let timezone = arguments.optional("timezone");
+
+        Ok(ParseTimestampAutoFn { value, timezone }.as_expr())
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES | kind::INTEGER | kind::TIMESTAMP,
+                required: true,
+            },
+            Parameter {
+                keyword: "timezone",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseTimestampAutoFn {
+    value: Box<dyn Expression>,
+    timezone: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for ParseTimestampAutoFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let timezone = self
+            .timezone
+            .as_ref()
+            .map(|tz| tz.resolve(ctx))
+            .transpose()?;
+
+        parse_timestamp_auto(value, timezone, ctx)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::timestamp().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expected() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2021, 2, 11, 16, 0, 0).unwrap()
+    }
+
+    test_function![
+        parse_timestamp_auto => ParseTimestampAuto;
+
+        rfc3339 {
+            args: func_args![value: "2021-02-11T16:00:00Z"],
+            want: Ok(expected()),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: TimeZone::Named(chrono_tz::UTC),
+        }
+
+        rfc2822 {
+            args: func_args![value: "Thu, 11 Feb 2021 17:00:00 +0100"],
+            want: Ok(expected()),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: TimeZone::Named(chrono_tz::UTC),
+        }
+
+        epoch_seconds {
+            args: func_args![value: "1613059200"],
+            want: Ok(expected()),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: TimeZone::Named(chrono_tz::UTC),
+        }
+
+        epoch_millis {
+            args: func_args![value: "1613059200000"],
+            want: Ok(expected()),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: TimeZone::Named(chrono_tz::UTC),
+        }
+
+        epoch_micros_integer {
+            args: func_args![value: 1_613_059_200_000_000_i64],
+            want: Ok(expected()),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: TimeZone::Named(chrono_tz::UTC),
+        }
+
+        epoch_nanos {
+            args: func_args![value: "1613059200000000000"],
+            want: Ok(expected()),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: TimeZone::Named(chrono_tz::UTC),
+        }
+
+        common_format_in_timezone {
+            args: func_args![value: "2021-02-11 17:00:00", timezone: "Europe/Paris"],
+            want: Ok(expected()),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: TimeZone::Named(chrono_tz::UTC),
+        }
+
+        common_log_format {
+            args: func_args![value: "11/Feb/2021:16:00:00 +0000"],
+            want: Ok(expected()),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: TimeZone::Named(chrono_tz::UTC),
+        }
+
+        common_log_format_with_offset {
+            args: func_args![value: "11/Feb/2021:09:00:00 -0700"],
+            want: Ok(expected()),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: TimeZone::Named(chrono_tz::UTC),
+        }
+
+        offset_ignores_timezone {
+            args: func_args![value: "2021-02-11 17:00:00.000 +0100", timezone: "America/New_York"],
+            want: Ok(expected()),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: TimeZone::Named(chrono_tz::UTC),
+        }
+
+        undetectable {
+            args: func_args![value: "yesterday"],
+            want: Err("unable to detect timestamp format of 'yesterday'"),
+            tdef: TypeDef::timestamp().fallible(),
+            tz: TimeZone::Named(chrono_tz::UTC),
+        }
+    ];
+}
@@ -0,0 +1,140 @@
+use crate::compiler::prelude::*;
+
+use super::util::key_path;
+
+fn pick(value: Value, keys: Value, paths: Value) -> Resolved {
+    let object = Value::Object(value.try_object()?);
+    let paths = paths.try_boolean()?;
+    let mut picked = Value::Object(ObjectMap::new());
+
+    for key in keys.try_array()? {
+        let key = match key {
+            Value::Bytes(key) => String::from_utf8_lossy(&key).into_owned(),
+            key => return Err(format!("keys must be strings, got {}", key.kind()).into()),
+        };
+        let path = key_path(&key, paths)?;
+        if let Some(value) = object.get(&path) {
+            picked.insert(&path, value.clone());
+        }
+    }
+
+    Ok(picked)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Pick;
+
+impl Function for Pick {
+    fn identifier(&self) -> &'static str {
+        "pick"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::OBJECT,
+                required: true,
+            },
+            Parameter {
+                keyword: "keys",
+                kind: kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "paths",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "pick keys",
+                source: r#"pick!({"a": 1, "b": 2, "c": 3}, ["a", "c", "d"])"#,
+                result: Ok(r#"{"a": 1, "c": 3}"#),
+            },
+            Example {
+                title: "pick nested paths",
+                source: r#"pick!({"http": {"method": "GET", "body": "..."}, "host": "a"}, ["http.method", "host"], paths: true)"#,
+                result: Ok(r#"{"http": {"method": "GET"}, "host": "a"}"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let keys = arguments.required("keys");
+        let paths = arguments.optional("paths").unwrap_or_else(|| expr!(false));
+
+        Ok(PickFn { value, keys, paths }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PickFn {
+    value: Box<dyn Expression>,
+    keys: Box<dyn Expression>,
+    paths: Box<dyn Expression>,
+}
+
+impl FunctionExpression for PickFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let keys = self.keys.resolve(ctx)?;
+        let paths = self.paths.resolve(ctx)?;
+
+        pick(value, keys, paths)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(Collection::any()).fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        pick => Pick;
+
+        top_level {
+            args: func_args![value: value!({"a": 1, "b": 2, "c": 3}), keys: value!(["a", "c"])],
+            want: Ok(value!({"a": 1, "c": 3})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        missing_keys_absent {
+            args: func_args![value: value!({"a": 1}), keys: value!(["a", "z"])],
+            want: Ok(value!({"a": 1})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        nested_paths {
+            args: func_args![value: value!({"a": {"b": 1, "c": 2}, "d": 3}), keys: value!(["a.b", "d"]), paths: true],
+            want: Ok(value!({"a": {"b": 1}, "d": 3})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        dotted_key_without_paths {
+            args: func_args![value: value!({"a.b": 1, "a": {"b": 2}}), keys: value!(["a.b"])],
+            want: Ok(value!({"a.b": 1})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        non_string_key {
+            args: func_args![value: value!({"a": 1}), keys: value!([1])],
+            want: Err("keys must be strings, got integer"),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+    ];
+}
@@ -1,3 +1,4 @@
+use super::luhn_check::is_luhn_valid;
 use crate::compiler::prelude::*;
 use base64::engine::Engine;
 use once_cell::sync::Lazy;
@@ -18,6 +19,22 @@ static US_SOCIAL_SECURITY_NUMBER: Lazy<regex::Regex> = Lazy::new(|| {
     ").unwrap()
 });
 
+// 13 to 19 digit card numbers, optionally grouped with single spaces or dashes. Matches are only
+// redacted when they pass the Luhn check, see `LuhnRedactor`.
+static CREDIT_CARD_NUMBER: Lazy<regex::Regex> =
+    Lazy::new(|| regex::Regex::new(r"\b\d(?:[ -]?\d){12,18}\b").unwrap());
+
+static EMAIL_ADDRESS: Lazy<regex::Regex> = Lazy::new(|| {
+    regex::Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}").unwrap()
+});
+
+static IPV4_ADDRESS: Lazy<regex::Regex> = Lazy::new(|| {
+    regex::Regex::new(
+        r"\b(?:(?:25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)\.){3}(?:25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)\b",
+    )
+    .unwrap()
+});
+
 #[derive(Clone, Copy, Debug)]
 pub struct Redact;
 
@@ -58,6 +75,11 @@ impl Function for Redact {
                 source: r#"redact({ "name": "John Doe", "ssn": "123-12-1234"}, filters: ["us_social_security_number"])"#,
                 result: Ok(r#"{ "name": "John Doe", "ssn": "[REDACTED]" }"#),
             },
+            Example {
+                title: "pattern presets",
+                source: r#"redact({ "user": { "email": "jdoe@example.com", "card": "4111 1111 1111 1111" } }, filters: ["email", "credit_card"])"#,
+                result: Ok(r#"{ "user": { "card": "[REDACTED]", "email": "[REDACTED]" } }"#),
+            },
             Example {
                 title: "text redactor",
                 source: r#"redact("my id is 123456", filters: [r'\d+'], redactor: {"type": "text", "replacement": "***"})"#,
@@ -201,6 +223,9 @@ impl FunctionExpression for RedactFn {
 enum Filter {
     Pattern(Vec<Pattern>),
     UsSocialSecurityNumber,
+    CreditCardNumber,
+    EmailAddress,
+    Ipv4Address,
 }
 
 #[derive(Debug, Clone)]
@@ -224,7 +249,6 @@ impl TryFrom<Value> for Filter {
                 }?;
 
                 match r#type.as_ref() {
-                    b"us_social_security_number" => Ok(Filter::UsSocialSecurityNumber),
                     b"pattern" => {
                         let patterns = match object
                             .get("patterns")
@@ -244,13 +268,12 @@ impl TryFrom<Value> for Filter {
                         }?;
                         Ok(Filter::Pattern(patterns))
                     }
-                    _ => Err("unknown filter name"),
+                    name => Filter::from_name(name).ok_or("unknown filter name"),
                 }
             }
             Value::Bytes(bytes) => match bytes.as_ref() {
                 b"pattern" => Err("pattern cannot be used without arguments"),
-                b"us_social_security_number" => Ok(Filter::UsSocialSecurityNumber),
-                _ => Err("unknown filter name"),
+                name => Filter::from_name(name).ok_or("unknown filter name"),
             },
             Value::Regex(regex) => Ok(Filter::Pattern(vec![Pattern::Regex((*regex).clone())])),
             _ => Err("unknown literal for filter, must be a regex, filter name, or object"),
@@ -259,6 +282,17 @@ impl TryFrom<Value> for Filter {
 }
 
 impl Filter {
+    /// Looks up one of the built-in pattern presets by name.
+    fn from_name(name: &[u8]) -> Option<Self> {
+        match name {
+            b"us_social_security_number" | b"us_ssn" => Some(Filter::UsSocialSecurityNumber),
+            b"credit_card" => Some(Filter::CreditCardNumber),
+            b"email" => Some(Filter::EmailAddress),
+            b"ipv4" => Some(Filter::Ipv4Address),
+            _ => None,
+        }
+    }
+
     fn redact<'t>(&self, input: &'t str, redactor: &Redactor) -> Cow<'t, str> {
         match &self {
             Filter::Pattern(patterns) => {
@@ -274,6 +308,11 @@ impl Filter {
             Filter::UsSocialSecurityNumber => {
                 US_SOCIAL_SECURITY_NUMBER.replace_all(input, redactor)
             }
+            Filter::CreditCardNumber => {
+                CREDIT_CARD_NUMBER.replace_all(input, LuhnRedactor(redactor))
+            }
+            Filter::EmailAddress => EMAIL_ADDRESS.replace_all(input, redactor),
+            Filter::Ipv4Address => IPV4_ADDRESS.replace_all(input, redactor),
         }
     }
 }
@@ -415,6 +454,26 @@ impl regex::Replacer for &Redactor {
     }
 }
 
+/// Redacts the card numbers that pass the Luhn check, leaving other digit runs, such as
+/// timestamps, order ids and phone numbers, as they are.
+struct LuhnRedactor<'a>(&'a Redactor);
+
+impl regex::Replacer for LuhnRedactor<'_> {
+    fn replace_append(&mut self, caps: &regex::Captures, dst: &mut String) {
+        let digits = caps[0]
+            .bytes()
+            .filter(u8::is_ascii_digit)
+            .map(|byte| u32::from(byte - b'0'))
+            .collect::<Vec<_>>();
+
+        if is_luhn_valid(&digits) {
+            self.0.replace_str(&caps[0], dst);
+        } else {
+            dst.push_str(&caps[0]);
+        }
+    }
+}
+
 impl TryFrom<Value> for Redactor {
     type Error = &'static str;
 
@@ -513,6 +572,95 @@ mod test {
              tdef: TypeDef::bytes().infallible(),
         }
 
+        us_ssn_alias {
+             args: func_args![
+                 value: "hello 123-12-1234 world",
+                 filters: vec!["us_ssn"],
+             ],
+             want: Ok("hello [REDACTED] world"),
+             tdef: TypeDef::bytes().infallible(),
+        }
+
+        email {
+             args: func_args![
+                 value: "contact jane.doe+logs@mail.example.co.uk today",
+                 filters: vec!["email"],
+             ],
+             want: Ok("contact [REDACTED] today"),
+             tdef: TypeDef::bytes().infallible(),
+        }
+
+        credit_card {
+             args: func_args![
+                 value: "paid with 4111-1111-1111-1111 and 5500000000000004",
+                 filters: vec!["credit_card"],
+             ],
+             want: Ok("paid with [REDACTED] and [REDACTED]"),
+             tdef: TypeDef::bytes().infallible(),
+        }
+
+        credit_card_luhn {
+             args: func_args![
+                 value: "order 1712345678901 at 1700000000000 paid with 4111 1111 1111 1111",
+                 filters: vec!["credit_card"],
+             ],
+             want: Ok("order 1712345678901 at 1700000000000 paid with [REDACTED]"),
+             tdef: TypeDef::bytes().infallible(),
+        }
+
+        ipv4 {
+             args: func_args![
+                 value: "client 192.168.1.10 not 999.1.1.1",
+                 filters: vec!["ipv4"],
+             ],
+             want: Ok("client [REDACTED] not 999.1.1.1"),
+             tdef: TypeDef::bytes().infallible(),
+        }
+
+        presets_in_nested_object {
+             args: func_args![
+                 value: value!({
+                     "user": {
+                         "name": "Jane",
+                         "contact": {"email": "jane@example.com"},
+                         "payments": [{"card": "4111 1111 1111 1111", "amount": 42}],
+                     }
+                 }),
+                 filters: vec!["email", "credit_card"],
+             ],
+             want: Ok(value!({
+                 "user": {
+                     "name": "Jane",
+                     "contact": {"email": "[REDACTED]"},
+                     "payments": [{"card": "[REDACTED]", "amount": 42}],
+                 }
+             })),
+             tdef: TypeDef::object(btreemap! {
+                 Field::from("user") => Kind::object(btreemap! {
+                     Field::from("name") => Kind::bytes(),
+                     Field::from("contact") => Kind::object(btreemap! {
+                         Field::from("email") => Kind::bytes(),
+                     }),
+                     Field::from("payments") => Kind::array(btreemap! {
+                         Index::from(0) => Kind::object(btreemap! {
+                             Field::from("card") => Kind::bytes(),
+                             Field::from("amount") => Kind::integer(),
+                         }),
+                     }),
+                 }),
+             }).infallible(),
+        }
+
+        preset_object_form {
+             args: func_args![
+                 value: "mail jane@example.com",
+                 filters: vec![value!({"type": "email"})],
+                 redactor: btreemap!{"type" => "text", "replacement" => "<email>"},
+             ],
+             want: Ok("mail <email>"),
+             tdef: TypeDef::bytes().infallible(),
+        }
+
         invalid_filter {
              args: func_args![
                  value: "hello 123456 world",
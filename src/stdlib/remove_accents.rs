@@ -0,0 +1,126 @@
+use crate::compiler::prelude::*;
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+fn remove_accents(value: Value) -> Resolved {
+    let string = value.try_bytes_utf8_lossy()?;
+
+    // Decompose so accents become separate combining marks, drop them, then recompose
+    // whatever remains so characters without a decomposition are left untouched.
+    let stripped: String = string
+        .nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .nfc()
+        .collect();
+
+    Ok(stripped.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct RemoveAccents;
+
+impl Function for RemoveAccents {
+    fn identifier(&self) -> &'static str {
+        "remove_accents"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "remove accents",
+            source: r#"remove_accents("Crème brûlée")"#,
+            result: Ok("Creme brulee"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(RemoveAccentsFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RemoveAccentsFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for RemoveAccentsFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        remove_accents(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        remove_accents => RemoveAccents;
+
+        cafe {
+            args: func_args![value: "café"],
+            want: Ok("cafe"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        naive {
+            args: func_args![value: "naïve"],
+            want: Ok("naive"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        resume {
+            args: func_args![value: "résumé"],
+            want: Ok("resume"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        tilde {
+            args: func_args![value: "Señor Muñoz"],
+            want: Ok("Senor Munoz"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        decomposed_input {
+            args: func_args![value: "cafe\u{301}"],
+            want: Ok("cafe"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        uppercase {
+            args: func_args![value: "ÀÉÎÕÜ"],
+            want: Ok("AEIOU"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        no_decomposition_unchanged {
+            args: func_args![value: "straße øre"],
+            want: Ok("straße øre"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        ascii_unchanged {
+            args: func_args![value: "plain ascii"],
+            want: Ok("plain ascii"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
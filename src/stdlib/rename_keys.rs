@@ -0,0 +1,201 @@
+use crate::compiler::prelude::*;
+
+use super::util::key_path;
+
+fn rename_keys(value: Value, mapping: Value, overwrite: Value, paths: Value) -> Resolved {
+    let mut object = Value::Object(value.try_object()?);
+    let overwrite = overwrite.try_boolean()?;
+    let paths = paths.try_boolean()?;
+
+    // All renamed values are removed before any are re-inserted, so that mappings such as
+    // swapping two keys don't collide with each other.
+    let mut renamed = Vec::new();
+    for (from, to) in mapping.try_object()? {
+        let to = match to {
+            Value::Bytes(to) => String::from_utf8_lossy(&to).into_owned(),
+            to => return Err(format!("mapping values must be strings, got {}", to.kind()).into()),
+        };
+        if let Some(value) = object.remove(&key_path(&from, paths)?, false) {
+            renamed.push((from, to, value));
+        }
+    }
+
+    for (from, to, value) in renamed {
+        let path = key_path(&to, paths)?;
+        if !overwrite && object.contains(&path) {
+            return Err(format!("cannot rename \"{from}\" to \"{to}\": key already exists").into());
+        }
+        object.insert(&path, value);
+    }
+
+    Ok(object)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct RenameKeys;
+
+impl Function for RenameKeys {
+    fn identifier(&self) -> &'static str {
+        "rename_keys"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::OBJECT,
+                required: true,
+            },
+            Parameter {
+                keyword: "mapping",
+                kind: kind::OBJECT,
+                required: true,
+            },
+            Parameter {
+                keyword: "overwrite",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "paths",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "rename keys",
+                source: r#"rename_keys!({"msg": "hello", "lvl": "info"}, {"msg": "message", "lvl": "level"})"#,
+                result: Ok(r#"{"message": "hello", "level": "info"}"#),
+            },
+            Example {
+                title: "rename nested paths",
+                source: r#"rename_keys!({"http": {"status": 200}}, {"http.status": "response.code"}, paths: true)"#,
+                result: Ok(r#"{"http": {}, "response": {"code": 200}}"#),
+            },
+            Example {
+                title: "collision",
+                source: r#"rename_keys!({"a": 1, "b": 2}, {"a": "b"})"#,
+                result: Err(
+                    r#"function call error for "rename_keys" at (0:42): cannot rename "a" to "b": key already exists"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let mapping = arguments.required("mapping");
+        let overwrite = arguments
+            .optional("overwrite")
+            .unwrap_or_else(|| expr!(false));
+        let paths = arguments.optional("paths").unwrap_or_else(|| expr!(false));
+
+        Ok(RenameKeysFn {
+            value,
+            mapping,
+            overwrite,
+            paths,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RenameKeysFn {
+    value: Box<dyn Expression>,
+    mapping: Box<dyn Expression>,
+    overwrite: Box<dyn Expression>,
+    paths: Box<dyn Expression>,
+}
+
+impl FunctionExpression for RenameKeysFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let mapping = self.mapping.resolve(ctx)?;
+        let overwrite = self.overwrite.resolve(ctx)?;
+        let paths = self.paths.resolve(ctx)?;
+
+        rename_keys(value, mapping, overwrite, paths)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(Collection::any()).fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        rename_keys => RenameKeys;
+
+        two_top_level_keys {
+            args: func_args![value: value!({"msg": "hello", "lvl": "info", "host": "a"}),
+                             mapping: value!({"msg": "message", "lvl": "level"})],
+            want: Ok(value!({"message": "hello", "level": "info", "host": "a"})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        missing_key_ignored {
+            args: func_args![value: value!({"a": 1}),
+                             mapping: value!({"b": "c"})],
+            want: Ok(value!({"a": 1})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        swap {
+            args: func_args![value: value!({"a": 1, "b": 2}),
+                             mapping: value!({"a": "b", "b": "a"})],
+            want: Ok(value!({"a": 2, "b": 1})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        collision {
+            args: func_args![value: value!({"a": 1, "b": 2}),
+                             mapping: value!({"a": "b"})],
+            want: Err(r#"cannot rename "a" to "b": key already exists"#),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        collision_overwrite {
+            args: func_args![value: value!({"a": 1, "b": 2}),
+                             mapping: value!({"a": "b"}),
+                             overwrite: true],
+            want: Ok(value!({"b": 1})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        dotted_key_without_paths {
+            args: func_args![value: value!({"a.b": 1}),
+                             mapping: value!({"a.b": "c"})],
+            want: Ok(value!({"c": 1})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        nested_paths {
+            args: func_args![value: value!({"a": {"b": 1, "c": 2}}),
+                             mapping: value!({"a.b": "x.y"}),
+                             paths: true],
+            want: Ok(value!({"a": {"c": 2}, "x": {"y": 1}})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        non_string_mapping {
+            args: func_args![value: value!({"a": 1}),
+                             mapping: value!({"a": 1})],
+            want: Err("mapping values must be strings, got integer"),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+    ];
+}
@@ -1,43 +1,102 @@
+use crate::btreemap;
 use crate::compiler::prelude::*;
+use regex::{Captures, Regex, Replacer};
 
-fn replace(value: Value, with_value: Value, count: Value, pattern: Value) -> Resolved {
+/// Counts the substitutions made while replacing, so `with_count` doesn't need a second pass
+/// over the value.
+struct CountingReplacer<'a> {
+    with: &'a str,
+    count: usize,
+}
+
+impl Replacer for CountingReplacer<'_> {
+    fn replace_append(&mut self, captures: &Captures<'_>, dst: &mut String) {
+        self.count += 1;
+        captures.expand(self.with, dst);
+    }
+}
+
+fn replace_regex<R: Replacer>(regex: &Regex, value: &str, count: i64, with: R) -> Value {
+    match count {
+        i if i > 0 => {
+            Bytes::copy_from_slice(regex.replacen(value, i as usize, with).as_bytes()).into()
+        }
+        i if i < 0 => Bytes::copy_from_slice(regex.replace_all(value, with).as_bytes()).into(),
+        _ => value.into(),
+    }
+}
+
+fn replace(
+    value: Value,
+    with_value: Value,
+    count: Value,
+    pattern: Value,
+    with_count: bool,
+) -> Resolved {
     let value = value.try_bytes_utf8_lossy()?;
     let with = with_value.try_bytes_utf8_lossy()?;
     let count = count.try_integer()?;
-    match pattern {
+    let (replaced, replacements) = match pattern {
         Value::Bytes(bytes) => {
             let pattern = String::from_utf8_lossy(&bytes);
-            let replaced = match count {
-                i if i > 0 => value.replacen(pattern.as_ref(), &with, i as usize),
-                i if i < 0 => value.replace(pattern.as_ref(), &with),
-                _ => value.into_owned(),
+            let limit = match count {
+                i if i > 0 => i as usize,
+                i if i < 0 => usize::MAX,
+                _ => 0,
             };
 
-            Ok(replaced.into())
+            // Mirrors `str::replacen`, counting each substitution as it is made.
+            let mut replaced = String::with_capacity(value.len());
+            let mut replacements = 0;
+            let mut last_end = 0;
+            for (start, part) in value.match_indices(pattern.as_ref()).take(limit) {
+                replaced.push_str(&value[last_end..start]);
+                replaced.push_str(&with);
+                last_end = start + part.len();
+                replacements += 1;
+            }
+            replaced.push_str(&value[last_end..]);
+
+            (replaced.into(), replacements)
         }
         Value::Regex(regex) => {
-            let replaced = match count {
-                i if i > 0 => Bytes::copy_from_slice(
-                    regex.replacen(&value, i as usize, with.as_ref()).as_bytes(),
-                )
-                .into(),
-                i if i < 0 => {
-                    Bytes::copy_from_slice(regex.replace_all(&value, with.as_ref()).as_bytes())
-                        .into()
-                }
-                _ => value.into(),
-            };
-
-            Ok(replaced)
+            if with_count {
+                let mut replacer = CountingReplacer {
+                    with: &with,
+                    count: 0,
+                };
+                let replaced = replace_regex(&regex, &value, count, replacer.by_ref());
+                (replaced, replacer.count)
+            } else {
+                (replace_regex(&regex, &value, count, with.as_ref()), 0)
+            }
         }
-        value => Err(ValueError::Expected {
-            got: value.kind(),
-            expected: Kind::regex() | Kind::bytes(),
+        value => {
+            return Err(ValueError::Expected {
+                got: value.kind(),
+                expected: Kind::regex() | Kind::bytes(),
+            }
+            .into())
         }
-        .into()),
+    };
+
+    if with_count {
+        Ok(Value::Object(ObjectMap::from([
+            (KeyString::from("result"), replaced),
+            (KeyString::from("count"), replacements.into()),
+        ])))
+    } else {
+        Ok(replaced)
     }
 }
 
+fn with_count_type_def() -> TypeDef {
+    TypeDef::object(btreemap! {
+        Field::from("result") => Kind::bytes(),
+        Field::from("count") => Kind::integer(),
+    })
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct Replace;
 
@@ -68,6 +127,11 @@ impl Function for Replace {
                 kind: kind::INTEGER,
                 required: false,
             },
+            Parameter {
+                keyword: "with_count",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
         ]
     }
 
@@ -93,6 +157,11 @@ impl Function for Replace {
                 source: r#"replace("foo123bar", r'foo(?P<num>\d+)bar', "$num")"#,
                 result: Ok(r#""123""#),
             },
+            Example {
+                title: "replace with count",
+                source: r#"replace("foobar", "o", "i", with_count: true)"#,
+                result: Ok(r#"{ "count": 2, "result": "fiibar" }"#),
+            },
         ]
     }
 
@@ -106,12 +175,16 @@ impl Function for Replace {
         let pattern = arguments.required("pattern");
         let with = arguments.required("with");
         let count = arguments.optional("count").unwrap_or(expr!(-1));
+        let with_count = arguments
+            .optional("with_count")
+            .unwrap_or_else(|| expr!(false));
 
         Ok(ReplaceFn {
             value,
             pattern,
             with,
             count,
+            with_count,
         }
         .as_expr())
     }
@@ -123,6 +196,7 @@ struct ReplaceFn {
     pattern: Box<dyn Expression>,
     with: Box<dyn Expression>,
     count: Box<dyn Expression>,
+    with_count: Box<dyn Expression>,
 }
 
 impl FunctionExpression for ReplaceFn {
@@ -131,12 +205,21 @@ impl FunctionExpression for ReplaceFn {
         let with_value = self.with.resolve(ctx)?;
         let count = self.count.resolve(ctx)?;
         let pattern = self.pattern.resolve(ctx)?;
+        let with_count = self.with_count.resolve(ctx)?.try_boolean()?;
 
-        replace(value, with_value, count, pattern)
+        replace(value, with_value, count, pattern, with_count)
     }
 
-    fn type_def(&self, _: &state::TypeState) -> TypeDef {
-        TypeDef::bytes().infallible()
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        match self
+            .with_count
+            .resolve_constant(state)
+            .and_then(|with_count| with_count.as_boolean())
+        {
+            Some(true) => with_count_type_def().infallible(),
+            Some(false) => TypeDef::bytes().infallible(),
+            None => TypeDef::bytes().union(with_count_type_def()).infallible(),
+        }
     }
 }
 
@@ -144,6 +227,7 @@ impl FunctionExpression for ReplaceFn {
 #[allow(clippy::trivial_regex)]
 mod test {
     use super::*;
+    use crate::value;
 
     test_function![
         replace => Replace;
@@ -275,5 +359,68 @@ mod test {
             want: Ok("I like biscuits and bananas"),
             tdef: TypeDef::bytes().infallible(),
         }
+
+        with_count_string {
+            args: func_args![value: "I like apples and bananas",
+                             pattern: "a",
+                             with: "o",
+                             with_count: true
+            ],
+            want: Ok(value!({"result": "I like opples ond bononos", "count": 5})),
+            tdef: with_count_type_def().infallible(),
+        }
+
+        with_count_regex {
+            args: func_args![value: "ssn 123-45-6789 and 987-65-4321",
+                             pattern: regex::Regex::new(r"\d{3}-\d{2}-\d{4}").unwrap(),
+                             with: "[REDACTED]",
+                             with_count: true
+            ],
+            want: Ok(value!({"result": "ssn [REDACTED] and [REDACTED]", "count": 2})),
+            tdef: with_count_type_def().infallible(),
+        }
+
+        with_count_regex_capture_groups {
+            args: func_args![value: "alice@example.com, bob@example.com",
+                             pattern: regex::Regex::new(r"(?P<user>\w+)@example\.com").unwrap(),
+                             with: "$user at example",
+                             count: 1,
+                             with_count: true
+            ],
+            want: Ok(value!({"result": "alice at example, bob@example.com", "count": 1})),
+            tdef: with_count_type_def().infallible(),
+        }
+
+        with_count_limited {
+            args: func_args![value: "I like apples and bananas",
+                             pattern: regex::Regex::new("a").unwrap(),
+                             with: "o",
+                             count: 2,
+                             with_count: true
+            ],
+            want: Ok(value!({"result": "I like opples ond bananas", "count": 2})),
+            tdef: with_count_type_def().infallible(),
+        }
+
+        with_count_no_matches {
+            args: func_args![value: "I like apples and bananas",
+                             pattern: "kiwi",
+                             with: "o",
+                             with_count: true
+            ],
+            want: Ok(value!({"result": "I like apples and bananas", "count": 0})),
+            tdef: with_count_type_def().infallible(),
+        }
+
+        with_count_zero_count {
+            args: func_args![value: "I like apples and bananas",
+                             pattern: "a",
+                             with: "o",
+                             count: 0,
+                             with_count: true
+            ],
+            want: Ok(value!({"result": "I like apples and bananas", "count": 0})),
+            tdef: with_count_type_def().infallible(),
+        }
     ];
 }
@@ -0,0 +1,165 @@
+use crate::compiler::prelude::*;
+
+fn reverse(value: Value, strict: bool) -> Resolved {
+    match value {
+        Value::Array(mut array) => {
+            array.reverse();
+            Ok(array.into())
+        }
+        Value::Bytes(bytes) => match std::str::from_utf8(&bytes) {
+            Ok(string) => Ok(string.chars().rev().collect::<String>().into()),
+            Err(_) if strict => Err("value is not valid UTF-8".into()),
+            Err(_) => {
+                let mut bytes = bytes.to_vec();
+                bytes.reverse();
+                Ok(Value::Bytes(bytes.into()))
+            }
+        },
+        value => Err(ValueError::Expected {
+            got: value.kind(),
+            expected: Kind::bytes() | Kind::array(Collection::any()),
+        }
+        .into()),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Reverse;
+
+impl Function for Reverse {
+    fn identifier(&self) -> &'static str {
+        "reverse"
+    }
+
+    fn summary(&self) -> &'static str {
+        "reverse a string or an array"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Reverses the order of the elements of an array, or of the characters of a string.
+
+            Strings are reversed by Unicode scalar value, so multi-byte characters are kept
+            intact, although combining marks end up before the character they modify. Strings
+            that aren't valid UTF-8 are reversed byte by byte, unless `strict` is `true`, in
+            which case an error is returned instead.
+        "}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES | kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "strict",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "array",
+                source: "reverse([1, 2, 3])",
+                result: Ok("[3, 2, 1]"),
+            },
+            Example {
+                title: "string",
+                source: r#"reverse("héllo")"#,
+                result: Ok("olléh"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let strict = match arguments.optional_literal("strict", state)? {
+            Some(strict) => {
+                strict
+                    .as_boolean()
+                    .ok_or_else(|| function::Error::InvalidArgument {
+                        keyword: "strict",
+                        value: strict,
+                        error: "strict must be a boolean",
+                    })?
+            }
+            None => false,
+        };
+
+        Ok(ReverseFn { value, strict }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ReverseFn {
+    value: Box<dyn Expression>,
+    strict: bool,
+}
+
+impl FunctionExpression for ReverseFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        reverse(value, self.strict)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        match self.value.type_def(state) {
+            v if v.is_bytes() => TypeDef::bytes().maybe_fallible(self.strict),
+            v if v.is_array() => TypeDef::from(v.kind().clone())
+                .infallible()
+                .collect_subtypes(),
+            _ => TypeDef::bytes().or_array(Collection::any()).fallible(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        reverse => Reverse;
+
+        array {
+            args: func_args![value: value!([1, "two", true])],
+            want: Ok(value!([true, "two", 1])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::integer().or_bytes().or_boolean())),
+        }
+
+        ascii_string {
+            args: func_args![value: "hello"],
+            want: Ok("olleh"),
+            tdef: TypeDef::bytes(),
+        }
+
+        multi_byte_string {
+            args: func_args![value: "añb😀ç"],
+            want: Ok("ç😀bña"),
+            tdef: TypeDef::bytes(),
+        }
+
+        invalid_utf8 {
+            args: func_args![value: Value::Bytes(Bytes::from_static(b"a\xffb"))],
+            want: Ok(Value::Bytes(Bytes::from_static(b"b\xffa"))),
+            tdef: TypeDef::bytes(),
+        }
+
+        invalid_utf8_strict {
+            args: func_args![value: Value::Bytes(Bytes::from_static(b"a\xffb")), strict: true],
+            want: Err("value is not valid UTF-8"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
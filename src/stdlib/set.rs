@@ -1,7 +1,7 @@
 use crate::compiler::prelude::*;
 use crate::path::{OwnedSegment, OwnedValuePath};
 
-fn set(path: Value, mut value: Value, data: Value) -> Resolved {
+fn set(path: Value, mut value: Value, data: Value, pad_arrays: bool) -> Resolved {
     let path = match path {
         Value::Array(segments) => {
             let mut insert = OwnedValuePath::root();
@@ -34,10 +34,40 @@ fn set(path: Value, mut value: Value, data: Value) -> Resolved {
             .into())
         }
     };
+    if !pad_arrays {
+        check_in_bounds(&value, &path)?;
+    }
     value.insert(&path, data);
     Ok(value)
 }
 
+/// Errors if inserting at `path` would need to pad an array with nulls, either because an
+/// index is past the end of an existing array or because a new array would start after index 0.
+fn check_in_bounds(value: &Value, path: &OwnedValuePath) -> Result<(), ExpressionError> {
+    let mut current = Some(value);
+    for segment in &path.segments {
+        current = match (current, segment) {
+            (Some(Value::Array(array)), OwnedSegment::Index(index)) => {
+                let position = if *index < 0 {
+                    array.len().checked_sub(index.unsigned_abs())
+                } else {
+                    Some(index.unsigned_abs())
+                };
+                match position {
+                    Some(position) if position <= array.len() => array.get(position),
+                    _ => return Err(format!("index {index} is out of bounds").into()),
+                }
+            }
+            (Some(Value::Object(object)), OwnedSegment::Field(field)) => object.get(field.as_str()),
+            (_, OwnedSegment::Index(index)) if *index != 0 => {
+                return Err(format!("index {index} is out of bounds").into());
+            }
+            _ => None,
+        };
+    }
+    Ok(())
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct Set;
 
@@ -63,6 +93,11 @@ impl Function for Set {
                 kind: kind::ANY,
                 required: true,
             },
+            Parameter {
+                keyword: "pad_arrays",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
         ]
     }
 
@@ -88,6 +123,11 @@ impl Function for Set {
                 source: r#"set!(value: {"foo": { "bar": [] }}, path: ["foo", "bar", 1], data: "baz")"#,
                 result: Ok(r#"{ "foo": { "bar": [null, "baz"] } }"#),
             },
+            Example {
+                title: "out of bounds without padding",
+                source: r#"set!(value: {"foo": []}, path: ["foo", 1], data: "baz", pad_arrays: false)"#,
+                result: Err(r#"function call error for "set" at (0:74): index 1 is out of bounds"#),
+            },
             Example {
                 title: "external target",
                 source: indoc! {r#"
@@ -128,8 +168,17 @@ impl Function for Set {
         let value = arguments.required("value");
         let path = arguments.required("path");
         let data = arguments.required("data");
+        let pad_arrays = arguments
+            .optional("pad_arrays")
+            .unwrap_or_else(|| expr!(true));
 
-        Ok(SetFn { value, path, data }.as_expr())
+        Ok(SetFn {
+            value,
+            path,
+            data,
+            pad_arrays,
+        }
+        .as_expr())
     }
 }
 
@@ -138,6 +187,7 @@ pub(crate) struct SetFn {
     value: Box<dyn Expression>,
     path: Box<dyn Expression>,
     data: Box<dyn Expression>,
+    pad_arrays: Box<dyn Expression>,
 }
 
 impl FunctionExpression for SetFn {
@@ -145,8 +195,9 @@ impl FunctionExpression for SetFn {
         let path = self.path.resolve(ctx)?;
         let value = self.value.resolve(ctx)?;
         let data = self.data.resolve(ctx)?;
+        let pad_arrays = self.pad_arrays.resolve(ctx)?.try_boolean()?;
 
-        set(path, value, data)
+        set(path, value, data, pad_arrays)
     }
 
     fn type_def(&self, state: &state::TypeState) -> TypeDef {
@@ -185,5 +236,35 @@ mod tests {
             want: Ok(value!({ "foo": true })),
             tdef: TypeDef::object(Collection::any()).fallible(),
         }
+
+        nested_object_and_array {
+            args: func_args![value: value!({"a": [{"b": 1}, {"b": 2}]}), path: value!(["a", 1, "b"]), data: 3],
+            want: Ok(value!({"a": [{"b": 1}, {"b": 3}]})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        pads_arrays_by_default {
+            args: func_args![value: value!({"a": [1]}), path: value!(["a", 3]), data: 4],
+            want: Ok(value!({"a": [1, null, null, 4]})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        append_without_padding {
+            args: func_args![value: value!({"a": [1]}), path: value!(["a", 1]), data: 2, pad_arrays: false],
+            want: Ok(value!({"a": [1, 2]})),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        out_of_bounds_without_padding {
+            args: func_args![value: value!({"a": [1]}), path: value!(["a", 3]), data: 4, pad_arrays: false],
+            want: Err("index 3 is out of bounds"),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        new_array_without_padding {
+            args: func_args![value: value!({}), path: value!(["a", 2, "b"]), data: 4, pad_arrays: false],
+            want: Err("index 2 is out of bounds"),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
     ];
 }
@@ -3,7 +3,7 @@ use std::ops::Range;
 use crate::compiler::prelude::*;
 
 #[allow(clippy::cast_possible_wrap)]
-fn slice(start: i64, end: Option<i64>, value: Value) -> Resolved {
+fn slice(start: i64, end: Option<i64>, clamp: bool, value: Value) -> Resolved {
     let range = |len: i64| -> ExpressionResult<Range<usize>> {
         let start = match start {
             start if start < 0 => start + len,
@@ -16,6 +16,12 @@ fn slice(start: i64, end: Option<i64>, value: Value) -> Resolved {
             None => len,
         };
 
+        if clamp {
+            let start = start.clamp(0, len);
+            let end = end.clamp(start, len);
+            return Ok(start as usize..end as usize);
+        }
+
         match () {
             () if start < 0 || start > len => {
                 Err(format!(r#""start" must be between "{}" and "{len}""#, -len).into())
@@ -65,6 +71,11 @@ impl Function for Slice {
                 kind: kind::INTEGER,
                 required: false,
             },
+            Parameter {
+                keyword: "clamp",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
         ]
     }
 
@@ -85,6 +96,16 @@ impl Function for Slice {
                 source: "slice!([0, 1, 2], 1)",
                 result: Ok("[1, 2]"),
             },
+            Example {
+                title: "array negative start..end",
+                source: "slice!([0, 1, 2, 3, 4], -3, -1)",
+                result: Ok("[2, 3]"),
+            },
+            Example {
+                title: "clamped out of range",
+                source: "slice!([0, 1, 2], -10, 10, clamp: true)",
+                result: Ok("[0, 1, 2]"),
+            },
         ]
     }
 
@@ -97,8 +118,15 @@ impl Function for Slice {
         let value = arguments.required("value");
         let start = arguments.required("start");
         let end = arguments.optional("end");
+        let clamp = arguments.optional("clamp").unwrap_or(expr!(false));
 
-        Ok(SliceFn { value, start, end }.as_expr())
+        Ok(SliceFn {
+            value,
+            start,
+            end,
+            clamp,
+        }
+        .as_expr())
     }
 }
 
@@ -107,6 +135,7 @@ struct SliceFn {
     value: Box<dyn Expression>,
     start: Box<dyn Expression>,
     end: Option<Box<dyn Expression>>,
+    clamp: Box<dyn Expression>,
 }
 
 impl FunctionExpression for SliceFn {
@@ -116,9 +145,10 @@ impl FunctionExpression for SliceFn {
             Some(expr) => Some(expr.resolve(ctx)?.try_integer()?),
             None => None,
         };
+        let clamp = self.clamp.resolve(ctx)?.try_boolean()?;
         let value = self.value.resolve(ctx)?;
 
-        slice(start, end, value)
+        slice(start, end, clamp, value)
     }
 
     fn type_def(&self, state: &state::TypeState) -> TypeDef {
@@ -257,6 +287,53 @@ mod tests {
             tdef: TypeDef::array(Collection::from_unknown(Kind::integer().or_bytes().or_boolean())).fallible(),
         }
 
+        array_minus_start_minus_end {
+            args: func_args![value: vec![0, 1, 2, 3, 4],
+                             start: -3,
+                             end: -1
+            ],
+            want: Ok(vec![2, 3]),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::integer())).fallible(),
+        }
+
+        bytes_start_minus_end {
+            args: func_args![value: "foobar",
+                             start: 1,
+                             end: -2
+            ],
+            want: Ok("oob"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        clamp_minus_before_start {
+            args: func_args![value: vec![0, 1, 2],
+                             start: -5,
+                             end: -1,
+                             clamp: true
+            ],
+            want: Ok(vec![0, 1]),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::integer())).fallible(),
+        }
+
+        clamp_after_end {
+            args: func_args![value: "foo",
+                             start: 4,
+                             clamp: true
+            ],
+            want: Ok(""),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        clamp_end_before_start {
+            args: func_args![value: "foo",
+                             start: 2,
+                             end: -3,
+                             clamp: true
+            ],
+            want: Ok(""),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
         error_after_end {
             args: func_args![value: "foo",
                              start: 4
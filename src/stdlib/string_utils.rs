@@ -7,3 +7,15 @@ pub(crate) fn convert_to_string(value: Value, to_lowercase: bool) -> Result<Stri
         false => string.to_string(),
     })
 }
+
+/// Lowercases the string elements of an array so that it can be compared case-insensitively,
+/// leaving elements of other types as they are.
+pub(crate) fn lowercase_strings(values: Vec<Value>) -> Vec<Value> {
+    values
+        .into_iter()
+        .map(|value| match value {
+            Value::Bytes(bytes) => String::from_utf8_lossy(&bytes).to_lowercase().into(),
+            value => value,
+        })
+        .collect()
+}
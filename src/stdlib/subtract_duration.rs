@@ -0,0 +1,90 @@
+use crate::compiler::prelude::*;
+
+use super::add_duration::shift_timestamp;
+
+#[derive(Clone, Copy, Debug)]
+pub struct SubtractDuration;
+
+impl Function for SubtractDuration {
+    fn identifier(&self) -> &'static str {
+        "subtract_duration"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::TIMESTAMP,
+                required: true,
+            },
+            Parameter {
+                keyword: "duration",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "subtract days",
+            source: r#"subtract_duration!(t'2021-03-01T12:00:00Z', "2d")"#,
+            result: Ok("t'2021-02-27T12:00:00Z'"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let duration = arguments.required("duration");
+
+        Ok(SubtractDurationFn { value, duration }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SubtractDurationFn {
+    value: Box<dyn Expression>,
+    duration: Box<dyn Expression>,
+}
+
+impl FunctionExpression for SubtractDurationFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let duration = self.duration.resolve(ctx)?;
+
+        shift_timestamp(value, duration, true)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::timestamp().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    test_function![
+        subtract_duration => SubtractDuration;
+
+        crosses_day_boundary {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 2, 11, 1, 2, 0).unwrap(),
+                             duration: "1h30m"],
+            want: Ok(Utc.with_ymd_and_hms(2021, 2, 10, 23, 32, 0).unwrap()),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        days_across_month {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 3, 1, 12, 0, 0).unwrap(),
+                             duration: "2d"],
+            want: Ok(Utc.with_ymd_and_hms(2021, 2, 27, 12, 0, 0).unwrap()),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+    ];
+}
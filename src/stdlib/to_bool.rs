@@ -1,7 +1,7 @@
 use crate::compiler::conversion::Conversion;
 use crate::compiler::prelude::*;
 
-fn to_bool(value: Value) -> Resolved {
+pub(super) fn to_bool(value: Value) -> Resolved {
     use Value::{Boolean, Bytes, Float, Integer, Null};
 
     match value {
@@ -1,12 +1,54 @@
+use super::lru_cache::SharedLruCache;
 use crate::compiler::prelude::*;
+use crate::value::ValueRegex;
 use tracing::warn;
 
-fn to_regex(value: Value) -> Resolved {
+/// The number of compiled regexes kept by each `to_regex` call site.
+const CACHE_CAPACITY: usize = 128;
+
+/// Regexes built from runtime patterns, keyed by their source, which includes the flags, so
+/// that hot patterns are only compiled once.
+type RegexCache = SharedLruCache<String, ValueRegex>;
+
+fn to_regex(value: Value, flags: Option<Value>, cache: &RegexCache) -> Resolved {
     let string = value.try_bytes_utf8_lossy()?;
-    let regex = regex::Regex::new(string.as_ref())
-        .map_err(|err| format!("could not create regex: {err}"))
-        .map(Into::into)?;
-    Ok(regex)
+    let flags = match &flags {
+        Some(flags) => Some(flags.try_bytes_utf8_lossy()?),
+        None => None,
+    };
+    let source = regex_source(&string, flags.as_deref())?;
+    if let Some(regex) = cache.get(&source) {
+        return Ok(regex.into());
+    }
+
+    let regex = ValueRegex::from(compile_source(&source)?);
+    cache.insert(source, regex.clone());
+    Ok(regex.into())
+}
+
+/// Compiles `pattern`, applying `flags` as an inline group (e.g. `(?i)`) so that they are
+/// preserved by functions that rebuild the regex from its source string.
+fn build_regex(pattern: &str, flags: Option<&str>) -> Result<regex::Regex, String> {
+    compile_source(&regex_source(pattern, flags)?)
+}
+
+fn compile_source(source: &str) -> Result<regex::Regex, String> {
+    regex::Regex::new(source).map_err(|err| format!("could not create regex: {err}"))
+}
+
+fn regex_source(pattern: &str, flags: Option<&str>) -> Result<String, String> {
+    match flags {
+        Some(flags) if !flags.is_empty() => {
+            if let Some(flag) = flags
+                .chars()
+                .find(|flag| !matches!(flag, 'i' | 'm' | 's' | 'x'))
+            {
+                return Err(format!("unknown regex flag '{flag}'"));
+            }
+            Ok(format!("(?{flags}){pattern}"))
+        }
+        _ => Ok(pattern.to_owned()),
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -18,42 +60,91 @@ impl Function for ToRegex {
     }
 
     fn parameters(&self) -> &'static [Parameter] {
-        &[Parameter {
-            keyword: "value",
-            kind: kind::BYTES,
-            required: true,
-        }]
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "flags",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
     }
 
     fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "regex",
-            source: "to_regex(s'^foobar$') ?? r''",
-            result: Ok("r'^foobar$'"),
-        }]
+        &[
+            Example {
+                title: "regex",
+                source: "to_regex(s'^foobar$') ?? r''",
+                result: Ok("r'^foobar$'"),
+            },
+            Example {
+                title: "case insensitive",
+                source: r#"match("FOOBAR", to_regex!(s'^foo', flags: "i"))"#,
+                result: Ok("true"),
+            },
+        ]
     }
 
     fn compile(
         &self,
-        _state: &state::TypeState,
+        state: &state::TypeState,
         _ctx: &mut FunctionCompileContext,
         arguments: ArgumentList,
     ) -> Compiled {
         warn!("`to_regex` is an expensive function that could impact throughput.");
         let value = arguments.required("value");
-        Ok(ToRegexFn { value }.as_expr())
+        let flags = arguments.optional("flags");
+
+        // Literal patterns are validated up front so mistakes surface at compile time.
+        let literal_flags = match &flags {
+            Some(flags) => flags.resolve_constant(state),
+            None => Some(Value::Null),
+        };
+        if let (Some(Value::Bytes(pattern)), Some(flags)) =
+            (value.resolve_constant(state), literal_flags)
+        {
+            let flags = flags
+                .as_bytes()
+                .map(|f| String::from_utf8_lossy(f).into_owned());
+            if build_regex(&String::from_utf8_lossy(&pattern), flags.as_deref()).is_err() {
+                return Err(function::Error::InvalidArgument {
+                    keyword: "value",
+                    value: Value::Bytes(pattern),
+                    error: "invalid regular expression",
+                }
+                .into());
+            }
+        }
+
+        Ok(ToRegexFn {
+            value,
+            flags,
+            cache: RegexCache::new(CACHE_CAPACITY),
+        }
+        .as_expr())
     }
 }
 
 #[derive(Debug, Clone)]
 struct ToRegexFn {
     value: Box<dyn Expression>,
+    flags: Option<Box<dyn Expression>>,
+    cache: RegexCache,
 }
 
 impl FunctionExpression for ToRegexFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
-        to_regex(value)
+        let flags = self
+            .flags
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+        to_regex(value, flags, &self.cache)
     }
 
     fn type_def(&self, _: &state::TypeState) -> TypeDef {
@@ -63,6 +154,8 @@ impl FunctionExpression for ToRegexFn {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
 
     test_function![
@@ -76,8 +169,84 @@ mod tests {
 
         invalid_regex {
             args: func_args![value: "(+)"],
-            want: Err("could not create regex: regex parse error:\n    (+)\n     ^\nerror: repetition operator missing expression"),
+            want: Err("invalid argument"),
+            tdef: TypeDef::regex().fallible(),
+        }
+
+        case_insensitive {
+            args: func_args![value: "^foo", flags: "i"],
+            want: Ok(regex::Regex::new("(?i)^foo").expect("regex is valid")),
+            tdef: TypeDef::regex().fallible(),
+        }
+
+        multiple_flags {
+            args: func_args![value: "^foo.bar$", flags: "ms"],
+            want: Ok(regex::Regex::new("(?ms)^foo.bar$").expect("regex is valid")),
+            tdef: TypeDef::regex().fallible(),
+        }
+
+        unknown_flag {
+            args: func_args![value: "^foo", flags: "q"],
+            want: Err("invalid argument"),
             tdef: TypeDef::regex().fallible(),
         }
     ];
+
+    #[test]
+    fn runtime_errors() {
+        assert_eq!(
+            to_regex("(+)".into(), None, &RegexCache::new(CACHE_CAPACITY)).unwrap_err().to_string(),
+            "could not create regex: regex parse error:\n    (+)\n     ^\nerror: repetition operator missing expression"
+        );
+        assert_eq!(
+            to_regex(
+                "^foo".into(),
+                Some("iq".into()),
+                &RegexCache::new(CACHE_CAPACITY)
+            )
+            .unwrap_err()
+            .to_string(),
+            "unknown regex flag 'q'"
+        );
+    }
+
+    #[test]
+    fn flags_apply_when_matching() {
+        let Value::Regex(regex) = to_regex(
+            "^foo".into(),
+            Some("i".into()),
+            &RegexCache::new(CACHE_CAPACITY),
+        )
+        .unwrap() else {
+            panic!("expected regex");
+        };
+
+        assert!(regex.is_match("FOOBAR"));
+        // `match` rebuilds static patterns from their source, which keeps inline flags.
+        assert!(regex::Regex::new(regex.as_str())
+            .unwrap()
+            .is_match("FooBar"));
+    }
+
+    #[test]
+    fn cache_reuses_compiled_regex() {
+        let cache = RegexCache::new(CACHE_CAPACITY);
+        let build = |pattern: &str, flags: Option<&str>| {
+            let Value::Regex(regex) =
+                to_regex(pattern.into(), flags.map(Into::into), &cache).unwrap()
+            else {
+                panic!("expected regex");
+            };
+            regex
+        };
+
+        let first = build("^foo", Some("i"));
+        let second = build("^foo", Some("i"));
+        let other_flags = build("^foo", None);
+
+        assert!(Arc::ptr_eq(&first.into_inner(), &second.into_inner()));
+        assert_eq!(other_flags.as_str(), "^foo");
+        assert!(cache.get("(?i)^foo").is_some());
+        assert!(cache.get("^foo").is_some());
+    }
 }
@@ -1,6 +1,6 @@
 use crate::compiler::prelude::*;
 
-fn to_string(value: Value) -> Resolved {
+pub(super) fn to_string(value: Value) -> Resolved {
     use chrono::SecondsFormat;
     use Value::{Boolean, Bytes, Float, Integer, Null, Timestamp};
     let value = match value {
@@ -0,0 +1,133 @@
+use crate::compiler::prelude::*;
+
+fn to_valid_utf8(value: Value, replacement: Option<Value>) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let replacement = match replacement {
+        Some(replacement) => replacement.try_bytes_utf8_lossy()?.into_owned(),
+        None => char::REPLACEMENT_CHARACTER.to_string(),
+    };
+
+    let mut output = String::with_capacity(bytes.len());
+    for chunk in bytes.utf8_chunks() {
+        output.push_str(chunk.valid());
+        if !chunk.invalid().is_empty() {
+            output.push_str(&replacement);
+        }
+    }
+
+    Ok(output.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ToValidUtf8;
+
+impl Function for ToValidUtf8 {
+    fn identifier(&self) -> &'static str {
+        "to_valid_utf8"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "replacement",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "default replacement",
+                source: r#"to_valid_utf8(decode_base16!("62ff6172"))"#,
+                result: Ok("b�ar"),
+            },
+            Example {
+                title: "custom replacement",
+                source: r#"to_valid_utf8(decode_base16!("62ff6172"), replacement: "?")"#,
+                result: Ok("b?ar"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let replacement = arguments.optional("replacement");
+
+        Ok(ToValidUtf8Fn { value, replacement }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ToValidUtf8Fn {
+    value: Box<dyn Expression>,
+    replacement: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for ToValidUtf8Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let replacement = self
+            .replacement
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        to_valid_utf8(value, replacement)
+    }
+
+    fn type_def(&self, _state: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        to_valid_utf8 => ToValidUtf8;
+
+        already_valid {
+            args: func_args![value: value!("ñandú")],
+            want: Ok(value!("ñandú")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        default_replacement {
+            args: func_args![value: value!(b"b\xFFar")],
+            want: Ok(value!("b\u{FFFD}ar")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        custom_replacement {
+            args: func_args![value: value!(b"b\xFFar"), replacement: "?"],
+            want: Ok(value!("b?ar")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        empty_replacement {
+            args: func_args![value: value!(b"b\xFF\xFEar"), replacement: ""],
+            want: Ok(value!("bar")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        truncated_sequence {
+            args: func_args![value: value!(b"caf\xC3"), replacement: "?"],
+            want: Ok(value!("caf?")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
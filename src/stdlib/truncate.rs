@@ -5,7 +5,7 @@ fn truncate(value: Value, limit: Value, ellipsis: Value, suffix: Value) -> Resol
     let limit = limit.try_integer()?;
     let limit = if limit < 0 { 0 } else { limit as usize };
     let ellipsis = ellipsis.try_boolean()?;
-    let suffix = suffix.try_bytes_utf8_lossy()?.to_string();
+    let suffix = suffix.try_bytes_utf8_lossy()?;
     let pos = if let Some((pos, chr)) = value.char_indices().take(limit).last() {
         // char_indices gives us the starting position of the character at limit,
         // we want the end position.
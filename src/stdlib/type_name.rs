@@ -0,0 +1,143 @@
+use crate::compiler::prelude::*;
+
+/// Returns the name of the value's type, using the same names as `assert_type` and
+/// VRL's type errors.
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Bytes(_) => "string",
+        Value::Regex(_) => "regex",
+        Value::Integer(_) => "integer",
+        Value::Float(_) => "float",
+        Value::Boolean(_) => "boolean",
+        Value::Timestamp(_) => "timestamp",
+        Value::Object(_) => "object",
+        Value::Array(_) => "array",
+        Value::Null => "null",
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct TypeName;
+
+impl Function for TypeName {
+    fn identifier(&self) -> &'static str {
+        "type_name"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::ANY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "integer",
+                source: "type_name(42)",
+                result: Ok(r#""integer""#),
+            },
+            Example {
+                title: "object",
+                source: r#"type_name({"foo": "bar"})"#,
+                result: Ok(r#""object""#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(TypeNameFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TypeNameFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for TypeNameFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        Ok(type_name(&value).into())
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+    use chrono::{TimeZone, Utc};
+    use regex::Regex;
+
+    test_function![
+        type_name => TypeName;
+
+        string {
+            args: func_args![value: "foo"],
+            want: Ok("string"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        regex {
+            args: func_args![value: Value::Regex(Regex::new("^a").unwrap().into())],
+            want: Ok("regex"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        integer {
+            args: func_args![value: 1],
+            want: Ok("integer"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        float {
+            args: func_args![value: 1.5],
+            want: Ok("float"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        boolean {
+            args: func_args![value: true],
+            want: Ok("boolean"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        timestamp {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap()],
+            want: Ok("timestamp"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        object {
+            args: func_args![value: value!({"a": 1})],
+            want: Ok("object"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        array {
+            args: func_args![value: value!([1])],
+            want: Ok("array"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        null {
+            args: func_args![value: Value::Null],
+            want: Ok("null"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
@@ -28,6 +28,10 @@ impl Function for Upcase {
         }]
     }
 
+    fn fold(&self) -> bool {
+        true
+    }
+
     fn compile(
         &self,
         _state: &state::TypeState,
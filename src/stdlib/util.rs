@@ -1,4 +1,10 @@
-use crate::compiler::{Context, Expression, Resolved, TypeState};
+use std::io::Read;
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::compiler::value::VrlValueConvert;
+use crate::compiler::{Context, Expression, ExpressionError, Resolved, TimeZone, TypeState};
+use crate::path::{parse_value_path, OwnedValuePath};
 use crate::value::{KeyString, ObjectMap, Value};
 
 /// Rounds the given number to the given precision.
@@ -80,6 +86,80 @@ pub(crate) fn is_nullish(value: &crate::value::Value) -> bool {
     }
 }
 
+/// Converts an optional `max_size` argument into a byte limit, rejecting negative values.
+pub(crate) fn max_size_limit(max_size: Option<Value>) -> Result<Option<u64>, ExpressionError> {
+    max_size
+        .map(|value| {
+            let max_size = value.try_integer()?;
+            u64::try_from(max_size)
+                .map_err(|_| ExpressionError::from("max_size must be a non-negative integer"))
+        })
+        .transpose()
+}
+
+/// Reads a decompression stream to the end, erroring once more than `max_size` bytes
+/// have been produced so that a decompression bomb cannot exhaust memory.
+///
+/// `decoder` is the human-readable decoder name used in the error message when the
+/// stream itself is malformed.
+pub(crate) fn read_to_end_limited<R: std::io::Read>(
+    mut reader: R,
+    max_size: Option<u64>,
+    decoder: &str,
+) -> Result<Vec<u8>, ExpressionError> {
+    let mut buf = Vec::new();
+    let result = match max_size {
+        // Read one byte past the limit so payloads of exactly `max_size` are accepted.
+        Some(limit) => reader.take(limit.saturating_add(1)).read_to_end(&mut buf),
+        None => reader.read_to_end(&mut buf),
+    };
+
+    if result.is_err() {
+        return Err(format!("unable to decode value with {decoder} decoder").into());
+    }
+
+    match max_size {
+        Some(limit) if buf.len() as u64 > limit => {
+            Err(format!("decompressed size exceeds max_size of {limit} bytes").into())
+        }
+        _ => Ok(buf),
+    }
+}
+
+/// Converts an object key argument into a path. When `paths` is set the key is parsed as a
+/// dotted path such as `a.b`; otherwise it always refers to a single top-level field.
+pub(crate) fn key_path(key: &str, paths: bool) -> Result<OwnedValuePath, ExpressionError> {
+    if paths {
+        parse_value_path(key).map_err(|_| format!("invalid path: {key:?}").into())
+    } else {
+        Ok(OwnedValuePath::single_field(key))
+    }
+}
+
+/// Parses the optional `timezone` argument of the calendar functions.
+pub(crate) fn timezone_arg(timezone: Option<Value>) -> Result<Option<TimeZone>, ExpressionError> {
+    timezone
+        .map(|timezone| {
+            let timezone = timezone.try_bytes_utf8_lossy()?;
+            TimeZone::parse(&timezone)
+                .ok_or_else(|| format!("unable to parse timezone: {timezone}").into())
+        })
+        .transpose()
+}
+
+/// Returns the calendar date of `timestamp` in the optional `timezone` argument, falling
+/// back to UTC when no timezone is given.
+pub(crate) fn date_in_timezone(
+    timestamp: DateTime<Utc>,
+    timezone: Option<Value>,
+) -> Result<NaiveDate, ExpressionError> {
+    Ok(match timezone_arg(timezone)? {
+        None => timestamp.date_naive(),
+        Some(TimeZone::Named(tz)) => timestamp.with_timezone(&tz).date_naive(),
+        Some(TimeZone::Local) => timestamp.with_timezone(&chrono::Local).date_naive(),
+    })
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Base64Charset {
     Standard,
@@ -103,6 +183,92 @@ impl From<Base64Charset> for base64::alphabet::Alphabet {
     }
 }
 
+/// Encodes `bytes` as base64, using the SIMD backend when the `simd_base64` feature is enabled.
+pub(crate) fn base64_encode(bytes: &[u8], charset: Base64Charset, padding: bool) -> String {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "simd_base64")] {
+            simd_base64::encode(bytes, charset, padding)
+        } else {
+            scalar_base64::encode(bytes, charset, padding)
+        }
+    }
+}
+
+/// Decodes base64 `bytes`, accepting input with or without padding. Returns `None` if the input
+/// isn't valid base64 for the given charset.
+pub(crate) fn base64_decode(bytes: &[u8], charset: Base64Charset) -> Option<Vec<u8>> {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "simd_base64")] {
+            simd_base64::decode(bytes, charset)
+        } else {
+            scalar_base64::decode(bytes, charset)
+        }
+    }
+}
+
+#[cfg_attr(feature = "simd_base64", allow(dead_code))]
+pub(crate) mod scalar_base64 {
+    use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig};
+    use base64::engine::DecodePaddingMode;
+    use base64::Engine as _;
+
+    use super::Base64Charset;
+
+    pub(crate) fn encode(bytes: &[u8], charset: Base64Charset, padding: bool) -> String {
+        let config = GeneralPurposeConfig::new().with_encode_padding(padding);
+        GeneralPurpose::new(&charset.into(), config).encode(bytes)
+    }
+
+    pub(crate) fn decode(bytes: &[u8], charset: Base64Charset) -> Option<Vec<u8>> {
+        let config =
+            GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent);
+        GeneralPurpose::new(&charset.into(), config)
+            .decode(bytes)
+            .ok()
+    }
+}
+
+#[cfg(feature = "simd_base64")]
+pub(crate) mod simd_base64 {
+    use base64_simd::{Base64, STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
+
+    use super::Base64Charset;
+
+    fn engine(charset: Base64Charset, padding: bool) -> &'static Base64 {
+        match (charset, padding) {
+            (Base64Charset::Standard, true) => &STANDARD,
+            (Base64Charset::Standard, false) => &STANDARD_NO_PAD,
+            (Base64Charset::UrlSafe, true) => &URL_SAFE,
+            (Base64Charset::UrlSafe, false) => &URL_SAFE_NO_PAD,
+        }
+    }
+
+    pub(crate) fn encode(bytes: &[u8], charset: Base64Charset, padding: bool) -> String {
+        engine(charset, padding).encode_to_string(bytes)
+    }
+
+    pub(crate) fn decode(bytes: &[u8], charset: Base64Charset) -> Option<Vec<u8>> {
+        // `base64_simd` is strict about padding, so strip it here and only accept as much as
+        // the scalar decoder would.
+        let mut data = bytes;
+        let mut stripped = 0;
+        while stripped < 2 {
+            match data.split_last() {
+                Some((b'=', rest)) => {
+                    data = rest;
+                    stripped += 1;
+                }
+                _ => break,
+            }
+        }
+        if stripped > (4 - data.len() % 4) % 4 {
+            return None;
+        }
+
+        engine(charset, false).decode_to_vec(data).ok()
+    }
+}
+
 impl std::str::FromStr for Base64Charset {
     type Err = &'static str;
 
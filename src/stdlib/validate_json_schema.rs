@@ -0,0 +1,670 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use regex::Regex;
+
+use crate::compiler::prelude::*;
+
+/// Keywords that only annotate a schema and have no effect on validation.
+const ANNOTATIONS: &[&str] = &[
+    "$schema",
+    "$id",
+    "$comment",
+    "title",
+    "description",
+    "default",
+    "examples",
+    "format",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+];
+
+/// A compiled JSON Schema, supporting the keywords most used to describe the shape of an
+/// event. Unsupported keywords, such as `$ref`, are rejected when the schema is compiled
+/// rather than silently ignored.
+#[derive(Clone, Debug)]
+enum Schema {
+    Bool(bool),
+    Object(Box<SchemaObject>),
+}
+
+#[derive(Clone, Debug, Default)]
+struct SchemaObject {
+    types: Option<Vec<String>>,
+    enumeration: Option<Vec<Value>>,
+    constant: Option<Value>,
+    required: Vec<String>,
+    properties: BTreeMap<String, Schema>,
+    additional_properties: Option<Schema>,
+    items: Option<Schema>,
+    min_items: Option<usize>,
+    max_items: Option<usize>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    pattern: Option<Regex>,
+    minimum: Option<f64>,
+    maximum: Option<f64>,
+    exclusive_minimum: Option<f64>,
+    exclusive_maximum: Option<f64>,
+    all_of: Vec<Schema>,
+    any_of: Vec<Schema>,
+    one_of: Vec<Schema>,
+    not: Option<Schema>,
+}
+
+impl Schema {
+    fn compile(value: &Value) -> Result<Self, String> {
+        let object = match value {
+            Value::Boolean(value) => return Ok(Self::Bool(*value)),
+            Value::Object(object) => object,
+            value => {
+                return Err(format!(
+                    "schema must be an object or a boolean, got {value}"
+                ))
+            }
+        };
+
+        let mut schema = SchemaObject::default();
+        for (keyword, value) in object {
+            match keyword.as_str() {
+                "type" => {
+                    schema.types = Some(match value {
+                        Value::Array(types) => {
+                            types.iter().map(type_name).collect::<Result<_, _>>()?
+                        }
+                        value => vec![type_name(value)?],
+                    });
+                }
+                "enum" => schema.enumeration = Some(array(keyword, value)?.to_vec()),
+                "const" => schema.constant = Some(value.clone()),
+                "required" => {
+                    schema.required = array(keyword, value)?
+                        .iter()
+                        .map(|field| string(keyword, field))
+                        .collect::<Result<_, _>>()?;
+                }
+                "properties" => {
+                    schema.properties = value
+                        .as_object()
+                        .ok_or_else(|| format!(r#""{keyword}" must be an object"#))?
+                        .iter()
+                        .map(|(field, schema)| Ok((field.to_string(), Self::compile(schema)?)))
+                        .collect::<Result<_, String>>()?;
+                }
+                "additionalProperties" => {
+                    schema.additional_properties = Some(Self::compile(value)?);
+                }
+                "items" => schema.items = Some(Self::compile(value)?),
+                "minItems" => schema.min_items = Some(count(keyword, value)?),
+                "maxItems" => schema.max_items = Some(count(keyword, value)?),
+                "minLength" => schema.min_length = Some(count(keyword, value)?),
+                "maxLength" => schema.max_length = Some(count(keyword, value)?),
+                "pattern" => {
+                    let pattern = string(keyword, value)?;
+                    schema.pattern = Some(
+                        Regex::new(&pattern)
+                            .map_err(|err| format!(r#"invalid "{keyword}": {err}"#))?,
+                    );
+                }
+                "minimum" => schema.minimum = Some(number(keyword, value)?),
+                "maximum" => schema.maximum = Some(number(keyword, value)?),
+                "exclusiveMinimum" => schema.exclusive_minimum = Some(number(keyword, value)?),
+                "exclusiveMaximum" => schema.exclusive_maximum = Some(number(keyword, value)?),
+                "allOf" => schema.all_of = schemas(keyword, value)?,
+                "anyOf" => schema.any_of = schemas(keyword, value)?,
+                "oneOf" => schema.one_of = schemas(keyword, value)?,
+                "not" => schema.not = Some(Self::compile(value)?),
+                keyword if ANNOTATIONS.contains(&keyword) => {}
+                keyword => return Err(format!(r#"unsupported keyword "{keyword}""#)),
+            }
+        }
+
+        Ok(Self::Object(Box::new(schema)))
+    }
+
+    fn is_valid(&self, value: &Value) -> bool {
+        let mut errors = Vec::new();
+        self.validate(value, "", &mut errors);
+        errors.is_empty()
+    }
+
+    /// Validates `value`, found at `path`, appending a message for every violation to
+    /// `errors`.
+    fn validate(&self, value: &Value, path: &str, errors: &mut Vec<String>) {
+        let schema = match self {
+            Self::Bool(true) => return,
+            Self::Bool(false) => return error(errors, path, "no value is allowed"),
+            Self::Object(schema) => schema,
+        };
+
+        if let Some(types) = &schema.types {
+            if !types.iter().any(|name| has_type(value, name)) {
+                let expected = types.join(" or ");
+                let actual = json_type(value);
+                error(errors, path, &format!("expected {expected}, got {actual}"));
+            }
+        }
+        if let Some(values) = &schema.enumeration {
+            if !values.iter().any(|allowed| json_eq(value, allowed)) {
+                error(errors, path, "value is not one of the allowed values");
+            }
+        }
+        if let Some(constant) = &schema.constant {
+            if !json_eq(value, constant) {
+                error(errors, path, "value does not equal the expected constant");
+            }
+        }
+
+        match value {
+            Value::Object(object) => schema.validate_object(object, path, errors),
+            Value::Array(array) => schema.validate_array(array, path, errors),
+            Value::Bytes(bytes) => {
+                schema.validate_string(&String::from_utf8_lossy(bytes), path, errors);
+            }
+            Value::Integer(_) | Value::Float(_) => {
+                schema.validate_number(number_value(value).expect("number"), path, errors);
+            }
+            _ => {}
+        }
+
+        for subschema in &schema.all_of {
+            subschema.validate(value, path, errors);
+        }
+        if !schema.any_of.is_empty() && !schema.any_of.iter().any(|s| s.is_valid(value)) {
+            error(
+                errors,
+                path,
+                "value does not match any of the schemas in anyOf",
+            );
+        }
+        if !schema.one_of.is_empty() {
+            let matches = schema.one_of.iter().filter(|s| s.is_valid(value)).count();
+            if matches != 1 {
+                let message =
+                    format!("value matches {matches} of the schemas in oneOf, expected exactly 1");
+                error(errors, path, &message);
+            }
+        }
+        if let Some(not) = &schema.not {
+            if not.is_valid(value) {
+                error(errors, path, "value matches the schema in not");
+            }
+        }
+    }
+}
+
+impl SchemaObject {
+    fn validate_object(&self, object: &ObjectMap, path: &str, errors: &mut Vec<String>) {
+        for field in &self.required {
+            if !object.contains_key(field.as_str()) {
+                error(
+                    errors,
+                    path,
+                    &format!(r#"missing required field "{field}""#),
+                );
+            }
+        }
+        for (field, value) in object {
+            let field_path = field_path(path, field);
+            match self.properties.get(field.as_str()) {
+                Some(schema) => schema.validate(value, &field_path, errors),
+                None => match &self.additional_properties {
+                    Some(Schema::Bool(false)) => {
+                        error(errors, path, &format!(r#"unexpected field "{field}""#));
+                    }
+                    Some(schema) => schema.validate(value, &field_path, errors),
+                    None => {}
+                },
+            }
+        }
+    }
+
+    fn validate_array(&self, array: &[Value], path: &str, errors: &mut Vec<String>) {
+        if let Some(min) = self.min_items.filter(|min| array.len() < *min) {
+            error(
+                errors,
+                path,
+                &format!("expected at least {min} items, got {}", array.len()),
+            );
+        }
+        if let Some(max) = self.max_items.filter(|max| array.len() > *max) {
+            error(
+                errors,
+                path,
+                &format!("expected at most {max} items, got {}", array.len()),
+            );
+        }
+        if let Some(schema) = &self.items {
+            for (index, value) in array.iter().enumerate() {
+                schema.validate(value, &format!("{path}[{index}]"), errors);
+            }
+        }
+    }
+
+    fn validate_string(&self, string: &str, path: &str, errors: &mut Vec<String>) {
+        // Lengths are counted in characters, as JSON Schema counts code points.
+        let length = string.chars().count();
+        if let Some(min) = self.min_length.filter(|min| length < *min) {
+            error(
+                errors,
+                path,
+                &format!("expected at least {min} characters, got {length}"),
+            );
+        }
+        if let Some(max) = self.max_length.filter(|max| length > *max) {
+            error(
+                errors,
+                path,
+                &format!("expected at most {max} characters, got {length}"),
+            );
+        }
+        if let Some(pattern) = self.pattern.as_ref().filter(|p| !p.is_match(string)) {
+            error(
+                errors,
+                path,
+                &format!(r#"value does not match pattern "{pattern}""#),
+            );
+        }
+    }
+
+    fn validate_number(&self, number: f64, path: &str, errors: &mut Vec<String>) {
+        if let Some(min) = self.minimum.filter(|min| number < *min) {
+            error(
+                errors,
+                path,
+                &format!("{number} is less than the minimum of {min}"),
+            );
+        }
+        if let Some(max) = self.maximum.filter(|max| number > *max) {
+            error(
+                errors,
+                path,
+                &format!("{number} is greater than the maximum of {max}"),
+            );
+        }
+        if let Some(min) = self.exclusive_minimum.filter(|min| number <= *min) {
+            let message = format!("{number} is not greater than the exclusive minimum of {min}");
+            error(errors, path, &message);
+        }
+        if let Some(max) = self.exclusive_maximum.filter(|max| number >= *max) {
+            let message = format!("{number} is not less than the exclusive maximum of {max}");
+            error(errors, path, &message);
+        }
+    }
+}
+
+fn error(errors: &mut Vec<String>, path: &str, message: &str) {
+    let path = if path.is_empty() { "." } else { path };
+    errors.push(format!("{path}: {message}"));
+}
+
+fn field_path(path: &str, field: &str) -> String {
+    let mut path = path.to_owned();
+    if !field.is_empty() && field.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        let _ = write!(path, ".{field}");
+    } else {
+        let _ = write!(path, ".{field:?}");
+    }
+    path
+}
+
+fn type_name(value: &Value) -> Result<String, String> {
+    match value.as_str().as_deref() {
+        Some(
+            name @ ("null" | "boolean" | "object" | "array" | "number" | "integer" | "string"),
+        ) => Ok(name.to_owned()),
+        _ => Err(format!(r#"invalid "type": {value}"#)),
+    }
+}
+
+fn array<'a>(keyword: &str, value: &'a Value) -> Result<&'a [Value], String> {
+    value
+        .as_array()
+        .ok_or_else(|| format!(r#""{keyword}" must be an array"#))
+}
+
+fn string(keyword: &str, value: &Value) -> Result<String, String> {
+    value
+        .as_str()
+        .map(Cow::into_owned)
+        .ok_or_else(|| format!(r#""{keyword}" must be a string"#))
+}
+
+fn number(keyword: &str, value: &Value) -> Result<f64, String> {
+    number_value(value).ok_or_else(|| format!(r#""{keyword}" must be a number"#))
+}
+
+fn count(keyword: &str, value: &Value) -> Result<usize, String> {
+    value
+        .as_integer()
+        .and_then(|count| usize::try_from(count).ok())
+        .ok_or_else(|| format!(r#""{keyword}" must be a non-negative integer"#))
+}
+
+fn schemas(keyword: &str, value: &Value) -> Result<Vec<Schema>, String> {
+    let schemas = array(keyword, value)?;
+    if schemas.is_empty() {
+        return Err(format!(r#""{keyword}" must not be empty"#));
+    }
+    schemas.iter().map(Schema::compile).collect()
+}
+
+fn number_value(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(integer) => Some(*integer as f64),
+        Value::Float(float) => Some(float.into_inner()),
+        _ => None,
+    }
+}
+
+/// The JSON type of a value. Timestamps and regexes are encoded as JSON strings.
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Bytes(_) | Value::Timestamp(_) | Value::Regex(_) => "string",
+        Value::Integer(_) => "integer",
+        Value::Float(_) => "number",
+        Value::Boolean(_) => "boolean",
+        Value::Object(_) => "object",
+        Value::Array(_) => "array",
+        Value::Null => "null",
+    }
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match (name, value) {
+        ("number", Value::Integer(_)) => true,
+        // JSON Schema considers numbers with a zero fractional part to be integers.
+        ("integer", Value::Float(float)) => float.fract() == 0.0,
+        (name, value) => json_type(value) == name,
+    }
+}
+
+/// Compares values as JSON Schema does, where `1` and `1.0` are equal.
+pub(super) fn json_eq(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Array(left), Value::Array(right)) => {
+            left.len() == right.len() && left.iter().zip(right).all(|(l, r)| json_eq(l, r))
+        }
+        (Value::Object(left), Value::Object(right)) => {
+            left.len() == right.len()
+                && left
+                    .iter()
+                    .all(|(key, l)| right.get(key).is_some_and(|r| json_eq(l, r)))
+        }
+        (left, right) => match (number_value(left), number_value(right)) {
+            (Some(left), Some(right)) => left == right,
+            _ => left == right,
+        },
+    }
+}
+
+/// The schema may be given as an object or as a JSON document.
+fn schema_value(value: Value) -> Result<Value, String> {
+    match value {
+        Value::Bytes(bytes) => serde_json::from_slice::<'_, Value>(&bytes)
+            .map_err(|err| format!("unable to parse schema as JSON: {err}")),
+        value => Ok(value),
+    }
+}
+
+fn compile_schema(value: Value) -> Result<Schema, String> {
+    Schema::compile(&schema_value(value)?).map_err(|err| format!("invalid JSON Schema: {err}"))
+}
+
+fn validate_json_schema(value: &Value, schema: &Schema, errors: bool) -> Value {
+    let mut messages = Vec::new();
+    schema.validate(value, "", &mut messages);
+    if errors {
+        messages
+            .into_iter()
+            .map(Value::from)
+            .collect::<Vec<_>>()
+            .into()
+    } else {
+        messages.is_empty().into()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ValidateJsonSchema;
+
+impl Function for ValidateJsonSchema {
+    fn identifier(&self) -> &'static str {
+        "validate_json_schema"
+    }
+
+    fn summary(&self) -> &'static str {
+        "validate a value against a JSON Schema"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Validates `value` against a JSON Schema, given either as an object or as a JSON
+            string, and returns whether it is valid. When `errors` is `true`, an array of the
+            violations found is returned instead, which is empty for a valid value. Each
+            violation is prefixed with the path of the offending field, such as `.user.email`.
+
+            The `type`, `enum`, `const`, `required`, `properties`, `additionalProperties`,
+            `items`, `minItems`, `maxItems`, `minLength`, `maxLength`, `pattern`, `minimum`,
+            `maximum`, `exclusiveMinimum`, `exclusiveMaximum`, `allOf`, `anyOf`, `oneOf` and
+            `not` keywords are supported, and annotations such as `title` are ignored. Schemas
+            using any other keyword, such as `$ref`, are rejected.
+
+            A literal schema is compiled once, along with the program, and errors at compile
+            time if it is invalid. Otherwise the schema is compiled on every call, which makes
+            the function fallible.
+        "}
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "schema",
+                kind: kind::OBJECT | kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "errors",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "valid document",
+                source: r#"validate_json_schema({ "id": 1, "name": "vrl" }, { "type": "object", "required": ["id", "name"] })"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "invalid document errors",
+                source: r#"validate_json_schema({ "id": "1" }, s'{ "required": ["id", "name"], "properties": { "id": { "type": "integer" } } }', errors: true)"#,
+                result: Ok(
+                    r#"[".: missing required field \"name\"", ".id: expected integer, got string"]"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let schema = arguments.required("schema");
+        let schema = match schema.resolve_constant(state) {
+            Some(literal) => SchemaArgument::Compiled(compile_schema(literal).map_err(|err| {
+                Box::new(ExpressionError::from(err)) as Box<dyn DiagnosticMessage>
+            })?),
+            None => SchemaArgument::Dynamic(schema),
+        };
+        let errors = match arguments.optional_literal("errors", state)? {
+            Some(errors) => {
+                errors
+                    .as_boolean()
+                    .ok_or_else(|| function::Error::InvalidArgument {
+                        keyword: "errors",
+                        value: errors,
+                        error: "errors must be a boolean",
+                    })?
+            }
+            None => false,
+        };
+
+        Ok(ValidateJsonSchemaFn {
+            value,
+            schema,
+            errors,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+enum SchemaArgument {
+    Compiled(Schema),
+    Dynamic(Box<dyn Expression>),
+}
+
+#[derive(Debug, Clone)]
+struct ValidateJsonSchemaFn {
+    value: Box<dyn Expression>,
+    schema: SchemaArgument,
+    errors: bool,
+}
+
+impl FunctionExpression for ValidateJsonSchemaFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        match &self.schema {
+            SchemaArgument::Compiled(schema) => {
+                Ok(validate_json_schema(&value, schema, self.errors))
+            }
+            SchemaArgument::Dynamic(expr) => {
+                let schema = compile_schema(expr.resolve(ctx)?)?;
+                Ok(validate_json_schema(&value, &schema, self.errors))
+            }
+        }
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        let type_def = if self.errors {
+            TypeDef::array(Collection::from_unknown(Kind::bytes()))
+        } else {
+            TypeDef::boolean()
+        };
+        type_def.maybe_fallible(matches!(self.schema, SchemaArgument::Dynamic(_)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    fn user_schema() -> Value {
+        value!({
+            "type": "object",
+            "required": ["id", "email"],
+            "properties": {
+                "id": { "type": "integer", "minimum": 1 },
+                "email": { "type": "string", "pattern": "^[^@]+@[^@]+$" },
+                "tags": { "type": "array", "items": { "type": "string" }, "maxItems": 2 },
+                "role": { "enum": ["admin", "user"] },
+            },
+            "additionalProperties": false,
+        })
+    }
+
+    test_function![
+        validate_json_schema => ValidateJsonSchema;
+
+        passing {
+            args: func_args![
+                value: value!({ "id": 1, "email": "ada@example.com", "tags": ["a"], "role": "admin" }),
+                schema: user_schema(),
+            ],
+            want: Ok(true),
+            tdef: TypeDef::boolean(),
+        }
+
+        failing_required {
+            args: func_args![value: value!({ "id": 1 }), schema: user_schema()],
+            want: Ok(false),
+            tdef: TypeDef::boolean(),
+        }
+
+        failing_errors {
+            args: func_args![
+                value: value!({ "id": 0, "tags": ["a", 2, "c"], "role": "root", "extra": true }),
+                schema: user_schema(),
+                errors: true,
+            ],
+            want: Ok(value!([
+                r#".: missing required field "email""#,
+                r#".: unexpected field "extra""#,
+                ".id: 0 is less than the minimum of 1",
+                ".role: value is not one of the allowed values",
+                ".tags: expected at most 2 items, got 3",
+                ".tags[1]: expected string, got integer",
+            ])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())),
+        }
+
+        passing_errors {
+            args: func_args![value: value!({ "id": 1, "email": "ada@example.com" }), schema: user_schema(), errors: true],
+            want: Ok(value!([])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())),
+        }
+
+        json_schema_string {
+            args: func_args![
+                value: value!({ "level": "warn" }),
+                schema: r#"{ "required": ["level", "message"] }"#,
+                errors: true,
+            ],
+            want: Ok(value!([r#".: missing required field "message""#])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())),
+        }
+
+        combinators {
+            args: func_args![
+                value: value!(2.0),
+                schema: value!({ "oneOf": [{ "type": "integer" }, { "type": "number" }], "not": { "const": 2 } }),
+                errors: true,
+            ],
+            want: Ok(value!([
+                ".: value matches 2 of the schemas in oneOf, expected exactly 1",
+                ".: value matches the schema in not",
+            ])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes())),
+        }
+    ];
+
+    #[test]
+    fn unsupported_keyword() {
+        assert_eq!(
+            compile_schema(value!({ "properties": { "a": { "$ref": "#/$defs/a" } } })).unwrap_err(),
+            r#"invalid JSON Schema: unsupported keyword "$ref""#
+        );
+    }
+
+    #[test]
+    fn invalid_json() {
+        assert!(compile_schema(value!("{"))
+            .unwrap_err()
+            .starts_with("unable to parse schema as JSON"));
+    }
+}
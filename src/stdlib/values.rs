@@ -34,6 +34,11 @@ impl Function for Values {
                 source: r#"values({"key1": "val1", "key2": {"nestedkey1": "val3", "nestedkey2": "val4"}})"#,
                 result: Ok(r#"["val1", { "nestedkey1": "val3", "nestedkey2": "val4" }]"#),
             },
+            Example {
+                title: "get values from an empty object",
+                source: "values({})",
+                result: Ok("[]"),
+            },
         ]
     }
 
@@ -0,0 +1,119 @@
+use crate::compiler::prelude::*;
+use chrono::Datelike;
+
+use super::util::date_in_timezone;
+
+fn week_of_year(value: Value, timezone: Option<Value>) -> Resolved {
+    let date = date_in_timezone(value.try_timestamp()?, timezone)?;
+
+    Ok(i64::from(date.iso_week().week()).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct WeekOfYear;
+
+impl Function for WeekOfYear {
+    fn identifier(&self) -> &'static str {
+        "week_of_year"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::TIMESTAMP,
+                required: true,
+            },
+            Parameter {
+                keyword: "timezone",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "ISO week of year",
+                source: "week_of_year(t'2021-02-10T23:32:00Z')",
+                result: Ok("6"),
+            },
+            Example {
+                title: "ISO week of year in timezone",
+                source: r#"week_of_year!(t'2021-02-14T23:32:00Z', timezone: "Europe/Berlin")"#,
+                result: Ok("7"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let timezone = arguments.optional("timezone");
+
+        Ok(WeekOfYearFn { value, timezone }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct WeekOfYearFn {
+    value: Box<dyn Expression>,
+    timezone: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for WeekOfYearFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let timezone = self
+            .timezone
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        week_of_year(value, timezone)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::integer().maybe_fallible(self.timezone.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    test_function![
+        week_of_year => WeekOfYear;
+
+        mid_year {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 6, 15, 0, 0, 0).unwrap()],
+            want: Ok(24),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        january_in_previous_iso_year {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap()],
+            want: Ok(53),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        december_in_next_iso_year {
+            args: func_args![value: Utc.with_ymd_and_hms(2024, 12, 30, 12, 0, 0).unwrap()],
+            want: Ok(1),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        timezone_crosses_week {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 1, 4, 2, 0, 0).unwrap(),
+                             timezone: "America/New_York"],
+            want: Ok(53),
+            tdef: TypeDef::integer().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,157 @@
+use crate::compiler::prelude::*;
+use chrono::Datelike;
+
+use super::util::date_in_timezone;
+
+fn weekday(value: Value, timezone: Option<Value>, name: Value) -> Resolved {
+    let date = date_in_timezone(value.try_timestamp()?, timezone)?;
+
+    if name.try_boolean()? {
+        Ok(date.format("%A").to_string().into())
+    } else {
+        Ok(i64::from(date.weekday().num_days_from_monday()).into())
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Weekday;
+
+impl Function for Weekday {
+    fn identifier(&self) -> &'static str {
+        "weekday"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::TIMESTAMP,
+                required: true,
+            },
+            Parameter {
+                keyword: "timezone",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "name",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "weekday number (Monday is 0)",
+                source: "weekday(t'2021-02-10T23:32:00Z')",
+                result: Ok("2"),
+            },
+            Example {
+                title: "weekday name",
+                source: "weekday(t'2021-02-10T23:32:00Z', name: true)",
+                result: Ok("Wednesday"),
+            },
+            Example {
+                title: "weekday in timezone",
+                source: r#"weekday!(t'2021-02-10T23:32:00Z', timezone: "Europe/Berlin")"#,
+                result: Ok("3"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let timezone = arguments.optional("timezone");
+        let name = arguments.optional("name").unwrap_or_else(|| expr!(false));
+
+        Ok(WeekdayFn {
+            value,
+            timezone,
+            name,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct WeekdayFn {
+    value: Box<dyn Expression>,
+    timezone: Option<Box<dyn Expression>>,
+    name: Box<dyn Expression>,
+}
+
+impl FunctionExpression for WeekdayFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let timezone = self
+            .timezone
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+        let name = self.name.resolve(ctx)?;
+
+        weekday(value, timezone, name)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        match self
+            .name
+            .resolve_constant(state)
+            .and_then(|v| v.as_boolean())
+        {
+            Some(true) => TypeDef::bytes(),
+            Some(false) => TypeDef::integer(),
+            None => TypeDef::integer().or_bytes(),
+        }
+        .maybe_fallible(self.timezone.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    test_function![
+        weekday => Weekday;
+
+        monday_is_zero {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 2, 8, 12, 0, 0).unwrap()],
+            want: Ok(0),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        sunday_is_six {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 2, 14, 12, 0, 0).unwrap()],
+            want: Ok(6),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        name {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap(), name: true],
+            want: Ok("Friday"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        timezone {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 1, 1, 2, 0, 0).unwrap(),
+                             timezone: "America/New_York"],
+            want: Ok(3),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        invalid_timezone {
+            args: func_args![value: Utc.with_ymd_and_hms(2021, 1, 1, 2, 0, 0).unwrap(),
+                             timezone: "Mars/Olympus_Mons"],
+            want: Err("unable to parse timezone: Mars/Olympus_Mons"),
+            tdef: TypeDef::integer().fallible(),
+        }
+    ];
+}